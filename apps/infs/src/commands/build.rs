@@ -12,13 +12,27 @@
 //!
 //! Phases execute in canonical order (parse -> analyze -> codegen) regardless
 //! of the order flags appear on the command line. Each phase depends on the previous.
+//!
+//! ## Project Settings
+//!
+//! When the source file belongs to a project, the `[build] optimize` setting of the
+//! nearest `Inference.toml` above it is forwarded to infc as `--opt-level`, and each
+//! entry of its `[imports]` table as an `--import` flag. Each entry of its `[lints]`
+//! table is forwarded as an `--allow`, `--warn` or `--deny` flag whenever the analyze
+//! phase runs.
+//!
+//! When the source file lies in the project's `src/` directory, every other `.inf`
+//! file below `src/` is a module file and is forwarded as a `--module` flag. The module
+//! path follows the file path relative to `src/`: `src/math.inf` is the module `math`
+//! and `src/shapes/square.inf` the module `shapes::square`.
 
 use anyhow::{Context, Result, bail};
 use clap::Args;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::errors::InfsError;
+use crate::project::manifest::InferenceToml;
 use crate::toolchain::find_infc;
 
 /// Arguments for the build command.
@@ -69,15 +83,19 @@ pub struct BuildArgs {
 ///
 /// 1. Validates that the source file exists
 /// 2. Ensures at least one phase flag is specified
-/// 3. Locates the infc compiler binary
-/// 4. Builds and executes the infc command with appropriate flags
-/// 5. Propagates exit code from infc
+/// 3. Reads the optimization level from the project's `Inference.toml`, if any,
+///    and collects the module files of its `src/` directory
+/// 4. Locates the infc compiler binary
+/// 5. Builds and executes the infc command with appropriate flags
+/// 6. Propagates exit code from infc
 ///
 /// ## Errors
 ///
 /// Returns an error if:
 /// - The source file does not exist
 /// - No phase flags are specified
+/// - The project's `Inference.toml` is invalid
+/// - The name of a module file is not a valid module path
 /// - infc compiler cannot be found
 /// - infc exits with non-zero code (as `InfsError::ProcessExitCode`)
 pub fn execute(args: &BuildArgs) -> Result<()> {
@@ -93,10 +111,28 @@ pub fn execute(args: &BuildArgs) -> Result<()> {
         bail!("At least one of --parse, --analyze, or --codegen must be specified");
     }
 
+    let manifest_path = find_manifest(&args.path);
+    let manifest = manifest_path
+        .as_deref()
+        .map(InferenceToml::read_from_file)
+        .transpose()?;
+    let modules = match &manifest_path {
+        Some(manifest_path) => find_modules(manifest_path, &args.path)?,
+        None => Vec::new(),
+    };
+    let optimization_level = manifest
+        .as_ref()
+        .map(|manifest| manifest.build.optimization_level())
+        .transpose()?;
+
     let infc_path = find_infc()?;
 
     let mut cmd = Command::new(&infc_path);
     cmd.arg(&args.path);
+    for (module_path, file) in &modules {
+        cmd.arg("--module")
+            .arg(format!("{module_path}={}", file.display()));
+    }
 
     if need_parse {
         cmd.arg("--parse");
@@ -104,8 +140,19 @@ pub fn execute(args: &BuildArgs) -> Result<()> {
     if need_analyze {
         cmd.arg("--analyze");
     }
+    if need_analyze || need_codegen {
+        for (lint, level) in manifest.iter().flat_map(|manifest| &manifest.lints) {
+            cmd.arg(level.flag()).arg(lint);
+        }
+    }
     if need_codegen {
         cmd.arg("--codegen");
+        if let Some(level) = optimization_level {
+            cmd.arg("--opt-level").arg(level.to_string());
+        }
+        for (function, import) in manifest.iter().flat_map(|manifest| &manifest.imports) {
+            cmd.arg("--import").arg(import.to_flag_value(function));
+        }
     }
     if args.generate_wasm_output {
         cmd.arg("-o");
@@ -128,3 +175,133 @@ pub fn execute(args: &BuildArgs) -> Result<()> {
         Err(InfsError::process_exit_code(code).into())
     }
 }
+
+/// Returns the `Inference.toml` in the directory of `source` or the closest ancestor.
+fn find_manifest(source: &Path) -> Option<PathBuf> {
+    let source = source.canonicalize().ok()?;
+    source
+        .ancestors()
+        .skip(1)
+        .map(|directory| directory.join("Inference.toml"))
+        .find(|manifest| manifest.is_file())
+}
+
+/// Returns the module files of the project whose manifest is `manifest`, with their
+/// module paths, sorted by path.
+///
+/// Module files are the `.inf` files below the project's `src/` directory other than
+/// `source`. A project whose `source` lies outside `src/` has no module files.
+fn find_modules(manifest: &Path, source: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let src_dir = match manifest.parent() {
+        Some(root) => root.join("src"),
+        None => return Ok(Vec::new()),
+    };
+    let source = source.canonicalize()?;
+    if !source.starts_with(&src_dir) {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+    collect_inf_files(&src_dir, &mut files)?;
+    files.retain(|file| *file != source);
+    files.sort();
+
+    files
+        .into_iter()
+        .map(|file| {
+            let relative = file.strip_prefix(&src_dir)?.with_extension("");
+            let segments = relative
+                .components()
+                .map(|component| component.as_os_str().to_str())
+                .collect::<Option<Vec<_>>>()
+                .filter(|segments| segments.iter().all(|segment| is_identifier(segment)));
+            match segments {
+                Some(segments) => Ok((segments.join("::"), file)),
+                None => bail!(
+                    "Module file {} does not name a module: every directory and the file \
+                     name must be an identifier",
+                    file.display()
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Appends every `.inf` file below `directory` to `files`.
+fn collect_inf_files(directory: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = std::fs::read_dir(directory)
+        .with_context(|| format!("Failed to read directory {}", directory.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_inf_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "inf") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether `segment` can be a segment of a module path.
+fn is_identifier(segment: &str) -> bool {
+    segment.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_manifest_searches_ancestors() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let source_dir = temp.path().join("src").join("nested");
+        std::fs::create_dir_all(&source_dir).unwrap();
+        let source = source_dir.join("main.inf");
+        std::fs::write(&source, "").unwrap();
+        assert_eq!(find_manifest(&source), None);
+
+        let manifest = temp.path().join("Inference.toml");
+        std::fs::write(&manifest, "").unwrap();
+        assert_eq!(
+            find_manifest(&source),
+            Some(manifest.canonicalize().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_find_modules_maps_files_to_module_paths() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let src = root.join("src");
+        std::fs::create_dir_all(src.join("shapes")).unwrap();
+        let manifest = root.join("Inference.toml");
+        std::fs::write(&manifest, "").unwrap();
+        let main = src.join("main.inf");
+        std::fs::write(&main, "").unwrap();
+        std::fs::write(src.join("math.inf"), "").unwrap();
+        std::fs::write(src.join("shapes").join("square.inf"), "").unwrap();
+        std::fs::write(src.join("notes.txt"), "").unwrap();
+        std::fs::write(root.join("outside.inf"), "").unwrap();
+
+        assert_eq!(
+            find_modules(&manifest, &main).unwrap(),
+            vec![
+                ("math".to_string(), src.join("math.inf")),
+                (
+                    "shapes::square".to_string(),
+                    src.join("shapes").join("square.inf")
+                ),
+            ]
+        );
+        assert_eq!(
+            find_modules(&manifest, &root.join("outside.inf")).unwrap(),
+            Vec::new()
+        );
+
+        std::fs::write(src.join("bad-name.inf"), "").unwrap();
+        assert!(find_modules(&manifest, &main).is_err());
+    }
+}
@@ -50,7 +50,8 @@ pub struct RunArgs {
 
     /// Function to invoke as entry point.
     ///
-    /// Defaults to "main". The function must be exported (marked `pub` in source).
+    /// Defaults to "main". The function must be exported (marked `pub` in source);
+    /// functions of nested modules are named by their qualified name, such as `math::add`.
     /// For `main`, argc/argv arguments (0 0) are passed automatically.
     #[clap(long, default_value = "main")]
     pub entry_point: String,
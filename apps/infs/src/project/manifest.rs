@@ -22,6 +22,13 @@
 //!
 //! [verification]
 //! output-dir = "proofs/"
+//!
+//! [imports]
+//! log = { module = "host", name = "log_i32" }
+//!
+//! [lints]
+//! unused_variable = "deny"
+//! constant_condition = "allow"
 //! ```
 //!
 //! ## Reserved Names
@@ -31,7 +38,7 @@
 
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use std::process::Command;
 
@@ -98,6 +105,14 @@ pub struct InferenceToml {
     /// Verification configuration for Rocq output.
     #[serde(default, skip_serializing_if = "VerificationConfig::is_default")]
     pub verification: VerificationConfig,
+
+    /// WebAssembly import names of external functions.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub imports: BTreeMap<String, ImportConfig>,
+
+    /// Levels of the compiler lints, keyed by lint name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lints: BTreeMap<String, LintLevelConfig>,
 }
 
 /// Package metadata in the manifest.
@@ -171,6 +186,28 @@ impl BuildConfig {
     pub fn is_default(&self) -> bool {
         self.target == default_target() && self.optimize == default_optimize()
     }
+
+    /// Returns the LLVM optimization level selected by `optimize`.
+    ///
+    /// `"debug"` selects level 0 and `"release"` level 3; the levels `"0"` to `"3"` may
+    /// also be given directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other value.
+    pub fn optimization_level(&self) -> Result<u8> {
+        match self.optimize.as_str() {
+            "debug" => Ok(0),
+            "release" => Ok(3),
+            level => match level.parse::<u8>() {
+                Ok(level) if level <= 3 => Ok(level),
+                _ => bail!(
+                    "Invalid optimize value '{level}' in [build]: \
+                     expected \"debug\", \"release\" or a level from 0 to 3"
+                ),
+            },
+        }
+    }
 }
 
 /// Verification configuration for Rocq output.
@@ -197,6 +234,53 @@ impl VerificationConfig {
     }
 }
 
+/// Import name of one `external fn`, keyed by the function name in `[imports]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportConfig {
+    /// Module the function is imported from.
+    #[serde(default = "default_import_module")]
+    pub module: String,
+
+    /// Field name within the module; defaults to the function name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl ImportConfig {
+    /// Formats this import as an infc `--import` value for `function`.
+    #[must_use]
+    pub fn to_flag_value(&self, function: &str) -> String {
+        let field = self.name.as_deref().unwrap_or(function);
+        format!("{function}={}:{field}", self.module)
+    }
+}
+
+/// Level of one compiler lint in `[lints]`.
+///
+/// Lint names are checked by infc, which knows the lints it implements.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevelConfig {
+    /// The lint is not reported.
+    Allow,
+    /// The lint is reported as a warning, which does not stop the build.
+    Warn,
+    /// The lint is reported as an error.
+    Deny,
+}
+
+impl LintLevelConfig {
+    /// Returns the infc flag setting a lint to this level.
+    #[must_use]
+    pub fn flag(self) -> &'static str {
+        match self {
+            LintLevelConfig::Allow => "--allow",
+            LintLevelConfig::Warn => "--warn",
+            LintLevelConfig::Deny => "--deny",
+        }
+    }
+}
+
 /// Gets the infc version to use for new projects.
 ///
 /// Tries to detect the installed infc version first by running `infc --version`.
@@ -267,6 +351,10 @@ fn default_output_dir() -> String {
     String::from("proofs/")
 }
 
+fn default_import_module() -> String {
+    String::from("env")
+}
+
 impl InferenceToml {
     /// Creates a new manifest with the given project name.
     ///
@@ -285,9 +373,31 @@ impl InferenceToml {
             dependencies: Dependencies::default(),
             build: BuildConfig::default(),
             verification: VerificationConfig::default(),
+            imports: BTreeMap::new(),
+            lints: BTreeMap::new(),
         }
     }
 
+    /// Parses a manifest from TOML text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid manifest.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content).context("Failed to parse Inference.toml")
+    }
+
+    /// Reads and parses a manifest file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid manifest.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("In {}", path.display()))
+    }
+
     /// Serializes the manifest to TOML format.
     ///
     /// # Errors
@@ -403,6 +513,102 @@ mod tests {
         assert!(!config.is_default());
     }
 
+    #[test]
+    fn test_build_config_optimization_level() {
+        let level = |optimize: &str| {
+            BuildConfig {
+                target: default_target(),
+                optimize: optimize.to_string(),
+            }
+            .optimization_level()
+        };
+        assert_eq!(level("debug").unwrap(), 0);
+        assert_eq!(level("release").unwrap(), 3);
+        assert_eq!(level("2").unwrap(), 2);
+        assert!(level("4").is_err());
+        assert!(level("fast").is_err());
+    }
+
+    #[test]
+    fn test_from_toml_roundtrip() {
+        let mut manifest = InferenceToml::new("myproject");
+        manifest.build.optimize = String::from("release");
+        let parsed = InferenceToml::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(parsed, manifest);
+        assert_eq!(parsed.build.optimization_level().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_imports_table() {
+        let manifest = InferenceToml::from_toml(
+            r#"
+[package]
+name = "myproject"
+version = "0.1.0"
+infc_version = "0.1.0"
+
+[imports]
+log = { module = "host", name = "log_i32" }
+tick = {}
+"#,
+        )
+        .unwrap();
+        let flags: Vec<String> = manifest
+            .imports
+            .iter()
+            .map(|(function, import)| import.to_flag_value(function))
+            .collect();
+        assert_eq!(flags, ["log=host:log_i32", "tick=env:tick"]);
+        let parsed = InferenceToml::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(parsed, manifest);
+    }
+
+    #[test]
+    fn test_lints_table() {
+        let manifest = InferenceToml::from_toml(
+            r#"
+[package]
+name = "myproject"
+version = "0.1.0"
+infc_version = "0.1.0"
+
+[lints]
+unused_variable = "deny"
+constant_condition = "allow"
+"#,
+        )
+        .unwrap();
+        let flags: Vec<(&str, &str)> = manifest
+            .lints
+            .iter()
+            .map(|(lint, level)| (level.flag(), lint.as_str()))
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                ("--allow", "constant_condition"),
+                ("--deny", "unused_variable")
+            ]
+        );
+        let parsed = InferenceToml::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(parsed, manifest);
+    }
+
+    #[test]
+    fn test_lints_table_rejects_unknown_level() {
+        let result = InferenceToml::from_toml(
+            r#"
+[package]
+name = "myproject"
+version = "0.1.0"
+
+[lints]
+unused_variable = "forbid"
+"#,
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_verification_config_is_default() {
         let config = VerificationConfig::default();
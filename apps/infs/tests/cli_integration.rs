@@ -301,6 +301,53 @@ fn build_full_pipeline_with_v_output() {
     );
 }
 
+/// Verifies that `infs build` compiles the module files of a project's `src/` directory.
+///
+/// **Expected behavior**: `src/math.inf` and `src/shapes/square.inf` become the modules
+/// `math` and `shapes::square`, so `src/main.inf` calling into them compiles to WASM.
+#[test]
+fn build_loads_project_module_files() {
+    let Some(infc_path) = require_infc() else {
+        return;
+    };
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("Inference.toml")
+        .write_str("[package]\nname = \"modules\"\nversion = \"0.1.0\"\ninfc_version = \"0.1.0\"\n")
+        .unwrap();
+    let main = temp.child("src").child("main.inf");
+    main.write_str(
+        "pub fn run(x: i32) -> i32 {\n    return math::add(x, shapes::square::area(x));\n}",
+    )
+    .unwrap();
+    temp.child("src")
+        .child("math.inf")
+        .write_str("pub fn add(a: i32, b: i32) -> i32 { return a + b; }")
+        .unwrap();
+    temp.child("src")
+        .child("shapes")
+        .child("square.inf")
+        .write_str("pub fn area(side: i32) -> i32 { return side * side; }")
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc_path)
+        .current_dir(temp.path())
+        .arg("build")
+        .arg(main.path())
+        .arg("--codegen")
+        .arg("-o");
+
+    cmd.assert().success();
+
+    let wasm_output = temp.child("out").child("main.wasm");
+    assert!(
+        wasm_output.path().exists(),
+        "Expected WASM file at: {:?}",
+        wasm_output.path()
+    );
+}
+
 // =============================================================================
 // Version and Help Tests
 // =============================================================================
@@ -1691,8 +1738,8 @@ fn build_compiles_uzumaki_operator() {
     };
 
     let temp = assert_fs::TempDir::new().unwrap();
-    let src = codegen_test_file("nondet.inf");
-    let dest = temp.child("nondet.inf");
+    let src = codegen_test_file("nondet_quantified.inf");
+    let dest = temp.child("nondet_quantified.inf");
     std::fs::copy(&src, dest.path()).unwrap();
 
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
@@ -1707,7 +1754,7 @@ fn build_compiles_uzumaki_operator() {
         .success()
         .stdout(predicate::str::contains("WASM generated"));
 
-    let wasm_output = temp.child("out").child("nondet.wasm");
+    let wasm_output = temp.child("out").child("nondet_quantified.wasm");
     assert!(
         wasm_output.path().exists(),
         "Expected WASM file at: {:?}",
@@ -1725,8 +1772,8 @@ fn build_compiles_all_nondet_features() {
     };
 
     let temp = assert_fs::TempDir::new().unwrap();
-    let src = codegen_test_file("nondet.inf");
-    let dest = temp.child("nondet.inf");
+    let src = codegen_test_file("nondet_quantified.inf");
+    let dest = temp.child("nondet_quantified.inf");
     std::fs::copy(&src, dest.path()).unwrap();
 
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
@@ -1743,8 +1790,8 @@ fn build_compiles_all_nondet_features() {
         .stdout(predicate::str::contains("WASM generated"))
         .stdout(predicate::str::contains("V generated"));
 
-    let wasm_output = temp.child("out").child("nondet.wasm");
-    let v_output = temp.child("out").child("nondet.v");
+    let wasm_output = temp.child("out").child("nondet_quantified.wasm");
+    let v_output = temp.child("out").child("nondet_quantified.v");
     assert!(
         wasm_output.path().exists(),
         "Expected WASM file at: {:?}",
@@ -1757,6 +1804,40 @@ fn build_compiles_all_nondet_features() {
     );
 }
 
+/// Verifies that analysis rejects an exported function returning `@` outside of a quantifier.
+///
+/// **Expected behavior**: Exit with non-zero code, report the analysis error, no WASM output.
+#[test]
+fn build_rejects_exported_uzumaki_result() {
+    let Some(infc_path) = require_infc() else {
+        return;
+    };
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let src = codegen_test_file("nondet.inf");
+    let dest = temp.child("nondet.inf");
+    std::fs::copy(&src, dest.path()).unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc_path)
+        .current_dir(temp.path())
+        .arg("build")
+        .arg(dest.path())
+        .arg("--codegen")
+        .arg("-o");
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "exported function `hello_uzumaki` returns a non-deterministic value outside of a quantifier",
+    ));
+
+    let wasm_output = temp.child("out").child("nondet.wasm");
+    assert!(
+        !wasm_output.path().exists(),
+        "No WASM file expected at: {:?}",
+        wasm_output.path()
+    );
+}
+
 /// QA: TC-2.11 - Verify phases execute in correct order regardless of flag order.
 ///
 /// **Expected behavior**: Exit code 0, phases execute in order: parse -> analyze -> codegen.
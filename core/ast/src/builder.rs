@@ -57,6 +57,13 @@
 //!
 //! The builder also calls `collect_errors()` for each processed node to identify
 //! tree-sitter ERROR nodes from parse failures.
+//!
+//! # Modules
+//!
+//! Every added source file becomes a [`SourceFile`] node. A file added with
+//! [`Builder::add_module_source_code`] holds the body of a named module instead: its
+//! definitions are wrapped in a [`ModuleDefinition`], so that a project can be split into
+//! a main file and one file per module.
 
 use std::{
     rc::Rc,
@@ -86,7 +93,8 @@ use tree_sitter::Node;
 
 pub struct Builder<'a> {
     arena: Arena,
-    source_code: Vec<(Node<'a>, &'a [u8])>,
+    /// Source files to build, with the name of the module each one holds, if any.
+    source_code: Vec<(Node<'a>, &'a [u8], Option<String>)>,
     errors: Vec<anyhow::Error>,
 }
 
@@ -116,7 +124,29 @@ impl<'a> Builder<'a> {
             root.kind() == "source_file",
             "Expected a root node of type `source_file`"
         );
-        self.source_code.push((root, code));
+        self.source_code.push((root, code, None));
+    }
+
+    /// Adds a source file that holds the body of the module `name`, as if its definitions
+    /// were written inside `mod name { ... }`.
+    ///
+    /// The file becomes a source file with a single public [`ModuleDefinition`]; its `use`
+    /// directives stay on the source file. This is how projects split into several files
+    /// are built until the grammar supports module declarations.
+    ///
+    /// A path such as `math::ops` wraps the definitions in a module `ops` nested in a
+    /// module `math`. The enclosing modules belong to this file only, so no other file may
+    /// hold a module with the same path as one of them.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the `root` node is not of type `source_file`.
+    pub fn add_module_source_code(&mut self, name: &str, root: Node<'a>, code: &'a [u8]) {
+        assert!(
+            root.kind() == "source_file",
+            "Expected a root node of type `source_file`"
+        );
+        self.source_code.push((root, code, Some(name.to_string())));
     }
 
     /// Builds the AST from the root node and source code.
@@ -130,7 +160,7 @@ impl<'a> Builder<'a> {
     /// This function will return an error if the `source_file` is malformed and a valid AST cannot be constructed.
     #[allow(clippy::single_match_else)]
     pub fn build_ast(&'_ mut self) -> anyhow::Result<Arena> {
-        for (root, code) in &self.source_code.clone() {
+        for (root, code, module_name) in &self.source_code.clone() {
             let id = Self::get_node_id();
             let location = Self::get_location(root, code);
             let source = String::from_utf8_lossy(code);
@@ -140,6 +170,12 @@ impl<'a> Builder<'a> {
             );
             let source = source.into_owned();
             let mut ast = SourceFile::new(id, location, source);
+            let module_ids: Vec<u32> = module_name
+                .iter()
+                .flat_map(|name| name.split("::"))
+                .map(|_| Self::get_node_id())
+                .collect();
+            let mut definitions = Vec::new();
 
             for i in 0..root.child_count() {
                 if let Some(child) = root.child(u32::try_from(i).unwrap()) {
@@ -151,12 +187,45 @@ impl<'a> Builder<'a> {
                                 .push(Directive::Use(self.build_use_directive(id, &child, code)));
                         }
                         _ => {
-                            let definition = self.build_definition(id, &child, code);
-                            ast.definitions.push(definition);
+                            let definition = self.build_definition(
+                                *module_ids.last().unwrap_or(&id),
+                                &child,
+                                code,
+                            );
+                            definitions.push(definition);
                         }
                     }
                 }
             }
+            if let Some(module_name) = module_name {
+                let segments: Vec<&str> = module_name.split("::").collect();
+                for (index, segment) in segments.iter().enumerate().rev() {
+                    let module_id = module_ids[index];
+                    let parent_id = index.checked_sub(1).map_or(id, |parent| module_ids[parent]);
+                    let name = Rc::new(Identifier::new(
+                        Self::get_node_id(),
+                        (*segment).to_string(),
+                        location,
+                    ));
+                    self.arena.add_node(
+                        AstNode::Expression(Expression::Identifier(name.clone())),
+                        module_id,
+                    );
+                    let module = Rc::new(ModuleDefinition::new(
+                        module_id,
+                        Visibility::Public,
+                        name,
+                        Some(definitions),
+                        location,
+                    ));
+                    self.arena.add_node(
+                        AstNode::Definition(Definition::Module(module.clone())),
+                        parent_id,
+                    );
+                    definitions = vec![Definition::Module(module)];
+                }
+            }
+            ast.definitions = definitions;
             self.arena
                 .add_node(AstNode::Ast(Ast::SourceFile(Rc::new(ast))), u32::MAX);
             if !self.errors.is_empty() {
@@ -544,14 +613,15 @@ impl<'a> Builder<'a> {
         let mut arguments = None;
         let mut returns = None;
 
-        let mut cursor = node.walk();
-
-        let founded_arguments = node
-            .children_by_field_name("argument", &mut cursor)
-            .map(|segment| self.build_argument_type(id, &segment, code));
-        let founded_arguments: Vec<ArgumentType> = founded_arguments.collect();
-        if !founded_arguments.is_empty() {
-            arguments = Some(founded_arguments);
+        if let Some(argument_list_node) = node.child_by_field_name("argument_list") {
+            let mut cursor = argument_list_node.walk();
+            let founded_arguments = argument_list_node
+                .children_by_field_name("argument", &mut cursor)
+                .map(|segment| self.build_argument_type(id, &segment, code));
+            let founded_arguments: Vec<ArgumentType> = founded_arguments.collect();
+            if !founded_arguments.is_empty() {
+                arguments = Some(founded_arguments);
+            }
         }
 
         if let Some(returns_node) = node.child_by_field_name("returns") {
@@ -648,9 +718,7 @@ impl<'a> Builder<'a> {
         let name_node = node.child_by_field_name("name").unwrap();
         let type_node = node.child_by_field_name("type").unwrap();
         let ty = self.build_type(id, &type_node, code);
-        let is_mut = node
-            .child_by_field_name("mut")
-            .is_some_and(|n| n.kind() == "true");
+        let is_mut = node.child_by_field_name("mut").is_some();
         let name = self.build_identifier(id, &name_node, code);
         let node = Rc::new(Argument::new(id, location, name, is_mut, ty));
         self.arena.add_node(
@@ -669,9 +737,7 @@ impl<'a> Builder<'a> {
         self.collect_errors(node, code);
         let id = Self::get_node_id();
         let location = Self::get_location(node, code);
-        let is_mut = node
-            .child_by_field_name("mut")
-            .is_some_and(|n| n.kind() == "true");
+        let is_mut = node.child_by_field_name("mut").is_some();
         let node = Rc::new(SelfReference::new(id, location, is_mut));
         self.arena.add_node(
             AstNode::ArgumentType(ArgumentType::SelfReference(node.clone())),
@@ -975,13 +1041,14 @@ impl<'a> Builder<'a> {
         let location = Self::get_location(node, code);
         let ty = self.build_type(id, &node.child_by_field_name("type").unwrap(), code);
         let name = self.build_identifier(id, &node.child_by_field_name("name").unwrap(), code);
+        let is_mut = node.child_by_field_name("mut").is_some();
         let value = node
             .child_by_field_name("value")
             .map(|n| self.build_expression(id, &n, code));
         let is_undef = node.child_by_field_name("undef").is_some();
 
         let node = Rc::new(VariableDefinitionStatement::new(
-            id, location, name, ty, value, is_undef,
+            id, location, name, is_mut, ty, value, is_undef,
         ));
         self.arena.add_node(
             AstNode::Statement(Statement::VariableDefinition(node.clone())),
@@ -1123,8 +1190,21 @@ impl<'a> Builder<'a> {
         self.collect_errors(node, code);
         let id = Self::get_node_id();
         let location = Self::get_location(node, code);
-        let expression =
-            self.build_expression(id, &node.child_by_field_name("expression").unwrap(), code);
+        // The head `a.b` of a chain `a.b.c` is parsed as a `qualified_name`, whose object
+        // sits in its `qualifier` field; it is a member access all the same.
+        let expression_node = node
+            .child_by_field_name("expression")
+            .or_else(|| node.child_by_field_name("qualifier"))
+            .unwrap();
+        let expression = if expression_node.kind() == "qualified_name" {
+            Expression::MemberAccess(self.build_member_access_expression(
+                id,
+                &expression_node,
+                code,
+            ))
+        } else {
+            self.build_expression(id, &expression_node, code)
+        };
         let name = self.build_identifier(id, &node.child_by_field_name("name").unwrap(), code);
         let node = Rc::new(MemberAccessExpression::new(id, location, expression, name));
         self.arena.add_node(
@@ -1165,11 +1245,17 @@ impl<'a> Builder<'a> {
         self.collect_errors(node, code);
         let id = Self::get_node_id();
         let location = Self::get_location(node, code);
-        let function =
-            self.build_expression(id, &node.child_by_field_name("function").unwrap(), code);
+        let function_node = node.child_by_field_name("function").unwrap();
+        // `cast i64'(x)` calls a generic function with explicit type arguments: the callee
+        // is the base name and the type arguments are kept apart
+        let (function, mut type_parameters) = if function_node.kind() == "generic_name" {
+            let (base, type_parameters) = self.build_generic_name(id, &function_node, code);
+            (Expression::Identifier(base), Some(type_parameters))
+        } else {
+            (self.build_expression(id, &function_node, code), None)
+        };
         let mut argument_name_expression_map: Vec<(Option<Rc<Identifier>>, Expression)> =
             Vec::new();
-        let mut type_parameters = None;
         let mut pending_name: Option<Rc<Identifier>> = None;
         let mut cursor = node.walk();
         if cursor.goto_first_child() {
@@ -1228,6 +1314,28 @@ impl<'a> Builder<'a> {
         node
     }
 
+    /// Splits a `generic_name` such as `Pair i32' bool'` into its base identifier and its
+    /// type arguments.
+    fn build_generic_name(
+        &mut self,
+        parent_id: u32,
+        node: &Node,
+        code: &[u8],
+    ) -> (Rc<Identifier>, Vec<Rc<Identifier>>) {
+        let base = self.build_identifier(
+            parent_id,
+            &node.child_by_field_name("base_type").unwrap(),
+            code,
+        );
+        let arguments = node.child(1).unwrap();
+        let mut cursor = arguments.walk();
+        let type_parameters = arguments
+            .children_by_field_name("type", &mut cursor)
+            .map(|segment| self.build_identifier(parent_id, &segment, code))
+            .collect();
+        (base, type_parameters)
+    }
+
     fn build_struct_expression(
         &mut self,
         parent_id: u32,
@@ -1237,7 +1345,15 @@ impl<'a> Builder<'a> {
         self.collect_errors(node, code);
         let id = Self::get_node_id();
         let location = Self::get_location(node, code);
-        let name = self.build_identifier(id, &node.child_by_field_name("name").unwrap(), code);
+        let name_node = node.child_by_field_name("name").unwrap();
+        // `Pair i32' bool' { .. }` instantiates a generic struct: the name is the base
+        // type and the type arguments are kept apart, as for generic function calls
+        let (name, type_parameters) = if name_node.kind() == "generic_name" {
+            let (base, type_parameters) = self.build_generic_name(id, &name_node, code);
+            (base, Some(type_parameters))
+        } else {
+            (self.build_identifier(id, &name_node, code), None)
+        };
         let mut field_name_expression_map: Vec<(Rc<Identifier>, Expression)> = Vec::new();
         let mut pending_name: Option<Rc<Identifier>> = None;
         let mut cursor = node.walk();
@@ -1246,13 +1362,13 @@ impl<'a> Builder<'a> {
                 let child = cursor.node();
                 if let Some(field) = cursor.field_name() {
                     match field {
-                        "field" => {
+                        "field_name" => {
                             let expr = self.build_expression(id, &child, code);
                             if let Expression::Identifier(ident) = expr {
                                 pending_name = Some(ident);
                             }
                         }
-                        "value" => {
+                        "field_value" => {
                             let expr = self.build_expression(id, &child, code);
                             let name = pending_name
                                 .take()
@@ -1274,7 +1390,13 @@ impl<'a> Builder<'a> {
             Some(field_name_expression_map)
         };
 
-        let node = Rc::new(StructExpression::new(id, location, name, fields));
+        let node = Rc::new(StructExpression::new(
+            id,
+            location,
+            name,
+            type_parameters,
+            fields,
+        ));
         self.arena.add_node(
             AstNode::Expression(Expression::Struct(node.clone())),
             parent_id,
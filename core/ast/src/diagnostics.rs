@@ -0,0 +1,246 @@
+//! Diagnostics shared by the compiler phases.
+//!
+//! Errors stop compilation; warnings and notes do not. Each phase keeps its own error
+//! type for the errors it reports (`TypeCheckError`, `AnalysisError`) and converts them to
+//! a [`Diagnostic`] to report them alongside warnings.
+//!
+//! Warnings come from named [`Lint`]s. Every lint warns by default, and a [`LintConfig`]
+//! changes its [`LintLevel`]: an allowed lint is not reported, and a denied lint is
+//! reported as an error.
+
+use std::fmt::{self, Display, Formatter};
+
+use rustc_hash::FxHashMap;
+
+use crate::nodes::Location;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The program is rejected.
+    Error,
+    /// The program is accepted, but likely not what was meant.
+    Warning,
+    /// Additional information attached to another diagnostic.
+    Note,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Note => write!(f, "note"),
+        }
+    }
+}
+
+/// A named check reporting code that is valid but likely a mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// A `let` variable that is never read.
+    UnusedVariable,
+    /// A `use` whose imported name is never referred to.
+    UnusedImport,
+    /// A private function that is never called or referred to.
+    UnusedPrivateFunction,
+    /// A `let` that hides a variable, argument or constant of an enclosing scope.
+    ShadowedBinding,
+    /// An `if` whose condition is built from literals only.
+    ConstantCondition,
+    /// A name in a struct field type that is not a type and does not start with an
+    /// uppercase letter, which makes the struct generic over it.
+    ImplicitTypeParameter,
+}
+
+impl Lint {
+    /// Every lint.
+    pub const ALL: [Lint; 6] = [
+        Lint::UnusedVariable,
+        Lint::UnusedImport,
+        Lint::UnusedPrivateFunction,
+        Lint::ShadowedBinding,
+        Lint::ConstantCondition,
+        Lint::ImplicitTypeParameter,
+    ];
+
+    /// Returns the name of the lint, as used in `Inference.toml` and on the command line.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UnusedVariable => "unused_variable",
+            Lint::UnusedImport => "unused_import",
+            Lint::UnusedPrivateFunction => "unused_private_function",
+            Lint::ShadowedBinding => "shadowed_binding",
+            Lint::ConstantCondition => "constant_condition",
+            Lint::ImplicitTypeParameter => "implicit_type_parameter",
+        }
+    }
+
+    /// Returns the lint with the given name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|lint| lint.name() == name)
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// What to do with the diagnostics of a [`Lint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LintLevel {
+    /// Do not report the lint.
+    Allow,
+    /// Report the lint as a warning.
+    #[default]
+    Warn,
+    /// Report the lint as an error.
+    Deny,
+}
+
+impl LintLevel {
+    /// Returns the name of the level: `allow`, `warn` or `deny`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+        }
+    }
+
+    /// Returns the level with the given name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        [LintLevel::Allow, LintLevel::Warn, LintLevel::Deny]
+            .into_iter()
+            .find(|level| level.name() == name)
+    }
+}
+
+/// The level of every lint; lints that were not set warn.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    levels: FxHashMap<Lint, LintLevel>,
+}
+
+impl LintConfig {
+    /// Sets the level of `lint`, replacing the level set before.
+    pub fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels.insert(lint, level);
+    }
+
+    /// Returns the level of `lint`.
+    #[must_use]
+    pub fn level(&self, lint: Lint) -> LintLevel {
+        self.levels.get(&lint).copied().unwrap_or_default()
+    }
+
+    /// Applies the lint levels to `diagnostics`: drops those of allowed lints and makes
+    /// those of denied lints errors. Diagnostics without a lint are kept as they are.
+    #[must_use]
+    pub fn apply(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter_map(|mut diagnostic| {
+                match diagnostic.lint.map(|lint| self.level(lint)) {
+                    Some(LintLevel::Allow) => return None,
+                    Some(LintLevel::Deny) => diagnostic.severity = Severity::Error,
+                    Some(LintLevel::Warn) | None => {}
+                }
+                Some(diagnostic)
+            })
+            .collect()
+    }
+}
+
+/// A located message of some [`Severity`], with the notes that explain it.
+///
+/// Displayed as `line:column: severity: message`, followed by the name of the lint in
+/// brackets for lint diagnostics, and by each note on its own indented line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The lint that reported this diagnostic, if any.
+    pub lint: Option<Lint>,
+    pub message: String,
+    pub location: Location,
+    pub notes: Vec<Diagnostic>,
+}
+
+impl Diagnostic {
+    /// Creates an error diagnostic.
+    #[must_use]
+    pub fn error(location: Location, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, None, location, message)
+    }
+
+    /// Creates a warning diagnostic reported by `lint`.
+    #[must_use]
+    pub fn lint(lint: Lint, location: Location, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, Some(lint), location, message)
+    }
+
+    /// Creates a note, to be attached to another diagnostic with [`Self::with_note`].
+    #[must_use]
+    pub fn note(location: Location, message: impl Into<String>) -> Self {
+        Self::new(Severity::Note, None, location, message)
+    }
+
+    /// Creates an error diagnostic from an error of a compiler phase, whose message
+    /// starts with its location followed by `": "`.
+    #[must_use]
+    pub fn from_error(location: Location, error: &impl Display) -> Self {
+        let text = error.to_string();
+        let message = text
+            .strip_prefix(&format!("{location}: "))
+            .unwrap_or(&text)
+            .to_string();
+        Self::error(location, message)
+    }
+
+    fn new(
+        severity: Severity,
+        lint: Option<Lint>,
+        location: Location,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity,
+            lint,
+            message: message.into(),
+            location,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Attaches a note at `location` to this diagnostic.
+    #[must_use]
+    pub fn with_note(mut self, location: Location, message: impl Into<String>) -> Self {
+        self.notes.push(Self::note(location, message));
+        self
+    }
+
+    /// Returns `true` if this diagnostic rejects the program.
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.location, self.severity, self.message)?;
+        if let Some(lint) = self.lint {
+            write!(f, " [{lint}]")?;
+        }
+        for note in &self.notes {
+            write!(f, "\n  {note}")?;
+        }
+        Ok(())
+    }
+}
@@ -34,6 +34,7 @@
 //! - [`extern_prelude`] - External module discovery and parsing
 //! - [`parser_context::ParserContext`] - Multi-file parsing context (WIP)
 //! - [`errors`] - Structured error types for AST operations
+//! - [`diagnostics`] - Errors, warnings and lints shared by the compiler phases
 //!
 //! # Key Features
 //!
@@ -60,6 +61,7 @@
 #![warn(clippy::pedantic)]
 pub mod arena;
 pub mod builder;
+pub mod diagnostics;
 pub(crate) mod enums_impl;
 pub mod errors;
 pub mod extern_prelude;
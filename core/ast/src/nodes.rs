@@ -535,6 +535,7 @@ ast_nodes! {
 
     pub struct VariableDefinitionStatement {
         pub name: Rc<Identifier>,
+        pub is_mut: bool,
         pub ty: Type,
         pub value: Option<RefCell<Expression>>,
         pub is_uzumaki: bool,
@@ -573,6 +574,7 @@ ast_nodes! {
 
     pub struct StructExpression {
         pub name: Rc<Identifier>,
+        pub type_parameters: Option<Vec<Rc<Identifier>>>,
         pub fields: Option<Vec<(Rc<Identifier>, RefCell<Expression>)>>,
     }
 
@@ -106,6 +106,48 @@ impl SourceFile {
             })
             .collect()
     }
+    #[must_use]
+    pub fn struct_definitions(&self) -> Vec<Rc<StructDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Struct(struct_definition) => Some(struct_definition.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+    #[must_use]
+    pub fn enum_definitions(&self) -> Vec<Rc<EnumDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Enum(enum_definition) => Some(enum_definition.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+    #[must_use]
+    pub fn constant_definitions(&self) -> Vec<Rc<ConstantDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Constant(constant_definition) => Some(constant_definition.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+    #[must_use]
+    pub fn external_function_definitions(&self) -> Vec<Rc<ExternalFunctionDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::ExternalFunction(external_function_definition) => {
+                    Some(external_function_definition.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl BlockType {
@@ -185,6 +227,21 @@ impl Expression {
     }
 }
 
+impl ArgumentType {
+    /// Returns the name a call site can use to pass this parameter by name.
+    ///
+    /// Only `name: T` parameters can be named; `self`, `_: T` and bare types return `None`.
+    #[must_use]
+    pub fn parameter_name(&self) -> Option<String> {
+        match self {
+            ArgumentType::Argument(argument) => Some(argument.name()),
+            ArgumentType::SelfReference(_)
+            | ArgumentType::IgnoreArgument(_)
+            | ArgumentType::Type(_) => None,
+        }
+    }
+}
+
 impl UseDirective {
     #[must_use]
     pub fn new(
@@ -571,6 +628,7 @@ impl VariableDefinitionStatement {
         id: u32,
         location: Location,
         name: Rc<Identifier>,
+        is_mut: bool,
         type_: Type,
         value: Option<Expression>,
         is_uzumaki: bool,
@@ -579,6 +637,7 @@ impl VariableDefinitionStatement {
             id,
             location,
             name,
+            is_mut,
             ty: type_,
             value: value.map(RefCell::new),
             is_uzumaki,
@@ -694,6 +753,44 @@ impl FunctionCallExpression {
             String::new()
         }
     }
+
+    /// Maps every argument to the index of the callee parameter it binds to.
+    ///
+    /// Named arguments bind to the parameter with the same name, positional arguments
+    /// fill the remaining parameters from left to right. `parameter_names` holds one entry
+    /// per parameter, `None` for parameters that cannot be named (`_: T` or a bare type).
+    /// An argument that binds to no parameter maps to `None`.
+    #[must_use]
+    pub fn argument_parameter_indices(
+        &self,
+        parameter_names: &[Option<String>],
+    ) -> Vec<Option<usize>> {
+        let Some(arguments) = &self.arguments else {
+            return vec![];
+        };
+        let mut bound = vec![false; parameter_names.len()];
+        let mut indices: Vec<Option<usize>> = arguments
+            .iter()
+            .map(|(name, _)| {
+                let name = name.as_ref()?;
+                let index = parameter_names
+                    .iter()
+                    .position(|parameter| parameter.as_deref() == Some(name.name.as_str()))?;
+                if bound[index] {
+                    return None;
+                }
+                bound[index] = true;
+                Some(index)
+            })
+            .collect();
+        let mut free = (0..parameter_names.len()).filter(|i| !bound[*i]);
+        for ((name, _), index) in arguments.iter().zip(indices.iter_mut()) {
+            if name.is_none() {
+                *index = free.next();
+            }
+        }
+        indices
+    }
 }
 
 impl StructExpression {
@@ -702,6 +799,7 @@ impl StructExpression {
         id: u32,
         location: Location,
         name: Rc<Identifier>,
+        type_parameters: Option<Vec<Rc<Identifier>>>,
         fields: Option<Vec<(Rc<Identifier>, Expression)>>,
     ) -> Self {
         let fields = fields.map(|vec| {
@@ -713,6 +811,7 @@ impl StructExpression {
             id,
             location,
             name,
+            type_parameters,
             fields,
         }
     }
@@ -13,7 +13,7 @@
 //! The Inference compiler operates in three distinct phases:
 //!
 //! 1. **Parse** (`--parse`) – Builds the typed AST using tree-sitter
-//!    - Reads the source file
+//!    - Reads the source file and the module files given with `--module`
 //!    - Runs tree-sitter parser with Inference grammar
 //!    - Constructs arena-allocated AST nodes
 //!    - Validates syntax and basic structure
@@ -22,8 +22,10 @@
 //! 2. **Analyze** (`--analyze`) – Performs type checking and semantic validation
 //!    - Type inference and checking
 //!    - Symbol resolution
-//!    - Semantic validation
-//!    - Reports type errors and semantic issues
+//!    - Control flow and non-determinism checks
+//!    - Lints, configured with `--allow`, `--warn` and `--deny`
+//!    - Reports errors, warnings and notes with their locations; only errors (including
+//!      denied lints) stop compilation
 //!
 //! 3. **Codegen** (`--codegen`) – Emits WebAssembly binary
 //!    - Generates LLVM IR from typed AST
@@ -58,10 +60,12 @@
 //!
 //! - **Parse errors**: Syntax errors, malformed AST nodes
 //! - **Type errors**: Type mismatches, undefined symbols
+//! - **Analysis errors**: Control flow and non-determinism errors, and denied lints
 //! - **Codegen errors**: LLVM compilation failures
 //! - **IO errors**: File not found, permission issues
 //!
-//! All errors cause the process to exit with code 1.
+//! All errors cause the process to exit with code 1. Warnings are printed to stderr
+//! as `line:column: warning: message [lint]` and do not stop compilation.
 //!
 //! ## Exit Codes
 //!
@@ -82,6 +86,11 @@
 //! infc example.inf --analyze
 //! ```
 //!
+//! Fail on unused variables and silence constant conditions:
+//! ```bash
+//! infc example.inf --analyze --deny unused_variable --allow constant_condition
+//! ```
+//!
 //! Full compilation to WebAssembly:
 //! ```bash
 //! infc example.inf --codegen -o
@@ -92,6 +101,31 @@
 //! infc example.inf --codegen -o -v
 //! ```
 //!
+//! Compile with the built-in backend, without external tools:
+//! ```bash
+//! infc example.inf --codegen --backend builtin -o
+//! ```
+//!
+//! Keep the LLVM IR and object file of an unoptimized build for debugging:
+//! ```bash
+//! infc example.inf --codegen --opt-level 0 --emit=llvm-ir,obj,wasm
+//! ```
+//!
+//! Import the external function `log` as `host.log_i32` instead of `env.log`:
+//! ```bash
+//! infc example.inf --codegen -o --import log=host:log_i32
+//! ```
+//!
+//! Compile a program whose module `math` lives in `math.inf`:
+//! ```bash
+//! infc main.inf --module math=math.inf --codegen -o
+//! ```
+//!
+//! Annotate the Rocq translation with source locations:
+//! ```bash
+//! infc example.inf --codegen -g -v
+//! ```
+//!
 //! Only generate Rocq (no WASM file):
 //! ```bash
 //! infc example.inf --codegen -v
@@ -108,9 +142,8 @@
 //!
 //! ## Current Limitations
 //!
-//! - Single-file compilation only (multi-file projects not yet supported)
+//! - Module files are listed with `--module`; `infc` does not discover them
 //! - Output directory is relative to CWD, not source file location
-//! - Analysis phase is work-in-progress
 //!
 //! ## Tests
 //!
@@ -124,8 +157,11 @@
 
 mod parser;
 use clap::Parser;
-use inference::{analyze, codegen, parse, type_check, wasm_to_v};
-use parser::Cli;
+use inference::{
+    CodegenOptions, LintConfig, LintLevel, analyze_with_lints, codegen_with_options, parse_project,
+    type_check, wasm_to_v,
+};
+use parser::{Cli, EmitArg};
 use std::{
     fs,
     path::PathBuf,
@@ -141,7 +177,7 @@ use std::{
 ///    - Verify source file exists
 ///    - Ensure at least one phase flag is specified
 /// 3. **Execute compilation phases** in canonical order:
-///    - Parse: Build typed AST from source using tree-sitter
+///    - Parse: Build typed AST from the source and module files using tree-sitter
 ///    - Analyze: Type check and semantic validation
 ///    - Codegen: Generate LLVM IR and compile to WebAssembly
 /// 4. **Generate output files** (if requested):
@@ -205,9 +241,19 @@ fn main() {
             process::exit(1);
         }
     };
+    let mut modules = Vec::with_capacity(args.modules.len());
+    for (module_path, file) in &args.modules {
+        match fs::read_to_string(file) {
+            Ok(content) => modules.push((module_path.clone(), content)),
+            Err(e) => {
+                eprintln!("Error reading module file {}: {e}", file.display());
+                process::exit(1);
+            }
+        }
+    }
     let mut t_ast = None;
     if need_codegen || need_analyze || need_parse {
-        match parse(source_code.as_str()) {
+        match parse_project(source_code.as_str(), &modules) {
             Ok(ast) => {
                 println!("Parsed: {}", args.path.display());
                 t_ast = Some(ast);
@@ -233,11 +279,17 @@ fn main() {
                 process::exit(1);
             }
             Ok(tctx) => {
-                typed_context = Some(tctx);
-                if let Err(e) = analyze(typed_context.as_ref().unwrap()) {
-                    eprintln!("Analysis failed: {e}");
+                let diagnostics = analyze_with_lints(&tctx, &lint_config(&args));
+                for diagnostic in &diagnostics {
+                    eprintln!("{diagnostic}");
+                }
+                let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+                if errors > 0 {
+                    let noun = if errors == 1 { "error" } else { "errors" };
+                    eprintln!("Analysis failed: {errors} {noun}");
                     process::exit(1);
                 }
+                typed_context = Some(tctx);
                 println!("Analyzed: {}", args.path.display());
             }
         }
@@ -247,13 +299,30 @@ fn main() {
             eprintln!("Internal error: type check phase did not produce typed context");
             process::exit(1);
         };
-        let wasm = match codegen(&tctx) {
-            Ok(w) => w,
+        let emit_wasm = args.generate_wasm_output || args.emit.contains(&EmitArg::Wasm);
+        let options = CodegenOptions {
+            backend: args.backend.into(),
+            optimization_level: args.opt_level,
+            emit_llvm_ir: args.emit.contains(&EmitArg::LlvmIr),
+            emit_object: args.emit.contains(&EmitArg::Obj),
+            imports: args.imports.iter().cloned().collect(),
+            debug_info: args.debug_info.then(|| {
+                args.path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned()
+            }),
+            ..CodegenOptions::default()
+        };
+        let output = match codegen_with_options(&tctx, &options) {
+            Ok(output) => output,
             Err(e) => {
                 eprintln!("Codegen failed: {e}");
                 process::exit(1);
             }
         };
+        let wasm = output.wasm;
         println!("WASM generated");
         let source_fname = args
             .path
@@ -261,16 +330,19 @@ fn main() {
             .unwrap_or_else(|| std::ffi::OsStr::new("module"))
             .to_str()
             .unwrap();
-        if args.generate_wasm_output {
-            let wasm_file_path = output_path.join(format!("{source_fname}.wasm"));
-            if let Err(e) = fs::create_dir_all(&output_path) {
-                eprintln!("Failed to create output directory: {e}");
-                process::exit(1);
-            }
-            if let Err(e) = fs::write(&wasm_file_path, &wasm) {
-                eprintln!("Failed to write WASM file: {e}");
-                process::exit(1);
-            }
+        if let Some(llvm_ir) = &output.llvm_ir {
+            let ll_file_path = write_output(&output_path, source_fname, "ll", llvm_ir.as_bytes());
+            println!("LLVM IR generated at: {}", ll_file_path.to_string_lossy());
+        }
+        if let Some(object) = &output.object {
+            let obj_file_path = write_output(&output_path, source_fname, "o", object);
+            println!(
+                "Object file generated at: {}",
+                obj_file_path.to_string_lossy()
+            );
+        }
+        if emit_wasm {
+            let wasm_file_path = write_output(&output_path, source_fname, "wasm", &wasm);
             println!("WASM generated at: {}", wasm_file_path.to_string_lossy());
         }
         if args.generate_v_output {
@@ -297,6 +369,42 @@ fn main() {
     process::exit(0);
 }
 
+/// Builds the lint levels from `--allow`, `--warn` and `--deny`, applied in that order
+/// so that a later flag wins for a lint given to several of them.
+fn lint_config(args: &Cli) -> LintConfig {
+    let mut lints = LintConfig::default();
+    for (names, level) in [
+        (&args.allow, LintLevel::Allow),
+        (&args.warn, LintLevel::Warn),
+        (&args.deny, LintLevel::Deny),
+    ] {
+        for lint in names {
+            lints.set(*lint, level);
+        }
+    }
+    lints
+}
+
+/// Writes `contents` to `<output_path>/<source_fname>.<extension>`, creating the
+/// directory if needed, and returns the file path. Exits with code 1 on failure.
+fn write_output(
+    output_path: &std::path::Path,
+    source_fname: &str,
+    extension: &str,
+    contents: &[u8],
+) -> PathBuf {
+    let file_path = output_path.join(format!("{source_fname}.{extension}"));
+    if let Err(e) = fs::create_dir_all(output_path) {
+        eprintln!("Failed to create output directory: {e}");
+        process::exit(1);
+    }
+    if let Err(e) = fs::write(&file_path, contents) {
+        eprintln!("Failed to write {extension} file: {e}");
+        process::exit(1);
+    }
+    file_path
+}
+
 /// Unit test helpers for the CLI module.
 ///
 /// Most CLI testing is done through integration tests in `tests/cli_integration.rs`
@@ -5,7 +5,8 @@
 //!
 //! For comprehensive usage documentation, see `README.md` in this crate.
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use inference::{ImportName, Lint};
 
 /// Command line interface definition for the Inference compiler.
 ///
@@ -48,17 +49,25 @@ use clap::Parser;
     author,
     version,
     about = "Inference compiler CLI (infc)",
-    long_about = "The 'infc' command runs one or more compilation phases over a single .inf source file. \
+    long_about = "The 'infc' command runs one or more compilation phases over a .inf source file and the module files given with --module. \
 Parse builds the typed AST; analyze performs semantic/type inference; codegen emits WASM and can translate to V when -o is supplied."
 )]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Cli {
     /// Path to the source file to compile.
     ///
-    /// Currently only single-file compilation is supported. Multi-file projects
-    /// and project file (`.infp`) support is planned for future releases.
+    /// This is the main file of the program; the files of its modules are given with
+    /// `--module`. Project file (`.infp`) support is planned for future releases.
     pub(crate) path: std::path::PathBuf,
 
+    /// Source file of a module, as `PATH=FILE`.
+    ///
+    /// May be repeated. The definitions of `FILE` are placed in the module `PATH`, such
+    /// as `math` or `shapes::square`, as if they were written inside `mod math { ... }`,
+    /// so other files refer to them as `math::add`.
+    #[clap(long = "module", value_name = "PATH=FILE", value_parser = parse_module)]
+    pub(crate) modules: Vec<(String, std::path::PathBuf)>,
+
     /// Run the parse phase to build the typed AST.
     ///
     /// This phase reads the source file, runs tree-sitter parsing, and constructs
@@ -90,12 +99,80 @@ pub(crate) struct Cli {
     #[clap(long = "codegen", action = clap::ArgAction::SetTrue)]
     pub(crate) codegen: bool,
 
+    /// Code generation backend.
+    ///
+    /// `llvm` (default) compiles through LLVM and needs the external `inf-llc` and
+    /// `rust-lld` binaries. `builtin` emits WebAssembly directly without external tools
+    /// and without optimizations.
+    ///
+    /// This flag has no effect without `--codegen`.
+    #[clap(long = "backend", value_enum, default_value_t = BackendArg::Llvm)]
+    pub(crate) backend: BackendArg,
+
+    /// Optimization level passed to LLVM, from 0 (none) to 3 (aggressive).
+    ///
+    /// The built-in backend does not optimize and ignores this flag.
+    #[clap(
+        long = "opt-level",
+        default_value_t = 3,
+        value_parser = clap::value_parser!(u8).range(0..=3)
+    )]
+    pub(crate) opt_level: u8,
+
+    /// Artifacts to write to the `out/` directory, separated by commas.
+    ///
+    /// - `llvm-ir` writes the LLVM IR given to `inf-llc` to `out/<source_name>.ll`
+    /// - `obj` writes the WebAssembly object file to `out/<source_name>.o`
+    /// - `wasm` writes the linked module to `out/<source_name>.wasm`, like `-o`
+    ///
+    /// `llvm-ir` and `obj` require the LLVM backend. This flag has no effect without
+    /// `--codegen`.
+    #[clap(long = "emit", value_enum, value_delimiter = ',')]
+    pub(crate) emit: Vec<EmitArg>,
+
+    /// Import name of an external function, as `FUNCTION=MODULE:FIELD`.
+    ///
+    /// May be repeated. External functions without this flag are imported from module
+    /// `env` under their own name. This flag has no effect without `--codegen`.
+    #[clap(long = "import", value_name = "FUNCTION=MODULE:FIELD", value_parser = parse_import)]
+    pub(crate) imports: Vec<(String, ImportName)>,
+
+    /// Do not report the lint `LINT`.
+    ///
+    /// May be repeated. Lints are `unused_variable`, `unused_import`,
+    /// `unused_private_function`, `shadowed_binding`, `constant_condition` and
+    /// `implicit_type_parameter`; every lint warns unless allowed or denied. This flag has no effect without `--analyze` or
+    /// `--codegen`.
+    #[clap(long = "allow", value_name = "LINT", value_parser = parse_lint)]
+    pub(crate) allow: Vec<Lint>,
+
+    /// Report the lint `LINT` as a warning, which does not stop compilation.
+    ///
+    /// May be repeated. Overrides `--allow` for the same lint.
+    #[clap(long = "warn", value_name = "LINT", value_parser = parse_lint)]
+    pub(crate) warn: Vec<Lint>,
+
+    /// Report the lint `LINT` as an error, which makes analysis fail.
+    ///
+    /// May be repeated. Overrides `--allow` and `--warn` for the same lint.
+    #[clap(long = "deny", value_name = "LINT", value_parser = parse_lint)]
+    pub(crate) deny: Vec<Lint>,
+
+    /// Record source locations in DWARF line tables.
+    ///
+    /// The generated module maps every instruction to the `line:column` of the statement
+    /// it was lowered from, and the Rocq translation written by `-v` marks the
+    /// instructions with `(* <source_name>.inf:LINE:COLUMN *)` comments. This flag has no
+    /// effect without `--codegen`.
+    #[clap(short = 'g', long = "debug-info", action = clap::ArgAction::SetTrue)]
+    pub(crate) debug_info: bool,
+
     /// Generate output WASM binary file.
     ///
     /// When specified with `--codegen`, writes the compiled WebAssembly binary
     /// to `out/<source_name>.wasm` relative to the current working directory.
     ///
-    /// This flag has no effect without `--codegen`.
+    /// This flag has no effect without `--codegen`. Equivalent to `--emit=wasm`.
     #[clap(short = 'o', action = clap::ArgAction::SetTrue)]
     pub(crate) generate_wasm_output: bool,
 
@@ -112,3 +189,88 @@ pub(crate) struct Cli {
     #[clap(short = 'v', action = clap::ArgAction::SetTrue)]
     pub(crate) generate_v_output: bool,
 }
+
+/// Values accepted by `--backend`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum BackendArg {
+    /// Optimizing backend based on LLVM.
+    Llvm,
+    /// Built-in backend without external dependencies.
+    Builtin,
+}
+
+impl From<BackendArg> for inference::Backend {
+    fn from(backend: BackendArg) -> Self {
+        match backend {
+            BackendArg::Llvm => inference::Backend::Llvm,
+            BackendArg::Builtin => inference::Backend::Builtin,
+        }
+    }
+}
+
+/// Values accepted by `--emit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum EmitArg {
+    /// LLVM IR text (`.ll`).
+    LlvmIr,
+    /// WebAssembly object file (`.o`).
+    Obj,
+    /// Linked WebAssembly module (`.wasm`).
+    Wasm,
+}
+
+/// Parses a `--import` value of the form `FUNCTION=MODULE:FIELD`.
+fn parse_import(value: &str) -> Result<(String, ImportName), String> {
+    let (function, import) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected FUNCTION=MODULE:FIELD, found `{value}`"))?;
+    let (module, field) = import
+        .split_once(':')
+        .ok_or_else(|| format!("expected MODULE:FIELD after `=`, found `{import}`"))?;
+    if function.is_empty() || module.is_empty() || field.is_empty() {
+        return Err(format!(
+            "function, module and field must not be empty in `{value}`"
+        ));
+    }
+    Ok((
+        function.to_string(),
+        ImportName {
+            module: module.to_string(),
+            field: field.to_string(),
+        },
+    ))
+}
+
+/// Parses a `--module` value of the form `PATH=FILE`, where `PATH` is a module path such
+/// as `shapes::square`.
+fn parse_module(value: &str) -> Result<(String, std::path::PathBuf), String> {
+    let (path, file) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected PATH=FILE, found `{value}`"))?;
+    let is_identifier = |segment: &str| {
+        segment.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    if !path.split("::").all(is_identifier) {
+        return Err(format!(
+            "module path `{path}` must be identifiers separated by `::`"
+        ));
+    }
+    if file.is_empty() {
+        return Err(format!("file must not be empty in `{value}`"));
+    }
+    Ok((path.to_string(), std::path::PathBuf::from(file)))
+}
+
+/// Parses the name of a lint given to `--allow`, `--warn` or `--deny`.
+fn parse_lint(value: &str) -> Result<Lint, String> {
+    Lint::from_name(value).ok_or_else(|| {
+        let names: Vec<&str> = Lint::ALL.iter().map(|lint| lint.name()).collect();
+        format!(
+            "unknown lint `{value}`, expected one of: {}",
+            names.join(", ")
+        )
+    })
+}
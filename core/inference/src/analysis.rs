@@ -0,0 +1,704 @@
+//! Semantic analysis of the typed AST.
+//!
+//! These checks run after type checking and reject programs that are well typed but
+//! whose control flow is wrong or whose non-deterministic code cannot be interpreted by
+//! a verifier. Every function body is analyzed, including methods and functions of
+//! nested modules and specs:
+//!
+//! - A function that returns a value must return it on every path; falling off the end
+//!   of its body is a [`AnalysisError::MissingReturn`]. A function returning a spec is
+//!   the constructor of the spec and returns no value
+//! - `break` must be inside a loop, see [`AnalysisError::BreakOutsideLoop`]
+//! - A statement that follows a `return`, a `break` or a `loop` without a condition that
+//!   is never left is an [`AnalysisError::UnreachableStatement`]
+//! - `@` must be the whole value of a variable definition, an assignment or a `return`,
+//!   see [`AnalysisError::MisplacedUzumaki`]
+//! - An `assume` block must not assign to variables declared outside of it, nor call a
+//!   `mut self` method on them, see [`AnalysisError::SideEffectInAssume`]
+//! - `return`, and `break` out of a loop around the block, cannot leave a `forall`,
+//!   `exists` or `unique` block, see [`AnalysisError::QuantifierEscape`]
+//! - An exported function must not return a non-deterministic value computed outside of
+//!   a quantifier, see [`AnalysisError::NondeterministicResult`]
+//!
+//! Errors of a function are reported in the order of their locations.
+//!
+//! ## Control Flow
+//!
+//! The analysis follows the structure of the body: a block completes if its last
+//! statement does, an `if` completes if either arm does (an `if` without `else` always
+//! completes), and a loop with a condition always completes, since the condition may be
+//! false. A `loop` without a condition only completes if one of its `break`s is
+//! reachable; a `break` in a nested loop leaves that loop only. Conditions are not
+//! evaluated, so `if true { return 1; }` is not considered to always return.
+//!
+//! ## Non-determinism
+//!
+//! Each `@` introduces a value the verifier reasons about for every possibility, so it
+//! has to be bound to a name (or be the returned value) rather than be an operand,
+//! argument or condition. A `forall`, `exists` or `unique` block quantifies over the
+//! values bound inside it; control has to reach its end for the quantifier to mean
+//! anything, hence no `return` or `break` leaves it. An `assume` block only filters
+//! execution paths, so it may call functions and bind its own variables but not change
+//! the variables around it.
+//!
+//! A variable is non-deterministic if it is bound, outside of a quantifier, to `@` or to
+//! an expression that reads a non-deterministic variable. Values bound inside a
+//! quantifier are quantified over and do not make a variable non-deterministic. The
+//! analysis does not follow calls, and a variable stays non-deterministic once it is,
+//! whatever is assigned to it later. Exported functions are the public free functions,
+//! as in the default export policy of the code generator.
+
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use inference_ast::nodes::{
+    ArgumentType, BlockType, Definition, Expression, FunctionDefinition, Literal, Location,
+    SimpleTypeKind, Statement, Type, Visibility,
+};
+use inference_type_checker::{
+    type_info::{TypeInfo, TypeInfoKind},
+    typed_context::TypedContext,
+};
+use thiserror::Error;
+
+/// Semantic error found by [`analyze`](crate::analyze).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AnalysisError {
+    #[error("{location}: not all paths return a value in function `{name}`")]
+    MissingReturn { name: String, location: Location },
+
+    #[error("{location}: `break` outside of a loop")]
+    BreakOutsideLoop { location: Location },
+
+    /// A statement after one that never completes; `cause` is the location of the
+    /// `return`, `break` or statement that never completes.
+    #[error("{location}: unreachable statement, control never gets past {cause}")]
+    UnreachableStatement { cause: Location, location: Location },
+
+    #[error(
+        "{location}: `@` can only be the value of a variable definition, an assignment or a `return`"
+    )]
+    MisplacedUzumaki { location: Location },
+
+    /// An assignment in an `assume` block to `name`, which is declared outside of it, or a
+    /// call of a `mut self` method on it.
+    #[error("{location}: `assume` block assigns to `{name}`, which is declared outside of it")]
+    SideEffectInAssume { name: String, location: Location },
+
+    /// A `return` or `break` (`statement`) that leaves a `forall`, `exists` or `unique`
+    /// block (`block`).
+    #[error(
+        "{location}: `{statement}` cannot leave {} `{block}` block",
+        indefinite_article(.block)
+    )]
+    QuantifierEscape {
+        statement: &'static str,
+        block: &'static str,
+        location: Location,
+    },
+
+    #[error(
+        "{location}: exported function `{name}` returns a non-deterministic value outside of a quantifier"
+    )]
+    NondeterministicResult { name: String, location: Location },
+}
+
+impl AnalysisError {
+    /// Returns the source location associated with this error.
+    #[must_use]
+    pub fn location(&self) -> &Location {
+        match self {
+            AnalysisError::MissingReturn { location, .. }
+            | AnalysisError::BreakOutsideLoop { location }
+            | AnalysisError::UnreachableStatement { location, .. }
+            | AnalysisError::MisplacedUzumaki { location }
+            | AnalysisError::SideEffectInAssume { location, .. }
+            | AnalysisError::QuantifierEscape { location, .. }
+            | AnalysisError::NondeterministicResult { location, .. } => location,
+        }
+    }
+}
+
+/// Analyzes every function body in the typed context.
+///
+/// Errors are ordered by source file, then by function, then by position in the function.
+pub(crate) fn check(typed_context: &TypedContext) -> Vec<AnalysisError> {
+    let mut source_files = typed_context.source_files();
+    source_files.sort_by_key(|source_file| source_file.id);
+    let mut mutating_methods = HashSet::new();
+    for source_file in &source_files {
+        collect_mutating_methods(&source_file.definitions, "", &mut mutating_methods);
+    }
+    let mut errors = Vec::new();
+    for source_file in &source_files {
+        let mut functions = Vec::new();
+        collect_functions(&source_file.definitions, &mut functions);
+        for (function_definition, exported) in functions {
+            let mut control_flow_checker = ControlFlowChecker {
+                typed_context,
+                errors: Vec::new(),
+                loop_depth: 0,
+                quantifier: None,
+            };
+            control_flow_checker.check_function(function_definition);
+            let mut function_errors = control_flow_checker.errors;
+            function_errors.extend(NondeterminismChecker::check_function(
+                typed_context,
+                &mutating_methods,
+                function_definition,
+                exported,
+            ));
+            function_errors.sort_by_key(|error| error.location().offset_start);
+            errors.extend(function_errors);
+        }
+    }
+    errors
+}
+
+/// Collects the functions of `definitions` in order, with whether each is exported.
+pub(crate) fn collect_functions<'a>(
+    definitions: &'a [Definition],
+    functions: &mut Vec<(&'a Rc<FunctionDefinition>, bool)>,
+) {
+    for definition in definitions {
+        match definition {
+            Definition::Function(function_definition) => {
+                let exported = function_definition.visibility == Visibility::Public;
+                functions.push((function_definition, exported));
+            }
+            Definition::Struct(struct_definition) => {
+                functions.extend(
+                    struct_definition
+                        .methods
+                        .iter()
+                        .map(|method| (method, false)),
+                );
+            }
+            Definition::Module(module_definition) => {
+                if let Some(body) = &module_definition.body {
+                    collect_functions(body, functions);
+                }
+            }
+            Definition::Spec(spec_definition) => {
+                collect_functions(&spec_definition.definitions, functions);
+            }
+            Definition::Enum(_)
+            | Definition::Constant(_)
+            | Definition::ExternalFunction(_)
+            | Definition::Type(_) => {}
+        }
+    }
+}
+
+/// Collects the symbols of the methods taking `mut self` among `definitions`, which
+/// belong to the module at `path`: `Point::shift` at the top level, `math::Point::shift`
+/// in `mod math`.
+fn collect_mutating_methods(definitions: &[Definition], path: &str, methods: &mut HashSet<String>) {
+    let qualify = |name: String| {
+        if path.is_empty() {
+            name
+        } else {
+            format!("{path}::{name}")
+        }
+    };
+    for definition in definitions {
+        match definition {
+            Definition::Struct(struct_definition) => {
+                for method in &struct_definition.methods {
+                    let mut_self = method.arguments.iter().flatten().any(|argument| {
+                        matches!(argument, ArgumentType::SelfReference(self_ref) if self_ref.is_mut)
+                    });
+                    if mut_self {
+                        methods.insert(qualify(format!(
+                            "{}::{}",
+                            struct_definition.name(),
+                            method.name()
+                        )));
+                    }
+                }
+            }
+            Definition::Module(module_definition) => {
+                collect_mutating_methods(
+                    module_definition.body.as_deref().unwrap_or_default(),
+                    &qualify(module_definition.name()),
+                    methods,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the keyword of a `forall`, `exists` or `unique` block.
+fn quantifier_keyword(block: &BlockType) -> Option<&'static str> {
+    match block {
+        BlockType::Forall(_) => Some("forall"),
+        BlockType::Exists(_) => Some("exists"),
+        BlockType::Unique(_) => Some("unique"),
+        BlockType::Block(_) | BlockType::Assume(_) => None,
+    }
+}
+
+/// Returns the indefinite article to put before a quantifier keyword.
+fn indefinite_article(keyword: &str) -> &'static str {
+    if keyword == "exists" { "an" } else { "a" }
+}
+
+/// How control leaves a statement.
+#[derive(Debug, Clone, Copy)]
+struct Flow {
+    /// Whether execution may continue with the next statement.
+    completes: bool,
+
+    /// Whether some path leaves the innermost enclosing loop with `break`.
+    breaks: bool,
+}
+
+impl Flow {
+    const COMPLETES: Self = Self {
+        completes: true,
+        breaks: false,
+    };
+
+    const DIVERGES: Self = Self {
+        completes: false,
+        breaks: false,
+    };
+}
+
+struct ControlFlowChecker<'a> {
+    typed_context: &'a TypedContext,
+
+    errors: Vec<AnalysisError>,
+
+    /// Number of loops around the statement being analyzed.
+    loop_depth: u32,
+
+    /// Keyword of the innermost `forall`, `exists` or `unique` block around the
+    /// statement, with the number of loops around that block.
+    quantifier: Option<(&'static str, u32)>,
+}
+
+impl ControlFlowChecker<'_> {
+    fn check_function(&mut self, function_definition: &Rc<FunctionDefinition>) {
+        let flow = self.check_block(&function_definition.body);
+        let returns_value = match &function_definition.returns {
+            None | Some(Type::Simple(SimpleTypeKind::Unit)) => false,
+            Some(return_type) => !matches!(
+                self.typed_context.get_node_typeinfo(return_type.id()),
+                Some(TypeInfo {
+                    kind: TypeInfoKind::Spec(_),
+                    ..
+                })
+            ),
+        };
+        if returns_value && flow.completes {
+            self.errors.push(AnalysisError::MissingReturn {
+                name: function_definition.name(),
+                location: function_definition.location,
+            });
+        }
+    }
+
+    /// Analyzes the statements of a block in order. Only the first unreachable statement
+    /// is reported, but the rest are still analyzed for other errors.
+    fn check_block(&mut self, block: &BlockType) -> Flow {
+        let outer_quantifier = self.quantifier;
+        if let Some(keyword) = quantifier_keyword(block) {
+            self.quantifier = Some((keyword, self.loop_depth));
+        }
+        let mut flow = Flow::COMPLETES;
+        let mut diverged_at: Option<Location> = None;
+        let mut reported = false;
+        for statement in block.statements() {
+            if let Some(cause) = diverged_at
+                && !reported
+            {
+                self.errors.push(AnalysisError::UnreachableStatement {
+                    cause,
+                    location: statement.location(),
+                });
+                reported = true;
+            }
+            let statement_flow = self.check_statement(&statement);
+            flow.breaks |= statement_flow.breaks && diverged_at.is_none();
+            if !statement_flow.completes && diverged_at.is_none() {
+                diverged_at = Some(statement.location());
+                flow.completes = false;
+            }
+        }
+        self.quantifier = outer_quantifier;
+        flow
+    }
+
+    fn check_statement(&mut self, statement: &Statement) -> Flow {
+        match statement {
+            Statement::Block(block_type) => self.check_block(block_type),
+            Statement::Return(return_statement) => {
+                if let Some((block, _)) = self.quantifier {
+                    self.errors.push(AnalysisError::QuantifierEscape {
+                        statement: "return",
+                        block,
+                        location: return_statement.location,
+                    });
+                }
+                Flow::DIVERGES
+            }
+            Statement::Break(break_statement) => {
+                if self.loop_depth == 0 {
+                    self.errors.push(AnalysisError::BreakOutsideLoop {
+                        location: break_statement.location,
+                    });
+                    return Flow::DIVERGES;
+                }
+                if let Some((block, loop_depth)) = self.quantifier
+                    && loop_depth == self.loop_depth
+                {
+                    self.errors.push(AnalysisError::QuantifierEscape {
+                        statement: "break",
+                        block,
+                        location: break_statement.location,
+                    });
+                }
+                Flow {
+                    completes: false,
+                    breaks: true,
+                }
+            }
+            Statement::If(if_statement) => {
+                let if_flow = self.check_block(&if_statement.if_arm);
+                match &if_statement.else_arm {
+                    Some(else_arm) => {
+                        let else_flow = self.check_block(else_arm);
+                        Flow {
+                            completes: if_flow.completes || else_flow.completes,
+                            breaks: if_flow.breaks || else_flow.breaks,
+                        }
+                    }
+                    None => Flow {
+                        completes: true,
+                        breaks: if_flow.breaks,
+                    },
+                }
+            }
+            Statement::Loop(loop_statement) => {
+                self.loop_depth += 1;
+                let body_flow = self.check_block(&loop_statement.body);
+                self.loop_depth -= 1;
+                Flow {
+                    completes: loop_statement.condition.borrow().is_some() || body_flow.breaks,
+                    breaks: false,
+                }
+            }
+            Statement::Expression(_)
+            | Statement::Assign(_)
+            | Statement::VariableDefinition(_)
+            | Statement::TypeDefinition(_)
+            | Statement::Assert(_)
+            | Statement::ConstantDefinition(_) => Flow::COMPLETES,
+        }
+    }
+}
+
+struct NondeterminismChecker<'a> {
+    typed_context: &'a TypedContext,
+
+    /// Symbols of the methods taking `mut self`, which change their receiver.
+    mutating_methods: &'a HashSet<String>,
+
+    errors: Vec<AnalysisError>,
+
+    /// Name of the function being analyzed, if it is exported.
+    exported: Option<String>,
+
+    /// Variables in scope, innermost scope last. Each name maps to the id of the node
+    /// that declares it.
+    scopes: Vec<HashMap<String, u32>>,
+
+    /// Index in `scopes` of the scope of the innermost `assume` block around the
+    /// statement.
+    assume_scope: Option<usize>,
+
+    /// Number of `forall`, `exists` and `unique` blocks around the statement.
+    quantifier_depth: u32,
+
+    /// Declarations of the variables that may hold a non-deterministic value.
+    nondeterministic: HashSet<u32>,
+}
+
+impl<'a> NondeterminismChecker<'a> {
+    /// Analyzes the body of a function until no more variables become
+    /// non-deterministic, so that a value flowing backwards through a loop is seen, and
+    /// returns the errors of the last pass.
+    fn check_function(
+        typed_context: &'a TypedContext,
+        mutating_methods: &'a HashSet<String>,
+        function_definition: &Rc<FunctionDefinition>,
+        exported: bool,
+    ) -> Vec<AnalysisError> {
+        let mut checker = Self {
+            typed_context,
+            mutating_methods,
+            errors: Vec::new(),
+            exported: exported.then(|| function_definition.name()),
+            scopes: Vec::new(),
+            assume_scope: None,
+            quantifier_depth: 0,
+            nondeterministic: HashSet::new(),
+        };
+        loop {
+            let known = checker.nondeterministic.len();
+            checker.errors.clear();
+            checker.scopes = vec![parameters(function_definition)];
+            checker.check_block(&function_definition.body);
+            if checker.nondeterministic.len() == known {
+                return checker.errors;
+            }
+        }
+    }
+
+    fn check_block(&mut self, block: &BlockType) {
+        let outer_assume_scope = self.assume_scope;
+        let quantifier = quantifier_keyword(block).is_some();
+        self.scopes.push(HashMap::new());
+        if matches!(block, BlockType::Assume(_)) {
+            self.assume_scope = Some(self.scopes.len() - 1);
+        }
+        if quantifier {
+            self.quantifier_depth += 1;
+        }
+        for statement in block.statements() {
+            self.check_statement(&statement);
+        }
+        if quantifier {
+            self.quantifier_depth -= 1;
+        }
+        self.assume_scope = outer_assume_scope;
+        self.scopes.pop();
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block_type) => self.check_block(block_type),
+            Statement::Expression(expression) => self.check_expression(expression),
+            Statement::Assign(assign_statement) => {
+                let left = assign_statement.left.borrow();
+                let right = assign_statement.right.borrow();
+                self.check_expression(&left);
+                self.check_value(&right);
+                let Some(name) = assigned_variable(&left) else {
+                    return;
+                };
+                self.check_change_in_assume(&name, assign_statement.location);
+                let declaration = self.lookup(&name);
+                if let Some((_, id)) = declaration
+                    && self.quantifier_depth == 0
+                    && self.is_nondeterministic(&right)
+                {
+                    self.nondeterministic.insert(id);
+                }
+            }
+            Statement::Return(return_statement) => {
+                let expression = return_statement.expression.borrow();
+                self.check_value(&expression);
+                if let Some(name) = &self.exported
+                    && self.quantifier_depth == 0
+                    && self.is_nondeterministic(&expression)
+                {
+                    self.errors.push(AnalysisError::NondeterministicResult {
+                        name: name.clone(),
+                        location: return_statement.location,
+                    });
+                }
+            }
+            Statement::Loop(loop_statement) => {
+                if let Some(condition) = &*loop_statement.condition.borrow() {
+                    self.check_expression(condition);
+                }
+                self.check_block(&loop_statement.body);
+            }
+            Statement::If(if_statement) => {
+                self.check_expression(&if_statement.condition.borrow());
+                self.check_block(&if_statement.if_arm);
+                if let Some(else_arm) = &if_statement.else_arm {
+                    self.check_block(else_arm);
+                }
+            }
+            Statement::VariableDefinition(variable_definition) => {
+                if let Some(value) = &variable_definition.value {
+                    let value = value.borrow();
+                    self.check_value(&value);
+                    if self.quantifier_depth == 0 && self.is_nondeterministic(&value) {
+                        self.nondeterministic.insert(variable_definition.id);
+                    }
+                }
+                self.declare(&variable_definition.name.name, variable_definition.id);
+            }
+            Statement::ConstantDefinition(constant_definition) => {
+                self.declare(&constant_definition.name.name, constant_definition.id);
+            }
+            Statement::Assert(assert_statement) => {
+                self.check_expression(&assert_statement.expression.borrow());
+            }
+            Statement::Break(_) | Statement::TypeDefinition(_) => {}
+        }
+    }
+
+    /// Checks the value of a variable definition, an assignment or a `return`, which may
+    /// be `@`.
+    fn check_value(&mut self, expression: &Expression) {
+        if !matches!(expression, Expression::Uzumaki(_)) {
+            self.check_expression(expression);
+        }
+    }
+
+    /// Reports every `@` in the expression, and every call of a `mut self` method that
+    /// changes a variable declared outside of the enclosing `assume` block.
+    fn check_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Uzumaki(uzumaki_expression) => {
+                self.errors.push(AnalysisError::MisplacedUzumaki {
+                    location: uzumaki_expression.location,
+                });
+            }
+            Expression::FunctionCall(function_call) => {
+                if let Expression::MemberAccess(member_access) = &function_call.function
+                    && let Some(TypeInfo {
+                        kind: TypeInfoKind::Function(method),
+                        ..
+                    }) = self.typed_context.get_node_typeinfo(member_access.id)
+                    && self.mutating_methods.contains(&method)
+                    && let Some(name) = assigned_variable(&member_access.expression.borrow())
+                {
+                    self.check_change_in_assume(&name, function_call.location);
+                }
+            }
+            _ => {}
+        }
+        for operand in operands(expression) {
+            self.check_expression(&operand);
+        }
+    }
+
+    /// Reports a change at `location` of the variable `name` if it is declared outside of
+    /// the enclosing `assume` block.
+    fn check_change_in_assume(&mut self, name: &str, location: Location) {
+        if let Some(assume_scope) = self.assume_scope
+            && self
+                .lookup(name)
+                .is_none_or(|(scope, _)| scope < assume_scope)
+        {
+            self.errors.push(AnalysisError::SideEffectInAssume {
+                name: name.to_string(),
+                location,
+            });
+        }
+    }
+
+    /// Returns whether the expression is `@` or reads a non-deterministic variable.
+    fn is_nondeterministic(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Uzumaki(_) => true,
+            Expression::Identifier(identifier) => self
+                .lookup(&identifier.name)
+                .is_some_and(|(_, id)| self.nondeterministic.contains(&id)),
+            _ => operands(expression)
+                .iter()
+                .any(|operand| self.is_nondeterministic(operand)),
+        }
+    }
+
+    fn declare(&mut self, name: &str, id: u32) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), id);
+        }
+    }
+
+    /// Returns the index of the scope declaring `name` and the id of the declaration.
+    fn lookup(&self, name: &str) -> Option<(usize, u32)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, scope)| scope.get(name).map(|id| (index, *id)))
+    }
+}
+
+/// Returns the scope of the parameters of a function.
+fn parameters(function_definition: &FunctionDefinition) -> HashMap<String, u32> {
+    let mut scope = HashMap::new();
+    for argument in function_definition.arguments.iter().flatten() {
+        match argument {
+            ArgumentType::Argument(argument) => {
+                scope.insert(argument.name.name.clone(), argument.id);
+            }
+            ArgumentType::SelfReference(self_reference) => {
+                scope.insert("self".to_string(), self_reference.id);
+            }
+            ArgumentType::IgnoreArgument(_) | ArgumentType::Type(_) => {}
+        }
+    }
+    scope
+}
+
+/// Returns the name of the variable an assignment to `target` changes: the variable
+/// itself or the one whose field or element is assigned.
+fn assigned_variable(target: &Expression) -> Option<String> {
+    match target {
+        Expression::Identifier(identifier) => Some(identifier.name.clone()),
+        Expression::MemberAccess(member_access) => {
+            assigned_variable(&member_access.expression.borrow())
+        }
+        Expression::ArrayIndexAccess(array_index_access) => {
+            assigned_variable(&array_index_access.array.borrow())
+        }
+        Expression::Parenthesized(parenthesized) => {
+            assigned_variable(&parenthesized.expression.borrow())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the expressions an expression is built from.
+pub(crate) fn operands(expression: &Expression) -> Vec<Expression> {
+    match expression {
+        Expression::ArrayIndexAccess(array_index_access) => vec![
+            array_index_access.array.borrow().clone(),
+            array_index_access.index.borrow().clone(),
+        ],
+        Expression::Binary(binary) => {
+            vec![binary.left.borrow().clone(), binary.right.borrow().clone()]
+        }
+        Expression::MemberAccess(member_access) => vec![member_access.expression.borrow().clone()],
+        Expression::FunctionCall(function_call) => std::iter::once(function_call.function.clone())
+            .chain(
+                function_call
+                    .arguments
+                    .iter()
+                    .flatten()
+                    .map(|(_, argument)| argument.borrow().clone()),
+            )
+            .collect(),
+        Expression::Struct(struct_expression) => struct_expression
+            .fields
+            .iter()
+            .flatten()
+            .map(|(_, value)| value.borrow().clone())
+            .collect(),
+        Expression::PrefixUnary(prefix_unary) => vec![prefix_unary.expression.borrow().clone()],
+        Expression::Parenthesized(parenthesized) => vec![parenthesized.expression.borrow().clone()],
+        Expression::Literal(Literal::Array(array_literal)) => array_literal
+            .elements
+            .iter()
+            .flatten()
+            .map(|element| element.borrow().clone())
+            .collect(),
+        Expression::TypeMemberAccess(_)
+        | Expression::Literal(_)
+        | Expression::Identifier(_)
+        | Expression::Type(_)
+        | Expression::Uzumaki(_) => Vec::new(),
+    }
+}
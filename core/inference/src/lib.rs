@@ -68,28 +68,35 @@
 //! 3. **Resolve imports**: Bind import paths to symbols from other modules
 //! 4. **Collect functions**: Register function signatures and constants
 //! 5. **Infer variables**: Type-check function bodies and local variables
+//! 6. **Check imports**: Warn about imported names that are never used
 //!
-//! The result is a [`TypedContext`] that maps AST nodes to their inferred types.
+//! The result is a [`TypedContext`] that maps AST nodes to their inferred types and
+//! keeps the warnings found along the way.
 //!
 //! [`TypedContext`]: inference_type_checker::typed_context::TypedContext
 //!
 //! ### Phase 3: Analyze
 //!
-//! Performs semantic analysis on the typed AST. This phase is currently under
-//! active development (WIP) and serves as a placeholder for future semantic
-//! analysis passes.
+//! Performs semantic analysis on the typed AST: checks that rely on control flow
+//! rather than types, such as a function that does not return a value on every
+//! path, `break` outside of a loop and unreachable statements, and the rules of
+//! non-deterministic code, such as where `@` may appear and what `assume` and
+//! quantifier blocks may do. See [`analysis`].
 //!
 //! ```rust,no_run
 //! use inference::{parse, type_check, analyze};
 //!
-//! let source = "fn main() { return 0; }";
+//! let source = "fn main() -> i32 { return 0; }";
 //! let arena = parse(source)?;
 //! let typed_context = type_check(arena)?;
 //! analyze(&typed_context)?;
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 //!
-//! **Status**: Work in progress. Currently returns `Ok(())` without performing checks.
+//! [`analyze_with_lints`] reports the same errors as [`Diagnostic`]s, together with
+//! warnings for code that is valid but likely a mistake, such as unused variables.
+//! Each [`Lint`] can be allowed, or denied to turn its warnings into errors, with a
+//! [`LintConfig`]. Warnings do not block code generation.
 //!
 //! ### Phase 4: Codegen
 //!
@@ -153,7 +160,7 @@
 //! │                                               └─────────────┘
 //! └─────────────────────────────────────────────────────────────┘
 //!          ↓              ↓              ↓              ↓
-//!   inference_ast  type_checker  (WIP)  wasm_codegen  wasm_to_v
+//!   inference_ast  type_checker  analysis  wasm_codegen  wasm_to_v
 //! ```
 //!
 //! ## Error Handling
@@ -227,10 +234,9 @@
 //!
 //! ## Limitations
 //!
-//! - **Single-file support**: Multi-file compilation is not yet implemented.
-//!   The AST expects a single source file as input.
-//! - **Analyze phase**: The semantic analysis phase is work-in-progress and
-//!   currently returns `Ok(())` without performing any checks.
+//! - **Module files**: the grammar has no module declarations yet. [`parse_project`]
+//!   builds a project split into several files by giving each module file its module
+//!   path, and codegen links all of them into one module.
 //! - **External dependencies**: Code generation requires `inf-llc` and `rust-lld`
 //!   binaries in the `external/bin/` directory.
 //!
@@ -252,6 +258,8 @@
 //! - [`inference_type_checker::TypeCheckerBuilder`] - Type checking entry point
 //! - [`inference_type_checker::typed_context::TypedContext`] - Type information storage
 //! - [`inference_wasm_codegen::codegen`] - WebAssembly code generation entry point
+//! - [`inference_wasm_codegen::codegen_with_backend`] - Code generation with a chosen backend
+//! - [`inference_wasm_codegen::codegen_with_options`] - Code generation with full [`CodegenOptions`]
 //! - [`inference_wasm_to_v_translator::wasm_parser`] - WASM to Rocq translation
 //!
 //! ### External Resources
@@ -263,6 +271,15 @@
 
 use inference_ast::{arena::Arena, builder::Builder};
 use inference_type_checker::typed_context::TypedContext;
+pub use inference_wasm_codegen::{
+    Backend, CodegenOptions, CodegenOutput, ExportPolicy, FAILED_ASSERTION_ADDRESS, ImportName,
+};
+
+pub use crate::analysis::AnalysisError;
+pub use inference_ast::diagnostics::{Diagnostic, Lint, LintConfig, LintLevel, Severity};
+
+pub mod analysis;
+mod lints;
 
 /// Parses source code and builds an arena-based Abstract Syntax Tree.
 ///
@@ -349,18 +366,53 @@ use inference_type_checker::typed_context::TypedContext;
 /// [`Builder`]: inference_ast::builder::Builder
 /// [`Arena`]: inference_ast::arena::Arena
 pub fn parse(source_code: &str) -> anyhow::Result<Arena> {
+    parse_project(source_code, &[])
+}
+
+/// Parses a project split into a main file and one file per module, and builds a single
+/// [`Arena`] holding all of them.
+///
+/// Each module is given as its path, such as `math` or `shapes::square`, and its source
+/// code. The definitions of a module file are placed in that module, as if they were
+/// written inside `mod math { ... }`, so the main file refers to them as `math::add`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inference::parse_project;
+///
+/// let main = "pub fn run() -> i32 { return math::one(); }";
+/// let math = "pub fn one() -> i32 { return 1; }";
+/// let arena = parse_project(main, &[("math".to_string(), math.to_string())])?;
+/// assert_eq!(arena.source_files().len(), 2);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if any of the files contains syntax errors, see [`parse`].
+pub fn parse_project(main_source: &str, modules: &[(String, String)]) -> anyhow::Result<Arena> {
     let inference_language = tree_sitter_inference::language();
     let mut parser = tree_sitter::Parser::new();
     parser
         .set_language(&inference_language)
         .map_err(|e| anyhow::anyhow!("Failed to load Inference grammar: {e}"))?;
-    let tree = parser
-        .parse(source_code, None)
+    let main_tree = parser
+        .parse(main_source, None)
         .ok_or_else(|| anyhow::anyhow!("Failed to parse source code"))?;
-    let code = source_code.as_bytes();
-    let root_node = tree.root_node();
+    let module_trees = modules
+        .iter()
+        .map(|(path, source)| {
+            parser
+                .parse(source, None)
+                .ok_or_else(|| anyhow::anyhow!("Failed to parse module `{path}`"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
     let mut builder = Builder::new();
-    builder.add_source_code(root_node, code);
+    builder.add_source_code(main_tree.root_node(), main_source.as_bytes());
+    for ((path, source), tree) in modules.iter().zip(&module_trees) {
+        builder.add_module_source_code(path, tree.root_node(), source.as_bytes());
+    }
     let arena = builder.build_ast()?;
     Ok(arena)
 }
@@ -485,52 +537,98 @@ pub fn type_check(arena: Arena) -> anyhow::Result<TypedContext> {
 
 /// Performs semantic analysis on the typed AST.
 ///
-/// This function is currently a placeholder for future semantic analysis passes.
-/// Planned analyses include:
-/// - Dead code detection
-/// - Unused variable warnings
-/// - Unreachable code analysis
-/// - Control flow validation
-/// - Initialization checking
-///
-/// # Current Status
-///
-/// **Work in Progress**: This phase is under active development and currently
-/// returns `Ok(())` without performing any checks. Once implemented, it will
-/// provide additional semantic guarantees beyond type correctness.
+/// Checks every function body, see [`analysis`]:
+/// - Functions that return a value must return it on every path
+/// - `break` must be inside a loop
+/// - No statement may follow a `return`, a `break` or a loop that is never left
+/// - `@` is only the whole value of a variable definition, an assignment or a `return`
+/// - `assume` blocks do not assign to variables declared outside of them
+/// - No `return` or `break` leaves a `forall`, `exists` or `unique` block
+/// - Exported functions do not return non-deterministic values computed outside of a
+///   quantifier
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// use inference::{parse, type_check, analyze};
 ///
-/// let source = r#"fn main() { return 0; }"#;
+/// let source = r#"fn main() -> i32 { return 0; }"#;
 /// let arena = parse(source)?;
 /// let typed_context = type_check(arena)?;
-///
-/// // Currently a no-op, but will perform semantic checks in the future
 /// analyze(&typed_context)?;
 /// # Ok::<(), anyhow::Error>(())
 /// ```
 ///
+/// Warnings are not reported; use [`analyze_with_lints`] to get them as well.
+///
 /// # Errors
 ///
-/// Currently always returns `Ok(())`. Future implementations will return errors
-/// for semantic violations that are not type errors, such as:
-/// - Use of uninitialized variables
-/// - Unreachable code paths
-/// - Dead code that should be removed
-/// - Control flow violations (e.g., missing return statements)
-/// - Infinite loops without break conditions
+/// Returns an error listing every [`AnalysisError`] found, separated by `"; "`.
 ///
 /// # Parameters
 ///
 /// - `typed_context`: The typed AST context from [`type_check`]
-pub fn analyze(_: &TypedContext) -> anyhow::Result<()> {
-    // todo!("Type analysis not yet implemented");
+pub fn analyze(typed_context: &TypedContext) -> anyhow::Result<()> {
+    let messages: Vec<String> = analysis::check(typed_context)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    if !messages.is_empty() {
+        anyhow::bail!(messages.join("; "));
+    }
     Ok(())
 }
 
+/// Performs semantic analysis on the typed AST and runs the lints.
+///
+/// Returns the [`AnalysisError`]s of [`analyze`] as error diagnostics, together with the
+/// warnings of the type checker and of the lints of the analyze phase, in the order of
+/// their locations. The levels of `lints` are applied: diagnostics of allowed lints are
+/// dropped and those of denied lints become errors. The program is rejected if any of
+/// the diagnostics is an error, see [`Diagnostic::is_error`]; warnings do not block code
+/// generation.
+///
+/// Lints:
+/// - [`Lint::UnusedVariable`] - `let` variables that are never read
+/// - [`Lint::UnusedImport`] - imported names that are never used
+/// - [`Lint::UnusedPrivateFunction`] - private functions that are never used
+/// - [`Lint::ShadowedBinding`] - `let`s hiding a binding of an enclosing scope
+/// - [`Lint::ConstantCondition`] - `if` conditions built from literals only
+/// - [`Lint::ImplicitTypeParameter`] - struct field types naming an undefined lowercase
+///   type, which make the struct generic
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inference::{Lint, LintConfig, LintLevel, analyze_with_lints, parse, type_check};
+///
+/// let source = r#"fn main() -> i32 { let unused: i32 = 1; return 0; }"#;
+/// let typed_context = type_check(parse(source)?)?;
+/// let mut lints = LintConfig::default();
+/// lints.set(Lint::UnusedVariable, LintLevel::Deny);
+/// let diagnostics = analyze_with_lints(&typed_context, &lints);
+/// assert!(diagnostics.iter().any(|diagnostic| diagnostic.is_error()));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// # Parameters
+///
+/// - `typed_context`: The typed AST context from [`type_check`]
+/// - `lints`: The level of each lint; lints that are not set warn
+#[must_use]
+pub fn analyze_with_lints(typed_context: &TypedContext, lints: &LintConfig) -> Vec<Diagnostic> {
+    let mut diagnostics = typed_context.diagnostics().to_vec();
+    diagnostics.extend(
+        analysis::check(typed_context)
+            .iter()
+            .map(|error| Diagnostic::from_error(*error.location(), error)),
+    );
+    diagnostics.extend(lints::check(typed_context));
+    let mut diagnostics = lints.apply(diagnostics);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.location.offset_start);
+    diagnostics
+}
+
 /// Generates WebAssembly binary format from the typed AST.
 ///
 /// This function compiles the typed AST into WebAssembly bytecode using LLVM
@@ -648,11 +746,11 @@ pub fn analyze(_: &TypedContext) -> anyhow::Result<()> {
 /// - The `rust-lld` linker fails to produce a valid WASM binary
 /// - Required external binaries (`inf-llc`, `rust-lld`) are not found
 /// - Type information is missing or inconsistent in the [`TypedContext`]
-/// - More than one source file is present (multi-file not yet supported)
 ///
 /// # Dependencies
 ///
-/// This function requires the following external binaries:
+/// This function uses the default [`Backend::Llvm`], which requires the following
+/// external binaries:
 /// - **inf-llc**: Modified LLVM compiler with Inference intrinsic support
 /// - **rust-lld**: WebAssembly linker from the Rust toolchain
 ///
@@ -671,6 +769,76 @@ pub fn codegen(typed_context: &TypedContext) -> anyhow::Result<Vec<u8>> {
     inference_wasm_codegen::codegen(typed_context)
 }
 
+/// Generates WebAssembly binary from a typed AST with the given [`Backend`].
+///
+/// [`Backend::Builtin`] emits WebAssembly without the external `inf-llc` and `rust-lld`
+/// binaries, at the cost of optimizations; [`Backend::Llvm`] behaves like [`codegen`].
+/// Both backends produce modules with the same exports and behavior.
+///
+/// # Example
+///
+/// ```no_run
+/// use inference::{parse, type_check, codegen_with_backend, Backend};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let source = r#"pub fn add(a: i32, b: i32) -> i32 { return a + b; }"#;
+/// let arena = parse(source)?;
+/// let typed_context = type_check(arena)?;
+/// let wasm = codegen_with_backend(&typed_context, Backend::Builtin)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`codegen`], except that the built-in backend never fails
+/// for a missing external binary.
+pub fn codegen_with_backend(
+    typed_context: &TypedContext,
+    backend: Backend,
+) -> anyhow::Result<Vec<u8>> {
+    inference_wasm_codegen::codegen_with_backend(typed_context, backend)
+}
+
+/// Generates WebAssembly binary, and the requested intermediate artifacts, from a typed
+/// AST.
+///
+/// [`CodegenOptions`] selects the backend, the LLVM optimization level, the exported
+/// functions (see [`ExportPolicy`]) and whether the LLVM IR and object file are returned
+/// alongside the module. The IR is the exact input of `inf-llc`, which makes it the place
+/// to check how non-deterministic intrinsics are ordered.
+///
+/// # Example
+///
+/// ```no_run
+/// use inference::{parse, type_check, codegen_with_options, CodegenOptions};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let source = r#"pub fn add(a: i32, b: i32) -> i32 { return a + b; }"#;
+/// let arena = parse(source)?;
+/// let typed_context = type_check(arena)?;
+/// let options = CodegenOptions {
+///     optimization_level: 0,
+///     emit_llvm_ir: true,
+///     ..CodegenOptions::default()
+/// };
+/// let output = codegen_with_options(&typed_context, &options)?;
+/// println!("{}", output.llvm_ir.unwrap_or_default());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`codegen`], and an error if LLVM IR or an object file is
+/// requested from [`Backend::Builtin`].
+pub fn codegen_with_options(
+    typed_context: &TypedContext,
+    options: &CodegenOptions,
+) -> anyhow::Result<CodegenOutput> {
+    inference_wasm_codegen::codegen_with_options(typed_context, options)
+}
+
 /// Translates WebAssembly binary to Rocq (Coq) verification code.
 ///
 /// This function parses a WebAssembly binary and generates equivalent Rocq
@@ -0,0 +1,352 @@
+//! Lints of the analyze phase.
+//!
+//! Lints report code that is valid but likely a mistake. They produce warnings, which do
+//! not stop compilation unless the lint is denied, see
+//! [`analyze_with_lints`](crate::analyze_with_lints):
+//!
+//! - [`Lint::UnusedVariable`] - a `let` variable that is never read. Assigning to the
+//!   variable itself is not a read, while assigning to one of its fields or elements is.
+//!   Variables whose name starts with `_` are not reported
+//! - [`Lint::UnusedPrivateFunction`] - a private free function, other than `main`, that no
+//!   other function refers to. Functions a spec declares and spec constructors are not
+//!   reported, since they are required by the spec rather than called
+//! - [`Lint::ConstantCondition`] - an `if` whose condition is built from literals only,
+//!   such as `if 1 > 2`
+//!
+//! Unused imports, shadowed bindings and implicit type parameters are reported by the
+//! type checker, which resolves names.
+//!
+//! References are matched by name, so a private function is considered used as soon as
+//! another function mentions a value of the same name.
+
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use inference_ast::{
+    diagnostics::{Diagnostic, Lint},
+    nodes::{
+        BlockType, Definition, Expression, FunctionDefinition, Identifier, Literal, OperatorKind,
+        Statement, Type, UnaryOperatorKind, Visibility,
+    },
+};
+use inference_type_checker::{
+    type_info::{TypeInfo, TypeInfoKind},
+    typed_context::TypedContext,
+};
+
+use crate::analysis::{collect_functions, operands};
+
+/// Runs the lints on every function of the typed context.
+///
+/// Diagnostics are ordered by source file, then by function, then by position in the
+/// function; unused private functions come last.
+pub(crate) fn check(typed_context: &TypedContext) -> Vec<Diagnostic> {
+    let mut source_files = typed_context.source_files();
+    source_files.sort_by_key(|source_file| source_file.id);
+    let mut diagnostics = Vec::new();
+    // Functions referring to each name
+    let mut references: HashMap<String, HashSet<u32>> = HashMap::new();
+    for source_file in &source_files {
+        let mut functions = Vec::new();
+        collect_functions(&source_file.definitions, &mut functions);
+        for (function_definition, _) in functions {
+            let mut linter = BodyLinter::default();
+            linter.check_block(&function_definition.body);
+            linter.report_unused_variables();
+            diagnostics.extend(linter.diagnostics);
+            for name in linter.references {
+                references
+                    .entry(name)
+                    .or_default()
+                    .insert(function_definition.id);
+            }
+        }
+    }
+    let mut spec_functions = HashSet::new();
+    let mut private_functions = Vec::new();
+    for source_file in &source_files {
+        collect_spec_functions(&source_file.definitions, &mut spec_functions);
+        collect_private_functions(&source_file.definitions, &mut private_functions);
+    }
+    for function_definition in private_functions {
+        let name = function_definition.name();
+        let used = references
+            .get(&name)
+            .is_some_and(|referrers| referrers.iter().any(|id| *id != function_definition.id));
+        if used
+            || name == "main"
+            || spec_functions.contains(&name)
+            || is_spec_constructor(typed_context, function_definition)
+        {
+            continue;
+        }
+        diagnostics.push(Diagnostic::lint(
+            Lint::UnusedPrivateFunction,
+            function_definition.name.location,
+            format!("function `{name}` is never used"),
+        ));
+    }
+    diagnostics
+}
+
+/// Collects the names of the functions the specs among `definitions` declare.
+fn collect_spec_functions(definitions: &[Definition], names: &mut HashSet<String>) {
+    for definition in definitions {
+        match definition {
+            Definition::Spec(spec_definition) => {
+                for spec_member in &spec_definition.definitions {
+                    match spec_member {
+                        Definition::Function(function_definition) => {
+                            names.insert(function_definition.name());
+                        }
+                        Definition::ExternalFunction(external_function_definition) => {
+                            names.insert(external_function_definition.name());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Definition::Module(module_definition) => {
+                collect_spec_functions(
+                    module_definition.body.as_deref().unwrap_or_default(),
+                    names,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collects the private free functions of `definitions` and of the modules among them.
+fn collect_private_functions<'a>(
+    definitions: &'a [Definition],
+    functions: &mut Vec<&'a Rc<FunctionDefinition>>,
+) {
+    for definition in definitions {
+        match definition {
+            Definition::Function(function_definition)
+                if function_definition.visibility == Visibility::Private =>
+            {
+                functions.push(function_definition);
+            }
+            Definition::Module(module_definition) => {
+                collect_private_functions(
+                    module_definition.body.as_deref().unwrap_or_default(),
+                    functions,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_spec_constructor(
+    typed_context: &TypedContext,
+    function_definition: &FunctionDefinition,
+) -> bool {
+    function_definition
+        .returns
+        .as_ref()
+        .is_some_and(|return_type| {
+            matches!(
+                typed_context.get_node_typeinfo(return_type.id()),
+                Some(TypeInfo {
+                    kind: TypeInfoKind::Spec(_),
+                    ..
+                })
+            )
+        })
+}
+
+/// Lints the body of one function.
+#[derive(Default)]
+struct BodyLinter {
+    diagnostics: Vec<Diagnostic>,
+
+    /// Variables in scope, innermost scope last. Each `let` variable maps to its index in
+    /// `variables`; constants map to `None`.
+    scopes: Vec<HashMap<String, Option<usize>>>,
+
+    /// Every `let` variable of the body, with whether it is read.
+    variables: Vec<(Rc<Identifier>, bool)>,
+
+    /// Names of the values the body refers to.
+    references: HashSet<String>,
+}
+
+impl BodyLinter {
+    fn check_block(&mut self, block: &BlockType) {
+        self.scopes.push(HashMap::new());
+        for statement in block.statements() {
+            self.check_statement(&statement);
+        }
+        self.scopes.pop();
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block_type) => self.check_block(block_type),
+            Statement::Expression(expression) => self.read(expression),
+            Statement::Assign(assign_statement) => {
+                let left = assign_statement.left.borrow();
+                if !matches!(&*left, Expression::Identifier(_)) {
+                    self.read(&left);
+                }
+                self.read(&assign_statement.right.borrow());
+            }
+            Statement::Return(return_statement) => {
+                self.read(&return_statement.expression.borrow());
+            }
+            Statement::Loop(loop_statement) => {
+                if let Some(condition) = &*loop_statement.condition.borrow() {
+                    self.read(condition);
+                }
+                self.check_block(&loop_statement.body);
+            }
+            Statement::If(if_statement) => {
+                let condition = if_statement.condition.borrow();
+                if let Some(Constant::Bool(value)) = evaluate(&condition) {
+                    self.diagnostics.push(Diagnostic::lint(
+                        Lint::ConstantCondition,
+                        condition.location(),
+                        format!("condition is always `{value}`"),
+                    ));
+                }
+                self.read(&condition);
+                self.check_block(&if_statement.if_arm);
+                if let Some(else_arm) = &if_statement.else_arm {
+                    self.check_block(else_arm);
+                }
+            }
+            Statement::VariableDefinition(variable_definition) => {
+                if let Some(value) = &variable_definition.value {
+                    self.read(&value.borrow());
+                }
+                self.variables
+                    .push((variable_definition.name.clone(), false));
+                self.declare(
+                    &variable_definition.name.name,
+                    Some(self.variables.len() - 1),
+                );
+            }
+            Statement::ConstantDefinition(constant_definition) => {
+                self.declare(&constant_definition.name.name, None);
+            }
+            Statement::Assert(assert_statement) => {
+                self.read(&assert_statement.expression.borrow());
+            }
+            Statement::Break(_) | Statement::TypeDefinition(_) => {}
+        }
+    }
+
+    /// Marks the variables the expression reads as used.
+    fn read(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier) => {
+                if let Some(index) = self.lookup(&identifier.name) {
+                    self.variables[index].1 = true;
+                }
+                self.references.insert(identifier.name.clone());
+            }
+            Expression::Type(Type::QualifiedName(qualified_name)) => {
+                self.references.insert(qualified_name.name.name.clone());
+            }
+            _ => {}
+        }
+        for operand in operands(expression) {
+            self.read(&operand);
+        }
+    }
+
+    fn declare(&mut self, name: &str, variable: Option<usize>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), variable);
+        }
+    }
+
+    /// Returns the index of the `let` variable `name` refers to, if it does not refer to
+    /// a constant.
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .copied()
+            .flatten()
+    }
+
+    fn report_unused_variables(&mut self) {
+        for (name, used) in &self.variables {
+            if !used && !name.name.starts_with('_') {
+                self.diagnostics.push(Diagnostic::lint(
+                    Lint::UnusedVariable,
+                    name.location,
+                    format!("unused variable `{}`", name.name),
+                ));
+            }
+        }
+        self.diagnostics
+            .sort_by_key(|diagnostic| diagnostic.location.offset_start);
+    }
+}
+
+/// Value of an expression built from literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Constant {
+    Bool(bool),
+    Number(i128),
+}
+
+/// Evaluates an expression built from literals, or returns `None` if it reads anything
+/// else or its value is not known.
+fn evaluate(expression: &Expression) -> Option<Constant> {
+    match expression {
+        Expression::Literal(Literal::Bool(bool_literal)) => {
+            Some(Constant::Bool(bool_literal.value))
+        }
+        Expression::Literal(Literal::Number(number_literal)) => {
+            number_literal.value.parse().ok().map(Constant::Number)
+        }
+        Expression::Parenthesized(parenthesized) => evaluate(&parenthesized.expression.borrow()),
+        Expression::PrefixUnary(prefix_unary) => {
+            match (
+                &prefix_unary.operator,
+                evaluate(&prefix_unary.expression.borrow())?,
+            ) {
+                (UnaryOperatorKind::Not, Constant::Bool(value)) => Some(Constant::Bool(!value)),
+                (UnaryOperatorKind::Neg, Constant::Number(value)) => {
+                    value.checked_neg().map(Constant::Number)
+                }
+                _ => None,
+            }
+        }
+        Expression::Binary(binary) => {
+            let left = evaluate(&binary.left.borrow())?;
+            let right = evaluate(&binary.right.borrow())?;
+            match (&binary.operator, left, right) {
+                (OperatorKind::And, Constant::Bool(left), Constant::Bool(right)) => {
+                    Some(Constant::Bool(left && right))
+                }
+                (OperatorKind::Or, Constant::Bool(left), Constant::Bool(right)) => {
+                    Some(Constant::Bool(left || right))
+                }
+                (OperatorKind::Eq, left, right) => Some(Constant::Bool(left == right)),
+                (OperatorKind::Ne, left, right) => Some(Constant::Bool(left != right)),
+                (operator, Constant::Number(left), Constant::Number(right)) => match operator {
+                    OperatorKind::Lt => Some(Constant::Bool(left < right)),
+                    OperatorKind::Le => Some(Constant::Bool(left <= right)),
+                    OperatorKind::Gt => Some(Constant::Bool(left > right)),
+                    OperatorKind::Ge => Some(Constant::Bool(left >= right)),
+                    OperatorKind::Add => left.checked_add(right).map(Constant::Number),
+                    OperatorKind::Sub => left.checked_sub(right).map(Constant::Number),
+                    OperatorKind::Mul => left.checked_mul(right).map(Constant::Number),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
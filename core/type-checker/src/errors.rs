@@ -38,9 +38,39 @@
 //!
 //! **Function and Method Errors**:
 //! - [`TypeCheckError::ArgumentCountMismatch`] - Wrong number of arguments
+//! - [`TypeCheckError::UnknownNamedArgument`] - Named argument without a matching parameter
 //! - [`TypeCheckError::MethodNotFound`] - Undefined method on type
 //! - [`TypeCheckError::MethodCallOnNonStruct`] - Method call on primitive type
 //!
+//! **Mutability Errors**:
+//! - [`TypeCheckError::AssignmentToImmutable`] - Assignment to a variable or an argument
+//!   declared without `mut`, or to a constant, or a call of a `mut self` method on one
+//! - [`TypeCheckError::MutationThroughImmutableSelf`] - Assignment or `mut self` method
+//!   call through `self` in a method that takes `self` rather than `mut self`
+//!
+//! **Constant Errors**:
+//! - [`TypeCheckError::IntegerLiteralOutOfRange`] - Integer literal that does not fit
+//!   its type
+//! - [`TypeCheckError::ConstantOverflow`] - Expression of integer literals whose value
+//!   does not fit its type
+//! - [`TypeCheckError::DivisionByZero`] - Division or remainder of integer literals by
+//!   zero
+//!
+//! **Conversion Errors**:
+//! - [`TypeCheckError::LossyCast`] - `cast` between integer types that may change the
+//!   value
+//! - [`TypeCheckError::InvalidConversion`] - Conversion of or to a type that is not an
+//!   integer type
+//!
+//! **Spec Errors**:
+//! - [`TypeCheckError::MissingSpecFunction`] - Function declared by a claimed spec is
+//!   not defined
+//! - [`TypeCheckError::SpecFunctionSignatureMismatch`] - Function differs from its
+//!   declaration in a claimed spec
+//! - [`TypeCheckError::MissingSpecType`] - Type declared by a claimed spec is not defined
+//! - [`TypeCheckError::SpecTypeMismatch`] - Type alias names a different type than its
+//!   declaration in a claimed spec
+//!
 //! **Other Errors**:
 //! - [`TypeCheckError::FieldNotFound`] - Undefined struct field
 //! - [`TypeCheckError::VariantNotFound`] - Undefined enum variant
@@ -84,7 +114,7 @@ use std::fmt::{self, Display, Formatter};
 use inference_ast::nodes::{Location, OperatorKind, UnaryOperatorKind};
 use thiserror::Error;
 
-use crate::type_info::TypeInfo;
+use crate::type_info::{NumberType, TypeInfo};
 
 /// Kind of symbol registration for registration error context.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -118,6 +148,7 @@ pub enum TypeMismatchContext {
     Assignment,
     Return,
     VariableDefinition,
+    ConstantDefinition,
     BinaryOperation(OperatorKind),
     Condition,
     FunctionArgument {
@@ -132,6 +163,10 @@ pub enum TypeMismatchContext {
         arg_index: usize,
     },
     ArrayElement,
+    StructField {
+        struct_name: String,
+        field_name: String,
+    },
 }
 
 impl Display for TypeMismatchContext {
@@ -140,6 +175,7 @@ impl Display for TypeMismatchContext {
             TypeMismatchContext::Assignment => write!(f, "in assignment"),
             TypeMismatchContext::Return => write!(f, "in return statement"),
             TypeMismatchContext::VariableDefinition => write!(f, "in variable definition"),
+            TypeMismatchContext::ConstantDefinition => write!(f, "in constant definition"),
             TypeMismatchContext::BinaryOperation(op) => write!(f, "in binary operation `{op:?}`"),
             TypeMismatchContext::Condition => write!(f, "in condition"),
             TypeMismatchContext::FunctionArgument {
@@ -160,6 +196,28 @@ impl Display for TypeMismatchContext {
                 "in argument {arg_index} `{arg_name}` of method `{type_name}::{method_name}`"
             ),
             TypeMismatchContext::ArrayElement => write!(f, "in array element"),
+            TypeMismatchContext::StructField {
+                struct_name,
+                field_name,
+            } => write!(f, "in field `{field_name}` of struct `{struct_name}`"),
+        }
+    }
+}
+
+/// Kind of binding that an assignment cannot target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImmutableBindingKind {
+    Variable,
+    Argument,
+    Constant,
+}
+
+impl Display for ImmutableBindingKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ImmutableBindingKind::Variable => write!(f, "variable"),
+            ImmutableBindingKind::Argument => write!(f, "argument"),
+            ImmutableBindingKind::Constant => write!(f, "constant"),
         }
     }
 }
@@ -268,6 +326,14 @@ pub enum TypeCheckError {
         location: Location,
     },
 
+    #[error("{location}: {kind} `{name}` has no parameter `{argument}` left to bind")]
+    UnknownNamedArgument {
+        kind: &'static str,
+        name: String,
+        argument: String,
+        location: Location,
+    },
+
     #[error(
         "{location}: type parameter count mismatch for `{name}`: expected {expected}, found {found}"
     )]
@@ -399,7 +465,9 @@ pub enum TypeCheckError {
     ///
     /// This occurs when `Type::method()` syntax is used for a method that requires `self`.
     /// Use `instance.method()` instead.
-    #[error("{location}: instance method `{type_name}::{method_name}` requires a receiver, use `instance.{method_name}()` instead")]
+    #[error(
+        "{location}: instance method `{type_name}::{method_name}` requires a receiver, use `instance.{method_name}()` instead"
+    )]
     InstanceMethodCalledAsAssociated {
         type_name: String,
         method_name: String,
@@ -410,12 +478,129 @@ pub enum TypeCheckError {
     ///
     /// This occurs when `instance.function()` syntax is used for an associated function
     /// that doesn't take `self`. Use `Type::function()` instead.
-    #[error("{location}: associated function `{type_name}::{method_name}` cannot be called on an instance, use `{type_name}::{method_name}()` instead")]
+    #[error(
+        "{location}: associated function `{type_name}::{method_name}` cannot be called on an instance, use `{type_name}::{method_name}()` instead"
+    )]
     AssociatedFunctionCalledAsMethod {
         type_name: String,
         method_name: String,
         location: Location,
     },
+
+    /// Assignment to a binding that cannot change, or to a field or element of it. Calling
+    /// a `mut self` method on such a place is reported the same way.
+    ///
+    /// Variables and arguments are immutable unless declared `mut`; constants are always
+    /// immutable.
+    #[error("{location}: cannot assign to immutable {binding} `{name}`, declared at {declaration}")]
+    AssignmentToImmutable {
+        binding: ImmutableBindingKind,
+        name: String,
+        declaration: Location,
+        location: Location,
+    },
+
+    /// Assignment to `self`, or to a field or element of it, in a method that takes
+    /// `self` rather than `mut self`; also reported for a `mut self` method called on it.
+    #[error(
+        "{location}: cannot assign through `self`, which is not declared `mut` at {declaration}"
+    )]
+    MutationThroughImmutableSelf {
+        declaration: Location,
+        location: Location,
+    },
+
+    /// Integer literal whose value is outside the range of the type it was given.
+    #[error(
+        "{location}: integer literal `{literal}` is out of range for `{}`, which holds {} to {}",
+        ty.as_str(), ty.min_value(), ty.max_value()
+    )]
+    IntegerLiteralOutOfRange {
+        literal: String,
+        ty: NumberType,
+        location: Location,
+    },
+
+    /// Expression built only from integer literals whose value, computed at compile time,
+    /// is outside the range of its type, such as `127 + 1` of type `i8`.
+    #[error("{location}: constant expression `{expression}` overflows `{}`", ty.as_str())]
+    ConstantOverflow {
+        expression: String,
+        ty: NumberType,
+        location: Location,
+    },
+
+    /// Division or remainder by zero in an expression built only from integer literals.
+    #[error("{location}: division by zero")]
+    DivisionByZero { location: Location },
+
+    /// `cast` to an integer type that cannot hold every value of the argument type, such
+    /// as `i64` to `i32` or `i32` to `u32`.
+    #[error(
+        "{location}: `cast` from `{}` to `{}` may change the value; use `wrapping_cast` or `checked_cast`",
+        from.as_str(), to.as_str()
+    )]
+    LossyCast {
+        from: NumberType,
+        to: NumberType,
+        location: Location,
+    },
+
+    /// Built-in conversion whose argument or target type is not an integer type.
+    #[error(
+        "{location}: `{conversion}` converts between integer types, cannot convert `{from}` to `{to}`"
+    )]
+    InvalidConversion {
+        conversion: &'static str,
+        from: TypeInfo,
+        to: TypeInfo,
+        location: Location,
+    },
+
+    /// A module claims a spec, by defining a function that returns it, but does not
+    /// define a function the spec declares with `external fn`. Reported at the function
+    /// claiming the spec.
+    #[error("{location}: missing function `{function}` required by spec `{spec}`")]
+    MissingSpecFunction {
+        spec: String,
+        function: String,
+        location: Location,
+    },
+
+    /// Function whose parameter or return types differ from its declaration in a spec
+    /// its module claims.
+    #[error(
+        "{location}: function `{function}` does not match its declaration in spec `{spec}`: expected `{expected}`, found `{found}`"
+    )]
+    SpecFunctionSignatureMismatch {
+        spec: String,
+        function: String,
+        expected: String,
+        found: String,
+        location: Location,
+    },
+
+    /// A module claims a spec but does not define a type the spec declares with `type`.
+    /// Reported at the function claiming the spec.
+    #[error("{location}: missing type `{name}` required by spec `{spec}`")]
+    MissingSpecType {
+        spec: String,
+        name: String,
+        location: Location,
+    },
+
+    /// Type alias that names a different type than its declaration in a spec its module
+    /// claims. Aliases are resolved on both sides before comparing.
+    #[error(
+        "{location}: type `{name}` does not match its declaration in spec `{spec}`: expected `{expected}`, found `{found}`"
+    )]
+    SpecTypeMismatch {
+        spec: String,
+        name: String,
+        expected: String,
+        found: String,
+        location: Location,
+    },
 }
 
 impl TypeCheckError {
@@ -434,6 +619,7 @@ impl TypeCheckError {
             | TypeCheckError::ExpectedEnumType { location, .. }
             | TypeCheckError::MethodNotFound { location, .. }
             | TypeCheckError::ArgumentCountMismatch { location, .. }
+            | TypeCheckError::UnknownNamedArgument { location, .. }
             | TypeCheckError::TypeParameterCountMismatch { location, .. }
             | TypeCheckError::MissingTypeParameters { location, .. }
             | TypeCheckError::InvalidBinaryOperand { location, .. }
@@ -455,7 +641,18 @@ impl TypeCheckError {
             | TypeCheckError::ConflictingTypeInference { location, .. }
             | TypeCheckError::PrivateAccessViolation { location, .. }
             | TypeCheckError::InstanceMethodCalledAsAssociated { location, .. }
-            | TypeCheckError::AssociatedFunctionCalledAsMethod { location, .. } => location,
+            | TypeCheckError::AssociatedFunctionCalledAsMethod { location, .. }
+            | TypeCheckError::AssignmentToImmutable { location, .. }
+            | TypeCheckError::MutationThroughImmutableSelf { location, .. }
+            | TypeCheckError::IntegerLiteralOutOfRange { location, .. }
+            | TypeCheckError::ConstantOverflow { location, .. }
+            | TypeCheckError::DivisionByZero { location }
+            | TypeCheckError::LossyCast { location, .. }
+            | TypeCheckError::InvalidConversion { location, .. }
+            | TypeCheckError::MissingSpecFunction { location, .. }
+            | TypeCheckError::SpecFunctionSignatureMismatch { location, .. }
+            | TypeCheckError::MissingSpecType { location, .. }
+            | TypeCheckError::SpecTypeMismatch { location, .. } => location,
         }
     }
 }
@@ -463,7 +660,7 @@ impl TypeCheckError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::type_info::{NumberType, TypeInfoKind};
+    use crate::type_info::TypeInfoKind;
 
     fn test_location() -> Location {
         Location {
@@ -566,6 +763,14 @@ mod tests {
             .to_string(),
             "in argument 0 `dx` of method `Point::move_by`"
         );
+        assert_eq!(
+            TypeMismatchContext::StructField {
+                struct_name: "Pair".to_string(),
+                field_name: "fst".to_string(),
+            }
+            .to_string(),
+            "in field `fst` of struct `Pair`"
+        );
     }
 
     #[test]
@@ -644,6 +849,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_unknown_named_argument() {
+        let err = TypeCheckError::UnknownNamedArgument {
+            kind: "function",
+            name: "add".to_string(),
+            argument: "c".to_string(),
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: function `add` has no parameter `c` left to bind"
+        );
+    }
+
     #[test]
     fn display_type_parameter_count_mismatch() {
         let err = TypeCheckError::TypeParameterCountMismatch {
@@ -1030,4 +1249,126 @@ mod tests {
         assert!(msg.contains("new"));
         assert!(msg.contains("cannot be called on an instance"));
     }
+
+    #[test]
+    fn display_assignment_to_immutable_argument() {
+        let err = TypeCheckError::AssignmentToImmutable {
+            binding: ImmutableBindingKind::Argument,
+            name: "a".to_string(),
+            declaration: Location {
+                offset_start: 0,
+                offset_end: 1,
+                start_line: 1,
+                start_column: 1,
+                end_line: 1,
+                end_column: 2,
+            },
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: cannot assign to immutable argument `a`, declared at 1:1"
+        );
+    }
+
+    #[test]
+    fn display_mutation_through_immutable_self() {
+        let err = TypeCheckError::MutationThroughImmutableSelf {
+            declaration: test_location(),
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: cannot assign through `self`, which is not declared `mut` at 1:5"
+        );
+    }
+
+    #[test]
+    fn display_integer_literal_out_of_range() {
+        let err = TypeCheckError::IntegerLiteralOutOfRange {
+            literal: "300".to_string(),
+            ty: NumberType::U8,
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: integer literal `300` is out of range for `u8`, which holds 0 to 255"
+        );
+    }
+
+    #[test]
+    fn display_constant_overflow() {
+        let err = TypeCheckError::ConstantOverflow {
+            expression: "127 + 1".to_string(),
+            ty: NumberType::I8,
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: constant expression `127 + 1` overflows `i8`"
+        );
+    }
+
+    #[test]
+    fn display_lossy_cast() {
+        let err = TypeCheckError::LossyCast {
+            from: NumberType::I64,
+            to: NumberType::I32,
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: `cast` from `i64` to `i32` may change the value; use `wrapping_cast` or `checked_cast`"
+        );
+    }
+
+    #[test]
+    fn display_invalid_conversion() {
+        let err = TypeCheckError::InvalidConversion {
+            conversion: "checked_cast",
+            from: TypeInfo {
+                kind: TypeInfoKind::Bool,
+                type_params: vec![],
+            },
+            to: TypeInfo {
+                kind: TypeInfoKind::Number(NumberType::U8),
+                type_params: vec![],
+            },
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: `checked_cast` converts between integer types, cannot convert `Bool` to `u8`"
+        );
+    }
+
+    #[test]
+    fn display_spec_function_signature_mismatch() {
+        let err = TypeCheckError::SpecFunctionSignatureMismatch {
+            spec: "Arithmetic".to_string(),
+            function: "sum".to_string(),
+            expected: "fn(i32, i32) -> i32".to_string(),
+            found: "fn(u32, u32) -> u32".to_string(),
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: function `sum` does not match its declaration in spec `Arithmetic`: expected `fn(i32, i32) -> i32`, found `fn(u32, u32) -> u32`"
+        );
+    }
+
+    #[test]
+    fn display_spec_type_mismatch() {
+        let err = TypeCheckError::SpecTypeMismatch {
+            spec: "Ledger".to_string(),
+            name: "Address".to_string(),
+            expected: "u32".to_string(),
+            found: "i64".to_string(),
+            location: test_location(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "1:5: type `Address` does not match its declaration in spec `Ledger`: expected `u32`, found `i64`"
+        );
+    }
 }
@@ -24,6 +24,8 @@
 //! - Logical: `&&`, `||`, `!`
 //! - Bitwise: `&`, `|`, `^`, `<<`, `>>`, `~`
 //! - Unary: `-` (negation), `!` (logical NOT), `~` (bitwise NOT)
+//! - Integer conversions: `cast T'(x)` (widening only), `wrapping_cast T'(x)`,
+//!   `checked_cast T'(x)`
 //!
 //! **Error Handling**:
 //! - Comprehensive error types with detailed context
@@ -71,13 +73,15 @@
 //!
 //! ## Multi-Phase Architecture
 //!
-//! The type checker operates in five sequential phases:
+//! The type checker operates in six sequential phases:
 //!
 //! 1. **Process Directives** - Register raw import statements in scope tree
 //! 2. **Register Types** - Collect struct, enum, spec, and type alias definitions
 //! 3. **Resolve Imports** - Bind import paths to symbols in symbol table
 //! 4. **Register Functions** - Collect function and method signatures
-//! 5. **Infer Variables** - Type-check function bodies and variable declarations
+//! 5. **Check Specs** - Check that modules define the functions and types of the specs
+//!    they claim
+//! 6. **Infer Variables** - Type-check function bodies and variable declarations
 //!
 //! This ordering ensures that types are available before functions reference them,
 //! and imports are resolved before symbol lookup.
@@ -37,6 +37,9 @@ pub(crate) type ScopeRef = Rc<RefCell<Scope>>;
 pub(crate) struct FuncInfo {
     pub(crate) name: String,
     pub(crate) type_params: Vec<String>,
+    /// Parameter names in declaration order, `None` for parameters that cannot be
+    /// passed by name. Used to bind named call arguments.
+    pub(crate) param_names: Vec<Option<String>>,
     pub(crate) param_types: Vec<TypeInfo>,
     pub(crate) return_type: TypeInfo,
     pub(crate) visibility: Visibility,
@@ -92,12 +95,15 @@ pub(crate) struct EnumInfo {
 /// - `visibility`: Access control for the method
 /// - `scope_id`: The scope where this method is defined (for visibility checking)
 /// - `has_self`: Whether this method takes `self` as first argument
+/// - `mut_self`: Whether this method takes `mut self`, so it may only be called on a
+///   receiver that can be assigned
 #[derive(Debug, Clone)]
 pub(crate) struct MethodInfo {
     pub(crate) signature: FuncInfo,
     pub(crate) visibility: Visibility,
     pub(crate) scope_id: u32,
     pub(crate) has_self: bool,
+    pub(crate) mut_self: bool,
 }
 
 impl MethodInfo {
@@ -111,6 +117,30 @@ impl MethodInfo {
     }
 }
 
+/// How a name in scope was bound, which decides whether it may be assigned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Binding {
+    /// A variable or an argument declared `mut`.
+    Mutable,
+    /// A `let` variable declared without `mut`.
+    ImmutableVariable,
+    /// An argument declared without `mut`.
+    ImmutableArgument,
+    /// The receiver of a method that takes `self` rather than `mut self`.
+    ImmutableSelf,
+    /// A module-level or local constant.
+    Constant,
+}
+
+/// A variable, argument or constant in scope.
+#[derive(Debug, Clone)]
+pub(crate) struct VariableInfo {
+    pub(crate) ty: TypeInfo,
+    pub(crate) binding: Binding,
+    /// Location of the declaration, reported by mutability errors.
+    pub(crate) location: Location,
+}
+
 /// A single item in an import statement
 #[derive(Debug, Clone)]
 pub(crate) struct ImportItem {
@@ -257,7 +287,7 @@ pub(crate) struct Scope {
     pub(crate) parent: Option<ScopeRef>,
     pub(crate) children: Vec<ScopeRef>,
     pub(crate) symbols: FxHashMap<String, Symbol>,
-    pub(crate) variables: FxHashMap<String, (u32, TypeInfo)>,
+    pub(crate) variables: FxHashMap<String, VariableInfo>,
     pub(crate) methods: FxHashMap<String, Vec<MethodInfo>>,
     /// Unresolved imports registered in this scope
     pub(crate) imports: Vec<Import>,
@@ -320,25 +350,24 @@ impl Scope {
     pub(crate) fn insert_variable(
         &mut self,
         name: &str,
-        node_id: u32,
-        ty: TypeInfo,
+        variable: VariableInfo,
     ) -> anyhow::Result<()> {
         if self.variables.contains_key(name) {
             bail!("Variable `{name}` already declared in this scope");
         }
-        self.variables.insert(name.to_string(), (node_id, ty));
+        self.variables.insert(name.to_string(), variable);
         Ok(())
     }
 
     #[must_use = "this is a pure lookup with no side effects"]
-    fn lookup_variable_local(&self, name: &str) -> Option<(u32, TypeInfo)> {
+    fn lookup_variable_local(&self, name: &str) -> Option<VariableInfo> {
         self.variables.get(name).cloned()
     }
 
     #[must_use = "this is a pure lookup with no side effects"]
-    pub(crate) fn lookup_variable(&self, name: &str) -> Option<TypeInfo> {
-        if let Some((_, ty)) = self.lookup_variable_local(name) {
-            return Some(ty);
+    pub(crate) fn lookup_variable(&self, name: &str) -> Option<VariableInfo> {
+        if let Some(variable) = self.lookup_variable_local(name) {
+            return Some(variable);
         }
         if let Some(parent) = &self.parent {
             return parent.borrow().lookup_variable(name);
@@ -393,6 +422,8 @@ pub(crate) struct SymbolTable {
     root_scope: Option<ScopeRef>,
     current_scope: Option<ScopeRef>,
     next_scope_id: u32,
+    /// Names of the types, structs, enums and specs defined anywhere in the program.
+    type_names: FxHashSet<String>,
 }
 
 impl Default for SymbolTable {
@@ -403,6 +434,7 @@ impl Default for SymbolTable {
             root_scope: None,
             current_scope: None,
             next_scope_id: 0,
+            type_names: FxHashSet::default(),
         };
         table.init_root_scope();
         table.init_builtin_types();
@@ -490,10 +522,23 @@ impl SymbolTable {
         }
     }
 
+    /// Records the names of the types defined in the program, which take precedence over
+    /// builtin types of the same name in [`SymbolTable::type_info`].
+    pub(crate) fn set_type_names(&mut self, type_names: FxHashSet<String>) {
+        self.type_names = type_names;
+    }
+
+    /// Converts a type written in the source to a `TypeInfo`, treating `type_param_names`
+    /// as type parameters and names of types defined in the program as those types.
+    #[must_use]
+    pub(crate) fn type_info(&self, ty: &Type, type_param_names: &[String]) -> TypeInfo {
+        TypeInfo::new_with_declared_types(ty, type_param_names, &self.type_names)
+    }
+
     pub(crate) fn register_type(&mut self, name: &str, ty: Option<&Type>) -> anyhow::Result<()> {
         if let Some(scope) = &self.current_scope {
             let type_info = if let Some(ty) = ty {
-                TypeInfo::new(ty)
+                self.type_info(ty, &[])
             } else {
                 TypeInfo {
                     kind: crate::type_info::TypeInfoKind::Custom(name.to_string()),
@@ -575,27 +620,15 @@ impl SymbolTable {
         }
     }
 
-    pub(crate) fn register_function(
-        &mut self,
-        name: &str,
-        type_params: Vec<String>,
-        param_types: &[Type],
-        return_type: &Type,
-    ) -> Result<(), String> {
-        self.register_function_with_visibility(
-            name,
-            type_params,
-            param_types,
-            return_type,
-            Visibility::Private,
-        )
-    }
-
+    /// Registers a function signature in the current scope.
+    ///
+    /// `self` parameters are skipped, so `param_types` and `param_names` only describe
+    /// the arguments a call site has to pass.
     pub(crate) fn register_function_with_visibility(
         &mut self,
         name: &str,
         type_params: Vec<String>,
-        param_types: &[Type],
+        arguments: &[ArgumentType],
         return_type: &Type,
         visibility: Visibility,
     ) -> Result<(), String> {
@@ -603,14 +636,24 @@ impl SymbolTable {
             let scope_id = scope.borrow().id;
             // Use type_params when constructing TypeInfo so that
             // type parameters like T, U are recognized as Generic types
+            let (param_names, param_types) = arguments
+                .iter()
+                .filter_map(|argument| {
+                    let ty = match argument {
+                        ArgumentType::SelfReference(_) => return None,
+                        ArgumentType::IgnoreArgument(ignore_argument) => &ignore_argument.ty,
+                        ArgumentType::Argument(argument) => &argument.ty,
+                        ArgumentType::Type(ty) => ty,
+                    };
+                    Some((argument.parameter_name(), self.type_info(ty, &type_params)))
+                })
+                .unzip();
             let sig = FuncInfo {
                 name: name.to_string(),
                 type_params: type_params.clone(),
-                param_types: param_types
-                    .iter()
-                    .map(|t| TypeInfo::new_with_type_params(t, &type_params))
-                    .collect(),
-                return_type: TypeInfo::new_with_type_params(return_type, &type_params),
+                param_names,
+                param_types,
+                return_type: self.type_info(return_type, &type_params),
                 visibility,
                 definition_scope_id: scope_id,
             };
@@ -627,9 +670,18 @@ impl SymbolTable {
         &mut self,
         name: &str,
         var_type: TypeInfo,
+        binding: Binding,
+        location: Location,
     ) -> anyhow::Result<()> {
         if let Some(scope) = &self.current_scope {
-            scope.borrow_mut().insert_variable(name, 0, var_type)
+            scope.borrow_mut().insert_variable(
+                name,
+                VariableInfo {
+                    ty: var_type,
+                    binding,
+                    location,
+                },
+            )
         } else {
             bail!("No active scope to push variable")
         }
@@ -650,6 +702,12 @@ impl SymbolTable {
 
     #[must_use = "this is a pure lookup with no side effects"]
     pub(crate) fn lookup_variable(&self, name: &str) -> Option<TypeInfo> {
+        self.lookup_variable_info(name).map(|variable| variable.ty)
+    }
+
+    /// Looks up a variable together with how it was bound and where it was declared.
+    #[must_use = "this is a pure lookup with no side effects"]
+    pub(crate) fn lookup_variable_info(&self, name: &str) -> Option<VariableInfo> {
         self.current_scope
             .as_ref()
             .and_then(|scope| scope.borrow().lookup_variable(name))
@@ -685,6 +743,7 @@ impl SymbolTable {
         signature: FuncInfo,
         visibility: Visibility,
         has_self: bool,
+        mut_self: bool,
     ) -> anyhow::Result<()> {
         if let Some(scope) = &self.current_scope {
             let scope_id = scope.borrow().id;
@@ -693,6 +752,7 @@ impl SymbolTable {
                 visibility,
                 scope_id,
                 has_self,
+                mut_self,
             };
             scope.borrow_mut().insert_method(type_name, method_info);
             Ok(())
@@ -724,6 +784,57 @@ impl SymbolTable {
         self.mod_scopes.get(&key).map(|s| s.borrow().id)
     }
 
+    /// Makes an existing scope current again, such as a module scope entered in an earlier
+    /// phase. [`Self::pop_scope`] returns to its parent.
+    pub(crate) fn enter_scope(&mut self, scope_id: u32) {
+        if let Some(scope) = self.scopes.get(&scope_id) {
+            self.current_scope = Some(Rc::clone(scope));
+        }
+    }
+
+    /// Looks up the function `name` defined directly in the module at `module_path`.
+    ///
+    /// The path is relative to the current module, as in `math::add`; if no such module
+    /// is nested there, it is resolved relative to each enclosing module in turn, up to
+    /// the root.
+    #[must_use = "this is a pure lookup with no side effects"]
+    pub(crate) fn lookup_module_function(
+        &self,
+        module_path: &[String],
+        name: &str,
+    ) -> Option<FuncInfo> {
+        let mut scope = self.current_scope.clone();
+        while let Some(current) = scope {
+            let current = current.borrow();
+            let full_path = std::iter::once(current.full_path.as_str())
+                .filter(|path| !path.is_empty())
+                .chain(module_path.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join("::");
+            if let Some(module) = self.mod_scopes.get(&full_path) {
+                return module
+                    .borrow()
+                    .lookup_symbol_local(name)
+                    .and_then(Symbol::as_function)
+                    .cloned();
+            }
+            scope = current.parent.clone();
+        }
+        None
+    }
+
+    /// Returns `name` qualified with the path of the module scope `scope_id`, such as
+    /// `math::add`. Names defined in the root scope stay unqualified.
+    #[must_use = "this is a pure lookup with no side effects"]
+    pub(crate) fn qualified_name(&self, scope_id: u32, name: &str) -> String {
+        match self.get_scope(scope_id) {
+            Some(scope) if !scope.borrow().full_path.is_empty() => {
+                format!("{}::{name}", scope.borrow().full_path)
+            }
+            _ => name.to_string(),
+        }
+    }
+
     /// Get all public symbols from a scope (for glob imports).
     #[must_use = "this is a pure lookup with no side effects"]
     pub(crate) fn get_public_symbols_from_scope(&self, scope_id: u32) -> Vec<(String, Symbol)> {
@@ -891,18 +1002,6 @@ impl SymbolTable {
                     .as_ref()
                     .map(|tps| tps.iter().map(|p| p.name()).collect())
                     .unwrap_or_default();
-                let param_types: Vec<_> = f
-                    .arguments
-                    .as_ref()
-                    .unwrap_or(&vec![])
-                    .iter()
-                    .filter_map(|a| match a {
-                        ArgumentType::Argument(arg) => Some(arg.ty.clone()),
-                        ArgumentType::IgnoreArgument(ig) => Some(ig.ty.clone()),
-                        ArgumentType::Type(t) => Some(t.clone()),
-                        ArgumentType::SelfReference(_) => None,
-                    })
-                    .collect();
                 let return_type = f
                     .returns
                     .clone()
@@ -911,7 +1010,7 @@ impl SymbolTable {
                 self.register_function_with_visibility(
                     &f.name(),
                     type_params,
-                    &param_types,
+                    f.arguments.as_deref().unwrap_or_default(),
                     &return_type,
                     f.visibility.clone(),
                 )
@@ -1296,6 +1395,7 @@ mod tests {
                 signature: FuncInfo {
                     name: "get_value".to_string(),
                     type_params: vec![],
+                    param_names: vec![],
                     param_types: vec![],
                     return_type: TypeInfo::default(),
                     visibility: Visibility::Private,
@@ -1304,6 +1404,7 @@ mod tests {
                 visibility: Visibility::Private,
                 scope_id: 0,
                 has_self: true,
+                mut_self: false,
             };
             assert!(method_info.is_instance_method());
         }
@@ -1314,6 +1415,7 @@ mod tests {
                 signature: FuncInfo {
                     name: "new".to_string(),
                     type_params: vec![],
+                    param_names: vec![],
                     param_types: vec![],
                     return_type: TypeInfo::default(),
                     visibility: Visibility::Public,
@@ -1322,6 +1424,7 @@ mod tests {
                 visibility: Visibility::Public,
                 scope_id: 0,
                 has_self: false,
+                mut_self: false,
             };
             assert!(!method_info.is_instance_method());
         }
@@ -1333,12 +1436,13 @@ mod tests {
             let sig = FuncInfo {
                 name: "instance_method".to_string(),
                 type_params: vec![],
+                param_names: vec![],
                 param_types: vec![],
                 return_type: TypeInfo::default(),
                 visibility: Visibility::Public,
                 definition_scope_id: 0,
             };
-            let result = table.register_method("TestType", sig, Visibility::Public, true);
+            let result = table.register_method("TestType", sig, Visibility::Public, true, false);
             assert!(result.is_ok());
             let method_info = table.lookup_method("TestType", "instance_method");
             assert!(method_info.is_some());
@@ -1354,12 +1458,13 @@ mod tests {
             let sig = FuncInfo {
                 name: "constructor".to_string(),
                 type_params: vec![],
+                param_names: vec![],
                 param_types: vec![],
                 return_type: TypeInfo::default(),
                 visibility: Visibility::Public,
                 definition_scope_id: 0,
             };
-            let result = table.register_method("TestType", sig, Visibility::Public, false);
+            let result = table.register_method("TestType", sig, Visibility::Public, false, false);
             assert!(result.is_ok());
             let method_info = table.lookup_method("TestType", "constructor");
             assert!(method_info.is_some());
@@ -1374,6 +1479,7 @@ mod tests {
                 signature: FuncInfo {
                     name: "test".to_string(),
                     type_params: vec![],
+                    param_names: vec![],
                     param_types: vec![],
                     return_type: TypeInfo::default(),
                     visibility: Visibility::Private,
@@ -1382,11 +1488,13 @@ mod tests {
                 visibility: Visibility::Private,
                 scope_id: 0,
                 has_self: true,
+                mut_self: false,
             };
             let associated_fn = MethodInfo {
                 signature: FuncInfo {
                     name: "test".to_string(),
                     type_params: vec![],
+                    param_names: vec![],
                     param_types: vec![],
                     return_type: TypeInfo::default(),
                     visibility: Visibility::Private,
@@ -1395,6 +1503,7 @@ mod tests {
                 visibility: Visibility::Private,
                 scope_id: 0,
                 has_self: false,
+                mut_self: false,
             };
             // Verify accessor returns same value as field
             assert_eq!(
@@ -8,25 +8,35 @@
 //! 3. **resolve_imports** - Bind import paths to symbols
 //! 4. **collect_function_and_constant_definitions** - Register functions
 //! 5. **infer_variables** - Type-check function bodies
+//! 6. **check_unused_imports** - Warn about imported names that are never used
 //!
 //! The type checker continues after encountering errors to collect all issues
-//! before returning. Errors are deduplicated to avoid repeated reports.
+//! before returning. Errors are deduplicated to avoid repeated reports. Warnings
+//! (shadowed bindings, unused imports and lowercase struct type parameters) do not fail
+//! type checking; they are handed to the typed context as diagnostics.
 
 use std::rc::Rc;
 
 use anyhow::bail;
+use inference_ast::diagnostics::{Diagnostic, Lint};
 use inference_ast::extern_prelude::ExternPrelude;
 use inference_ast::nodes::{
-    ArgumentType, Definition, Directive, Expression, FunctionDefinition, Identifier, Literal,
-    Location, ModuleDefinition, OperatorKind, SimpleTypeKind, Statement, Type, UnaryOperatorKind,
-    UseDirective, Visibility,
+    Argument, ArgumentType, AstNode, ConstantDefinition, Definition, Directive, Expression,
+    FunctionCallExpression, FunctionDefinition, Identifier, Literal, Location, ModuleDefinition,
+    OperatorKind, SimpleTypeKind, SpecDefinition, Statement, StructDefinition, StructExpression,
+    Type, UnaryOperatorKind, UseDirective, Visibility,
 };
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
-    errors::{RegistrationKind, TypeCheckError, TypeMismatchContext, VisibilityContext},
-    symbol_table::{FuncInfo, Import, ImportItem, ImportKind, ResolvedImport, SymbolTable},
-    type_info::{NumberType, TypeInfo, TypeInfoKind},
+    errors::{
+        ImmutableBindingKind, RegistrationKind, TypeCheckError, TypeMismatchContext,
+        VisibilityContext,
+    },
+    symbol_table::{
+        Binding, FuncInfo, Import, ImportItem, ImportKind, ResolvedImport, StructInfo, SymbolTable,
+    },
+    type_info::{Conversion, NumberType, TypeInfo, TypeInfoKind},
     typed_context::TypedContext,
 };
 
@@ -36,6 +46,12 @@ pub(crate) struct TypeChecker {
     errors: Vec<TypeCheckError>,
     glob_resolution_in_progress: FxHashSet<u32>,
     reported_error_keys: FxHashSet<String>,
+    /// Scope of every module definition, keyed by node ID.
+    module_scopes: FxHashMap<u32, u32>,
+    /// Registered spec definitions, keyed by name, for conformance checking.
+    spec_definitions: FxHashMap<String, Rc<SpecDefinition>>,
+    /// Warnings, moved into the typed context once type checking succeeds.
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl TypeChecker {
@@ -67,33 +83,24 @@ impl TypeChecker {
     /// 2. `register_types()` - Collect type definitions into symbol table
     /// 3. `resolve_imports()` - Bind import paths to symbols
     /// 4. `collect_function_and_constant_definitions()` - Register functions
-    /// 5. Infer variable types in function bodies
+    /// 5. `check_spec_conformance()` - Check modules against the specs they claim
+    /// 6. Infer variable types in function bodies
+    /// 7. `check_constant_expressions()` - Check integer literals and fold constant
+    ///    expressions, once every literal has its final type
+    /// 8. `check_unused_imports()` - Warn about imported names that are never used
     pub fn infer_types(&mut self, ctx: &mut TypedContext) -> anyhow::Result<SymbolTable> {
         self.process_directives(ctx);
         self.register_types(ctx);
         self.resolve_imports();
         self.collect_function_and_constant_definitions(ctx);
+        self.check_spec_conformance(ctx);
         // Continue to inference phase even if registration had errors
         // to collect all errors before returning
         for source_file in ctx.source_files() {
-            for def in &source_file.definitions {
-                match def {
-                    Definition::Function(function_definition) => {
-                        self.infer_variables(function_definition.clone(), ctx);
-                    }
-                    Definition::Struct(struct_definition) => {
-                        let struct_type = TypeInfo {
-                            kind: TypeInfoKind::Struct(struct_definition.name()),
-                            type_params: vec![],
-                        };
-                        for method in &struct_definition.methods {
-                            self.infer_method_variables(method.clone(), struct_type.clone(), ctx);
-                        }
-                    }
-                    _ => {}
-                }
-            }
+            self.infer_definitions(&source_file.definitions, ctx);
         }
+        self.check_constant_expressions(ctx);
+        self.check_unused_imports(ctx);
         if !self.errors.is_empty() {
             let error_messages: Vec<String> = std::mem::take(&mut self.errors)
                 .into_iter()
@@ -101,315 +108,699 @@ impl TypeChecker {
                 .collect();
             bail!(error_messages.join("; "))
         }
+        ctx.diagnostics = std::mem::take(&mut self.diagnostics);
         Ok(self.symbol_table.clone())
     }
 
+    /// Type-checks the bodies of the functions and methods among `definitions`, and of
+    /// those in nested modules within the scope of their module.
+    fn infer_definitions(&mut self, definitions: &[Definition], ctx: &mut TypedContext) {
+        for definition in definitions {
+            match definition {
+                Definition::Function(function_definition) => {
+                    self.infer_variables(function_definition.clone(), ctx);
+                }
+                Definition::Struct(struct_definition) => {
+                    // Inside its methods a generic struct is instantiated with its own
+                    // type parameters
+                    let struct_type = TypeInfo {
+                        kind: TypeInfoKind::Struct(struct_definition.name()),
+                        type_params: self
+                            .symbol_table
+                            .lookup_struct(&struct_definition.name())
+                            .map(|struct_info| struct_info.type_params)
+                            .unwrap_or_default(),
+                    };
+                    for method in &struct_definition.methods {
+                        self.infer_method_variables(method.clone(), struct_type.clone(), ctx);
+                    }
+                }
+                Definition::Module(module) => {
+                    self.enter_module_scope(module);
+                    self.infer_definitions(module.body.as_deref().unwrap_or_default(), ctx);
+                    self.symbol_table.pop_scope();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Makes the scope of `module` current, creating it when the module is first visited,
+    /// so that every phase sees the symbols the earlier phases registered in it.
+    fn enter_module_scope(&mut self, module: &Rc<ModuleDefinition>) {
+        if let Some(scope_id) = self.module_scopes.get(&module.id) {
+            self.symbol_table.enter_scope(*scope_id);
+        } else {
+            let scope_id = self.symbol_table.enter_module(module);
+            self.module_scopes.insert(module.id, scope_id);
+        }
+    }
+
     /// Registers `Definition::Type`, `Definition::Struct`, `Definition::Enum`, and `Definition::Spec`
     fn register_types(&mut self, ctx: &mut TypedContext) {
+        let mut type_names = FxHashSet::default();
         for source_file in ctx.source_files() {
-            for definition in &source_file.definitions {
-                match definition {
-                    Definition::Type(type_definition) => {
-                        self.symbol_table
-                            .register_type(&type_definition.name(), Some(&type_definition.ty))
-                            .unwrap_or_else(|_| {
-                                self.errors.push(TypeCheckError::RegistrationFailed {
-                                    kind: RegistrationKind::Type,
-                                    name: type_definition.name(),
-                                    reason: None,
-                                    location: type_definition.location,
-                                });
-                            });
-                    }
-                    Definition::Struct(struct_definition) => {
-                        let fields: Vec<(String, TypeInfo, Visibility)> = struct_definition
-                            .fields
-                            .iter()
-                            .map(|f| {
-                                (
-                                    f.name.name.clone(),
-                                    TypeInfo::new(&f.type_),
-                                    Visibility::Private,
-                                )
-                            })
-                            .collect();
-                        self.symbol_table
-                            .register_struct(
-                                &struct_definition.name(),
-                                &fields,
-                                vec![],
-                                struct_definition.visibility.clone(),
-                            )
-                            .unwrap_or_else(|_| {
-                                self.errors.push(TypeCheckError::RegistrationFailed {
-                                    kind: RegistrationKind::Struct,
-                                    name: struct_definition.name(),
-                                    reason: None,
-                                    location: struct_definition.location,
-                                });
-                            });
+            Self::collect_type_names(&source_file.definitions, &mut type_names);
+        }
+        self.symbol_table.set_type_names(type_names.clone());
+        for source_file in ctx.source_files() {
+            self.register_type_definitions(&source_file.definitions, &type_names);
+        }
+    }
 
-                        let struct_name = struct_definition.name();
-                        for method in &struct_definition.methods {
-                            let has_self = method.arguments.as_ref().is_some_and(|args| {
-                                args.iter()
-                                    .any(|arg| matches!(arg, ArgumentType::SelfReference(_)))
-                            });
+    /// Collects the names of the types, structs, enums and specs defined among
+    /// `definitions` and in nested modules.
+    fn collect_type_names(definitions: &[Definition], type_names: &mut FxHashSet<String>) {
+        for definition in definitions {
+            match definition {
+                Definition::Type(type_definition) => {
+                    type_names.insert(type_definition.name());
+                }
+                Definition::Struct(struct_definition) => {
+                    type_names.insert(struct_definition.name());
+                }
+                Definition::Enum(enum_definition) => {
+                    type_names.insert(enum_definition.name());
+                }
+                Definition::Spec(spec_definition) => {
+                    type_names.insert(spec_definition.name());
+                }
+                Definition::Module(module) => {
+                    Self::collect_type_names(
+                        module.body.as_deref().unwrap_or_default(),
+                        type_names,
+                    );
+                }
+                Definition::Constant(_)
+                | Definition::Function(_)
+                | Definition::ExternalFunction(_) => {}
+            }
+        }
+    }
 
-                            let param_types: Vec<TypeInfo> = method
-                                .arguments
-                                .as_ref()
-                                .unwrap_or(&vec![])
-                                .iter()
-                                .filter_map(|param| match param {
-                                    ArgumentType::SelfReference(_) => None,
-                                    ArgumentType::IgnoreArgument(ignore_arg) => {
-                                        Some(TypeInfo::new(&ignore_arg.ty))
-                                    }
-                                    ArgumentType::Argument(arg) => Some(TypeInfo::new(&arg.ty)),
-                                    ArgumentType::Type(ty) => Some(TypeInfo::new(ty)),
-                                })
-                                .collect();
+    /// Returns the type parameters of a struct: the names its field types use that are
+    /// neither builtin types nor defined anywhere in the program, as their first use.
+    ///
+    /// `struct Pair { fst: A; snd: B; }` is generic over `A` and `B`.
+    fn struct_type_parameters(
+        struct_definition: &StructDefinition,
+        type_names: &FxHashSet<String>,
+    ) -> Vec<Rc<Identifier>> {
+        let mut type_params = Vec::new();
+        for field in &struct_definition.fields {
+            Self::collect_type_parameters(&field.type_, type_names, &mut type_params);
+        }
+        type_params
+    }
 
-                            let return_type = method
-                                .returns
-                                .as_ref()
-                                .map(TypeInfo::new)
-                                .unwrap_or_default();
+    /// Warns about type parameters of a struct whose name does not start with an uppercase
+    /// letter. Type parameters are named like types, so such a name is more likely a
+    /// misspelled type, such as `i3` for `i32`, than a parameter.
+    fn lint_implicit_type_parameters(
+        &mut self,
+        struct_definition: &StructDefinition,
+        type_params: &[Rc<Identifier>],
+    ) {
+        for type_param in type_params {
+            if !type_param
+                .name
+                .starts_with(|c: char| c.is_ascii_uppercase())
+            {
+                self.diagnostics.push(Diagnostic::lint(
+                    Lint::ImplicitTypeParameter,
+                    type_param.location,
+                    format!(
+                        "`{}` is not a type, so struct `{}` is generic over it",
+                        type_param.name,
+                        struct_definition.name()
+                    ),
+                ));
+            }
+        }
+    }
 
-                            let type_params: Vec<String> = method
-                                .type_parameters
-                                .as_ref()
-                                .unwrap_or(&vec![])
-                                .iter()
-                                .map(|p| p.name())
-                                .collect();
-
-                            let definition_scope_id =
-                                self.symbol_table.current_scope_id().unwrap_or(0);
-                            let signature = FuncInfo {
-                                name: method.name(),
-                                type_params,
-                                param_types,
-                                return_type,
-                                visibility: method.visibility.clone(),
-                                definition_scope_id,
-                            };
+    fn collect_type_parameters(
+        ty: &Type,
+        type_names: &FxHashSet<String>,
+        type_params: &mut Vec<Rc<Identifier>>,
+    ) {
+        let mut add = |identifier: &Rc<Identifier>| {
+            if TypeInfoKind::from_builtin_str(&identifier.name).is_none()
+                && !type_names.contains(&identifier.name)
+                && !type_params
+                    .iter()
+                    .any(|type_param| type_param.name == identifier.name)
+            {
+                type_params.push(identifier.clone());
+            }
+        };
+        match ty {
+            Type::Custom(identifier) => add(identifier),
+            Type::Generic(generic_type) => {
+                for parameter in &generic_type.parameters {
+                    add(parameter);
+                }
+            }
+            Type::Array(array_type) => {
+                Self::collect_type_parameters(&array_type.element_type, type_names, type_params);
+            }
+            Type::Function(function_type) => {
+                for parameter in function_type.parameters.iter().flatten() {
+                    Self::collect_type_parameters(parameter, type_names, type_params);
+                }
+                if let Some(returns) = &function_type.returns {
+                    Self::collect_type_parameters(returns, type_names, type_params);
+                }
+            }
+            Type::Simple(_) | Type::QualifiedName(_) | Type::Qualified(_) => {}
+        }
+    }
 
-                            self.symbol_table
-                                .register_method(
-                                    &struct_name,
-                                    signature,
-                                    method.visibility.clone(),
-                                    has_self,
-                                )
-                                .unwrap_or_else(|err| {
-                                    self.errors.push(TypeCheckError::RegistrationFailed {
-                                        kind: RegistrationKind::Method,
-                                        name: format!("{struct_name}::{}", method.name()),
-                                        reason: Some(err.to_string()),
-                                        location: method.location,
-                                    });
-                                });
-                        }
-                    }
-                    Definition::Enum(enum_definition) => {
-                        let variants: Vec<&str> = enum_definition
-                            .variants
+    /// Registers the type definitions among `definitions`, and those of nested modules in
+    /// the scope of their module.
+    #[allow(clippy::too_many_lines)]
+    fn register_type_definitions(
+        &mut self,
+        definitions: &[Definition],
+        type_names: &FxHashSet<String>,
+    ) {
+        for definition in definitions {
+            match definition {
+                Definition::Type(type_definition) => {
+                    self.symbol_table
+                        .register_type(&type_definition.name(), Some(&type_definition.ty))
+                        .unwrap_or_else(|_| {
+                            self.errors.push(TypeCheckError::RegistrationFailed {
+                                kind: RegistrationKind::Type,
+                                name: type_definition.name(),
+                                reason: None,
+                                location: type_definition.location,
+                            });
+                        });
+                }
+                Definition::Struct(struct_definition) => {
+                    let struct_type_params =
+                        Self::struct_type_parameters(struct_definition, type_names);
+                    self.lint_implicit_type_parameters(struct_definition, &struct_type_params);
+                    let struct_type_params: Vec<String> = struct_type_params
+                        .iter()
+                        .map(|type_param| type_param.name())
+                        .collect();
+                    let fields: Vec<(String, TypeInfo, Visibility)> = struct_definition
+                        .fields
+                        .iter()
+                        .map(|f| {
+                            (
+                                f.name.name.clone(),
+                                self.symbol_table.type_info(&f.type_, &struct_type_params),
+                                Visibility::Private,
+                            )
+                        })
+                        .collect();
+                    self.symbol_table
+                        .register_struct(
+                            &struct_definition.name(),
+                            &fields,
+                            struct_type_params.clone(),
+                            struct_definition.visibility.clone(),
+                        )
+                        .unwrap_or_else(|_| {
+                            self.errors.push(TypeCheckError::RegistrationFailed {
+                                kind: RegistrationKind::Struct,
+                                name: struct_definition.name(),
+                                reason: None,
+                                location: struct_definition.location,
+                            });
+                        });
+
+                    let struct_name = struct_definition.name();
+                    for method in &struct_definition.methods {
+                        let type_params: Vec<String> = method
+                            .type_parameters
+                            .as_ref()
+                            .unwrap_or(&vec![])
                             .iter()
-                            .map(|v| v.name.as_str())
+                            .map(|p| p.name())
                             .collect();
+                        // The struct's type parameters are in scope in its methods
+                        let type_param_names =
+                            [struct_type_params.as_slice(), &type_params].concat();
+
+                        let has_self = method.arguments.as_ref().is_some_and(|args| {
+                            args.iter()
+                                .any(|arg| matches!(arg, ArgumentType::SelfReference(_)))
+                        });
+                        let mut_self = method.arguments.as_ref().is_some_and(|args| {
+                            args.iter().any(|arg| {
+                                matches!(arg, ArgumentType::SelfReference(self_ref) if self_ref.is_mut)
+                            })
+                        });
+
+                        let (param_names, param_types): (Vec<_>, Vec<_>) = method
+                            .arguments
+                            .as_ref()
+                            .unwrap_or(&vec![])
+                            .iter()
+                            .filter_map(|param| {
+                                let type_info = match param {
+                                    ArgumentType::SelfReference(_) => return None,
+                                    ArgumentType::IgnoreArgument(ignore_arg) => self
+                                        .symbol_table
+                                        .type_info(&ignore_arg.ty, &type_param_names),
+                                    ArgumentType::Argument(arg) => {
+                                        self.symbol_table.type_info(&arg.ty, &type_param_names)
+                                    }
+                                    ArgumentType::Type(ty) => {
+                                        self.symbol_table.type_info(ty, &type_param_names)
+                                    }
+                                };
+                                Some((param.parameter_name(), type_info))
+                            })
+                            .unzip();
+
+                        let return_type = method
+                            .returns
+                            .as_ref()
+                            .map(|r| self.symbol_table.type_info(r, &type_param_names))
+                            .unwrap_or_default();
+
+                        let definition_scope_id = self.symbol_table.current_scope_id().unwrap_or(0);
+                        let signature = FuncInfo {
+                            name: method.name(),
+                            type_params,
+                            param_names,
+                            param_types,
+                            return_type,
+                            visibility: method.visibility.clone(),
+                            definition_scope_id,
+                        };
+
                         self.symbol_table
-                            .register_enum(
-                                &enum_definition.name(),
-                                &variants,
-                                enum_definition.visibility.clone(),
+                            .register_method(
+                                &struct_name,
+                                signature,
+                                method.visibility.clone(),
+                                has_self,
+                                mut_self,
                             )
-                            .unwrap_or_else(|_| {
+                            .unwrap_or_else(|err| {
                                 self.errors.push(TypeCheckError::RegistrationFailed {
-                                    kind: RegistrationKind::Enum,
-                                    name: enum_definition.name(),
-                                    reason: None,
-                                    location: enum_definition.location,
+                                    kind: RegistrationKind::Method,
+                                    name: format!("{struct_name}::{}", method.name()),
+                                    reason: Some(err.to_string()),
+                                    location: method.location,
                                 });
                             });
                     }
-                    Definition::Spec(spec_definition) => {
-                        self.symbol_table
-                            .register_spec(&spec_definition.name())
-                            .unwrap_or_else(|_| {
-                                self.errors.push(TypeCheckError::RegistrationFailed {
-                                    kind: RegistrationKind::Spec,
-                                    name: spec_definition.name(),
-                                    reason: None,
-                                    location: spec_definition.location,
-                                });
+                }
+                Definition::Enum(enum_definition) => {
+                    let variants: Vec<&str> = enum_definition
+                        .variants
+                        .iter()
+                        .map(|v| v.name.as_str())
+                        .collect();
+                    self.symbol_table
+                        .register_enum(
+                            &enum_definition.name(),
+                            &variants,
+                            enum_definition.visibility.clone(),
+                        )
+                        .unwrap_or_else(|_| {
+                            self.errors.push(TypeCheckError::RegistrationFailed {
+                                kind: RegistrationKind::Enum,
+                                name: enum_definition.name(),
+                                reason: None,
+                                location: enum_definition.location,
                             });
+                        });
+                }
+                Definition::Spec(spec_definition) => {
+                    if self
+                        .symbol_table
+                        .register_spec(&spec_definition.name())
+                        .is_ok()
+                    {
+                        self.spec_definitions
+                            .insert(spec_definition.name(), spec_definition.clone());
+                    } else {
+                        self.errors.push(TypeCheckError::RegistrationFailed {
+                            kind: RegistrationKind::Spec,
+                            name: spec_definition.name(),
+                            reason: None,
+                            location: spec_definition.location,
+                        });
                     }
-                    Definition::Constant(_)
-                    | Definition::Function(_)
-                    | Definition::ExternalFunction(_)
-                    | Definition::Module(_) => {}
                 }
+                Definition::Module(module) => {
+                    self.enter_module_scope(module);
+                    self.register_type_definitions(
+                        module.body.as_deref().unwrap_or_default(),
+                        type_names,
+                    );
+                    self.symbol_table.pop_scope();
+                }
+                Definition::Constant(_)
+                | Definition::Function(_)
+                | Definition::ExternalFunction(_) => {}
             }
         }
     }
 
     /// Registers `Definition::Function`, `Definition::ExternalFunction`, and `Definition::Constant`
-    #[allow(clippy::too_many_lines)]
     fn collect_function_and_constant_definitions(&mut self, ctx: &mut TypedContext) {
-        for sf in ctx.source_files() {
-            for definition in &sf.definitions {
-                match definition {
-                    Definition::Constant(constant_definition) => {
-                        let const_type = TypeInfo::new(&constant_definition.ty);
-                        if let Err(err) = self
-                            .symbol_table
-                            .push_variable_to_scope(&constant_definition.name(), const_type.clone())
-                        {
-                            self.errors.push(TypeCheckError::RegistrationFailed {
-                                kind: RegistrationKind::Variable,
-                                name: constant_definition.name(),
-                                reason: Some(err.to_string()),
-                                location: constant_definition.location,
-                            });
-                        }
-                        ctx.set_node_typeinfo(constant_definition.value.id(), const_type);
+        for source_file in ctx.source_files() {
+            self.collect_definitions(&source_file.definitions, ctx);
+        }
+    }
+
+    /// Registers the functions and constants among `definitions`, and those of nested
+    /// modules in the scope of their module.
+    #[allow(clippy::too_many_lines)]
+    fn collect_definitions(&mut self, definitions: &[Definition], ctx: &mut TypedContext) {
+        for definition in definitions {
+            match definition {
+                Definition::Constant(constant_definition) => {
+                    let const_type = self.declared_type(&constant_definition.ty, &[]);
+                    if let Err(err) = self.symbol_table.push_variable_to_scope(
+                        &constant_definition.name(),
+                        const_type.clone(),
+                        Binding::Constant,
+                        constant_definition.location,
+                    ) {
+                        self.errors.push(TypeCheckError::RegistrationFailed {
+                            kind: RegistrationKind::Variable,
+                            name: constant_definition.name(),
+                            reason: Some(err.to_string()),
+                            location: constant_definition.location,
+                        });
                     }
-                    Definition::Function(function_definition) => {
-                        for param in function_definition.arguments.as_ref().unwrap_or(&vec![]) {
-                            match param {
-                                ArgumentType::SelfReference(self_ref) => {
-                                    self.errors.push(TypeCheckError::SelfReferenceInFunction {
-                                        function_name: function_definition.name(),
-                                        location: self_ref.location,
-                                    });
-                                }
-                                ArgumentType::IgnoreArgument(ignore_argument) => {
-                                    self.validate_type(
-                                        &ignore_argument.ty,
-                                        function_definition.type_parameters.as_ref(),
-                                    );
-                                    ctx.set_node_typeinfo(
-                                        ignore_argument.id,
-                                        TypeInfo::new(&ignore_argument.ty),
-                                    );
-                                }
-                                ArgumentType::Argument(arg) => {
-                                    self.validate_type(
-                                        &arg.ty,
-                                        function_definition.type_parameters.as_ref(),
-                                    );
-                                    let type_info = TypeInfo::new(&arg.ty);
-                                    ctx.set_node_typeinfo(arg.id, type_info.clone());
-                                    ctx.set_node_typeinfo(arg.name.id, type_info);
-                                }
-                                ArgumentType::Type(ty) => {
-                                    self.validate_type(
-                                        ty,
-                                        function_definition.type_parameters.as_ref(),
-                                    );
-                                }
+                    self.infer_constant_value(constant_definition, &const_type, ctx);
+                    ctx.set_node_typeinfo(constant_definition.id, const_type);
+                }
+                Definition::Function(function_definition) => {
+                    for param in function_definition.arguments.as_ref().unwrap_or(&vec![]) {
+                        match param {
+                            ArgumentType::SelfReference(self_ref) => {
+                                self.errors.push(TypeCheckError::SelfReferenceInFunction {
+                                    function_name: function_definition.name(),
+                                    location: self_ref.location,
+                                });
+                            }
+                            ArgumentType::IgnoreArgument(ignore_argument) => {
+                                self.validate_type(
+                                    &ignore_argument.ty,
+                                    function_definition.type_parameters.as_ref(),
+                                );
+                                ctx.set_node_typeinfo(
+                                    ignore_argument.id,
+                                    self.symbol_table.type_info(&ignore_argument.ty, &[]),
+                                );
+                            }
+                            ArgumentType::Argument(arg) => {
+                                self.validate_type(
+                                    &arg.ty,
+                                    function_definition.type_parameters.as_ref(),
+                                );
+                                let type_info =
+                                    self.resolve_type(&self.symbol_table.type_info(&arg.ty, &[]));
+                                ctx.set_node_typeinfo(arg.id, type_info.clone());
+                                ctx.set_node_typeinfo(arg.name.id, type_info);
+                            }
+                            ArgumentType::Type(ty) => {
+                                self.validate_type(
+                                    ty,
+                                    function_definition.type_parameters.as_ref(),
+                                );
                             }
                         }
-                        ctx.set_node_typeinfo(
-                            function_definition.name.id,
-                            TypeInfo {
-                                kind: TypeInfoKind::Function(function_definition.name()),
-                                type_params: function_definition
-                                    .type_parameters
-                                    .as_ref()
-                                    .map_or(vec![], |p| p.iter().map(|i| i.name.clone()).collect()),
-                            },
-                        );
-                        if let Some(return_type) = &function_definition.returns {
-                            self.validate_type(
-                                return_type,
-                                function_definition.type_parameters.as_ref(),
-                            );
-                            ctx.set_node_typeinfo(return_type.id(), TypeInfo::new(return_type));
-                        }
-                        // Register function even if parameter validation had errors
-                        // to allow error recovery and prevent spurious UndefinedFunction errors
-                        if let Err(err) = self.symbol_table.register_function(
-                            &function_definition.name(),
-                            function_definition
+                    }
+                    ctx.set_node_typeinfo(
+                        function_definition.name.id,
+                        TypeInfo {
+                            kind: TypeInfoKind::Function(function_definition.name()),
+                            type_params: function_definition
                                 .type_parameters
                                 .as_ref()
-                                .unwrap_or(&vec![])
-                                .iter()
-                                .map(|param| param.name())
-                                .collect::<Vec<_>>(),
-                            &function_definition
-                                .arguments
-                                .as_ref()
-                                .unwrap_or(&vec![])
-                                .iter()
-                                .filter_map(|param| match param {
-                                    ArgumentType::SelfReference(_) => None,
-                                    ArgumentType::IgnoreArgument(ignore_argument) => {
-                                        Some(ignore_argument.ty.clone())
-                                    }
-                                    ArgumentType::Argument(argument) => Some(argument.ty.clone()),
-                                    ArgumentType::Type(ty) => Some(ty.clone()),
-                                })
-                                .collect::<Vec<_>>(),
-                            &function_definition
-                                .returns
-                                .as_ref()
-                                .unwrap_or(&Type::Simple(SimpleTypeKind::Unit))
-                                .clone(),
-                        ) {
-                            self.errors.push(TypeCheckError::RegistrationFailed {
-                                kind: RegistrationKind::Function,
-                                name: function_definition.name(),
-                                reason: Some(err),
-                                location: function_definition.location,
-                            });
+                                .map_or(vec![], |p| p.iter().map(|i| i.name.clone()).collect()),
+                        },
+                    );
+                    if let Some(return_type) = &function_definition.returns {
+                        self.validate_type(
+                            return_type,
+                            function_definition.type_parameters.as_ref(),
+                        );
+                        ctx.set_node_typeinfo(
+                            return_type.id(),
+                            self.symbol_table.type_info(return_type, &[]),
+                        );
+                    }
+                    // Register function even if parameter validation had errors
+                    // to allow error recovery and prevent spurious UndefinedFunction errors
+                    if let Err(err) = self.symbol_table.register_function_with_visibility(
+                        &function_definition.name(),
+                        function_definition
+                            .type_parameters
+                            .as_ref()
+                            .unwrap_or(&vec![])
+                            .iter()
+                            .map(|param| param.name())
+                            .collect::<Vec<_>>(),
+                        function_definition.arguments.as_deref().unwrap_or_default(),
+                        &function_definition
+                            .returns
+                            .as_ref()
+                            .unwrap_or(&Type::Simple(SimpleTypeKind::Unit))
+                            .clone(),
+                        function_definition.visibility.clone(),
+                    ) {
+                        self.errors.push(TypeCheckError::RegistrationFailed {
+                            kind: RegistrationKind::Function,
+                            name: function_definition.name(),
+                            reason: Some(err),
+                            location: function_definition.location,
+                        });
+                    }
+                }
+                Definition::ExternalFunction(external_function_definition) => {
+                    if let Err(err) = self.symbol_table.register_function_with_visibility(
+                        &external_function_definition.name(),
+                        vec![],
+                        external_function_definition
+                            .arguments
+                            .as_deref()
+                            .unwrap_or_default(),
+                        &external_function_definition
+                            .returns
+                            .as_ref()
+                            .unwrap_or(&Type::Simple(SimpleTypeKind::Unit))
+                            .clone(),
+                        external_function_definition.visibility.clone(),
+                    ) {
+                        self.errors.push(TypeCheckError::RegistrationFailed {
+                            kind: RegistrationKind::Function,
+                            name: external_function_definition.name(),
+                            reason: Some(err),
+                            location: external_function_definition.location,
+                        });
+                    }
+                }
+                Definition::Module(module) => {
+                    self.enter_module_scope(module);
+                    self.collect_definitions(module.body.as_deref().unwrap_or_default(), ctx);
+                    self.symbol_table.pop_scope();
+                }
+                Definition::Spec(_)
+                | Definition::Struct(_)
+                | Definition::Enum(_)
+                | Definition::Type(_) => {}
+            }
+        }
+    }
+
+    /// Checks that every module claiming a spec conforms to it.
+    ///
+    /// A function whose return type is a spec is the constructor of that spec and claims
+    /// it for the module the function is defined in. The module must define every
+    /// function the spec declares with `external fn`, with the same parameter and return
+    /// types, and every type the spec declares with `type`. A type alias must name the
+    /// same type as the declaration; a struct or enum of the same name always conforms.
+    fn check_spec_conformance(&mut self, ctx: &mut TypedContext) {
+        let mut source_files = ctx.source_files();
+        source_files.sort_by_key(|source_file| source_file.id);
+        for source_file in &source_files {
+            self.check_module_conformance(&source_file.definitions, ctx);
+        }
+    }
+
+    /// Checks the specs claimed by functions among `definitions` against `definitions`,
+    /// and those of nested modules against their module.
+    fn check_module_conformance(&mut self, definitions: &[Definition], ctx: &mut TypedContext) {
+        for definition in definitions {
+            match definition {
+                Definition::Function(function_definition) => {
+                    if let Some(return_type @ Type::Custom(name)) = &function_definition.returns
+                        && let Some(spec_type) = self.symbol_table.lookup_type(&name.name)
+                        && let TypeInfoKind::Spec(spec_name) = &spec_type.kind
+                        && let Some(spec_definition) = self.spec_definitions.get(spec_name).cloned()
+                    {
+                        ctx.set_node_typeinfo(return_type.id(), spec_type.clone());
+                        self.check_conformance(&spec_definition, definitions, function_definition);
+                    }
+                }
+                Definition::Module(module) => {
+                    self.enter_module_scope(module);
+                    self.check_module_conformance(module.body.as_deref().unwrap_or_default(), ctx);
+                    self.symbol_table.pop_scope();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks that `definitions`, the module of `constructor`, provide the functions and
+    /// types that `spec` declares.
+    fn check_conformance(
+        &mut self,
+        spec: &SpecDefinition,
+        definitions: &[Definition],
+        constructor: &FunctionDefinition,
+    ) {
+        for requirement in &spec.definitions {
+            match requirement {
+                Definition::ExternalFunction(declaration) => {
+                    let implementation =
+                        definitions.iter().find_map(|definition| match definition {
+                            Definition::Function(function_definition)
+                                if function_definition.name() == declaration.name() =>
+                            {
+                                Some((
+                                    function_definition.arguments.as_deref().unwrap_or_default(),
+                                    function_definition.returns.as_ref(),
+                                    function_definition.location,
+                                ))
+                            }
+                            Definition::ExternalFunction(external_function_definition)
+                                if external_function_definition.name() == declaration.name() =>
+                            {
+                                Some((
+                                    external_function_definition
+                                        .arguments
+                                        .as_deref()
+                                        .unwrap_or_default(),
+                                    external_function_definition.returns.as_ref(),
+                                    external_function_definition.location,
+                                ))
+                            }
+                            _ => None,
+                        });
+                    let expected = (
+                        declaration.arguments.as_deref().unwrap_or_default(),
+                        declaration.returns.as_ref(),
+                    );
+                    match implementation {
+                        None => self.errors.push(TypeCheckError::MissingSpecFunction {
+                            spec: spec.name(),
+                            function: declaration.name(),
+                            location: constructor.location,
+                        }),
+                        Some((arguments, returns, location))
+                            if !self.signatures_equal(expected, (arguments, returns)) =>
+                        {
+                            self.errors
+                                .push(TypeCheckError::SpecFunctionSignatureMismatch {
+                                    spec: spec.name(),
+                                    function: declaration.name(),
+                                    expected: Self::signature_to_string(expected),
+                                    found: Self::signature_to_string((arguments, returns)),
+                                    location,
+                                });
                         }
+                        Some(_) => {}
                     }
-                    Definition::ExternalFunction(external_function_definition) => {
-                        if let Err(err) = self.symbol_table.register_function(
-                            &external_function_definition.name(),
-                            vec![],
-                            &external_function_definition
-                                .arguments
-                                .as_ref()
-                                .unwrap_or(&vec![])
-                                .iter()
-                                .filter_map(|param| match param {
-                                    ArgumentType::SelfReference(_) => None,
-                                    ArgumentType::IgnoreArgument(ignore_argument) => {
-                                        Some(ignore_argument.ty.clone())
-                                    }
-                                    ArgumentType::Argument(argument) => Some(argument.ty.clone()),
-                                    ArgumentType::Type(ty) => Some(ty.clone()),
-                                })
-                                .collect::<Vec<_>>(),
-                            &external_function_definition
-                                .returns
-                                .as_ref()
-                                .unwrap_or(&Type::Simple(SimpleTypeKind::Unit))
-                                .clone(),
-                        ) {
-                            self.errors.push(TypeCheckError::RegistrationFailed {
-                                kind: RegistrationKind::Function,
-                                name: external_function_definition.name(),
-                                reason: Some(err),
-                                location: external_function_definition.location,
+                }
+                Definition::Type(type_definition) => {
+                    let provided = definitions.iter().find(|definition| match definition {
+                        Definition::Type(provided) => provided.name() == type_definition.name(),
+                        Definition::Struct(provided) => provided.name() == type_definition.name(),
+                        Definition::Enum(provided) => provided.name() == type_definition.name(),
+                        _ => false,
+                    });
+                    match provided {
+                        None => self.errors.push(TypeCheckError::MissingSpecType {
+                            spec: spec.name(),
+                            name: type_definition.name(),
+                            location: constructor.location,
+                        }),
+                        Some(Definition::Type(provided))
+                            if !self.same_type(
+                                &self.declared_type(&type_definition.ty, &[]),
+                                &self.declared_type(&provided.ty, &[]),
+                            ) =>
+                        {
+                            self.errors.push(TypeCheckError::SpecTypeMismatch {
+                                spec: spec.name(),
+                                name: type_definition.name(),
+                                expected: TypeInfo::new(&type_definition.ty).to_string(),
+                                found: TypeInfo::new(&provided.ty).to_string(),
+                                location: provided.location,
                             });
                         }
+                        Some(_) => {}
                     }
-                    Definition::Spec(_)
-                    | Definition::Struct(_)
-                    | Definition::Enum(_)
-                    | Definition::Type(_)
-                    | Definition::Module(_) => {}
                 }
+                _ => {}
             }
         }
     }
 
+    /// Types of the parameters of a function; `self` has no type and is skipped.
+    fn parameter_types(arguments: &[ArgumentType]) -> Vec<&Type> {
+        arguments
+            .iter()
+            .filter_map(|argument| match argument {
+                ArgumentType::Argument(argument) => Some(&argument.ty),
+                ArgumentType::IgnoreArgument(ignore_argument) => Some(&ignore_argument.ty),
+                ArgumentType::Type(ty) => Some(ty),
+                ArgumentType::SelfReference(_) => None,
+            })
+            .collect()
+    }
+
+    /// Compares two function signatures, given as their arguments and return type.
+    /// Parameter names are not part of the signature; a missing return type is `()`.
+    /// Aliases are resolved in the current scope.
+    fn signatures_equal(
+        &self,
+        (left_arguments, left_returns): (&[ArgumentType], Option<&Type>),
+        (right_arguments, right_returns): (&[ArgumentType], Option<&Type>),
+    ) -> bool {
+        let type_info = |ty: &Type| self.symbol_table.type_info(ty, &[]);
+        let return_type = |returns: Option<&Type>| returns.map(type_info).unwrap_or_default();
+        let left_parameters = Self::parameter_types(left_arguments);
+        let right_parameters = Self::parameter_types(right_arguments);
+        left_parameters.len() == right_parameters.len()
+            && left_parameters
+                .iter()
+                .zip(&right_parameters)
+                .all(|(left, right)| self.same_type(&type_info(left), &type_info(right)))
+            && self.same_type(&return_type(left_returns), &return_type(right_returns))
+    }
+
+    /// Renders a function signature as `fn(i32, i32) -> i32`.
+    fn signature_to_string((arguments, returns): (&[ArgumentType], Option<&Type>)) -> String {
+        let parameters = Self::parameter_types(arguments)
+            .into_iter()
+            .map(|ty| TypeInfo::new(ty).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match returns {
+            Some(ty) if !matches!(ty, Type::Simple(SimpleTypeKind::Unit)) => {
+                format!("fn({parameters}) -> {}", TypeInfo::new(ty))
+            }
+            _ => format!("fn({parameters})"),
+        }
+    }
+
     /// Validates that a type reference is well-formed.
     ///
     /// Checks that:
@@ -445,6 +836,19 @@ impl TypeChecker {
                         location: generic_type.base.location,
                     });
                 }
+                // An instantiated struct takes as many type arguments as it has parameters
+                if let Some(struct_info) =
+                    self.symbol_table.lookup_struct(&generic_type.base.name())
+                    && struct_info.type_params.len() != generic_type.parameters.len()
+                {
+                    self.errors
+                        .push(TypeCheckError::TypeParameterCountMismatch {
+                            name: generic_type.base.name(),
+                            expected: struct_info.type_params.len(),
+                            found: generic_type.parameters.len(),
+                            location: generic_type.location,
+                        });
+                }
                 // Validate each parameter in the generic type
                 for param in &generic_type.parameters {
                     // Check if it's a declared type parameter or a known type
@@ -470,6 +874,18 @@ impl TypeChecker {
                         location: identifier.location,
                     });
                 }
+                // A generic struct named without type arguments takes none of them
+                if let Some(struct_info) = self.symbol_table.lookup_struct(&identifier.name)
+                    && !struct_info.type_params.is_empty()
+                {
+                    self.errors
+                        .push(TypeCheckError::TypeParameterCountMismatch {
+                            name: identifier.name.clone(),
+                            expected: struct_info.type_params.len(),
+                            found: 0,
+                            location: identifier.location,
+                        });
+                }
             }
         }
     }
@@ -493,11 +909,13 @@ impl TypeChecker {
             for argument in arguments {
                 match argument {
                     ArgumentType::Argument(arg) => {
-                        let arg_type = TypeInfo::new_with_type_params(&arg.ty, &type_param_names);
-                        if let Err(err) = self
-                            .symbol_table
-                            .push_variable_to_scope(&arg.name(), arg_type)
-                        {
+                        let arg_type = self.declared_type(&arg.ty, &type_param_names);
+                        if let Err(err) = self.symbol_table.push_variable_to_scope(
+                            &arg.name(),
+                            arg_type,
+                            Self::argument_binding(arg),
+                            arg.location,
+                        ) {
                             self.errors.push(TypeCheckError::RegistrationFailed {
                                 kind: RegistrationKind::Variable,
                                 name: arg.name(),
@@ -521,7 +939,7 @@ impl TypeChecker {
         let return_type = function_definition
             .returns
             .as_ref()
-            .map(|r| TypeInfo::new_with_type_params(r, &type_param_names))
+            .map(|r| self.declared_type(r, &type_param_names))
             .unwrap_or_default();
 
         for stmt in &mut function_definition.body.statements() {
@@ -538,14 +956,29 @@ impl TypeChecker {
         ctx: &mut TypedContext,
     ) {
         self.symbol_table.push_scope();
+        let type_param_names: Vec<String> = self_type
+            .type_params
+            .iter()
+            .cloned()
+            .chain(
+                method_definition
+                    .type_parameters
+                    .iter()
+                    .flatten()
+                    .map(|p| p.name()),
+            )
+            .collect();
         if let Some(arguments) = &method_definition.arguments {
             for argument in arguments {
                 match argument {
                     ArgumentType::Argument(arg) => {
-                        if let Err(err) = self
-                            .symbol_table
-                            .push_variable_to_scope(&arg.name(), TypeInfo::new(&arg.ty))
-                        {
+                        let arg_type = self.declared_type(&arg.ty, &type_param_names);
+                        if let Err(err) = self.symbol_table.push_variable_to_scope(
+                            &arg.name(),
+                            arg_type,
+                            Self::argument_binding(arg),
+                            arg.location,
+                        ) {
                             self.errors.push(TypeCheckError::RegistrationFailed {
                                 kind: RegistrationKind::Variable,
                                 name: arg.name(),
@@ -555,10 +988,17 @@ impl TypeChecker {
                         }
                     }
                     ArgumentType::SelfReference(self_ref) => {
-                        if let Err(err) = self
-                            .symbol_table
-                            .push_variable_to_scope("self", self_type.clone())
-                        {
+                        let binding = if self_ref.is_mut {
+                            Binding::Mutable
+                        } else {
+                            Binding::ImmutableSelf
+                        };
+                        if let Err(err) = self.symbol_table.push_variable_to_scope(
+                            "self",
+                            self_type.clone(),
+                            binding,
+                            self_ref.location,
+                        ) {
                             self.errors.push(TypeCheckError::RegistrationFailed {
                                 kind: RegistrationKind::Variable,
                                 name: "self".to_string(),
@@ -571,16 +1011,13 @@ impl TypeChecker {
                 }
             }
         }
+        let return_type = method_definition
+            .returns
+            .as_ref()
+            .map(|r| self.declared_type(r, &type_param_names))
+            .unwrap_or_default();
         for stmt in &mut method_definition.body.statements() {
-            self.infer_statement(
-                stmt,
-                &method_definition
-                    .returns
-                    .as_ref()
-                    .map(TypeInfo::new)
-                    .unwrap_or_default(),
-                ctx,
-            );
+            self.infer_statement(stmt, &return_type, ctx);
         }
         self.symbol_table.pop_scope();
     }
@@ -594,6 +1031,10 @@ impl TypeChecker {
     ) {
         match statement {
             Statement::Assign(assign_statement) => {
+                self.check_mutable_place(
+                    &assign_statement.left.borrow(),
+                    assign_statement.location,
+                );
                 let target_type = self.infer_expression(&assign_statement.left.borrow(), ctx);
                 let right_expr = assign_statement.right.borrow();
                 if let Expression::Uzumaki(uzumaki_rc) = &*right_expr {
@@ -605,9 +1046,12 @@ impl TypeChecker {
                         });
                     }
                 } else {
+                    if let Some(target) = &target_type {
+                        Self::propagate_expected_type(&right_expr, target, ctx);
+                    }
                     let value_type = self.infer_expression(&right_expr, ctx);
                     if let (Some(target), Some(val)) = (target_type, value_type)
-                        && target != val
+                        && !self.same_type(&target, &val)
                     {
                         self.errors.push(TypeCheckError::TypeMismatch {
                             expected: target,
@@ -638,9 +1082,14 @@ impl TypeChecker {
                         return_type.clone(),
                     );
                 } else {
+                    Self::propagate_expected_type(
+                        &return_statement.expression.borrow(),
+                        return_type,
+                        ctx,
+                    );
                     let value_type =
                         self.infer_expression(&return_statement.expression.borrow(), ctx);
-                    if *return_type != value_type.clone().unwrap_or_default() {
+                    if !self.same_type(return_type, &value_type.clone().unwrap_or_default()) {
                         self.errors.push(TypeCheckError::TypeMismatch {
                             expected: return_type.clone(),
                             found: value_type.unwrap_or_default(),
@@ -652,6 +1101,7 @@ impl TypeChecker {
             }
             Statement::Loop(loop_statement) => {
                 if let Some(condition) = &*loop_statement.condition.borrow() {
+                    Self::propagate_expected_type(condition, &TypeInfo::boolean(), ctx);
                     let condition_type = self.infer_expression(condition, ctx);
                     if condition_type.is_none()
                         || condition_type.as_ref().unwrap().kind != TypeInfoKind::Bool
@@ -672,6 +1122,11 @@ impl TypeChecker {
             }
             Statement::Break(_) => {}
             Statement::If(if_statement) => {
+                Self::propagate_expected_type(
+                    &if_statement.condition.borrow(),
+                    &TypeInfo::boolean(),
+                    ctx,
+                );
                 let condition_type = self.infer_expression(&if_statement.condition.borrow(), ctx);
                 if condition_type.is_none()
                     || condition_type.as_ref().unwrap().kind != TypeInfoKind::Bool
@@ -698,13 +1153,14 @@ impl TypeChecker {
                 }
             }
             Statement::VariableDefinition(variable_definition_statement) => {
-                let target_type = TypeInfo::new(&variable_definition_statement.ty);
+                let target_type = self.declared_type(&variable_definition_statement.ty, &[]);
                 if let Some(initial_value) = variable_definition_statement.value.as_ref() {
                     let mut expr_ref = initial_value.borrow_mut();
+                    Self::propagate_expected_type(&expr_ref, &target_type, ctx);
                     if let Expression::Uzumaki(uzumaki_rc) = &mut *expr_ref {
                         ctx.set_node_typeinfo(uzumaki_rc.id, target_type.clone());
                     } else if let Some(init_type) = self.infer_expression(&expr_ref, ctx)
-                        && init_type != TypeInfo::new(&variable_definition_statement.ty)
+                        && !self.same_type(&init_type, &target_type)
                     {
                         self.errors.push(TypeCheckError::TypeMismatch {
                             expected: target_type.clone(),
@@ -714,16 +1170,42 @@ impl TypeChecker {
                         });
                     }
                 }
-                if let Err(err) = self.symbol_table.push_variable_to_scope(
-                    &variable_definition_statement.name(),
-                    TypeInfo::new(&variable_definition_statement.ty),
+                let name = variable_definition_statement.name();
+                let shadowed = self.symbol_table.lookup_variable_info(&name);
+                let binding = if variable_definition_statement.is_mut {
+                    Binding::Mutable
+                } else {
+                    Binding::ImmutableVariable
+                };
+                match self.symbol_table.push_variable_to_scope(
+                    &name,
+                    target_type.clone(),
+                    binding,
+                    variable_definition_statement.location,
                 ) {
-                    self.errors.push(TypeCheckError::RegistrationFailed {
-                        kind: RegistrationKind::Variable,
-                        name: variable_definition_statement.name(),
-                        reason: Some(err.to_string()),
-                        location: variable_definition_statement.location,
-                    });
+                    Ok(()) => {
+                        if let Some(shadowed) = shadowed {
+                            self.diagnostics.push(
+                                Diagnostic::lint(
+                                    Lint::ShadowedBinding,
+                                    variable_definition_statement.name.location,
+                                    format!("`{name}` shadows a binding of an enclosing scope"),
+                                )
+                                .with_note(
+                                    shadowed.location,
+                                    format!("`{name}` was first bound here"),
+                                ),
+                            );
+                        }
+                    }
+                    Err(err) => {
+                        self.errors.push(TypeCheckError::RegistrationFailed {
+                            kind: RegistrationKind::Variable,
+                            name,
+                            reason: Some(err.to_string()),
+                            location: variable_definition_statement.location,
+                        });
+                    }
                 }
                 ctx.set_node_typeinfo(variable_definition_statement.name.id, target_type.clone());
                 ctx.set_node_typeinfo(variable_definition_statement.id, target_type);
@@ -743,6 +1225,11 @@ impl TypeChecker {
                 }
             }
             Statement::Assert(assert_statement) => {
+                Self::propagate_expected_type(
+                    &assert_statement.expression.borrow(),
+                    &TypeInfo::boolean(),
+                    ctx,
+                );
                 let condition_type =
                     self.infer_expression(&assert_statement.expression.borrow(), ctx);
                 if condition_type.is_none()
@@ -757,11 +1244,13 @@ impl TypeChecker {
                 }
             }
             Statement::ConstantDefinition(constant_definition) => {
-                let constant_type = TypeInfo::new(&constant_definition.ty);
-                if let Err(err) = self
-                    .symbol_table
-                    .push_variable_to_scope(&constant_definition.name(), constant_type.clone())
-                {
+                let constant_type = self.declared_type(&constant_definition.ty, &[]);
+                if let Err(err) = self.symbol_table.push_variable_to_scope(
+                    &constant_definition.name(),
+                    constant_type.clone(),
+                    Binding::Constant,
+                    constant_definition.location,
+                ) {
                     self.errors.push(TypeCheckError::RegistrationFailed {
                         kind: RegistrationKind::Variable,
                         name: constant_definition.name(),
@@ -769,12 +1258,37 @@ impl TypeChecker {
                         location: constant_definition.location,
                     });
                 }
-                ctx.set_node_typeinfo(constant_definition.value.id(), constant_type.clone());
+                self.infer_constant_value(constant_definition, &constant_type, ctx);
                 ctx.set_node_typeinfo(constant_definition.id, constant_type);
             }
         }
     }
 
+    /// Types the literal a constant is initialized with against the declared type.
+    ///
+    /// Array literals are inferred element by element, so every element expression gets
+    /// its own type; the literal itself is recorded with the declared type.
+    fn infer_constant_value(
+        &mut self,
+        constant_definition: &ConstantDefinition,
+        constant_type: &TypeInfo,
+        ctx: &mut TypedContext,
+    ) {
+        let value = Expression::Literal(constant_definition.value.clone());
+        Self::propagate_expected_type(&value, constant_type, ctx);
+        if let Some(value_type) = self.infer_expression(&value, ctx)
+            && !self.same_type(&value_type, constant_type)
+        {
+            self.errors.push(TypeCheckError::TypeMismatch {
+                expected: constant_type.clone(),
+                found: value_type,
+                context: TypeMismatchContext::ConstantDefinition,
+                location: constant_definition.location,
+            });
+        }
+        ctx.set_node_typeinfo(constant_definition.value.id(), constant_type.clone());
+    }
+
     #[allow(clippy::too_many_lines)]
     fn infer_expression(
         &mut self,
@@ -823,6 +1337,7 @@ impl TypeChecker {
                 } else if let Some(object_type) =
                     self.infer_expression(&member_access_expression.expression.borrow(), ctx)
                 {
+                    let object_type = self.resolve_type(&object_type);
                     let struct_name = match &object_type.kind {
                         TypeInfoKind::Struct(name) => Some(name.clone()),
                         TypeInfoKind::Custom(name) => {
@@ -850,7 +1365,14 @@ impl TypeChecker {
                                         field_name: field_name.clone(),
                                     },
                                 );
-                                let field_type = field_info.type_info.clone();
+                                // A field of a generic struct takes the type arguments
+                                // of the instance it is read from
+                                let substitutions = self.instantiation(
+                                    &struct_info.type_params,
+                                    &object_type.type_params,
+                                );
+                                let field_type = self
+                                    .resolve_type(&field_info.type_info.substitute(&substitutions));
                                 ctx.set_node_typeinfo(
                                     member_access_expression.id,
                                     field_type.clone(),
@@ -1045,27 +1567,28 @@ impl TypeChecker {
                                 });
                             }
 
-                            if let Some(arguments) = &function_call_expression.arguments {
-                                for arg in arguments {
-                                    self.infer_expression(&arg.1.borrow(), ctx);
-                                }
-                            }
+                            self.infer_call_arguments(
+                                "method",
+                                &format!("{}::{}", type_name, method_name),
+                                signature,
+                                function_call_expression,
+                                &FxHashMap::default(),
+                                ctx,
+                            );
 
                             ctx.set_node_typeinfo(
                                 type_member_access.id,
                                 TypeInfo {
-                                    kind: TypeInfoKind::Function(format!(
-                                        "{}::{}",
-                                        type_name, method_name
+                                    kind: TypeInfoKind::Function(self.symbol_table.qualified_name(
+                                        method_info.scope_id,
+                                        &format!("{type_name}::{method_name}"),
                                     )),
                                     type_params: vec![],
                                 },
                             );
-                            ctx.set_node_typeinfo(
-                                function_call_expression.id,
-                                signature.return_type.clone(),
-                            );
-                            return Some(signature.return_type.clone());
+                            let return_type = self.resolve_type(&signature.return_type);
+                            ctx.set_node_typeinfo(function_call_expression.id, return_type.clone());
+                            return Some(return_type);
                         }
                         // Not an enum and not a method - fall through to standard function handling
                     }
@@ -1078,6 +1601,7 @@ impl TypeChecker {
                         self.infer_expression(&member_access.expression.borrow(), ctx);
 
                     if let Some(receiver_type) = receiver_type {
+                        let receiver_type = self.resolve_type(&receiver_type);
                         let type_name = match &receiver_type.kind {
                             TypeInfoKind::Struct(name) => Some(name.clone()),
                             TypeInfoKind::Custom(name) => {
@@ -1119,6 +1643,14 @@ impl TypeChecker {
                                     },
                                 );
 
+                                // A `mut self` method may change its receiver
+                                if method_info.mut_self {
+                                    self.check_mutable_place(
+                                        &member_access.expression.borrow(),
+                                        function_call_expression.location,
+                                    );
+                                }
+
                                 let signature = &method_info.signature;
                                 let arg_count = function_call_expression
                                     .arguments
@@ -1135,27 +1667,47 @@ impl TypeChecker {
                                     });
                                 }
 
-                                if let Some(arguments) = &function_call_expression.arguments {
-                                    for arg in arguments {
-                                        self.infer_expression(&arg.1.borrow(), ctx);
-                                    }
-                                }
+                                // Methods of a generic struct are instantiated with the
+                                // type arguments of the receiver
+                                let substitutions = self
+                                    .symbol_table
+                                    .lookup_struct(&type_name)
+                                    .map(|struct_info| {
+                                        self.instantiation(
+                                            &struct_info.type_params,
+                                            &receiver_type.type_params,
+                                        )
+                                    })
+                                    .unwrap_or_default();
+                                self.infer_call_arguments(
+                                    "method",
+                                    &format!("{}::{}", type_name, method_name),
+                                    signature,
+                                    function_call_expression,
+                                    &substitutions,
+                                    ctx,
+                                );
 
                                 ctx.set_node_typeinfo(
                                     member_access.id,
                                     TypeInfo {
-                                        kind: TypeInfoKind::Function(format!(
-                                            "{}::{}",
-                                            type_name, method_name
-                                        )),
+                                        kind: TypeInfoKind::Function(
+                                            self.symbol_table.qualified_name(
+                                                method_info.scope_id,
+                                                &format!("{type_name}::{method_name}"),
+                                            ),
+                                        ),
                                         type_params: vec![],
                                     },
                                 );
+                                let return_type = self.resolve_type(
+                                    &signature.return_type.substitute(&substitutions),
+                                );
                                 ctx.set_node_typeinfo(
                                     function_call_expression.id,
-                                    signature.return_type.clone(),
+                                    return_type.clone(),
                                 );
-                                return Some(signature.return_type.clone());
+                                return Some(return_type);
                             }
                             self.errors.push(TypeCheckError::MethodNotFound {
                                 type_name,
@@ -1185,23 +1737,58 @@ impl TypeChecker {
                     return None;
                 }
 
-                let signature = if let Some(s) = self
-                    .symbol_table
-                    .lookup_function(&function_call_expression.name())
+                // `module::function()` names a function of another module
+                let (name, signature) = match &function_call_expression.function {
+                    Expression::TypeMemberAccess(type_member_access) => {
+                        let function_name = &type_member_access.name.name;
+                        let qualifier = type_member_access.expression.borrow();
+                        match Self::module_path(&qualifier) {
+                            Some(module_path) => {
+                                Self::set_module_path_typeinfo(&qualifier, ctx);
+                                (
+                                    format!("{}::{function_name}", module_path.join("::")),
+                                    self.symbol_table
+                                        .lookup_module_function(&module_path, function_name),
+                                )
+                            }
+                            None => (function_name.clone(), None),
+                        }
+                    }
+                    _ => (
+                        function_call_expression.name(),
+                        self.symbol_table
+                            .lookup_function(&function_call_expression.name()),
+                    ),
+                };
+                if signature.is_none()
+                    && let Some(conversion) = Conversion::from_name(&name)
                 {
+                    return self.infer_conversion(conversion, function_call_expression, ctx);
+                }
+                let signature = if let Some(s) = signature {
                     // Check visibility of the function
                     self.check_and_report_visibility(
                         &s.visibility,
                         s.definition_scope_id,
                         &function_call_expression.location,
-                        VisibilityContext::Function {
-                            name: function_call_expression.name(),
+                        VisibilityContext::Function { name: name.clone() },
+                    );
+                    // The callee records the symbol of the function it resolves to, which
+                    // is qualified with the module the function is defined in
+                    ctx.set_node_typeinfo(
+                        function_call_expression.function.id(),
+                        TypeInfo {
+                            kind: TypeInfoKind::Function(
+                                self.symbol_table
+                                    .qualified_name(s.definition_scope_id, &s.name),
+                            ),
+                            type_params: vec![],
                         },
                     );
-                    s.clone()
+                    s
                 } else {
                     self.push_error_dedup(TypeCheckError::UndefinedFunction {
-                        name: function_call_expression.name(),
+                        name,
                         location: function_call_expression.location,
                     });
                     if let Some(arguments) = &function_call_expression.arguments {
@@ -1216,7 +1803,7 @@ impl TypeChecker {
                 {
                     self.errors.push(TypeCheckError::ArgumentCountMismatch {
                         kind: "function",
-                        name: function_call_expression.name(),
+                        name,
                         expected: signature.param_types.len(),
                         found: arguments.len(),
                         location: function_call_expression.location,
@@ -1233,7 +1820,7 @@ impl TypeChecker {
                         if type_parameters.len() != signature.type_params.len() {
                             self.errors
                                 .push(TypeCheckError::TypeParameterCountMismatch {
-                                    name: function_call_expression.name(),
+                                    name: name.clone(),
                                     expected: signature.type_params.len(),
                                     found: type_parameters.len(),
                                     location: function_call_expression.location,
@@ -1263,13 +1850,12 @@ impl TypeChecker {
                         // Try to infer type parameters from arguments
                         let inferred = self.infer_type_params_from_args(
                             &signature,
-                            function_call_expression.arguments.as_ref(),
-                            &function_call_expression.location,
+                            function_call_expression,
                             ctx,
                         );
                         if inferred.is_empty() && !signature.type_params.is_empty() {
                             self.errors.push(TypeCheckError::MissingTypeParameters {
-                                function_name: function_call_expression.name(),
+                                function_name: name.clone(),
                                 expected: signature.type_params.len(),
                                 location: function_call_expression.location,
                             });
@@ -1281,14 +1867,17 @@ impl TypeChecker {
                 };
 
                 // Apply substitution to return type
-                let return_type = signature.return_type.substitute(&substitutions);
-
-                // Infer argument types
-                if let Some(arguments) = &function_call_expression.arguments {
-                    for arg in arguments {
-                        self.infer_expression(&arg.1.borrow(), ctx);
-                    }
-                }
+                let return_type =
+                    self.resolve_type(&signature.return_type.substitute(&substitutions));
+
+                self.infer_call_arguments(
+                    "function",
+                    &name,
+                    &signature,
+                    function_call_expression,
+                    &substitutions,
+                    ctx,
+                );
 
                 ctx.set_node_typeinfo(function_call_expression.id, return_type.clone());
                 Some(return_type)
@@ -1299,7 +1888,61 @@ impl TypeChecker {
                 }
                 let struct_type = self.symbol_table.lookup_type(&struct_expression.name());
                 if let Some(struct_type) = struct_type {
+                    let struct_info = self.symbol_table.lookup_struct(&struct_expression.name());
+                    let substitutions = struct_info
+                        .as_ref()
+                        .map(|info| {
+                            self.struct_expression_substitutions(struct_expression, info, ctx)
+                        })
+                        .unwrap_or_default();
+                    let struct_type = TypeInfo {
+                        type_params: struct_type
+                            .type_params
+                            .iter()
+                            .map(|type_param| {
+                                substitutions.get(type_param).map_or_else(
+                                    || type_param.clone(),
+                                    TypeInfo::type_argument_name,
+                                )
+                            })
+                            .collect(),
+                        ..struct_type
+                    };
                     ctx.set_node_typeinfo(struct_expression.id, struct_type.clone());
+                    // Field initializers take the declared field type, so that
+                    // `Point { x: 1 }` with `x: i64` types the literal as `i64`
+                    for (field_name, value) in struct_expression.fields.iter().flatten() {
+                        let field_type = struct_info
+                            .as_ref()
+                            .and_then(|info| info.fields.get(&field_name.name))
+                            .map(|field_info| {
+                                self.resolve_type(&field_info.type_info.substitute(&substitutions))
+                            });
+                        if let Some(field_type) = &field_type {
+                            Self::propagate_expected_type(&value.borrow(), field_type, ctx);
+                        } else if struct_info.is_some() {
+                            self.errors.push(TypeCheckError::FieldNotFound {
+                                struct_name: struct_expression.name(),
+                                field_name: field_name.name.clone(),
+                                location: field_name.location,
+                            });
+                        }
+                        let value_type = self.infer_expression(&value.borrow(), ctx);
+                        if let (Some(field_type), Some(value_type)) = (field_type, value_type)
+                            && !field_type.has_unresolved_params()
+                            && !self.same_type(&field_type, &value_type)
+                        {
+                            self.errors.push(TypeCheckError::TypeMismatch {
+                                expected: field_type,
+                                found: value_type,
+                                context: TypeMismatchContext::StructField {
+                                    struct_name: struct_expression.name(),
+                                    field_name: field_name.name.clone(),
+                                },
+                                location: field_name.location,
+                            });
+                        }
+                    }
                     return Some(struct_type);
                 }
                 self.push_error_dedup(TypeCheckError::UndefinedStruct {
@@ -1384,10 +2027,27 @@ impl TypeChecker {
                 if let Some(type_info) = ctx.get_node_typeinfo(binary_expression.id) {
                     return Some(type_info.clone());
                 }
-                let left_type = self.infer_expression(&binary_expression.left.borrow(), ctx);
-                let right_type = self.infer_expression(&binary_expression.right.borrow(), ctx);
+                // Infer the operand that is not an untyped number literal first, so the literal
+                // on the other side takes its type (e.g. `x + 1` where `x: u8`).
+                let left_expr = binary_expression.left.borrow();
+                let right_expr = binary_expression.right.borrow();
+                let (left_type, right_type) = if Self::is_untyped_operand(&left_expr, ctx)
+                    && !Self::is_untyped_operand(&right_expr, ctx)
+                {
+                    let right_type = self.infer_expression(&right_expr, ctx);
+                    if let Some(right_type) = &right_type {
+                        Self::propagate_expected_type(&left_expr, right_type, ctx);
+                    }
+                    (self.infer_expression(&left_expr, ctx), right_type)
+                } else {
+                    let left_type = self.infer_expression(&left_expr, ctx);
+                    if let Some(left_type) = &left_type {
+                        Self::propagate_expected_type(&right_expr, left_type, ctx);
+                    }
+                    (left_type, self.infer_expression(&right_expr, ctx))
+                };
                 if let (Some(left_type), Some(right_type)) = (left_type, right_type) {
-                    if left_type != right_type {
+                    if !self.same_type(&left_type, &right_type) {
                         self.errors.push(TypeCheckError::BinaryOperandTypeMismatch {
                             operator: binary_expression.operator.clone(),
                             left: left_type.clone(),
@@ -1443,7 +2103,7 @@ impl TypeChecker {
                                     location: binary_expression.location,
                                 });
                             }
-                            if left_type != right_type {
+                            if !self.same_type(&left_type, &right_type) {
                                 self.errors.push(TypeCheckError::BinaryOperandTypeMismatch {
                                     operator: binary_expression.operator.clone(),
                                     left: left_type.clone(),
@@ -1472,7 +2132,7 @@ impl TypeChecker {
                         for element in &elements[1..] {
                             let element_type = self.infer_expression(&element.borrow(), ctx);
                             if let Some(element_type) = element_type
-                                && element_type != element_type_info
+                                && !self.same_type(&element_type, &element_type_info)
                             {
                                 self.errors.push(TypeCheckError::ArrayElementTypeMismatch {
                                     expected: element_type_info.clone(),
@@ -1526,220 +2186,428 @@ impl TypeChecker {
                         name: identifier.name.clone(),
                         location: identifier.location,
                     });
-                    None
+                    None
+                }
+            }
+            Expression::Type(type_expr) => {
+                let type_info = self.symbol_table.type_info(type_expr, &[]);
+                ctx.set_node_typeinfo(type_expr.id(), type_info.clone());
+                if let Type::Array(array_type) = type_expr {
+                    self.infer_expression(&array_type.size.clone(), ctx);
+                }
+                Some(type_info)
+            }
+            Expression::Uzumaki(uzumaki) => {
+                let type_info = ctx.get_node_typeinfo(uzumaki.id);
+                if type_info.is_none() {
+                    self.errors.push(TypeCheckError::CannotInferUzumakiType {
+                        location: uzumaki.location,
+                    });
+                }
+                type_info
+            }
+        }
+    }
+
+    /// Returns `true` if `expression` is built only from integer literals that have not been
+    /// assigned a type yet (optionally negated, complemented, parenthesized or combined
+    /// with arithmetic operators).
+    fn is_untyped_number(expression: &Expression, ctx: &TypedContext) -> bool {
+        match expression {
+            Expression::Literal(Literal::Number(number_literal)) => {
+                ctx.get_node_typeinfo(number_literal.id).is_none()
+            }
+            Expression::Parenthesized(parenthesized_expression) => {
+                Self::is_untyped_number(&parenthesized_expression.expression.borrow(), ctx)
+            }
+            Expression::PrefixUnary(prefix_unary_expression) => {
+                prefix_unary_expression.operator != UnaryOperatorKind::Not
+                    && Self::is_untyped_number(&prefix_unary_expression.expression.borrow(), ctx)
+            }
+            Expression::Binary(binary_expression) => {
+                Self::is_arithmetic_operator(&binary_expression.operator)
+                    && Self::is_untyped_number(&binary_expression.left.borrow(), ctx)
+                    && Self::is_untyped_number(&binary_expression.right.borrow(), ctx)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if `expression` takes its type from the other operand of a binary
+    /// operator: an untyped number (see [`Self::is_untyped_number`]) or an untyped `@`.
+    fn is_untyped_operand(expression: &Expression, ctx: &TypedContext) -> bool {
+        match expression {
+            Expression::Uzumaki(uzumaki) => ctx.get_node_typeinfo(uzumaki.id).is_none(),
+            _ => Self::is_untyped_number(expression, ctx),
+        }
+    }
+
+    /// Assigns the expected number type to untyped integer literals in `expression`, and
+    /// the expected type to an untyped `@`.
+    ///
+    /// Integer literals default to `i32`. When the surrounding context expects another
+    /// integer type (a typed variable, a return type, the other operand of a binary
+    /// operator), the literals take that type instead, so `let x: u8 = 1;` type-checks.
+    /// `@` has no type of its own; wherever one is expected it takes it, and the analyze
+    /// phase decides whether `@` may appear there.
+    fn propagate_expected_type(
+        expression: &Expression,
+        expected: &TypeInfo,
+        ctx: &mut TypedContext,
+    ) {
+        if let Expression::Uzumaki(uzumaki) = expression {
+            if ctx.get_node_typeinfo(uzumaki.id).is_none() {
+                ctx.set_node_typeinfo(uzumaki.id, expected.clone());
+            }
+            return;
+        }
+        if let (
+            TypeInfoKind::Array(element_type, _),
+            Expression::Literal(Literal::Array(array_literal)),
+        ) = (&expected.kind, expression)
+        {
+            for element in array_literal.elements.iter().flatten() {
+                Self::propagate_expected_type(&element.borrow(), element_type, ctx);
+            }
+            return;
+        }
+        if !expected.is_number() {
+            return;
+        }
+        match expression {
+            Expression::Literal(Literal::Number(number_literal)) => {
+                if ctx.get_node_typeinfo(number_literal.id).is_none() {
+                    ctx.set_node_typeinfo(number_literal.id, expected.clone());
+                }
+            }
+            Expression::Parenthesized(parenthesized_expression) => {
+                Self::propagate_expected_type(
+                    &parenthesized_expression.expression.borrow(),
+                    expected,
+                    ctx,
+                );
+            }
+            Expression::PrefixUnary(prefix_unary_expression)
+                if prefix_unary_expression.operator != UnaryOperatorKind::Not =>
+            {
+                Self::propagate_expected_type(
+                    &prefix_unary_expression.expression.borrow(),
+                    expected,
+                    ctx,
+                );
+            }
+            Expression::Binary(binary_expression)
+                if Self::is_arithmetic_operator(&binary_expression.operator) =>
+            {
+                Self::propagate_expected_type(&binary_expression.left.borrow(), expected, ctx);
+                Self::propagate_expected_type(&binary_expression.right.borrow(), expected, ctx);
+            }
+            _ => {}
+        }
+    }
+
+    fn is_arithmetic_operator(operator: &OperatorKind) -> bool {
+        !matches!(
+            operator,
+            OperatorKind::And
+                | OperatorKind::Or
+                | OperatorKind::Eq
+                | OperatorKind::Ne
+                | OperatorKind::Lt
+                | OperatorKind::Le
+                | OperatorKind::Gt
+                | OperatorKind::Ge
+        )
+    }
+
+    /// Checks every integer literal against the range of its type, and evaluates
+    /// expressions built only from integer literals (see [`Self::is_constant_number`]),
+    /// reporting those whose value does not fit their type.
+    ///
+    /// Each constant expression is evaluated once, from its outermost node; only the
+    /// innermost overflowing part of it is reported.
+    fn check_constant_expressions(&mut self, ctx: &TypedContext) {
+        let mut roots: Vec<Expression> = ctx
+            .filter_nodes(|node| {
+                matches!(node, AstNode::Expression(expression) if Self::is_constant_number(expression))
+            })
+            .into_iter()
+            .filter_map(|node| match node {
+                AstNode::Expression(expression) => Some(expression),
+                _ => None,
+            })
+            .filter(|expression| {
+                !matches!(
+                    ctx.get_parent_node(expression.id()),
+                    Some(AstNode::Expression(parent)) if Self::is_constant_number(&parent)
+                )
+            })
+            .collect();
+        roots.sort_by_key(Expression::id);
+        for root in &roots {
+            self.evaluate_constant(root, ctx);
+        }
+    }
+
+    /// Returns `true` if `expression` is built only from integer literals, optionally
+    /// negated, complemented, parenthesized or combined with arithmetic operators.
+    fn is_constant_number(expression: &Expression) -> bool {
+        match expression {
+            Expression::Literal(Literal::Number(_)) => true,
+            Expression::Parenthesized(parenthesized_expression) => {
+                Self::is_constant_number(&parenthesized_expression.expression.borrow())
+            }
+            Expression::PrefixUnary(prefix_unary_expression) => {
+                prefix_unary_expression.operator != UnaryOperatorKind::Not
+                    && Self::is_constant_number(&prefix_unary_expression.expression.borrow())
+            }
+            Expression::Binary(binary_expression) => {
+                Self::is_arithmetic_operator(&binary_expression.operator)
+                    && Self::is_constant_number(&binary_expression.left.borrow())
+                    && Self::is_constant_number(&binary_expression.right.borrow())
+            }
+            _ => false,
+        }
+    }
+
+    /// Computes the value of a constant expression in the integer type the type checker
+    /// gave it, as the program would at run time.
+    ///
+    /// Returns `None` if the value does not fit its type or the expression divides by
+    /// zero, in which case an error is reported, or if it cannot be computed at compile
+    /// time: the expression is not typed as an integer or raises to a negative power.
+    fn evaluate_constant(&mut self, expression: &Expression, ctx: &TypedContext) -> Option<i128> {
+        let TypeInfoKind::Number(number_type) = ctx.get_node_typeinfo(expression.id())?.kind else {
+            return None;
+        };
+        let value = match expression {
+            Expression::Literal(Literal::Number(number_literal)) => {
+                let value = number_literal
+                    .value
+                    .parse::<i128>()
+                    .ok()
+                    .filter(|value| number_type.contains(*value));
+                if value.is_none() {
+                    self.errors.push(TypeCheckError::IntegerLiteralOutOfRange {
+                        literal: number_literal.value.clone(),
+                        ty: number_type,
+                        location: number_literal.location,
+                    });
                 }
+                return value;
             }
-            Expression::Type(type_expr) => {
-                let type_info = TypeInfo::new(type_expr);
-                ctx.set_node_typeinfo(type_expr.id(), type_info.clone());
-                if let Type::Array(array_type) = type_expr {
-                    self.infer_expression(&array_type.size.clone(), ctx);
+            Expression::Parenthesized(parenthesized_expression) => {
+                return self.evaluate_constant(&parenthesized_expression.expression.borrow(), ctx);
+            }
+            Expression::PrefixUnary(prefix_unary_expression) => {
+                let operand =
+                    self.evaluate_constant(&prefix_unary_expression.expression.borrow(), ctx)?;
+                match prefix_unary_expression.operator {
+                    UnaryOperatorKind::Neg => -operand,
+                    UnaryOperatorKind::BitNot if number_type.is_signed() => !operand,
+                    UnaryOperatorKind::BitNot => number_type.max_value() - operand,
+                    UnaryOperatorKind::Not => return None,
                 }
-                Some(type_info)
             }
-            Expression::Uzumaki(uzumaki) => ctx.get_node_typeinfo(uzumaki.id),
+            Expression::Binary(binary_expression) => {
+                // Both operands are evaluated so that errors in either are reported
+                let left = self.evaluate_constant(&binary_expression.left.borrow(), ctx);
+                let right = self.evaluate_constant(&binary_expression.right.borrow(), ctx);
+                let (left, right) = (left?, right?);
+                if right == 0
+                    && matches!(
+                        binary_expression.operator,
+                        OperatorKind::Div | OperatorKind::Mod
+                    )
+                {
+                    self.errors.push(TypeCheckError::DivisionByZero {
+                        location: binary_expression.location,
+                    });
+                    return None;
+                }
+                Self::apply_constant_operator(
+                    &binary_expression.operator,
+                    left,
+                    right,
+                    number_type,
+                )?
+            }
+            _ => return None,
+        };
+        if number_type.contains(value) {
+            return Some(value);
         }
+        self.errors.push(TypeCheckError::ConstantOverflow {
+            expression: ctx
+                .get_node_source(expression.id())
+                .unwrap_or_default()
+                .to_string(),
+            ty: number_type,
+            location: expression.location(),
+        });
+        None
     }
 
-    #[allow(dead_code)]
-    fn types_equal(left: &Type, right: &Type) -> bool {
-        match (left, right) {
-            (Type::Simple(left), Type::Simple(right)) => left == right,
-            (Type::Array(left), Type::Array(right)) => {
-                Self::types_equal(&left.element_type, &right.element_type)
-            }
-            (Type::Generic(left), Type::Generic(right)) => {
-                left.base.name() == right.base.name() && left.parameters == right.parameters
-            }
-            (Type::Qualified(left), Type::Qualified(right)) => left.name() == right.name(),
-            (Type::QualifiedName(left), Type::QualifiedName(right)) => {
-                left.qualifier() == right.qualifier() && left.name() == right.name()
-            }
-            (Type::Custom(left), Type::Custom(right)) => left.name() == right.name(),
-            (Type::Function(left), Type::Function(right)) => {
-                let left_has_return_type = left.returns.is_some();
-                let right_has_return_type = right.returns.is_some();
-                if left_has_return_type != right_has_return_type {
-                    return false;
-                }
-                if left_has_return_type
-                    && let (Some(left_return_type), Some(right_return_type)) =
-                        (&left.returns, &right.returns)
-                    && !Self::types_equal(left_return_type, right_return_type)
-                {
-                    return false;
-                }
-                let left_has_parameters = left.parameters.is_some();
-                let right_has_parameters = right.parameters.is_some();
-                if left_has_parameters != right_has_parameters {
-                    return false;
-                }
-                if left_has_parameters
-                    && let (Some(left_parameters), Some(right_parameters)) =
-                        (&left.parameters, &right.parameters)
-                {
-                    if left_parameters.len() != right_parameters.len() {
-                        return false;
-                    }
-                    for (left_param, right_param) in
-                        left_parameters.iter().zip(right_parameters.iter())
-                    {
-                        if !Self::types_equal(left_param, right_param) {
-                            return false;
-                        }
-                    }
+    /// Applies a binary operator to two values of `number_type`. The result is exact,
+    /// and may therefore be out of the range of `number_type`; shifts by at least the
+    /// width of the type produce a value that is always out of range.
+    fn apply_constant_operator(
+        operator: &OperatorKind,
+        left: i128,
+        right: i128,
+        number_type: NumberType,
+    ) -> Option<i128> {
+        const OUT_OF_RANGE: i128 = i128::MAX;
+        let value = match operator {
+            OperatorKind::Add => left + right,
+            OperatorKind::Sub => left - right,
+            OperatorKind::Mul => left.checked_mul(right).unwrap_or(OUT_OF_RANGE),
+            OperatorKind::Div => left / right,
+            OperatorKind::Mod => left % right,
+            OperatorKind::Pow if right < 0 => return None,
+            OperatorKind::Pow => u32::try_from(right)
+                .ok()
+                .and_then(|exponent| left.checked_pow(exponent))
+                .unwrap_or(OUT_OF_RANGE),
+            OperatorKind::BitAnd => left & right,
+            OperatorKind::BitOr => left | right,
+            OperatorKind::BitXor => left ^ right,
+            OperatorKind::Shl | OperatorKind::Shr
+                if right < 0 || right >= i128::from(number_type.bits()) =>
+            {
+                OUT_OF_RANGE
+            }
+            OperatorKind::Shl => {
+                // Bits shifted past the width of the type are discarded, as at run time
+                let width = number_type.bits();
+                let bits = (left << right) & ((1 << width) - 1);
+                if number_type.is_signed() && bits >= 1 << (width - 1) {
+                    bits - (1 << width)
+                } else {
+                    bits
                 }
-                true
             }
-            _ => false,
+            OperatorKind::Shr => left >> right,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Compares an expected and an actual type, after resolving the aliases in both.
+    ///
+    /// Declared struct and enum types are recorded as `Custom(name)`, while struct
+    /// literals and constructors produce `Struct(name)` and variant accesses produce
+    /// `Enum(name)`; each pair denotes the same type, also as array elements.
+    fn same_type(&self, left: &TypeInfo, right: &TypeInfo) -> bool {
+        let left = self.resolve_type(left);
+        let right = self.resolve_type(right);
+        match (&left.kind, &right.kind) {
+            (
+                TypeInfoKind::Custom(left_name),
+                TypeInfoKind::Struct(right_name) | TypeInfoKind::Enum(right_name),
+            )
+            | (
+                TypeInfoKind::Struct(left_name) | TypeInfoKind::Enum(left_name),
+                TypeInfoKind::Custom(right_name),
+            ) => left_name == right_name && left.type_params == right.type_params,
+            (
+                TypeInfoKind::Array(left_element, left_length),
+                TypeInfoKind::Array(right_element, right_length),
+            ) => left_length == right_length && self.same_type(left_element, right_element),
+            _ => left == right,
         }
     }
 
-    /// Process a module definition.
+    /// Converts a type written in the source to a `TypeInfo`, resolving aliases.
+    fn declared_type(&self, ty: &Type, type_param_names: &[String]) -> TypeInfo {
+        self.resolve_type(&self.symbol_table.type_info(ty, type_param_names))
+    }
+
+    /// Resolves `type_info` in the current scope.
     ///
-    /// Creates a new scope for the module and processes all definitions within it.
-    /// After processing, pops back to the parent scope.
-    #[allow(dead_code)]
-    fn process_module_definition(
-        &mut self,
-        module: &Rc<ModuleDefinition>,
-        ctx: &mut TypedContext,
-    ) -> anyhow::Result<()> {
-        let _scope_id = self.symbol_table.enter_module(module);
+    /// Aliases (`type Index = i32;`) are replaced by the type they name, following
+    /// chains of aliases; an alias that leads back to itself is left as it is. An
+    /// instantiation of a generic struct (`Pair i32' bool'`) becomes the struct type
+    /// with its type arguments resolved. Struct, enum and spec names are kept.
+    fn resolve_type(&self, type_info: &TypeInfo) -> TypeInfo {
+        self.resolve_type_with(type_info, &mut Vec::new())
+    }
 
-        if let Some(body) = &module.body {
-            for definition in body {
-                match definition {
-                    Definition::Type(type_definition) => {
-                        self.symbol_table
-                            .register_type(&type_definition.name(), Some(&type_definition.ty))
-                            .unwrap_or_else(|_| {
-                                self.errors.push(TypeCheckError::RegistrationFailed {
-                                    kind: RegistrationKind::Type,
-                                    name: type_definition.name(),
-                                    reason: None,
-                                    location: type_definition.location,
-                                });
-                            });
-                    }
-                    Definition::Struct(struct_definition) => {
-                        let fields: Vec<(String, TypeInfo, Visibility)> = struct_definition
-                            .fields
-                            .iter()
-                            .map(|f| {
-                                (
-                                    f.name.name.clone(),
-                                    TypeInfo::new(&f.type_),
-                                    Visibility::Private,
-                                )
-                            })
-                            .collect();
-                        self.symbol_table
-                            .register_struct(
-                                &struct_definition.name(),
-                                &fields,
-                                vec![],
-                                struct_definition.visibility.clone(),
-                            )
-                            .unwrap_or_else(|_| {
-                                self.errors.push(TypeCheckError::RegistrationFailed {
-                                    kind: RegistrationKind::Struct,
-                                    name: struct_definition.name(),
-                                    reason: None,
-                                    location: struct_definition.location,
-                                });
-                            });
-                    }
-                    Definition::Enum(enum_definition) => {
-                        let variants: Vec<&str> = enum_definition
-                            .variants
-                            .iter()
-                            .map(|v| v.name.as_str())
-                            .collect();
-                        self.symbol_table
-                            .register_enum(
-                                &enum_definition.name(),
-                                &variants,
-                                enum_definition.visibility.clone(),
-                            )
-                            .unwrap_or_else(|_| {
-                                self.errors.push(TypeCheckError::RegistrationFailed {
-                                    kind: RegistrationKind::Enum,
-                                    name: enum_definition.name(),
-                                    reason: None,
-                                    location: enum_definition.location,
-                                });
-                            });
-                    }
-                    Definition::Spec(spec_definition) => {
-                        self.symbol_table
-                            .register_spec(&spec_definition.name())
-                            .unwrap_or_else(|_| {
-                                self.errors.push(TypeCheckError::RegistrationFailed {
-                                    kind: RegistrationKind::Spec,
-                                    name: spec_definition.name(),
-                                    reason: None,
-                                    location: spec_definition.location,
-                                });
-                            });
-                    }
-                    Definition::Module(nested_module) => {
-                        self.process_module_definition(nested_module, ctx)?;
-                    }
-                    Definition::Function(function_definition) => {
-                        self.infer_variables(function_definition.clone(), ctx);
-                    }
-                    Definition::Constant(constant_definition) => {
-                        if let Err(err) = self.symbol_table.push_variable_to_scope(
-                            &constant_definition.name(),
-                            TypeInfo::new(&constant_definition.ty),
-                        ) {
-                            self.errors.push(TypeCheckError::RegistrationFailed {
-                                kind: RegistrationKind::Variable,
-                                name: constant_definition.name(),
-                                reason: Some(err.to_string()),
-                                location: constant_definition.location,
-                            });
-                        }
-                    }
-                    Definition::ExternalFunction(external_function_definition) => {
-                        if let Err(err) = self.symbol_table.register_function(
-                            &external_function_definition.name(),
-                            vec![],
-                            &external_function_definition
-                                .arguments
-                                .as_ref()
-                                .unwrap_or(&vec![])
-                                .iter()
-                                .filter_map(|param| match param {
-                                    ArgumentType::SelfReference(_) => None,
-                                    ArgumentType::IgnoreArgument(ignore_argument) => {
-                                        Some(ignore_argument.ty.clone())
-                                    }
-                                    ArgumentType::Argument(argument) => Some(argument.ty.clone()),
-                                    ArgumentType::Type(ty) => Some(ty.clone()),
-                                })
-                                .collect::<Vec<_>>(),
-                            &external_function_definition
-                                .returns
-                                .as_ref()
-                                .unwrap_or(&Type::Simple(SimpleTypeKind::Unit))
-                                .clone(),
-                        ) {
-                            self.errors.push(TypeCheckError::RegistrationFailed {
-                                kind: RegistrationKind::Function,
-                                name: external_function_definition.name(),
-                                reason: Some(err),
-                                location: external_function_definition.location,
-                            });
-                        }
-                    }
+    fn resolve_type_with(&self, type_info: &TypeInfo, aliases: &mut Vec<String>) -> TypeInfo {
+        match &type_info.kind {
+            TypeInfoKind::Custom(name) | TypeInfoKind::Generic(name)
+                if !type_info.type_params.is_empty()
+                    && self.symbol_table.lookup_struct(name).is_some() =>
+            {
+                TypeInfo {
+                    kind: TypeInfoKind::Custom(name.clone()),
+                    type_params: type_info
+                        .type_params
+                        .iter()
+                        .map(|type_argument| self.type_argument(type_argument).type_argument_name())
+                        .collect(),
+                }
+            }
+            TypeInfoKind::Custom(name) if !aliases.contains(name) => {
+                let Some(target) = self.symbol_table.lookup_type(name) else {
+                    return type_info.clone();
+                };
+                let names_itself = match &target.kind {
+                    TypeInfoKind::Struct(_) | TypeInfoKind::Enum(_) | TypeInfoKind::Spec(_) => true,
+                    TypeInfoKind::Custom(target_name) => target_name == name,
+                    _ => false,
+                };
+                if names_itself {
+                    return type_info.clone();
                 }
+                aliases.push(name.clone());
+                let resolved = self.resolve_type_with(&target, aliases);
+                aliases.pop();
+                resolved
             }
+            TypeInfoKind::Array(element_type, length) => TypeInfo {
+                kind: TypeInfoKind::Array(
+                    Box::new(self.resolve_type_with(element_type, aliases)),
+                    *length,
+                ),
+                type_params: vec![],
+            },
+            _ => type_info.clone(),
         }
+    }
 
-        self.symbol_table.pop_scope();
-        Ok(())
+    /// Returns the type a type argument names. A name that is not a type in scope is a
+    /// type variable, such as a type parameter of the enclosing generic struct.
+    fn type_argument(&self, name: &str) -> TypeInfo {
+        match self.symbol_table.lookup_type(name) {
+            Some(type_info) => self.resolve_type(&TypeInfo {
+                kind: type_info.kind,
+                type_params: vec![],
+            }),
+            None => TypeInfo {
+                kind: TypeInfoKind::Generic(name.to_string()),
+                type_params: vec![],
+            },
+        }
+    }
+
+    /// Binds the type parameters of a generic struct to the type arguments of one of its
+    /// instantiations. Arguments that do not match the parameters in number bind nothing.
+    fn instantiation(
+        &self,
+        type_params: &[String],
+        type_arguments: &[String],
+    ) -> FxHashMap<String, TypeInfo> {
+        if type_params.len() != type_arguments.len() {
+            return FxHashMap::default();
+        }
+        type_params
+            .iter()
+            .zip(type_arguments)
+            .map(|(type_param, type_argument)| {
+                (type_param.clone(), self.type_argument(type_argument))
+            })
+            .collect()
     }
 
     /// Process all use directives in source files (Phase A of import resolution).
@@ -1770,6 +2638,115 @@ impl TypeChecker {
         }
     }
 
+    /// Warns about every name a `use` directive imports that no other identifier in the
+    /// program refers to.
+    fn check_unused_imports(&mut self, ctx: &TypedContext) {
+        let directives: Vec<Rc<UseDirective>> = ctx
+            .source_files()
+            .iter()
+            .flat_map(|source_file| source_file.directives.clone())
+            .map(|Directive::Use(use_directive)| use_directive)
+            .collect();
+        // Identifiers that name something rather than refer to it
+        let mut declaring_identifiers = FxHashSet::default();
+        for directive in &directives {
+            for identifier in directive
+                .segments
+                .iter()
+                .chain(directive.imported_types.iter())
+                .flatten()
+            {
+                declaring_identifiers.insert(identifier.id);
+            }
+        }
+        for source_file in ctx.source_files() {
+            Self::collect_definition_names(&source_file.definitions, &mut declaring_identifiers);
+        }
+        let used_names: FxHashSet<String> = ctx
+            .filter_nodes(|node| {
+                matches!(
+                    node,
+                    AstNode::Expression(
+                        Expression::Identifier(_) | Expression::Type(Type::Custom(_))
+                    )
+                )
+            })
+            .into_iter()
+            .filter_map(|node| match node {
+                AstNode::Expression(
+                    Expression::Identifier(identifier) | Expression::Type(Type::Custom(identifier)),
+                ) if !declaring_identifiers.contains(&identifier.id) => {
+                    Some(identifier.name.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        for directive in &directives {
+            let imported: Vec<&Rc<Identifier>> = match &directive.imported_types {
+                Some(names) if !names.is_empty() => names.iter().collect(),
+                _ => directive
+                    .segments
+                    .as_ref()
+                    .and_then(|segments| segments.last())
+                    .into_iter()
+                    .collect(),
+            };
+            for identifier in imported {
+                if !used_names.contains(&identifier.name) {
+                    self.diagnostics.push(Diagnostic::lint(
+                        Lint::UnusedImport,
+                        identifier.location,
+                        format!("unused import `{}`", identifier.name),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Collects the ids of the names of `definitions`, of the definitions nested in them
+    /// and of struct methods.
+    fn collect_definition_names(definitions: &[Definition], ids: &mut FxHashSet<u32>) {
+        for definition in definitions {
+            match definition {
+                Definition::Spec(spec_definition) => {
+                    ids.insert(spec_definition.name.id);
+                    Self::collect_definition_names(&spec_definition.definitions, ids);
+                }
+                Definition::Struct(struct_definition) => {
+                    ids.insert(struct_definition.name.id);
+                    ids.extend(
+                        struct_definition
+                            .methods
+                            .iter()
+                            .map(|method| method.name.id),
+                    );
+                }
+                Definition::Enum(enum_definition) => {
+                    ids.insert(enum_definition.name.id);
+                }
+                Definition::Constant(constant_definition) => {
+                    ids.insert(constant_definition.name.id);
+                }
+                Definition::Function(function_definition) => {
+                    ids.insert(function_definition.name.id);
+                }
+                Definition::ExternalFunction(external_function_definition) => {
+                    ids.insert(external_function_definition.name.id);
+                }
+                Definition::Type(type_definition) => {
+                    ids.insert(type_definition.name.id);
+                }
+                Definition::Module(module_definition) => {
+                    ids.insert(module_definition.name.id);
+                    Self::collect_definition_names(
+                        module_definition.body.as_deref().unwrap_or_default(),
+                        ids,
+                    );
+                }
+            }
+        }
+    }
+
     /// Process a use statement (Phase A: registration only).
     /// Converts UseDirective AST to Import and registers in current scope.
     fn process_use_statement(
@@ -2011,51 +2988,362 @@ impl TypeChecker {
         }
     }
 
+    /// Returns the module path named by the qualifier of `module::function`, such as
+    /// `["math", "ops"]` for `math::ops`, or `None` if the expression is not a path.
+    fn module_path(expression: &Expression) -> Option<Vec<String>> {
+        match expression {
+            Expression::Identifier(identifier) => Some(vec![identifier.name.clone()]),
+            Expression::Type(Type::Custom(identifier)) => Some(vec![identifier.name.clone()]),
+            Expression::Type(Type::Qualified(type_qualified_name)) => Some(vec![
+                type_qualified_name.alias.name.clone(),
+                type_qualified_name.name.name.clone(),
+            ]),
+            Expression::TypeMemberAccess(type_member_access) => {
+                let mut path = Self::module_path(&type_member_access.expression.borrow())?;
+                path.push(type_member_access.name.name.clone());
+                Some(path)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns how an argument is bound: mutable only when declared `mut`.
+    fn argument_binding(argument: &Argument) -> Binding {
+        if argument.is_mut {
+            Binding::Mutable
+        } else {
+            Binding::ImmutableArgument
+        }
+    }
+
+    /// Reports an assignment, or a call of a `mut self` method, whose target is, or is a
+    /// field or element of, a binding that cannot change: a variable or an argument
+    /// declared without `mut`, a constant, or the `self` of a method that does not take
+    /// `mut self`. Both the assignment or call at `location` and the declaration are
+    /// reported.
+    fn check_mutable_place(&mut self, target: &Expression, location: Location) {
+        let Some(root) = Self::place_root(target) else {
+            return;
+        };
+        let Some(variable) = self.symbol_table.lookup_variable_info(&root.name) else {
+            return;
+        };
+        let binding = match variable.binding {
+            Binding::Mutable => return,
+            Binding::ImmutableSelf => {
+                self.errors
+                    .push(TypeCheckError::MutationThroughImmutableSelf {
+                        declaration: variable.location,
+                        location,
+                    });
+                return;
+            }
+            Binding::ImmutableVariable => ImmutableBindingKind::Variable,
+            Binding::ImmutableArgument => ImmutableBindingKind::Argument,
+            Binding::Constant => ImmutableBindingKind::Constant,
+        };
+        self.errors.push(TypeCheckError::AssignmentToImmutable {
+            binding,
+            name: root.name.clone(),
+            declaration: variable.location,
+            location,
+        });
+    }
+
+    /// Returns the variable a place expression such as `a`, `a.b` or `a[i].b` belongs
+    /// to, or `None` if the expression does not denote a place in a variable.
+    fn place_root(expression: &Expression) -> Option<Rc<Identifier>> {
+        match expression {
+            Expression::Identifier(identifier) => Some(identifier.clone()),
+            Expression::MemberAccess(member_access) => {
+                Self::place_root(&member_access.expression.borrow())
+            }
+            Expression::ArrayIndexAccess(array_index_access) => {
+                Self::place_root(&array_index_access.array.borrow())
+            }
+            Expression::Parenthesized(parenthesized) => {
+                Self::place_root(&parenthesized.expression.borrow())
+            }
+            _ => None,
+        }
+    }
+
+    /// Records the nested paths of a module path such as `math::ops`, which name modules
+    /// rather than values, as qualified names.
+    fn set_module_path_typeinfo(expression: &Expression, ctx: &mut TypedContext) {
+        if let Expression::TypeMemberAccess(type_member_access) = expression
+            && let Some(module_path) = Self::module_path(expression)
+        {
+            ctx.set_node_typeinfo(
+                type_member_access.id,
+                TypeInfo {
+                    kind: TypeInfoKind::QualifiedName(module_path.join("::")),
+                    type_params: vec![],
+                },
+            );
+            Self::set_module_path_typeinfo(&type_member_access.expression.borrow(), ctx);
+        }
+    }
+
+    /// Infers a call of a built-in conversion such as `cast i64'(x)`, whose type is its
+    /// type argument.
+    ///
+    /// Both the argument and the target type must be integer types. `cast` also requires
+    /// every value of the argument type to fit the target type; `wrapping_cast` and
+    /// `checked_cast` convert between any two integer types.
+    fn infer_conversion(
+        &mut self,
+        conversion: Conversion,
+        function_call_expression: &FunctionCallExpression,
+        ctx: &mut TypedContext,
+    ) -> Option<TypeInfo> {
+        let name = conversion.name();
+        let argument_types: Vec<Option<TypeInfo>> = function_call_expression
+            .arguments
+            .iter()
+            .flatten()
+            .map(|(_, argument)| self.infer_expression(&argument.borrow(), ctx))
+            .collect();
+        if argument_types.len() != 1 {
+            self.errors.push(TypeCheckError::ArgumentCountMismatch {
+                kind: "function",
+                name: name.to_string(),
+                expected: 1,
+                found: argument_types.len(),
+                location: function_call_expression.location,
+            });
+            return None;
+        }
+        let type_arguments = function_call_expression
+            .type_parameters
+            .as_deref()
+            .unwrap_or_default();
+        let [target] = type_arguments else {
+            self.errors
+                .push(TypeCheckError::TypeParameterCountMismatch {
+                    name: name.to_string(),
+                    expected: 1,
+                    found: type_arguments.len(),
+                    location: function_call_expression.location,
+                });
+            return None;
+        };
+        let target = self.type_argument(&target.name);
+        let source = self.resolve_type(argument_types.into_iter().next().flatten().as_ref()?);
+        let (TypeInfoKind::Number(from), TypeInfoKind::Number(to)) = (&source.kind, &target.kind)
+        else {
+            self.errors.push(TypeCheckError::InvalidConversion {
+                conversion: name,
+                from: source,
+                to: target,
+                location: function_call_expression.location,
+            });
+            return None;
+        };
+        if conversion == Conversion::Cast && !from.fits_in(*to) {
+            self.errors.push(TypeCheckError::LossyCast {
+                from: *from,
+                to: *to,
+                location: function_call_expression.location,
+            });
+        }
+        ctx.set_node_typeinfo(function_call_expression.id, target.clone());
+        Some(target)
+    }
+
+    /// Infers the arguments of a resolved call.
+    ///
+    /// Each argument is bound to a parameter of `signature` (named arguments by name,
+    /// positional ones in order). The parameter type, after applying `substitutions`, is
+    /// propagated to untyped numeric literals so that `f(x: 5)` with `x: u8` types the
+    /// literal as `u8`, and the argument must then have the parameter type. Named
+    /// arguments that bind to no parameter are reported.
+    fn infer_call_arguments(
+        &mut self,
+        kind: &'static str,
+        name: &str,
+        signature: &FuncInfo,
+        call: &FunctionCallExpression,
+        substitutions: &FxHashMap<String, TypeInfo>,
+        ctx: &mut TypedContext,
+    ) {
+        let Some(arguments) = &call.arguments else {
+            return;
+        };
+        let parameter_indices = call.argument_parameter_indices(&signature.param_names);
+        for ((argument_name, argument), parameter_index) in arguments.iter().zip(parameter_indices)
+        {
+            let parameter_type = parameter_index.map(|parameter_index| {
+                self.resolve_type(&signature.param_types[parameter_index].substitute(substitutions))
+            });
+            if let Some(parameter_type) = &parameter_type {
+                Self::propagate_expected_type(&argument.borrow(), parameter_type, ctx);
+            } else if let Some(argument_name) = argument_name {
+                self.errors.push(TypeCheckError::UnknownNamedArgument {
+                    kind,
+                    name: name.to_string(),
+                    argument: argument_name.name(),
+                    location: argument_name.location,
+                });
+            }
+            let argument_type = self.infer_expression(&argument.borrow(), ctx);
+            if let (Some(parameter_index), Some(parameter_type), Some(argument_type)) =
+                (parameter_index, parameter_type, argument_type)
+                && !parameter_type.has_unresolved_params()
+                && !self.same_type(&parameter_type, &argument_type)
+            {
+                let arg_name = signature.param_names[parameter_index]
+                    .clone()
+                    .unwrap_or_else(|| "_".to_string());
+                let context = match name.rsplit_once("::") {
+                    Some((type_name, method_name)) if kind == "method" => {
+                        TypeMismatchContext::MethodArgument {
+                            type_name: type_name.to_string(),
+                            method_name: method_name.to_string(),
+                            arg_name,
+                            arg_index: parameter_index,
+                        }
+                    }
+                    _ => TypeMismatchContext::FunctionArgument {
+                        function_name: name.to_string(),
+                        arg_name,
+                        arg_index: parameter_index,
+                    },
+                };
+                self.errors.push(TypeCheckError::TypeMismatch {
+                    expected: parameter_type,
+                    found: argument_type,
+                    context,
+                    location: argument.borrow().location(),
+                });
+            }
+        }
+    }
+
+    /// Binds the type parameters of the struct a struct expression builds.
+    ///
+    /// Explicit type arguments (`Pair i32' bool' { .. }`) must match the type parameters
+    /// in number. Without them, each type parameter is inferred from the value of a field
+    /// whose type mentions it, the way type parameters of a generic function are
+    /// inferred from its arguments.
+    fn struct_expression_substitutions(
+        &mut self,
+        struct_expression: &StructExpression,
+        struct_info: &StructInfo,
+        ctx: &mut TypedContext,
+    ) -> FxHashMap<String, TypeInfo> {
+        if let Some(type_parameters) = &struct_expression.type_parameters {
+            if type_parameters.len() != struct_info.type_params.len() {
+                self.errors
+                    .push(TypeCheckError::TypeParameterCountMismatch {
+                        name: struct_expression.name(),
+                        expected: struct_info.type_params.len(),
+                        found: type_parameters.len(),
+                        location: struct_expression.location,
+                    });
+                return FxHashMap::default();
+            }
+            let type_arguments: Vec<String> =
+                type_parameters.iter().map(|p| p.name.clone()).collect();
+            return self.instantiation(&struct_info.type_params, &type_arguments);
+        }
+        let mut substitutions: FxHashMap<String, TypeInfo> = FxHashMap::default();
+        if struct_info.type_params.is_empty() {
+            return substitutions;
+        }
+        for (field_name, value) in struct_expression.fields.iter().flatten() {
+            let Some(field_info) = struct_info.fields.get(&field_name.name) else {
+                continue;
+            };
+            if !field_info.type_info.has_unresolved_params() {
+                continue;
+            }
+            let Some(value_type) = self.infer_expression(&value.borrow(), ctx) else {
+                continue;
+            };
+            let mut bindings = Vec::new();
+            self.bind_type_params(&field_info.type_info, &value_type, &mut bindings);
+            for (type_param_name, bound_type) in bindings {
+                if let Some(existing) = substitutions.get(&type_param_name) {
+                    if !self.same_type(existing, &bound_type) {
+                        self.errors.push(TypeCheckError::ConflictingTypeInference {
+                            param_name: type_param_name,
+                            first: existing.clone(),
+                            second: bound_type,
+                            location: struct_expression.location,
+                        });
+                    }
+                } else {
+                    substitutions.insert(type_param_name, bound_type);
+                }
+            }
+        }
+        for type_param in &struct_info.type_params {
+            if !substitutions.contains_key(type_param) {
+                self.errors.push(TypeCheckError::CannotInferTypeParameter {
+                    function_name: struct_expression.name(),
+                    param_name: type_param.clone(),
+                    location: struct_expression.location,
+                });
+            }
+        }
+        substitutions
+    }
+
     /// Attempt to infer type parameters from argument types.
     ///
     /// For each parameter that is a type variable (Generic), try to find a
-    /// concrete type from the corresponding argument.
+    /// concrete type from the argument bound to it.
     ///
     /// Returns a substitution map if inference succeeds, empty map otherwise.
-    #[allow(clippy::type_complexity)]
     fn infer_type_params_from_args(
         &mut self,
         signature: &FuncInfo,
-        arguments: Option<&Vec<(Option<Rc<Identifier>>, std::cell::RefCell<Expression>)>>,
-        call_location: &Location,
+        call: &FunctionCallExpression,
         ctx: &mut TypedContext,
     ) -> FxHashMap<String, TypeInfo> {
         let mut substitutions = FxHashMap::default();
+        let call_location = &call.location;
 
-        let args = match arguments {
+        let args = match &call.arguments {
             Some(args) => args,
             None => return substitutions,
         };
+        let parameter_indices = call.argument_parameter_indices(&signature.param_names);
 
-        // For each parameter, check if it contains a type variable
-        for (i, param_type) in signature.param_types.iter().enumerate() {
-            if i >= args.len() {
-                break;
-            }
+        // For each bound argument, check if its parameter is a type variable
+        for (arg, parameter_index) in args.iter().zip(parameter_indices) {
+            let Some(parameter_index) = parameter_index else {
+                continue;
+            };
+            let param_type = &signature.param_types[parameter_index];
 
-            // If the parameter type is a type variable, infer from argument
-            if let TypeInfoKind::Generic(type_param_name) = &param_type.kind {
+            // If the parameter type mentions a type variable, infer from argument
+            if param_type.has_unresolved_params() {
                 // Infer the argument type
-                let arg_type = self.infer_expression(&args[i].1.borrow(), ctx);
+                let arg_type = self.infer_expression(&arg.1.borrow(), ctx);
 
                 if let Some(arg_type) = arg_type {
-                    // Check for conflicting inference
-                    if let Some(existing) = substitutions.get(type_param_name) {
-                        if *existing != arg_type {
-                            self.errors.push(TypeCheckError::ConflictingTypeInference {
-                                param_name: type_param_name.clone(),
-                                first: existing.clone(),
-                                second: arg_type.clone(),
-                                location: *call_location,
-                            });
+                    let mut bindings = Vec::new();
+                    self.bind_type_params(param_type, &arg_type, &mut bindings);
+                    // Type arguments that are concrete in the parameter bind nothing
+                    bindings.retain(|(type_param_name, _)| {
+                        signature.type_params.contains(type_param_name)
+                    });
+                    for (type_param_name, bound_type) in bindings {
+                        // Check for conflicting inference
+                        if let Some(existing) = substitutions.get(&type_param_name) {
+                            if !self.same_type(existing, &bound_type) {
+                                self.errors.push(TypeCheckError::ConflictingTypeInference {
+                                    param_name: type_param_name,
+                                    first: existing.clone(),
+                                    second: bound_type,
+                                    location: *call_location,
+                                });
+                            }
+                        } else {
+                            substitutions.insert(type_param_name, bound_type);
                         }
-                    } else {
-                        substitutions.insert(type_param_name.clone(), arg_type);
                     }
                 }
             }
@@ -2075,6 +3363,48 @@ impl TypeChecker {
         substitutions
     }
 
+    /// Collect the type variables of `param_type` with the matching parts of `arg_type`.
+    ///
+    /// A parameter of type `T` binds `T` to the whole argument type, a parameter of
+    /// type `[T; N]` binds `T` to the element type of an array argument, and a parameter
+    /// of type `Pair T' U'` binds `T` and `U` to the type arguments of a `Pair` argument.
+    fn bind_type_params(
+        &self,
+        param_type: &TypeInfo,
+        arg_type: &TypeInfo,
+        bindings: &mut Vec<(String, TypeInfo)>,
+    ) {
+        match (&param_type.kind, &arg_type.kind) {
+            (TypeInfoKind::Generic(type_param_name), _) if param_type.type_params.is_empty() => {
+                bindings.push((type_param_name.clone(), arg_type.clone()));
+            }
+            (TypeInfoKind::Array(param_element, _), TypeInfoKind::Array(arg_element, _)) => {
+                self.bind_type_params(param_element, arg_element, bindings);
+            }
+            (
+                TypeInfoKind::Generic(param_name) | TypeInfoKind::Custom(param_name),
+                TypeInfoKind::Struct(arg_name) | TypeInfoKind::Custom(arg_name),
+            ) if param_name == arg_name
+                && param_type.type_params.len() == arg_type.type_params.len() =>
+            {
+                for (param_argument, arg_argument) in
+                    param_type.type_params.iter().zip(&arg_type.type_params)
+                {
+                    let param_argument = TypeInfo {
+                        kind: TypeInfoKind::Generic(param_argument.clone()),
+                        type_params: vec![],
+                    };
+                    self.bind_type_params(
+                        &param_argument,
+                        &self.type_argument(arg_argument),
+                        bindings,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Push an error, deduplicating errors for the same unknown type/function/identifier.
     /// This prevents duplicate errors when registration fails but inference continues.
     fn push_error_dedup(&mut self, error: TypeCheckError) {
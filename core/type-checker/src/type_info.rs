@@ -107,7 +107,7 @@ use std::{
 };
 
 use inference_ast::nodes::{Expression, Literal, SimpleTypeKind, Type};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
 pub enum NumberType {
@@ -161,6 +161,92 @@ impl NumberType {
             NumberType::I8 | NumberType::I16 | NumberType::I32 | NumberType::I64
         )
     }
+
+    /// Returns the width of this numeric type in bits.
+    #[must_use = "this is a pure query with no side effects"]
+    pub const fn bits(&self) -> u32 {
+        match self {
+            NumberType::I8 | NumberType::U8 => 8,
+            NumberType::I16 | NumberType::U16 => 16,
+            NumberType::I32 | NumberType::U32 => 32,
+            NumberType::I64 | NumberType::U64 => 64,
+        }
+    }
+
+    /// Returns the smallest value of this numeric type.
+    #[must_use = "this is a pure query with no side effects"]
+    pub const fn min_value(&self) -> i128 {
+        if self.is_signed() {
+            -(1 << (self.bits() - 1))
+        } else {
+            0
+        }
+    }
+
+    /// Returns the largest value of this numeric type.
+    #[must_use = "this is a pure query with no side effects"]
+    pub const fn max_value(&self) -> i128 {
+        if self.is_signed() {
+            (1 << (self.bits() - 1)) - 1
+        } else {
+            (1 << self.bits()) - 1
+        }
+    }
+
+    /// Returns `true` if `value` is representable in this numeric type.
+    #[must_use = "this is a pure check with no side effects"]
+    pub const fn contains(&self, value: i128) -> bool {
+        self.min_value() <= value && value <= self.max_value()
+    }
+
+    /// Returns `true` if every value of this numeric type is representable in `target`,
+    /// as for `i32` to `i64` or `u8` to `i16`, but not `i8` to `u64`.
+    #[must_use = "this is a pure check with no side effects"]
+    pub const fn fits_in(&self, target: NumberType) -> bool {
+        target.contains(self.min_value()) && target.contains(self.max_value())
+    }
+}
+
+/// Built-in conversion between integer types, called like a generic function whose type
+/// argument is the target type: `cast i64'(x)`.
+///
+/// A user-defined function of the same name takes precedence over the built-in.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+pub enum Conversion {
+    /// `cast`: only between types where every value of the source type fits the target
+    /// type, so that the value never changes.
+    Cast,
+    /// `wrapping_cast`: keeps the low bits of the value, read as the target type.
+    WrappingCast,
+    /// `checked_cast`: keeps the value, trapping when the target type cannot hold it.
+    CheckedCast,
+}
+
+impl Conversion {
+    pub const ALL: &'static [Conversion] = &[
+        Conversion::Cast,
+        Conversion::WrappingCast,
+        Conversion::CheckedCast,
+    ];
+
+    /// Returns the name the conversion is called by.
+    #[must_use = "returns the name without modifying self"]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Conversion::Cast => "cast",
+            Conversion::WrappingCast => "wrapping_cast",
+            Conversion::CheckedCast => "checked_cast",
+        }
+    }
+
+    /// Returns the conversion called `name`, if any.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|conversion| conversion.name() == name)
+            .copied()
+    }
 }
 
 impl std::str::FromStr for NumberType {
@@ -326,6 +412,20 @@ impl TypeInfo {
     /// `TypeInfoKind::Generic("T")` instead of `TypeInfoKind::Custom("T")`.
     #[must_use]
     pub fn new_with_type_params(ty: &Type, type_param_names: &[String]) -> Self {
+        Self::new_with_declared_types(ty, type_param_names, &FxHashSet::default())
+    }
+
+    /// Like [`TypeInfo::new_with_type_params`], with the names of the types defined in
+    /// the program.
+    ///
+    /// A defined type takes precedence over a builtin type of the same name, so with
+    /// `enum Unit { Value }` declared, `Unit` is `Custom("Unit")` rather than the unit type.
+    #[must_use]
+    pub fn new_with_declared_types(
+        ty: &Type,
+        type_param_names: &[String],
+        declared_type_names: &FxHashSet<String>,
+    ) -> Self {
         match ty {
             Type::Simple(simple) => Self {
                 kind: Self::type_kind_from_simple_type_kind(simple),
@@ -351,9 +451,10 @@ impl TypeInfo {
                 let size = extract_array_size(array.size.clone());
                 Self {
                     kind: TypeInfoKind::Array(
-                        Box::new(Self::new_with_type_params(
+                        Box::new(Self::new_with_declared_types(
                             &array.element_type,
                             type_param_names,
+                            declared_type_names,
                         )),
                         size,
                     ),
@@ -367,14 +468,22 @@ impl TypeInfo {
                     .map(|params| {
                         params
                             .iter()
-                            .map(|p| TypeInfo::new_with_type_params(p, type_param_names))
+                            .map(|p| {
+                                TypeInfo::new_with_declared_types(
+                                    p,
+                                    type_param_names,
+                                    declared_type_names,
+                                )
+                            })
                             .collect::<Vec<_>>()
                     })
                     .unwrap_or_default();
                 let return_type = func
                     .returns
                     .as_ref()
-                    .map(|r| TypeInfo::new_with_type_params(r, type_param_names))
+                    .map(|r| {
+                        TypeInfo::new_with_declared_types(r, type_param_names, declared_type_names)
+                    })
                     .unwrap_or_default();
                 Self {
                     kind: TypeInfoKind::Function(format!(
@@ -393,6 +502,12 @@ impl TypeInfo {
                         type_params: vec![],
                     };
                 }
+                if declared_type_names.contains(&custom.name) {
+                    return Self {
+                        kind: TypeInfoKind::Custom(custom.name.clone()),
+                        type_params: vec![],
+                    };
+                }
                 Self {
                     kind: Self::type_kind_from_simple_type(&custom.name),
                     type_params: vec![],
@@ -440,16 +555,13 @@ impl TypeInfo {
     ///
     /// If this TypeInfo is a `Generic("T")` and substitutions has `T -> i32`, returns i32.
     /// For compound types (arrays, functions), recursively substitutes.
-    /// After successful substitution, `type_params` should be empty.
+    /// The type arguments of an instantiated generic type (`Pair T' i32'`) are
+    /// substituted by name.
     #[must_use = "substitution returns a new TypeInfo, original is unchanged"]
     pub fn substitute(&self, substitutions: &FxHashMap<String, TypeInfo>) -> TypeInfo {
         match &self.kind {
-            TypeInfoKind::Generic(name) => {
-                if let Some(concrete) = substitutions.get(name) {
-                    concrete.clone()
-                } else {
-                    self.clone()
-                }
+            TypeInfoKind::Generic(name) if substitutions.contains_key(name) => {
+                substitutions[name].clone()
             }
             TypeInfoKind::Array(elem_type, length) => {
                 let substituted_elem = elem_type.substitute(substitutions);
@@ -463,13 +575,37 @@ impl TypeInfo {
             | TypeInfoKind::Bool
             | TypeInfoKind::String
             | TypeInfoKind::Number(_)
+            | TypeInfoKind::Generic(_)
             | TypeInfoKind::Custom(_)
             | TypeInfoKind::QualifiedName(_)
             | TypeInfoKind::Qualified(_)
             | TypeInfoKind::Function(_)
             | TypeInfoKind::Struct(_)
             | TypeInfoKind::Enum(_)
-            | TypeInfoKind::Spec(_) => self.clone(),
+            | TypeInfoKind::Spec(_) => TypeInfo {
+                kind: self.kind.clone(),
+                type_params: self
+                    .type_params
+                    .iter()
+                    .map(|type_param| {
+                        substitutions
+                            .get(type_param)
+                            .map_or_else(|| type_param.clone(), TypeInfo::type_argument_name)
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    /// Returns the name this type is written with as a type argument (`i32` in
+    /// `Pair i32' bool'`), which is how the arguments of generic types are recorded.
+    #[must_use = "returns the name without modifying self"]
+    pub fn type_argument_name(&self) -> String {
+        match &self.kind {
+            TypeInfoKind::Generic(name) if self.type_params.is_empty() => name.clone(),
+            kind => kind
+                .as_builtin_str()
+                .map_or_else(|| self.to_string(), str::to_string),
         }
     }
 
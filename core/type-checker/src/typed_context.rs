@@ -11,6 +11,7 @@
 //! - Access to the original AST arena for node traversal
 //! - Convenience methods for common type queries
 //! - Symbol table with type and function definitions
+//! - Warnings reported while type checking
 //!
 //! ## Architecture
 //!
@@ -87,6 +88,7 @@ use crate::{
 };
 use inference_ast::{
     arena::Arena,
+    diagnostics::Diagnostic,
     nodes::{AstNode, Expression, FunctionDefinition, Location, SourceFile},
 };
 use rustc_hash::FxHashMap;
@@ -96,6 +98,7 @@ pub struct TypedContext {
     pub(crate) symbol_table: SymbolTable,
     node_types: FxHashMap<u32, TypeInfo>,
     arena: Arena,
+    pub(crate) diagnostics: Vec<Diagnostic>,
 }
 
 impl TypedContext {
@@ -104,9 +107,19 @@ impl TypedContext {
             symbol_table: SymbolTable::default(),
             node_types: FxHashMap::default(),
             arena,
+            diagnostics: Vec::new(),
         }
     }
 
+    /// Returns the warnings the type checker reported for the program.
+    ///
+    /// Type errors make type checking fail, so they never appear here. The lint levels
+    /// of the project are not applied yet.
+    #[must_use = "returns diagnostics without side effects"]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     /// Returns all source files in the arena.
     ///
     /// Each source file contains its definitions (functions, structs, enums, etc.)
@@ -283,6 +296,12 @@ impl TypedContext {
             .and_then(|parent_id| self.arena.find_node(parent_id))
     }
 
+    /// Returns the source text of a node, or `None` if the node is unknown.
+    #[must_use = "this is a pure lookup with no side effects"]
+    pub fn get_node_source(&self, node_id: u32) -> Option<&str> {
+        self.arena.get_node_source(node_id)
+    }
+
     pub(crate) fn set_node_typeinfo(&mut self, node_id: u32, type_info: TypeInfo) {
         self.node_types.insert(node_id, type_info);
     }
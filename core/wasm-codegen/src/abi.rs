@@ -0,0 +1,196 @@
+//! Calling convention shared by the code generation backends.
+//!
+//! Both backends pass arguments and results the same way, so a module produced by one
+//! backend has the same exported signatures as a module produced by the other:
+//!
+//! | Position | Present for | WebAssembly type |
+//! |----------|-------------|------------------|
+//! | result pointer | struct and array results | `i32` address |
+//! | `self` pointer | instance methods | `i32` address |
+//! | explicit parameters | always | `i32`/`i64` value, or `i32` address for structs and arrays |
+//!
+//! A struct or array argument is passed by the address of the caller's value, and the
+//! callee copies it before use, so assigning to a parameter never changes the caller's
+//! value. A struct or array result is written to memory provided by the caller.
+//!
+//! External functions, which become WebAssembly imports, follow the same convention: a host
+//! implementing one receives struct and array arguments as addresses into the exported
+//! `memory`, must not modify them, and writes a struct or array result to the address
+//! passed first.
+
+use inference_ast::nodes::{ArgumentType, ExternalFunctionDefinition, FunctionDefinition, Type};
+use inference_type_checker::type_info::{TypeInfo, TypeInfoKind};
+
+use crate::{
+    errors::CodegenError,
+    layout::DataLayout,
+    modules,
+    monomorphize::{Instance, Substitutions},
+};
+
+/// How a function receives its arguments and returns its result.
+///
+/// The parameter list is, in order: the result pointer (only for struct and array
+/// results), the `self` pointer (only for instance methods), then the explicit parameters.
+/// Struct and array parameters and results travel as pointers into linear memory;
+/// everything else is passed by value.
+#[derive(Debug, Clone)]
+pub(crate) struct FunctionAbi {
+    /// Names of the explicit parameters, `None` for parameters that cannot be passed by
+    /// name (`_: T` or a bare type). Used to bind named call arguments.
+    pub(crate) parameter_names: Vec<Option<String>>,
+
+    /// Types of the explicit parameters.
+    pub(crate) parameter_types: Vec<TypeInfo>,
+
+    /// Declared result type, `unit` for functions without one.
+    pub(crate) return_type: TypeInfo,
+
+    /// Whether the function is an instance method taking `self` by pointer.
+    pub(crate) has_self: bool,
+
+    /// Whether the result is a struct or array written through a leading result pointer.
+    pub(crate) has_result_pointer: bool,
+}
+
+impl FunctionAbi {
+    /// Computes the calling convention of a function or method.
+    ///
+    /// `owner` is the name of the struct a method belongs to, `None` for free functions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter or the result has a type without a WebAssembly
+    /// representation yet (strings, generics, function types, qualified names).
+    pub(crate) fn new(
+        function_definition: &FunctionDefinition,
+        owner: Option<&str>,
+        layout: &DataLayout,
+    ) -> Result<Self, CodegenError> {
+        Self::from_signature(
+            function_definition.arguments.as_deref(),
+            function_definition.returns.as_ref(),
+            owner,
+            &Substitutions::default(),
+            layout,
+        )
+    }
+
+    /// Computes the calling convention of an instance of a generic function or method,
+    /// with every type parameter replaced by its type argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter or the result has a type without a WebAssembly
+    /// representation yet after substitution.
+    pub(crate) fn instance(instance: &Instance, layout: &DataLayout) -> Result<Self, CodegenError> {
+        Self::from_signature(
+            instance.function.arguments.as_deref(),
+            instance.function.returns.as_ref(),
+            instance.owner.as_deref(),
+            &instance.substitutions,
+            layout,
+        )
+    }
+
+    /// Computes the calling convention of an external function.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter or the result has a type without a WebAssembly
+    /// representation yet.
+    pub(crate) fn external(
+        external_function_definition: &ExternalFunctionDefinition,
+        layout: &DataLayout,
+    ) -> Result<Self, CodegenError> {
+        Self::from_signature(
+            external_function_definition.arguments.as_deref(),
+            external_function_definition.returns.as_ref(),
+            None,
+            &Substitutions::default(),
+            layout,
+        )
+    }
+
+    /// Computes the calling convention of a signature; `substitutions` gives the types of
+    /// the type parameters it mentions.
+    fn from_signature(
+        arguments: Option<&[ArgumentType]>,
+        returns: Option<&Type>,
+        owner: Option<&str>,
+        substitutions: &Substitutions,
+        layout: &DataLayout,
+    ) -> Result<Self, CodegenError> {
+        let type_parameters: Vec<String> = substitutions.keys().cloned().collect();
+        let resolve = |ty: &Type| {
+            layout
+                .resolve_aliases(&TypeInfo::new_with_type_params(ty, &type_parameters))
+                .substitute(substitutions)
+        };
+        let mut parameter_names = Vec::new();
+        let mut parameter_types = Vec::new();
+        let mut has_self = false;
+        for argument in arguments.into_iter().flatten() {
+            let ty = match argument {
+                ArgumentType::SelfReference(self_reference) => {
+                    let self_type = TypeInfo {
+                        kind: TypeInfoKind::Struct(
+                            owner.expect("`self` must belong to a struct").into(),
+                        ),
+                        type_params: vec![],
+                    };
+                    layout.check_type(&self_type, self_reference.location)?;
+                    has_self = true;
+                    continue;
+                }
+                ArgumentType::IgnoreArgument(ignore_argument) => &ignore_argument.ty,
+                ArgumentType::Argument(arg) => &arg.ty,
+                ArgumentType::Type(ty) => ty,
+            };
+            let parameter_type = resolve(ty);
+            layout.check_type(&parameter_type, ty.location())?;
+            parameter_names.push(argument.parameter_name());
+            parameter_types.push(parameter_type);
+        }
+        let return_type = returns.map_or_else(TypeInfo::default, resolve);
+        if let Some(returns) = returns
+            && return_type.kind != TypeInfoKind::Unit
+        {
+            layout.check_type(&return_type, returns.location())?;
+        }
+        let has_result_pointer = layout.is_aggregate(&return_type);
+        Ok(Self {
+            parameter_names,
+            parameter_types,
+            return_type,
+            has_self,
+            has_result_pointer,
+        })
+    }
+
+    /// Returns the symbol of a function defined in the module at `module_path`: its name,
+    /// or `Struct::method` for methods, qualified with the module path as described in
+    /// [`crate::modules`]. This is the name the type checker records for calls.
+    pub(crate) fn symbol(
+        module_path: &str,
+        function_definition: &FunctionDefinition,
+        owner: Option<&str>,
+    ) -> String {
+        let name = match owner {
+            Some(owner) => format!("{owner}::{}", function_definition.name()),
+            None => function_definition.name(),
+        };
+        modules::qualify(module_path, &name)
+    }
+
+    /// Index of the first explicit parameter in the parameter list.
+    pub(crate) fn first_parameter_index(&self) -> u32 {
+        u32::from(self.has_result_pointer) + u32::from(self.has_self)
+    }
+
+    /// Returns whether the function produces no value: it returns `unit` or writes its
+    /// result through the result pointer.
+    pub(crate) fn returns_nothing(&self) -> bool {
+        self.has_result_pointer || self.return_type.kind == TypeInfoKind::Unit
+    }
+}
@@ -35,9 +35,39 @@
 //! | `i16`, `u16`   | i16       | i32       |
 //! | `i32`, `u32`   | i32       | i32       |
 //! | `i64`, `u64`   | i64       | i64       |
+//! | enum           | i8, i16 or i32 (discriminant) | i32 |
+//! | struct, `[T; N]` | `ptr` to `[size x i8]` | i32 (address) |
 //!
 //! Note: WebAssembly only supports i32, i64, f32, and f64 as value types. Smaller integer
-//! types use i32 with appropriate truncation/extension.
+//! types use i32 with appropriate truncation/extension. Structs and arrays live in linear
+//! memory, laid out as described in [`crate::layout`], and are handled through their
+//! address.
+//!
+//! Enum values are discriminants numbered in declaration order. The numbering of every
+//! enum is recorded in the `inference.enums` custom section, one `Enum::Variant=N` line
+//! per variant, so that tools reading the module can refer to variants by name.
+//!
+//! Module-level constants produce no code of their own. Scalar constants are inlined at
+//! every use; struct and array constants are placed in a read-only data segment named
+//! `.rodata.NAME`, which functions read like any other aggregate.
+//!
+//! Generic functions are lowered once per instance, under symbols such as `identity<i32>`;
+//! see [`crate::monomorphize`].
+//!
+//! Indexing an array checks the index against the array length first; an out-of-bounds
+//! index executes `unreachable`, so it traps at runtime instead of reading or writing
+//! neighbouring memory.
+//!
+//! Integer conversions (`cast`, `wrapping_cast`, `checked_cast`) become `sext` or `zext`,
+//! by the signedness of the source type, or `trunc`; a `checked_cast` of a value out of
+//! the range of the target type executes `unreachable` as well.
+//!
+//! An `assert` branches to a block that stores the assertion's ID at
+//! [`FAILED_ASSERTION_ADDRESS`] and calls `llvm.trap` when its condition is false.
+//!
+//! With debug info enabled, every function gets a `DISubprogram` and every instruction
+//! the `!dbg` location of the statement it was lowered from. Only line tables are
+//! requested, which `inf-llc` emits as DWARF and `rust-lld` keeps in the linked module.
 //!
 //! # Non-Deterministic Operations
 //!
@@ -45,7 +75,7 @@
 //! are recognized by inf-llc and compiled to custom WASM instructions with binary encoding
 //! in the 0xfc prefix space:
 //!
-//! - `uzumaki()` - Non-deterministic value generation (0xfc 0x3a for i32, 0xfc 0x3c for i64)
+//! - `uzumaki()` - Non-deterministic value generation (0xfc 0x31 for i32, 0xfc 0x32 for i64)
 //! - `forall { ... }` - Universal quantification block (0xfc 0x3a start, 0xfc 0x3b end)
 //! - `exists { ... }` - Existential quantification block (0xfc 0x3c start, 0xfc 0x3d end)
 //! - `assume { ... }` - Assumption block for preconditions (0xfc 0x3e start, 0xfc 0x3f end)
@@ -55,28 +85,48 @@
 //!
 //! Inference source:
 //! ```inference
-//! pub fn example() -> i32 {
-//!     return @;
+//! pub fn example() {
+//!     forall {
+//!         let x: i32 = @;
+//!     }
 //! }
 //! ```
 //!
 //! Generated LLVM IR:
 //! ```llvm
-//! define i32 @example() {
+//! define void @example() #0 {
 //! entry:
+//!   %x = alloca i32, align 4
+//!   call void @llvm.wasm.forall.start()
 //!   %uz_i32 = call i32 @llvm.wasm.uzumaki.i32()
-//!   ret i32 %uz_i32
+//!   store i32 %uz_i32, ptr %x, align 4
+//!   call void @llvm.wasm.forall.end()
+//!   ret void
 //! }
+//! declare void @llvm.wasm.forall.start()
 //! declare i32 @llvm.wasm.uzumaki.i32()
+//! declare void @llvm.wasm.forall.end()
+//! attributes #0 = { noinline optnone "wasm-export-name"="example" }
 //! ```
 //!
 //! Compiled WebAssembly (text format):
 //! ```wat
-//! (func $example (export "example") (result i32)
-//!   i32.uzumaki  ;; 0xfc 0x3a
+//! (func $example (export "example")
+//!   (local i32)
+//!   forall.start  ;; 0xfc 0x3a
+//!   i32.uzumaki   ;; 0xfc 0x31
+//!   local.set 0
+//!   forall.end    ;; 0xfc 0x3b
 //! )
 //! ```
 //!
+//! Exported functions may not return `@` outside of a quantifier, so `@` is bound to a
+//! variable inside `forall` here.
+//!
+//! `@` of `bool` and of the narrow integer types truncates the i32 intrinsic's result to
+//! the type's width, and `@` of a struct or array generates each field and element in
+//! turn.
+//!
 //! See the [language spec](https://github.com/Inferara/inference-language-spec) for details
 //! on non-deterministic semantics and the [custom intrinsics PR](https://github.com/Inferara/llvm-project/pull/2)
 //! for LLVM implementation details.
@@ -89,23 +139,54 @@
 
 //TODO: don't forget to remove
 #![allow(dead_code)]
-use crate::utils;
+use crate::{
+    ExportPolicy, ImportName,
+    abi::FunctionAbi,
+    debug_info::{DWARF_VERSION, PRODUCER},
+    errors::CodegenError,
+    layout::{
+        ASSERTS_SECTION_NAME, DataLayout, ENUMS_SECTION_NAME, FAILED_ASSERTION_ADDRESS,
+        number_literal_bits,
+    },
+    modules,
+    monomorphize::{Instance, Monomorphization, Substitutions},
+    utils,
+};
 use inference_ast::nodes::{
-    BlockType, Expression, FunctionDefinition, Literal, SimpleTypeKind, Statement, Type, Visibility,
+    BinaryExpression, BlockType, ConstantDefinition, Expression, ExternalFunctionDefinition,
+    FunctionCallExpression, FunctionDefinition, Literal, Location, OperatorKind,
+    PrefixUnaryExpression, Statement, UnaryOperatorKind,
 };
 use inference_type_checker::{
-    type_info::{NumberType, TypeInfoKind},
+    type_info::{Conversion, NumberType, TypeInfo, TypeInfoKind},
     typed_context::TypedContext,
 };
 use inkwell::{
+    AddressSpace, IntPredicate,
     attributes::{Attribute, AttributeLoc},
+    basic_block::BasicBlock,
     builder::Builder,
     context::Context,
-    module::Module,
-    types::BasicTypeEnum,
-    values::{FunctionValue, PointerValue},
+    debug_info::{
+        AsDIScope, DICompileUnit, DIFlags, DIFlagsConstants, DISubprogram, DWARFEmissionKind,
+        DWARFSourceLanguage, DebugInfoBuilder,
+    },
+    module::{FlagBehavior, Linkage, Module},
+    types::{BasicMetadataTypeEnum, BasicTypeEnum, IntType, PointerType},
+    values::{
+        BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, IntValue, PointerValue,
+    },
 };
-use std::{cell::RefCell, collections::HashMap, iter::Peekable, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    collections::HashMap,
+    iter::Peekable,
+    rc::Rc,
+};
+
+/// LLVM intrinsic that traps; lowered to the WASM `unreachable` instruction.
+const TRAP_INTRINSIC: &str = "llvm.trap";
 
 // ================================================================================================
 // LLVM Intrinsic Names for Non-Deterministic Operations
@@ -122,11 +203,11 @@ use std::{cell::RefCell, collections::HashMap, iter::Peekable, rc::Rc};
 // Reference: https://github.com/Inferara/llvm-project/pull/2
 
 /// LLVM intrinsic for non-deterministic i32 value generation.
-/// Compiles to WASM instruction 0xfc 0x3a.
+/// Compiles to WASM instruction 0xfc 0x31.
 const UZUMAKI_I32_INTRINSIC: &str = "llvm.wasm.uzumaki.i32";
 
 /// LLVM intrinsic for non-deterministic i64 value generation.
-/// Compiles to WASM instruction 0xfc 0x3c.
+/// Compiles to WASM instruction 0xfc 0x32.
 const UZUMAKI_I64_INTRINSIC: &str = "llvm.wasm.uzumaki.i64";
 
 /// LLVM intrinsic marking the start of a forall (universal quantification) block.
@@ -174,9 +255,9 @@ const UNIQUE_END_INTRINSIC: &str = "llvm.wasm.unique.end";
 ///
 /// # Variable Storage
 ///
-/// Local variables and constants are stored in a `RefCell<HashMap>` mapping names to
-/// (pointer, type) pairs. This allows mutation during IR generation while maintaining
-/// Rust's borrowing rules through interior mutability.
+/// Local variables and constants are stored in a `RefCell` holding a stack of scopes, each
+/// a `HashMap` mapping names to (pointer, type) pairs. This allows mutation during IR
+/// generation while maintaining Rust's borrowing rules through interior mutability.
 ///
 /// # Internal Usage Example
 ///
@@ -189,7 +270,13 @@ const UNIQUE_END_INTRINSIC: &str = "llvm.wasm.unique.end";
 ///
 /// // Create LLVM context and compiler
 /// let context = Context::create();
-/// let compiler = Compiler::new(&context, "wasm_module");
+/// let compiler = Compiler::new(
+///     &context,
+///     "wasm_module",
+///     layout,
+///     monomorphization,
+///     ExportPolicy::Public,
+/// );
 ///
 /// // Visit function definitions from typed AST
 /// for func_def in typed_context.source_files()[0].function_definitions() {
@@ -197,7 +284,7 @@ const UNIQUE_END_INTRINSIC: &str = "llvm.wasm.unique.end";
 /// }
 ///
 /// // Compile to WebAssembly
-/// let wasm_bytes = compiler.compile_to_wasm("output.wasm", 3)?;
+/// let wasm_bytes = compiler.compile_to_wasm("output.wasm", 3)?.wasm;
 /// ```
 pub(crate) struct Compiler<'ctx> {
     /// LLVM context for creating types and values.
@@ -212,26 +299,79 @@ pub(crate) struct Compiler<'ctx> {
     /// Variable storage mapping names to stack-allocated pointers and their types.
     ///
     /// Each variable is stored as an alloca (stack allocation) in the LLVM IR entry block.
-    /// The `HashMap` maps variable names to tuples of (pointer to variable, LLVM type).
+    /// Every block pushes a scope, a `HashMap` from variable names to tuples of (pointer
+    /// to variable, LLVM type), and pops it when it ends; the innermost scope is last.
     ///
     /// This design enables:
     /// - SSA (Static Single Assignment) form in LLVM IR through load/store operations
     /// - Type-safe variable access during expression lowering
-    /// - Proper variable scoping (though current implementation uses a flat namespace)
+    /// - Block scoping: a `let` in a block gets its own alloca, and the variable it
+    ///   shadows is visible again once the block ends
     ///
     /// The `RefCell` provides interior mutability, allowing the compiler to add variables
     /// during IR generation while maintaining Rust's borrowing rules.
-    variables: RefCell<HashMap<String, (PointerValue<'ctx>, BasicTypeEnum<'ctx>)>>,
+    variables: RefCell<Vec<HashMap<String, (PointerValue<'ctx>, BasicTypeEnum<'ctx>)>>>,
+
+    /// Which functions the module exports.
+    exports: ExportPolicy,
 
-    /// Tracks whether a `main` function was compiled.
+    /// Exit blocks of the loops enclosing the statement being lowered.
     ///
-    /// Used to conditionally export `main` during linking. When true, the linker receives
-    /// the `--export=main` flag, which creates a wrapper that provides argc/argv compatibility
-    /// for command-line style execution.
+    /// The innermost loop is on top. A `break` branches to the last element, which makes
+    /// nested loops work without any extra bookkeeping in the statement lowering.
+    loop_exit_blocks: RefCell<Vec<BasicBlock<'ctx>>>,
+
+    /// Calling convention of every declared function, keyed by symbol name.
+    function_abis: RefCell<HashMap<String, FunctionAbi>>,
+
+    /// Caller-provided memory for the struct or array result of the function being lowered.
+    ///
+    /// `None` when the current function returns a scalar or nothing.
+    result_pointer: RefCell<Option<PointerValue<'ctx>>>,
+
+    /// Module-level constants, keyed by name qualified with their module path.
     ///
-    /// Note: Only public `main` functions are tracked. Private `main` functions are compiled
-    /// but not exported from the WebAssembly module.
-    has_main: RefCell<bool>,
+    /// Local variables shadow module-level constants of the same name.
+    constants: RefCell<HashMap<String, ModuleConstant<'ctx>>>,
+
+    /// Path of the module the function being lowered is defined in, which determines the
+    /// constants its names refer to.
+    module_path: RefCell<String>,
+
+    /// Sizes, alignments and field offsets of all struct types, used for arrays as well.
+    layout: DataLayout,
+
+    /// Instances of the generic functions, and the instance each generic call targets.
+    monomorphization: Monomorphization,
+
+    /// Type arguments of the instance being lowered; empty for non-generic functions.
+    substitutions: RefCell<Substitutions>,
+
+    /// Errors for the constructs that could not be lowered so far.
+    ///
+    /// Lowering records an error and moves on to the next statement or definition, so a
+    /// single run reports every unsupported construct; see [`Self::take_errors`].
+    errors: RefCell<Vec<CodegenError>>,
+
+    /// Debug info builder, when source locations are recorded.
+    debug_info: Option<DebugInfo<'ctx>>,
+}
+
+/// State for attaching source locations to the generated IR.
+struct DebugInfo<'ctx> {
+    builder: DebugInfoBuilder<'ctx>,
+    compile_unit: DICompileUnit<'ctx>,
+    /// Subprogram of the function being lowered, the scope of its locations.
+    subprogram: Cell<Option<DISubprogram<'ctx>>>,
+}
+
+/// The value of a module-level constant as seen from function bodies.
+#[derive(Debug, Clone, Copy)]
+enum ModuleConstant<'ctx> {
+    /// A `bool` or integer constant, inlined at every use.
+    Scalar(IntValue<'ctx>),
+    /// A struct or array constant, read from a read-only global.
+    Aggregate(PointerValue<'ctx>),
 }
 
 impl<'ctx> Compiler<'ctx> {
@@ -241,19 +381,91 @@ impl<'ctx> Compiler<'ctx> {
     ///
     /// - `context` - LLVM context for creating types and values
     /// - `module_name` - Name for the generated LLVM module (typically `wasm_module`)
-    pub(crate) fn new(context: &'ctx Context, module_name: &str) -> Self {
+    /// - `layout` - Memory layout of the struct types used by the program
+    /// - `monomorphization` - Instances of the generic functions called by the program
+    /// - `exports` - Which functions the module exports
+    /// - `source_name` - Name of the source file to record in DWARF debug info, or `None`
+    ///   to emit none
+    pub(crate) fn new(
+        context: &'ctx Context,
+        module_name: &str,
+        layout: DataLayout,
+        monomorphization: Monomorphization,
+        exports: ExportPolicy,
+        source_name: Option<&str>,
+    ) -> Self {
         let module = context.create_module(module_name);
         let builder = context.create_builder();
+        let debug_info = source_name.map(|source_name| {
+            let i32_type = context.i32_type();
+            module.add_basic_value_flag(
+                "Dwarf Version",
+                FlagBehavior::Warning,
+                i32_type.const_int(u64::from(DWARF_VERSION), false),
+            );
+            module.add_basic_value_flag(
+                "Debug Info Version",
+                FlagBehavior::Warning,
+                i32_type.const_int(
+                    u64::from(inkwell::debug_info::debug_metadata_version()),
+                    false,
+                ),
+            );
+            // DWARF defines no language code for Inference; C is used, as by other
+            // languages without one.
+            let (builder, compile_unit) = module.create_debug_info_builder(
+                true,
+                DWARFSourceLanguage::C,
+                source_name,
+                ".",
+                PRODUCER,
+                false,
+                "",
+                0,
+                "",
+                DWARFEmissionKind::LineTablesOnly,
+                0,
+                false,
+                false,
+                "",
+                "",
+            );
+            DebugInfo {
+                builder,
+                compile_unit,
+                subprogram: Cell::new(None),
+            }
+        });
 
         Self {
             context,
             module,
             builder,
-            variables: RefCell::new(HashMap::new()),
-            has_main: RefCell::new(false), //TODO: revisit
+            variables: RefCell::new(Vec::new()),
+            exports,
+            loop_exit_blocks: RefCell::new(Vec::new()),
+            function_abis: RefCell::new(HashMap::new()),
+            result_pointer: RefCell::new(None),
+            constants: RefCell::new(HashMap::new()),
+            module_path: RefCell::new(String::new()),
+            layout,
+            monomorphization,
+            substitutions: RefCell::new(Substitutions::default()),
+            errors: RefCell::new(Vec::new()),
+            debug_info,
         }
     }
 
+    /// Records an error for a construct that could not be lowered.
+    fn report(&self, error: CodegenError) {
+        self.errors.borrow_mut().push(error);
+    }
+
+    /// Returns the errors recorded so far, in the order they were found, and clears them.
+    pub(crate) fn take_errors(&self) -> Vec<CodegenError> {
+        self.errors.take()
+    }
+
     /// Adds optimization barriers to a function to prevent LLVM from optimizing away
     /// non-deterministic intrinsic calls.
     ///
@@ -275,100 +487,419 @@ impl<'ctx> Compiler<'ctx> {
         function.add_attribute(AttributeLoc::Function, noinline);
     }
 
-    /// Translates an AST function definition to LLVM IR.
+    /// Marks a narrow integer parameter or return value as sign- or zero-extended.
+    ///
+    /// WebAssembly has no 8- or 16-bit value types, so `bool`, `i8`, `i16`, `u8`, `u16` and
+    /// enums with narrow discriminants travel as `i32`. The `signext`/`zeroext` attributes
+    /// make the upper bits well-defined for callers outside the module (e.g. a host
+    /// reading an exported `u8` result).
+    fn add_extension_attribute(
+        &self,
+        function: FunctionValue<'ctx>,
+        location: AttributeLoc,
+        type_info: &TypeInfo,
+    ) {
+        if let Some(enum_layout) = self.layout.enum_layout(type_info) {
+            let discriminant_type = enum_layout.discriminant_type();
+            self.add_extension_attribute(function, location, &discriminant_type);
+            return;
+        }
+        let attribute_name = match type_info.kind {
+            TypeInfoKind::Number(NumberType::I8 | NumberType::I16) => "signext",
+            TypeInfoKind::Bool | TypeInfoKind::Number(NumberType::U8 | NumberType::U16) => {
+                "zeroext"
+            }
+            _ => return,
+        };
+        let kind_id = Attribute::get_named_enum_kind_id(attribute_name);
+        function.add_attribute(location, self.context.create_enum_attribute(kind_id, 0));
+    }
+
+    /// Makes a module-level constant available to the function bodies lowered afterwards.
+    ///
+    /// Scalar constants are lowered once to an LLVM constant that every use inlines.
+    /// Struct and array constants are encoded byte by byte according to the data layout
+    /// and placed in an internal constant global, which the linker keeps in its own
+    /// `.rodata.NAME` data segment.
     ///
-    /// This is the main entry point for function compilation. It performs several steps:
+    /// A constant of an unsupported type, or an aggregate constant with an element that is
+    /// not a literal, a struct literal or an enum variant, is reported and left undefined.
+    pub(crate) fn define_constant(
+        &self,
+        constant_definition: &ConstantDefinition,
+        module_path: &str,
+        ctx: &TypedContext,
+    ) {
+        if let Err(error) = self.try_define_constant(constant_definition, module_path, ctx) {
+            self.report(error);
+        }
+    }
+
+    fn try_define_constant(
+        &self,
+        constant_definition: &ConstantDefinition,
+        module_path: &str,
+        ctx: &TypedContext,
+    ) -> Result<(), CodegenError> {
+        let name = modules::qualify(module_path, &constant_definition.name());
+        let type_info = ctx
+            .get_node_typeinfo(constant_definition.id)
+            .expect("Constant definition must have a type info");
+        self.layout
+            .check_type(&type_info, constant_definition.location)?;
+        let constant = if self.layout.is_aggregate(&type_info) {
+            let (size, align) = self.layout.size_and_align(&type_info);
+            let mut bytes = vec![0; size as usize];
+            self.layout.encode_constant(
+                &mut bytes,
+                &Expression::Literal(constant_definition.value.clone()),
+                &type_info,
+            )?;
+            let initializer = self.context.const_string(&bytes, false);
+            let global = self.module.add_global(initializer.get_type(), None, &name);
+            global.set_initializer(&initializer);
+            global.set_constant(true);
+            global.set_linkage(Linkage::Internal);
+            global.set_alignment(align);
+            ModuleConstant::Aggregate(global.as_pointer_value())
+        } else {
+            ModuleConstant::Scalar(self.lower_literal(&constant_definition.value, ctx)?)
+        };
+        self.constants.borrow_mut().insert(name, constant);
+        Ok(())
+    }
+
+    /// Declares an AST function in the LLVM module without lowering its body.
+    ///
+    /// All functions are declared before any body is lowered, so a call can refer to a
+    /// function defined later in the file, and a function can call itself. The declaration
+    /// performs the following steps:
     ///
-    /// 1. **Type mapping** - Maps the return type from `Type::Simple(SimpleTypeKind)` to
-    ///    corresponding LLVM types (void, bool, i8, i16, i32, i64)
+    /// 1. **Type mapping** - Maps scalar parameter and return types to corresponding LLVM
+    ///    types (void, i1, i8, i16, i32, i64). Struct parameters, `self` and struct results
+    ///    are passed as pointers, see [`FunctionAbi`]
     /// 2. **Function creation** - Declares the function in the LLVM module with the
-    ///    appropriate signature
-    /// 3. **Export annotation** - Adds `wasm-export-name` attribute to make the function
-    ///    accessible from WebAssembly
+    ///    appropriate signature, marking narrow parameters and results as sign- or
+    ///    zero-extended
+    /// 3. **Export annotation** - Adds `wasm-export-name` attribute to the functions
+    ///    selected by the [`ExportPolicy`]
     /// 4. **Optimization barriers** - If the function contains non-deterministic blocks,
     ///    applies `optnone` and `noinline` attributes to prevent optimization
-    /// 5. **Body lowering** - Recursively lowers the function body statements to LLVM IR
-    /// 6. **Return handling** - Inserts implicit void return for functions without explicit
-    ///    return statements
     ///
-    /// # Type Safety Improvement
-    ///
-    /// Prior to the introduction of `SimpleTypeKind`, this method used string-based type
-    /// matching (`simple_type.name.to_lowercase().as_str()`), which was error-prone and
-    /// lacked compile-time verification. The refactoring to use `SimpleTypeKind` enum
-    /// provides type-safe pattern matching and ensures all primitive types are explicitly
-    /// handled.
+    /// Functions are declared under their symbol, see [`FunctionAbi::symbol`]: the name, or
+    /// `Struct::method` for methods, qualified with the module path. This is the same name
+    /// the type checker records for calls.
     ///
     /// # Parameters
     ///
-    /// - `function_definition` - AST node representing the function to compile
-    /// - `ctx` - Typed context containing type information for all AST nodes
+    /// - `function_definition` - AST node of the function or method
+    /// - `module_path` - Path of the module the function is defined in, empty for the root
+    ///   module
+    /// - `owner` - Name of the struct a method belongs to, `None` for free functions
     ///
-    /// # Panics
-    ///
-    /// This method will panic if it encounters unsupported type constructs (arrays,
-    /// generics, function types, qualified names, custom types) in return positions,
-    /// as these are not yet implemented. The `todo!()` markers indicate planned future
-    /// support.
-    pub(crate) fn visit_function_definition(
+    /// A function with a parameter or return type that has no WebAssembly representation
+    /// yet (strings, generics, function types, qualified names) is reported and not
+    /// declared; its body is skipped by [`Self::visit_function_definition`].
+    pub(crate) fn declare_function(
         &self,
         function_definition: &Rc<FunctionDefinition>,
-        ctx: &TypedContext,
+        module_path: &str,
+        owner: Option<&str>,
     ) {
-        let fn_name = function_definition.name();
-        let fn_type = match &function_definition.returns {
-            Some(ret_type) => match ret_type {
-                Type::Simple(SimpleTypeKind::Unit) => self.context.void_type().fn_type(&[], false),
-                Type::Simple(SimpleTypeKind::Bool) => self.context.bool_type().fn_type(&[], false),
-                Type::Simple(SimpleTypeKind::I8 | SimpleTypeKind::U8) => {
-                    self.context.i8_type().fn_type(&[], false)
-                }
-                Type::Simple(SimpleTypeKind::I16 | SimpleTypeKind::U16) => {
-                    self.context.i16_type().fn_type(&[], false)
-                }
-                Type::Simple(SimpleTypeKind::I32 | SimpleTypeKind::U32) => {
-                    self.context.i32_type().fn_type(&[], false)
-                }
-                Type::Simple(SimpleTypeKind::I64 | SimpleTypeKind::U64) => {
-                    self.context.i64_type().fn_type(&[], false)
-                }
-                Type::Array(_array_type) => todo!(),
-                Type::Generic(_generic_type) => todo!(),
-                Type::Function(_function_type) => todo!(),
-                Type::QualifiedName(_qualified_name) => todo!(),
-                Type::Qualified(_type_qualified_name) => todo!(),
-                Type::Custom(_identifier) => todo!(),
-            },
-            None => self.context.void_type().fn_type(&[], false),
-        };
-        let function = self.module.add_function(fn_name.as_str(), fn_type, None);
+        if let Err(error) = self.try_declare_function(function_definition, module_path, owner) {
+            self.report(error);
+        }
+    }
+
+    fn try_declare_function(
+        &self,
+        function_definition: &Rc<FunctionDefinition>,
+        module_path: &str,
+        owner: Option<&str>,
+    ) -> Result<(), CodegenError> {
+        let fn_name = FunctionAbi::symbol(module_path, function_definition, owner);
+        let abi = FunctionAbi::new(function_definition, owner, &self.layout)?;
+        let function = self.add_function_declaration(&fn_name, abi);
 
-        // Only export public functions. Skip "main" - LLD handles its export specially
-        // to avoid duplicate export errors from the entry point wrapper.
-        let is_main = fn_name == "main";
-        let should_export = function_definition.visibility == Visibility::Public && !is_main;
-        if should_export {
+        if self
+            .exports
+            .is_exported(function_definition, module_path, owner)
+        {
             let export_name_attr = self
                 .context
                 .create_string_attribute("wasm-export-name", fn_name.as_str());
             function.add_attribute(AttributeLoc::Function, export_name_attr);
         }
-        if is_main && function_definition.visibility == Visibility::Public {
-            *self.has_main.borrow_mut() = true;
-        }
         if function_definition.is_non_det() {
             self.add_optimization_barriers(function);
         }
+        Ok(())
+    }
+
+    /// Returns the instances of generic functions to declare and lower.
+    pub(crate) fn instances(&self) -> Vec<Instance> {
+        self.monomorphization.instances().to_vec()
+    }
+
+    /// Declares an instance of a generic function under its symbol, see
+    /// [`crate::monomorphize`].
+    ///
+    /// Instances are never exported. An instance with a parameter or return type that has
+    /// no WebAssembly representation yet is reported and not declared.
+    pub(crate) fn declare_instance(&self, instance: &Instance) {
+        match FunctionAbi::instance(instance, &self.layout) {
+            Ok(abi) => {
+                let function = self.add_function_declaration(&instance.symbol, abi);
+                if instance.function.is_non_det() {
+                    self.add_optimization_barriers(function);
+                }
+            }
+            Err(error) => self.report(error),
+        }
+    }
+
+    /// Declares an external function as a WebAssembly import.
+    ///
+    /// The function is declared without a body under its name qualified with `module_path`
+    /// and carries the `wasm-import-module` and `wasm-import-name` attributes, which make
+    /// the linker emit a function import instead of reporting an undefined symbol. Only
+    /// external functions that are called end up in the linked module.
+    ///
+    /// An external function with a parameter or return type that has no WebAssembly
+    /// representation yet is reported and not declared.
+    pub(crate) fn declare_external_function(
+        &self,
+        external_function_definition: &ExternalFunctionDefinition,
+        module_path: &str,
+        import: &ImportName,
+    ) {
+        match FunctionAbi::external(external_function_definition, &self.layout) {
+            Ok(abi) => {
+                let symbol = modules::qualify(module_path, &external_function_definition.name());
+                let function = self.add_function_declaration(&symbol, abi);
+                for (key, value) in [
+                    ("wasm-import-module", import.module.as_str()),
+                    ("wasm-import-name", import.field.as_str()),
+                ] {
+                    let attribute = self.context.create_string_attribute(key, value);
+                    function.add_attribute(AttributeLoc::Function, attribute);
+                }
+            }
+            Err(error) => self.report(error),
+        }
+    }
+
+    /// Adds a function with the signature described by `abi` to the module and records
+    /// the ABI for calls.
+    fn add_function_declaration(&self, symbol: &str, abi: FunctionAbi) -> FunctionValue<'ctx> {
+        let return_type = abi.return_type.clone();
+
+        let pointer_type: BasicMetadataTypeEnum<'ctx> = self.pointer_type().into();
+        let mut param_types = Vec::new();
+        if abi.has_result_pointer {
+            param_types.push(pointer_type);
+        }
+        if abi.has_self {
+            param_types.push(pointer_type);
+        }
+        for parameter_type in &abi.parameter_types {
+            if self.layout.is_aggregate(parameter_type) {
+                param_types.push(pointer_type);
+            } else {
+                param_types.push(self.int_type(parameter_type).into());
+            }
+        }
+        let fn_type = if abi.returns_nothing() {
+            self.context.void_type().fn_type(&param_types, false)
+        } else {
+            self.int_type(&return_type).fn_type(&param_types, false)
+        };
+        let function = self.module.add_function(symbol, fn_type, None);
+        if !abi.has_result_pointer {
+            self.add_extension_attribute(function, AttributeLoc::Return, &return_type);
+        }
+        for (index, parameter_type) in (abi.first_parameter_index()..).zip(&abi.parameter_types) {
+            self.add_extension_attribute(function, AttributeLoc::Param(index), parameter_type);
+        }
+        self.function_abis
+            .borrow_mut()
+            .insert(symbol.to_string(), abi);
+        function
+    }
+
+    /// Translates an AST function definition to LLVM IR.
+    ///
+    /// This is the main entry point for function compilation. The function must already be
+    /// declared via [`Self::declare_function`]; functions whose declaration failed are
+    /// skipped. The body is lowered in three steps:
+    ///
+    /// 1. **Parameter spilling** - Each named scalar parameter is stored into an
+    ///    entry-block alloca, and each struct or array parameter is copied into a fresh
+    ///    stack slot,
+    ///    so parameters can be read and reassigned like any other local without affecting
+    ///    the caller. `self` refers directly to the receiver, so methods can update it
+    /// 2. **Body lowering** - Recursively lowers the function body statements to LLVM IR
+    /// 3. **Return handling** - Inserts implicit void return for functions without explicit
+    ///    return statements. Non-void functions whose last block is left open (e.g. after an
+    ///    `if`/`else` where both arms return) get an `unreachable` terminator instead
+    ///
+    /// # Parameters
+    ///
+    /// - `function_definition` - AST node representing the function to compile
+    /// - `module_path` - Path of the module the function is defined in, empty for the root
+    ///   module
+    /// - `owner` - Name of the struct a method belongs to, `None` for free functions
+    /// - `ctx` - Typed context containing type information for all AST nodes
+    ///
+    /// Statements that cannot be lowered are reported and skipped, see
+    /// [`Self::lower_statement`].
+    pub(crate) fn visit_function_definition(
+        &self,
+        function_definition: &Rc<FunctionDefinition>,
+        module_path: &str,
+        owner: Option<&str>,
+        ctx: &TypedContext,
+    ) {
+        let fn_name = FunctionAbi::symbol(module_path, function_definition, owner);
+        *self.substitutions.borrow_mut() = Substitutions::default();
+        self.lower_function(&fn_name, module_path, function_definition, owner, ctx);
+    }
+
+    /// Translates an instance of a generic function to LLVM IR, like
+    /// [`Self::visit_function_definition`], with every type parameter replaced by its type
+    /// argument. The instance must already be declared via [`Self::declare_instance`].
+    pub(crate) fn visit_instance(&self, instance: &Instance, ctx: &TypedContext) {
+        self.substitutions
+            .borrow_mut()
+            .clone_from(&instance.substitutions);
+        self.lower_function(
+            &instance.symbol,
+            &instance.module_path,
+            &instance.function,
+            instance.owner.as_deref(),
+            ctx,
+        );
+    }
+
+    /// Lowers the body of the declared function `fn_name`.
+    fn lower_function(
+        &self,
+        fn_name: &str,
+        module_path: &str,
+        function_definition: &Rc<FunctionDefinition>,
+        owner: Option<&str>,
+        ctx: &TypedContext,
+    ) {
+        let Some(function) = self.module.get_function(fn_name) else {
+            return;
+        };
+        let abi = self.function_abis.borrow()[fn_name].clone();
+        *self.variables.borrow_mut() = vec![HashMap::new()];
+        module_path.clone_into(&mut self.module_path.borrow_mut());
         let entry = self.context.append_basic_block(function, "entry");
         self.builder.position_at_end(entry);
-        self.lower_statement(
+        self.enter_subprogram(function, fn_name, function_definition.location);
+
+        let mut parameters = function.get_param_iter();
+        *self.result_pointer.borrow_mut() = if abi.has_result_pointer {
+            let result = parameters.next().unwrap().into_pointer_value();
+            result.set_name("result");
+            Some(result)
+        } else {
+            None
+        };
+        if abi.has_self {
+            let receiver = parameters.next().unwrap().into_pointer_value();
+            receiver.set_name("self");
+            let self_type = TypeInfo {
+                kind: TypeInfoKind::Struct(owner.expect("`self` must belong to a struct").into()),
+                type_params: vec![],
+            };
+            self.define_variable("self".to_string(), receiver, self.memory_type(&self_type));
+        }
+        for ((parameter, name), parameter_type) in parameters
+            .zip(abi.parameter_names)
+            .zip(&abi.parameter_types)
+        {
+            let Some(name) = name else {
+                continue;
+            };
+            parameter.set_name(&name);
+            let local = self.build_local(parameter_type, &name);
+            if self.layout.is_aggregate(parameter_type) {
+                self.copy_value(local, parameter.into_pointer_value(), parameter_type);
+            } else {
+                self.builder.build_store(local, parameter).unwrap();
+            }
+            self.define_variable(name, local, self.memory_type(parameter_type));
+        }
+
+        if let Err(error) = self.lower_statement(
             std::iter::once(Statement::Block(function_definition.body.clone())).peekable(),
             &mut vec![function_definition.body.clone()],
             ctx,
-        );
-        if function_definition.is_void() {
-            self.builder.build_return(None).unwrap();
+        ) {
+            self.report(error);
+        }
+        if !self.is_current_block_terminated() {
+            if function_definition.is_void() {
+                self.builder.build_return(None).unwrap();
+            } else {
+                self.builder.build_unreachable().unwrap();
+            }
         }
+        self.builder.unset_current_debug_location();
+    }
+
+    /// Attaches a subprogram for `function` to it and makes the function definition the
+    /// current debug location, when debug info is enabled.
+    ///
+    /// Only line tables are emitted, so the subprogram has no parameter or result types.
+    fn enter_subprogram(&self, function: FunctionValue<'ctx>, fn_name: &str, location: Location) {
+        let Some(debug_info) = &self.debug_info else {
+            return;
+        };
+        let file = debug_info.compile_unit.get_file();
+        let subroutine_type =
+            debug_info
+                .builder
+                .create_subroutine_type(file, None, &[], DIFlags::PUBLIC);
+        let subprogram = debug_info.builder.create_function(
+            debug_info.compile_unit.as_debug_info_scope(),
+            fn_name,
+            None,
+            file,
+            location.start_line,
+            subroutine_type,
+            false,
+            true,
+            location.start_line,
+            DIFlags::PUBLIC,
+            false,
+        );
+        function.set_subprogram(subprogram);
+        debug_info.subprogram.set(Some(subprogram));
+        self.set_debug_location(location);
+    }
+
+    /// Makes `location` the debug location of the instructions built from now on, when
+    /// debug info is enabled.
+    fn set_debug_location(&self, location: Location) {
+        let Some(debug_info) = &self.debug_info else {
+            return;
+        };
+        let subprogram = debug_info
+            .subprogram
+            .get()
+            .expect("Debug locations must be set inside a function");
+        let debug_location = debug_info.builder.create_debug_location(
+            self.context,
+            location.start_line,
+            location.start_column,
+            subprogram.as_debug_info_scope(),
+            None,
+        );
+        self.builder.set_current_debug_location(debug_location);
     }
 
     /// Recursively lowers AST statements to LLVM IR instructions.
@@ -383,7 +914,20 @@ impl<'ctx> Compiler<'ctx> {
     ///   nested statements with appropriate intrinsic calls
     /// - **Expression statements** - Evaluate expressions and handle side effects
     /// - **Return statements** - Generate LLVM return instructions
-    /// - **Constant definitions** - Allocate stack storage and initialize values
+    /// - **Control flow** - `if`/`else`, conditional and unconditional `loop`, and `break`
+    ///   are lowered to LLVM basic blocks and branches
+    /// - **Variable and constant definitions** - Allocate stack storage and initialize values
+    /// - **Assignments** - Store a new value into a local variable
+    ///
+    /// Statements that follow a terminator in the same block (e.g. code after `return` or
+    /// `break`) are unreachable and are not lowered, which keeps every LLVM basic block
+    /// well-formed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the statement uses a construct that cannot be lowered yet. A
+    /// block reports the errors of its statements through [`Self::report`] and continues
+    /// with the next statement, so lowering a block itself does not fail.
     ///
     /// # Non-Deterministic Blocks
     ///
@@ -415,106 +959,166 @@ impl<'ctx> Compiler<'ctx> {
     /// - `statements_iterator` - Iterator over statements to lower
     /// - `parent_blocks_stack` - Stack tracking enclosing block contexts
     /// - `ctx` - Typed context for type information lookup
-    #[allow(clippy::too_many_lines)]
+    ///
+    /// # Debug Locations
+    ///
+    /// With debug info enabled, the instructions of a statement carry its location. The
+    /// location of the enclosing statement is restored afterwards, so the branches that
+    /// close an `if` or `loop` belong to that statement rather than to the last statement
+    /// of its body.
     fn lower_statement<I: Iterator<Item = Statement>>(
         &self,
         mut statements_iterator: Peekable<I>,
         parent_blocks_stack: &mut Vec<BlockType>,
         ctx: &TypedContext,
-    ) {
+    ) -> Result<(), CodegenError> {
+        let enclosing = self.builder.get_current_debug_location();
+        if let Some(statement) = statements_iterator.peek() {
+            self.set_debug_location(statement.location());
+        }
+        let result = self.lower_statement_kind(statements_iterator, parent_blocks_stack, ctx);
+        if let Some(location) = enclosing {
+            self.builder.set_current_debug_location(location);
+        }
+        result
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn lower_statement_kind<I: Iterator<Item = Statement>>(
+        &self,
+        mut statements_iterator: Peekable<I>,
+        parent_blocks_stack: &mut Vec<BlockType>,
+        ctx: &TypedContext,
+    ) -> Result<(), CodegenError> {
         let statement = statements_iterator.next().unwrap();
         match statement {
-            Statement::Block(block_type) => match block_type {
-                BlockType::Block(block) => {
-                    parent_blocks_stack.push(BlockType::Block(block.clone()));
-                    for stmt in block.statements.clone() {
-                        self.lower_statement(
-                            std::iter::once(stmt).peekable(),
-                            parent_blocks_stack,
-                            ctx,
-                        );
+            Statement::Block(block_type) => {
+                // Every block opens a scope for the variables it defines.
+                self.variables.borrow_mut().push(HashMap::new());
+                match block_type {
+                    BlockType::Block(block) => {
+                        parent_blocks_stack.push(BlockType::Block(block.clone()));
+                        for stmt in block.statements.clone() {
+                            if self.is_current_block_terminated() {
+                                break;
+                            }
+                            if let Err(error) = self.lower_statement(
+                                std::iter::once(stmt).peekable(),
+                                parent_blocks_stack,
+                                ctx,
+                            ) {
+                                self.report(error);
+                            }
+                        }
+                        parent_blocks_stack.pop();
                     }
-                    parent_blocks_stack.pop();
-                }
-                BlockType::Forall(forall_block) => {
-                    let forall_start = self.forall_start_intrinsic();
-                    self.builder
-                        .build_call(forall_start, &[], "")
-                        .expect("Failed to build forall intrinsic call");
-                    parent_blocks_stack.push(BlockType::Forall(forall_block.clone()));
-                    for stmt in forall_block.statements.clone() {
-                        self.lower_statement(
-                            std::iter::once(stmt).peekable(),
-                            parent_blocks_stack,
-                            ctx,
-                        );
+                    BlockType::Forall(forall_block) => {
+                        let forall_start = self.forall_start_intrinsic();
+                        self.builder
+                            .build_call(forall_start, &[], "")
+                            .expect("Failed to build forall intrinsic call");
+                        parent_blocks_stack.push(BlockType::Forall(forall_block.clone()));
+                        for stmt in forall_block.statements.clone() {
+                            if self.is_current_block_terminated() {
+                                break;
+                            }
+                            if let Err(error) = self.lower_statement(
+                                std::iter::once(stmt).peekable(),
+                                parent_blocks_stack,
+                                ctx,
+                            ) {
+                                self.report(error);
+                            }
+                        }
+                        let forall_end = self.forall_end_intrinsic();
+                        self.builder
+                            .build_call(forall_end, &[], "")
+                            .expect("Failed to build forall end intrinsic call");
+                        parent_blocks_stack.pop();
                     }
-                    let forall_end = self.forall_end_intrinsic();
-                    self.builder
-                        .build_call(forall_end, &[], "")
-                        .expect("Failed to build forall end intrinsic call");
-                    parent_blocks_stack.pop();
-                }
-                BlockType::Assume(assume_block) => {
-                    let assume_start = self.assume_start_intrinsic();
-                    self.builder
-                        .build_call(assume_start, &[], "")
-                        .expect("Failed to build assume intrinsic call");
-                    parent_blocks_stack.push(BlockType::Assume(assume_block.clone()));
-                    for stmt in assume_block.statements.clone() {
-                        self.lower_statement(
-                            std::iter::once(stmt).peekable(),
-                            parent_blocks_stack,
-                            ctx,
-                        );
+                    BlockType::Assume(assume_block) => {
+                        let assume_start = self.assume_start_intrinsic();
+                        self.builder
+                            .build_call(assume_start, &[], "")
+                            .expect("Failed to build assume intrinsic call");
+                        parent_blocks_stack.push(BlockType::Assume(assume_block.clone()));
+                        for stmt in assume_block.statements.clone() {
+                            if self.is_current_block_terminated() {
+                                break;
+                            }
+                            if let Err(error) = self.lower_statement(
+                                std::iter::once(stmt).peekable(),
+                                parent_blocks_stack,
+                                ctx,
+                            ) {
+                                self.report(error);
+                            }
+                        }
+                        let assume_end = self.assume_end_intrinsic();
+                        self.builder
+                            .build_call(assume_end, &[], "")
+                            .expect("Failed to build assume end intrinsic call");
+                        parent_blocks_stack.pop();
                     }
-                    let assume_end = self.assume_end_intrinsic();
-                    self.builder
-                        .build_call(assume_end, &[], "")
-                        .expect("Failed to build assume end intrinsic call");
-                    parent_blocks_stack.pop();
-                }
-                BlockType::Exists(exists_block) => {
-                    let exists_start = self.exists_start_intrinsic();
-                    self.builder
-                        .build_call(exists_start, &[], "")
-                        .expect("Failed to build exists intrinsic call");
-                    parent_blocks_stack.push(BlockType::Exists(exists_block.clone()));
-                    for stmt in exists_block.statements.clone() {
-                        self.lower_statement(
-                            std::iter::once(stmt).peekable(),
-                            parent_blocks_stack,
-                            ctx,
-                        );
+                    BlockType::Exists(exists_block) => {
+                        let exists_start = self.exists_start_intrinsic();
+                        self.builder
+                            .build_call(exists_start, &[], "")
+                            .expect("Failed to build exists intrinsic call");
+                        parent_blocks_stack.push(BlockType::Exists(exists_block.clone()));
+                        for stmt in exists_block.statements.clone() {
+                            if self.is_current_block_terminated() {
+                                break;
+                            }
+                            if let Err(error) = self.lower_statement(
+                                std::iter::once(stmt).peekable(),
+                                parent_blocks_stack,
+                                ctx,
+                            ) {
+                                self.report(error);
+                            }
+                        }
+                        let exists_end = self.exists_end_intrinsic();
+                        self.builder
+                            .build_call(exists_end, &[], "")
+                            .expect("Failed to build exists end intrinsic call");
+                        parent_blocks_stack.pop();
                     }
-                    let exists_end = self.exists_end_intrinsic();
-                    self.builder
-                        .build_call(exists_end, &[], "")
-                        .expect("Failed to build exists end intrinsic call");
-                    parent_blocks_stack.pop();
-                }
-                BlockType::Unique(unique_block) => {
-                    let unique_start = self.unique_start_intrinsic();
-                    self.builder
-                        .build_call(unique_start, &[], "")
-                        .expect("Failed to build unique intrinsic call");
-                    parent_blocks_stack.push(BlockType::Unique(unique_block.clone()));
-                    for stmt in unique_block.statements.clone() {
-                        self.lower_statement(
-                            std::iter::once(stmt).peekable(),
-                            parent_blocks_stack,
-                            ctx,
-                        );
+                    BlockType::Unique(unique_block) => {
+                        let unique_start = self.unique_start_intrinsic();
+                        self.builder
+                            .build_call(unique_start, &[], "")
+                            .expect("Failed to build unique intrinsic call");
+                        parent_blocks_stack.push(BlockType::Unique(unique_block.clone()));
+                        for stmt in unique_block.statements.clone() {
+                            if self.is_current_block_terminated() {
+                                break;
+                            }
+                            if let Err(error) = self.lower_statement(
+                                std::iter::once(stmt).peekable(),
+                                parent_blocks_stack,
+                                ctx,
+                            ) {
+                                self.report(error);
+                            }
+                        }
+                        let unique_end = self.unique_end_intrinsic();
+                        self.builder
+                            .build_call(unique_end, &[], "")
+                            .expect("Failed to build unique end intrinsic call");
+                        parent_blocks_stack.pop();
                     }
-                    let unique_end = self.unique_end_intrinsic();
-                    self.builder
-                        .build_call(unique_end, &[], "")
-                        .expect("Failed to build unique end intrinsic call");
-                    parent_blocks_stack.pop();
                 }
-            },
+                self.variables.borrow_mut().pop();
+            }
             Statement::Expression(expression) => {
-                let expr = self.lower_expression(&expression, ctx);
+                // Calls are lowered separately since a call to a void function is a valid
+                // statement but produces no value.
+                let expr = if let Expression::FunctionCall(function_call_expression) = &expression {
+                    self.lower_function_call(function_call_expression, ctx)?
+                } else {
+                    Some(self.lower_expression(&expression, ctx)?.into())
+                };
                 // FIXME: revisit this logic #45
                 //
                 // This handles the case where a non-deterministic void block ends with an
@@ -525,7 +1129,8 @@ impl<'ctx> Compiler<'ctx> {
                 // This is a workaround that ensures side effects are preserved. A better
                 // approach would be to explicitly model void expressions or use LLVM's
                 // volatile operations for intrinsic calls.
-                if statements_iterator.peek().is_none()
+                if let Some(expr) = expr
+                    && statements_iterator.peek().is_none()
                     && parent_blocks_stack.first().unwrap().is_non_det()
                     && parent_blocks_stack.first().unwrap().is_void()
                 {
@@ -533,94 +1138,183 @@ impl<'ctx> Compiler<'ctx> {
                     self.builder.build_store(local, expr).unwrap();
                 }
             }
-            Statement::Assign(_assign_statement) => todo!(),
+            Statement::Assign(assign_statement) => {
+                // The target is a variable, a (possibly nested) struct field or an array
+                // element; all are places in memory, and aggregate values are copied.
+                let target = assign_statement.left.borrow();
+                let target_type = self.expression_type(&target, ctx);
+                let target_pointer = self.lower_address(&target, ctx)?;
+                self.store_expression(
+                    target_pointer,
+                    &target_type,
+                    &assign_statement.right.borrow(),
+                    ctx,
+                )?;
+            }
             Statement::Return(return_statement) => {
-                let ret = self.lower_expression(&return_statement.expression.borrow(), ctx);
-                self.builder.build_return(Some(&ret)).unwrap();
-            }
-            Statement::Loop(_loop_statement) => todo!(),
-            Statement::Break(_break_statement) => todo!(),
-            Statement::If(_if_statement) => todo!(),
-            Statement::VariableDefinition(_variable_definition_statement) => {
-                // Variable definition support is currently disabled pending implementation of:
-                // 1. Type resolution for non-i32 types
-                // 2. Complex expression evaluation (beyond uzumaki and literals)
-                // 3. Proper variable scoping (currently uses flat namespace)
-                // 4. Mutable vs immutable variable semantics
-                //
-                // When re-enabled, this will follow the same pattern as constant definitions:
-                // - Allocate stack storage (alloca)
-                // - Lower the initialization expression
-                // - Store the value to the allocated pointer
-                // - Register in the variables HashMap for later loads
-            }
-            Statement::TypeDefinition(_type_definition_statement) => todo!(),
-            Statement::Assert(_assert_statement) => todo!(),
+                // A bare `return;` carries a unit literal, which has no LLVM value. Struct
+                // and array results are written to the caller's memory instead.
+                let result_pointer = *self.result_pointer.borrow();
+                if let Expression::Literal(Literal::Unit(_)) =
+                    &*return_statement.expression.borrow()
+                {
+                    self.builder.build_return(None).unwrap();
+                } else if let Some(result_pointer) = result_pointer {
+                    let expression = return_statement.expression.borrow();
+                    let result_type = self.expression_type(&expression, ctx);
+                    self.store_expression(result_pointer, &result_type, &expression, ctx)?;
+                    self.builder.build_return(None).unwrap();
+                } else {
+                    let ret = self.lower_expression(&return_statement.expression.borrow(), ctx)?;
+                    self.builder.build_return(Some(&ret)).unwrap();
+                }
+            }
+            Statement::Loop(loop_statement) => {
+                // loop.cond: evaluates the optional condition on every iteration
+                // loop.body: the loop body, branching back to loop.cond when it falls through
+                // loop.end:  the continuation, also the target of `break`
+                let function = self.current_function();
+                let cond_block = self.context.append_basic_block(function, "loop.cond");
+                let body_block = self.context.append_basic_block(function, "loop.body");
+                let exit_block = self.context.append_basic_block(function, "loop.end");
+                self.builder.build_unconditional_branch(cond_block).unwrap();
+                self.builder.position_at_end(cond_block);
+                if let Some(condition) = &*loop_statement.condition.borrow() {
+                    let condition = self.lower_condition(condition, ctx)?;
+                    self.builder
+                        .build_conditional_branch(condition, body_block, exit_block)
+                        .unwrap();
+                } else {
+                    self.builder.build_unconditional_branch(body_block).unwrap();
+                }
+                self.builder.position_at_end(body_block);
+                self.loop_exit_blocks.borrow_mut().push(exit_block);
+                let body = self.lower_statement(
+                    std::iter::once(Statement::Block(loop_statement.body.clone())).peekable(),
+                    parent_blocks_stack,
+                    ctx,
+                );
+                self.loop_exit_blocks.borrow_mut().pop();
+                body?;
+                if !self.is_current_block_terminated() {
+                    self.builder.build_unconditional_branch(cond_block).unwrap();
+                }
+                self.builder.position_at_end(exit_block);
+            }
+            Statement::Break(break_statement) => {
+                let exit_block = *self.loop_exit_blocks.borrow().last().ok_or(
+                    CodegenError::BreakOutsideLoop {
+                        location: break_statement.location,
+                    },
+                )?;
+                self.builder.build_unconditional_branch(exit_block).unwrap();
+            }
+            Statement::If(if_statement) => {
+                let function = self.current_function();
+                let condition = self.lower_condition(&if_statement.condition.borrow(), ctx)?;
+                let then_block = self.context.append_basic_block(function, "if.then");
+                let else_block = if_statement
+                    .else_arm
+                    .as_ref()
+                    .map(|_| self.context.append_basic_block(function, "if.else"));
+                let merge_block = self.context.append_basic_block(function, "if.end");
+                self.builder
+                    .build_conditional_branch(
+                        condition,
+                        then_block,
+                        else_block.unwrap_or(merge_block),
+                    )
+                    .unwrap();
+
+                self.builder.position_at_end(then_block);
+                self.lower_statement(
+                    std::iter::once(Statement::Block(if_statement.if_arm.clone())).peekable(),
+                    parent_blocks_stack,
+                    ctx,
+                )?;
+                if !self.is_current_block_terminated() {
+                    self.builder
+                        .build_unconditional_branch(merge_block)
+                        .unwrap();
+                }
+
+                if let (Some(else_arm), Some(else_block)) = (&if_statement.else_arm, else_block) {
+                    self.builder.position_at_end(else_block);
+                    self.lower_statement(
+                        std::iter::once(Statement::Block(else_arm.clone())).peekable(),
+                        parent_blocks_stack,
+                        ctx,
+                    )?;
+                    if !self.is_current_block_terminated() {
+                        self.builder
+                            .build_unconditional_branch(merge_block)
+                            .unwrap();
+                    }
+                }
+                self.builder.position_at_end(merge_block);
+            }
+            Statement::VariableDefinition(variable_definition_statement) => {
+                // Variables follow the same pattern as constant definitions: an entry-block
+                // alloca, an optional store of the initializer, and a registration in the
+                // innermost scope. A definition shadowing an outer one gets its own alloca,
+                // so the outer variable keeps its value once the block ends.
+                let type_info = self
+                    .node_type(variable_definition_statement.id, ctx)
+                    .expect("Variable definition must have a type info");
+                self.layout
+                    .check_type(&type_info, variable_definition_statement.location)?;
+                let name = variable_definition_statement.name();
+                let local = self.build_local(&type_info, &name);
+                if let Some(value) = &variable_definition_statement.value {
+                    self.store_expression(local, &type_info, &value.borrow(), ctx)?;
+                }
+                self.define_variable(name, local, self.memory_type(&type_info));
+            }
+            Statement::TypeDefinition(type_definition_statement) => {
+                return Err(CodegenError::UnsupportedStatement {
+                    statement: "type definition",
+                    location: type_definition_statement.location,
+                });
+            }
+            Statement::Assert(assert_statement) => {
+                let function = self.current_function();
+                let condition = self.lower_condition(&assert_statement.expression.borrow(), ctx)?;
+                let ok_block = self.context.append_basic_block(function, "assert.ok");
+                let fail_block = self.context.append_basic_block(function, "assert.fail");
+                self.builder
+                    .build_conditional_branch(condition, ok_block, fail_block)
+                    .unwrap();
+                self.builder.position_at_end(fail_block);
+                self.build_assertion_failure(self.layout.assertion_id(assert_statement.id));
+                self.builder.position_at_end(ok_block);
+            }
             Statement::ConstantDefinition(constant_definition) => {
                 // Constant definitions are lowered by:
                 // 1. Looking up the type from TypedContext
                 // 2. Creating a stack allocation (alloca) for the constant
                 // 3. Lowering the literal value to an LLVM constant
                 // 4. Storing the constant to the allocated pointer
-                // 5. Registering in the variables HashMap for identifier resolution
+                // 5. Registering in the innermost scope for identifier resolution
                 //
-                // Currently only i32 number literals are fully implemented. Other types
-                // will follow the same pattern once expression lowering is expanded.
-                match ctx
-                    .get_node_typeinfo(constant_definition.id)
-                    .expect("Constant definition must have a type info")
-                    .kind
-                {
-                    TypeInfoKind::Unit => todo!(),
-                    TypeInfoKind::Bool => todo!(),
-                    TypeInfoKind::String => todo!(),
-                    TypeInfoKind::Number(number_type_kind_number_type) => {
-                        match number_type_kind_number_type {
-                            NumberType::I8 => todo!(),
-                            NumberType::I16 => todo!(),
-                            NumberType::I32 => {
-                                let ctx_type = self.context.i32_type();
-                                match &constant_definition.value {
-                                    Literal::Number(number_literal) => {
-                                        let val = ctx_type.const_int(
-                                            number_literal.value.parse::<u64>().unwrap_or(0),
-                                            false,
-                                        );
-                                        let local = self
-                                            .builder
-                                            .build_alloca(ctx_type, &constant_definition.name())
-                                            .unwrap();
-                                        self.builder.build_store(local, val).unwrap();
-                                        self.variables.borrow_mut().insert(
-                                            constant_definition.name(),
-                                            (local, ctx_type.into()),
-                                        );
-                                    }
-                                    _ => panic!(
-                                        "Constant value for i32 should be a number literal. Found: {:?}",
-                                        constant_definition.value
-                                    ),
-                                }
-                            }
-                            NumberType::I64 => todo!(),
-                            NumberType::U8 => todo!(),
-                            NumberType::U16 => todo!(),
-                            NumberType::U32 => todo!(),
-                            NumberType::U64 => todo!(),
-                        }
-                    }
-                    TypeInfoKind::Custom(_) => todo!(),
-                    TypeInfoKind::Array(_type_info, _) => todo!(),
-                    TypeInfoKind::Generic(_) => todo!(),
-                    TypeInfoKind::QualifiedName(_) => todo!(),
-                    TypeInfoKind::Qualified(_) => todo!(),
-                    TypeInfoKind::Function(_) => todo!(),
-                    TypeInfoKind::Struct(_) => todo!(),
-                    TypeInfoKind::Enum(_) => todo!(),
-                    TypeInfoKind::Spec(_) => todo!(),
+                // Scalar constants of every width are supported; the literal carries the
+                // constant's type, so lowering it yields a value of the right LLVM type.
+                let type_info = self
+                    .node_type(constant_definition.id, ctx)
+                    .expect("Constant definition must have a type info");
+                if !matches!(type_info.kind, TypeInfoKind::Bool | TypeInfoKind::Number(_)) {
+                    return Err(CodegenError::UnsupportedLocalConstant {
+                        type_info,
+                        location: constant_definition.location,
+                    });
                 }
+                let ty = self.int_type(&type_info);
+                let value = self.lower_literal(&constant_definition.value, ctx)?;
+                let local = self.build_entry_alloca(ty.into(), &constant_definition.name());
+                self.builder.build_store(local, value).unwrap();
+                self.define_variable(constant_definition.name(), local, ty.into());
             }
         }
+        Ok(())
     }
 
     /// Lowers an AST expression to an LLVM integer value.
@@ -633,12 +1327,18 @@ impl<'ctx> Compiler<'ctx> {
     ///
     /// - **Literals** - Compile-time constants (numbers, booleans)
     /// - **Identifiers** - Load values from local variables
+    /// - **Binary and unary operators** - Arithmetic, bitwise, comparison and logical
+    ///   operators for `bool` and every integer width
+    /// - **Parenthesized expressions** - Lowered as their inner expression
+    /// - **Field and element reads** - Load a struct field or a bounds-checked array element
     /// - **Uzumaki** - Non-deterministic value generation via intrinsics
     ///
     /// # Type Context
     ///
-    /// The `TypedContext` is used to query type information for expressions, particularly
-    /// for uzumaki expressions which can produce different integer types (i32, i64).
+    /// The `TypedContext` is used to query type information for expressions: the width of
+    /// literals, the signedness of operands (which selects between signed and unsigned
+    /// division, remainder, comparison and right shift), and the type produced by uzumaki
+    /// expressions.
     ///
     /// # Parameters
     ///
@@ -648,44 +1348,1073 @@ impl<'ctx> Compiler<'ctx> {
     /// # Returns
     ///
     /// LLVM integer value representing the expression result
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression, or one of its operands, cannot be lowered yet.
     fn lower_expression(
         &self,
         expression: &Expression,
         ctx: &TypedContext,
-    ) -> inkwell::values::IntValue<'ctx> {
-        match expression {
-            Expression::ArrayIndexAccess(_array_index_access_expression) => todo!(),
-            Expression::Binary(_binary_expression) => todo!(),
-            Expression::MemberAccess(_member_access_expression) => todo!(),
-            Expression::TypeMemberAccess(_type_member_access_expression) => todo!(),
-            Expression::FunctionCall(_function_call_expression) => todo!(),
-            Expression::Struct(_struct_expression) => todo!(),
-            Expression::PrefixUnary(_prefix_unary_expression) => todo!(),
-            Expression::Parenthesized(_parenthesized_expression) => todo!(),
-            Expression::Literal(literal) => self.lower_literal(literal),
-            Expression::Identifier(identifier) => {
-                let (ptr, ty) = self
-                    .variables
-                    .borrow()
-                    .get(&identifier.name)
-                    .copied()
-                    .expect("Variable not found");
+    ) -> Result<IntValue<'ctx>, CodegenError> {
+        let value = match expression {
+            Expression::ArrayIndexAccess(_) | Expression::MemberAccess(_) => {
+                let element_type = self.expression_type(expression, ctx);
+                let element_pointer = self.lower_address(expression, ctx)?;
+                let name = match expression {
+                    Expression::MemberAccess(member_access_expression) => {
+                        member_access_expression.name.name.as_str()
+                    }
+                    _ => "element",
+                };
                 self.builder
-                    .build_load(ty, ptr, &identifier.name)
+                    .build_load(self.int_type(&element_type), element_pointer, name)
                     .unwrap()
                     .into_int_value()
             }
-            Expression::Type(_) => todo!(),
+            Expression::Binary(binary_expression) => {
+                self.lower_binary_expression(binary_expression, ctx)?
+            }
+            Expression::TypeMemberAccess(type_member_access_expression) => {
+                let enum_type = self.expression_type(expression, ctx);
+                let discriminant = self
+                    .layout
+                    .enum_layout(&enum_type)
+                    .unwrap_or_else(|| panic!("Type member access on non-enum type `{enum_type}`"))
+                    .discriminant(&type_member_access_expression.name.name);
+                self.int_type(&enum_type)
+                    .const_int(u64::from(discriminant), false)
+            }
+            Expression::FunctionCall(function_call_expression) => self
+                .lower_function_call(function_call_expression, ctx)?
+                .expect("A function call used as a value must return a value")
+                .into_int_value(),
+            Expression::Struct(_struct_expression) => {
+                unreachable!("Struct values are lowered through `lower_address`")
+            }
+            Expression::PrefixUnary(prefix_unary_expression) => {
+                self.lower_prefix_unary_expression(prefix_unary_expression, ctx)?
+            }
+            Expression::Parenthesized(parenthesized_expression) => {
+                self.lower_expression(&parenthesized_expression.expression.borrow(), ctx)?
+            }
+            Expression::Literal(literal) => self.lower_literal(literal, ctx)?,
+            Expression::Identifier(identifier) => {
+                let variable = self.variable(&identifier.name);
+                if let Some((ptr, ty)) = variable {
+                    return Ok(self
+                        .builder
+                        .build_load(ty, ptr, &identifier.name)
+                        .unwrap()
+                        .into_int_value());
+                }
+                match self.module_constant(&identifier.name, identifier.location)? {
+                    ModuleConstant::Scalar(value) => value,
+                    ModuleConstant::Aggregate(_) => {
+                        unreachable!("Aggregate constants are lowered through `lower_address`")
+                    }
+                }
+            }
+            Expression::Type(type_expression) => {
+                return Err(CodegenError::UnsupportedExpression {
+                    expression: "type",
+                    location: type_expression.location(),
+                });
+            }
             Expression::Uzumaki(uzumaki_expression) => {
-                if ctx.is_node_i32(uzumaki_expression.id) {
-                    return self.lower_uzumaki_i32_expression();
+                let type_info = self.expression_type(expression, ctx);
+                self.layout
+                    .check_uzumaki_type(&type_info, uzumaki_expression.location)?;
+                self.lower_uzumaki(&type_info)
+            }
+        };
+        Ok(value)
+    }
+
+    /// Lowers a call to a function or method defined in the module.
+    ///
+    /// Free functions are called by name. For method calls the type checker records the
+    /// callee as `Struct::method` on the callee expression; instance methods additionally
+    /// receive the address of the receiver as `self`, so a method can update it.
+    ///
+    /// Arguments are evaluated left to right as written and then passed in the callee's
+    /// parameter order: named arguments go to the parameter with the same name, positional
+    /// arguments fill the remaining parameters in order. Struct and array arguments are
+    /// passed by address and copied by the callee. The callee must already be declared, which
+    /// [`Self::declare_function`] guarantees for every function in the module, including
+    /// the caller itself.
+    ///
+    /// # Returns
+    ///
+    /// The scalar call result, a pointer to a fresh stack slot holding a struct or array
+    /// result, or `None` when the callee returns `unit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the callee is not a function or method name, if the callee has
+    /// no compiled definition (external functions, or functions whose declaration failed),
+    /// or if an argument cannot be lowered.
+    fn lower_function_call(
+        &self,
+        function_call_expression: &FunctionCallExpression,
+        ctx: &TypedContext,
+    ) -> Result<Option<BasicValueEnum<'ctx>>, CodegenError> {
+        // The type checker gives the callee of a built-in conversion no type
+        if let Expression::Identifier(callee) = &function_call_expression.function
+            && self.node_type(callee.id, ctx).is_none()
+            && let Some(conversion) = Conversion::from_name(&callee.name)
+        {
+            return Ok(Some(
+                self.lower_conversion(conversion, function_call_expression, ctx)?
+                    .into(),
+            ));
+        }
+        let (name, receiver) = match &function_call_expression.function {
+            callee @ (Expression::Identifier(_)
+            | Expression::MemberAccess(_)
+            | Expression::TypeMemberAccess(_)) => {
+                let TypeInfoKind::Function(name) = self.expression_type(callee, ctx).kind else {
+                    panic!("Callee must resolve to a function");
+                };
+                let receiver = match callee {
+                    Expression::MemberAccess(member_access_expression) => {
+                        Some(&member_access_expression.expression)
+                    }
+                    _ => None,
+                };
+                (name, receiver)
+            }
+            callee => {
+                return Err(CodegenError::UnsupportedExpression {
+                    expression: "indirect call",
+                    location: callee.location(),
+                });
+            }
+        };
+        let caller = self
+            .current_function()
+            .get_name()
+            .to_string_lossy()
+            .into_owned();
+        let name = self
+            .monomorphization
+            .call_target(&caller, function_call_expression.id, name);
+        let Some(abi) = self.function_abis.borrow().get(&name).cloned() else {
+            return Err(CodegenError::UnavailableSymbol {
+                name,
+                location: function_call_expression.location,
+            });
+        };
+        let function = self
+            .module
+            .get_function(&name)
+            .expect("Every function with an ABI must be declared");
+
+        let mut arguments: Vec<Option<BasicMetadataValueEnum<'ctx>>> =
+            vec![None; function.count_params() as usize];
+        let result = if abi.has_result_pointer {
+            let result_type = self
+                .node_type(function_call_expression.id, ctx)
+                .expect("Function call must have a type info");
+            let result = self.build_local(&result_type, "call.result");
+            arguments[0] = Some(result.into());
+            Some(result)
+        } else {
+            None
+        };
+        if let (true, Some(receiver)) = (abi.has_self, receiver) {
+            let receiver = self.lower_address(&receiver.borrow(), ctx)?;
+            arguments[u32::from(abi.has_result_pointer) as usize] = Some(receiver.into());
+        }
+        let parameter_indices =
+            function_call_expression.argument_parameter_indices(&abi.parameter_names);
+        for ((_, argument), parameter_index) in function_call_expression
+            .arguments
+            .iter()
+            .flatten()
+            .zip(parameter_indices)
+        {
+            let parameter_index = parameter_index.expect("Every argument must bind to a parameter");
+            let argument = argument.borrow();
+            let value: BasicMetadataValueEnum<'ctx> = if self
+                .layout
+                .is_aggregate(&abi.parameter_types[parameter_index])
+            {
+                self.lower_address(&argument, ctx)?.into()
+            } else {
+                self.lower_expression(&argument, ctx)?.into()
+            };
+            arguments[abi.first_parameter_index() as usize + parameter_index] = Some(value);
+        }
+        let arguments: Vec<BasicMetadataValueEnum<'ctx>> = arguments
+            .into_iter()
+            .map(|argument| argument.expect("Every parameter must receive an argument"))
+            .collect();
+        let call = self.builder.build_call(function, &arguments, "").unwrap();
+        Ok(match result {
+            Some(result) => Some(result.into()),
+            None => call.try_as_basic_value().basic(),
+        })
+    }
+
+    /// Lowers a built-in conversion between integer types to `sext` or `zext`, by the
+    /// signedness of the source type, when the target type is wider and to `trunc` when it
+    /// is narrower.
+    ///
+    /// `checked_cast` first compares the value against the bounds of the target type the
+    /// source type can exceed, and branches to a block that executes `unreachable` if it
+    /// is out of range.
+    fn lower_conversion(
+        &self,
+        conversion: Conversion,
+        function_call_expression: &FunctionCallExpression,
+        ctx: &TypedContext,
+    ) -> Result<IntValue<'ctx>, CodegenError> {
+        let (_, argument) = function_call_expression
+            .arguments
+            .iter()
+            .flatten()
+            .next()
+            .expect("A conversion takes one argument");
+        let argument = argument.borrow();
+        let source_type = self.expression_type(&argument, ctx);
+        let target_type = self
+            .node_type(function_call_expression.id, ctx)
+            .expect("Conversion must have a type info");
+        let (TypeInfoKind::Number(source), TypeInfoKind::Number(target)) =
+            (&source_type.kind, &target_type.kind)
+        else {
+            panic!("Conversions are between integer types");
+        };
+        let value = self.lower_expression(&argument, ctx)?;
+        if conversion == Conversion::CheckedCast && !source.fits_in(*target) {
+            let source_int_type = self.int_type(&source_type);
+            let bound = |bound: i128| {
+                let bound = i64::try_from(bound).expect("Integer bounds fit in 64 bits");
+                source_int_type.const_int(bound.cast_unsigned(), true)
+            };
+            let mut out_of_range = self.context.bool_type().const_zero();
+            if target.max_value() < source.max_value() {
+                let predicate = if source.is_signed() {
+                    IntPredicate::SGT
+                } else {
+                    IntPredicate::UGT
+                };
+                let above = self
+                    .builder
+                    .build_int_compare(predicate, value, bound(target.max_value()), "cast.above")
+                    .unwrap();
+                out_of_range = self
+                    .builder
+                    .build_or(out_of_range, above, "cast.out_of_range")
+                    .unwrap();
+            }
+            if source.min_value() < target.min_value() {
+                let below = self
+                    .builder
+                    .build_int_compare(
+                        IntPredicate::SLT,
+                        value,
+                        bound(target.min_value()),
+                        "cast.below",
+                    )
+                    .unwrap();
+                out_of_range = self
+                    .builder
+                    .build_or(out_of_range, below, "cast.out_of_range")
+                    .unwrap();
+            }
+            let function = self.current_function();
+            let ok_block = self.context.append_basic_block(function, "cast.ok");
+            let trap_block = self.context.append_basic_block(function, "cast.trap");
+            self.builder
+                .build_conditional_branch(out_of_range, trap_block, ok_block)
+                .unwrap();
+            self.builder.position_at_end(trap_block);
+            self.builder.build_unreachable().unwrap();
+            self.builder.position_at_end(ok_block);
+        }
+        let target_int_type = self.int_type(&target_type);
+        Ok(match source.bits().cmp(&target.bits()) {
+            Ordering::Less if source.is_signed() => self
+                .builder
+                .build_int_s_extend(value, target_int_type, "cast")
+                .unwrap(),
+            Ordering::Less => self
+                .builder
+                .build_int_z_extend(value, target_int_type, "cast")
+                .unwrap(),
+            Ordering::Greater => self
+                .builder
+                .build_int_truncate(value, target_int_type, "cast")
+                .unwrap(),
+            Ordering::Equal => value,
+        })
+    }
+
+    /// Looks up a module-level constant that is not shadowed by a local variable: the one
+    /// of the innermost module around the current function that defines `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no constant called `name` exists. The type checker rejects
+    /// references to unknown names, so this means the definition of `name` could not be
+    /// lowered, either as a module-level constant or as a local variable.
+    fn module_constant(
+        &self,
+        name: &str,
+        location: Location,
+    ) -> Result<ModuleConstant<'ctx>, CodegenError> {
+        let constants = self.constants.borrow();
+        modules::candidates(&self.module_path.borrow(), name)
+            .iter()
+            .find_map(|candidate| constants.get(candidate).copied())
+            .ok_or_else(|| CodegenError::UnavailableSymbol {
+                name: name.to_string(),
+                location,
+            })
+    }
+
+    /// Lowers an expression that denotes a place in memory and returns its address.
+    ///
+    /// Variables, struct fields and array elements are places that can be read and
+    /// assigned. Struct and array literals and calls returning an aggregate have no place
+    /// of their own, so they are materialized in a fresh stack slot whose address is
+    /// returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression has no address (a scalar module-level constant
+    /// or a computed scalar value), if its type has no memory layout, or if a
+    /// subexpression cannot be lowered.
+    fn lower_address(
+        &self,
+        expression: &Expression,
+        ctx: &TypedContext,
+    ) -> Result<PointerValue<'ctx>, CodegenError> {
+        let address = match expression {
+            Expression::Identifier(identifier) => {
+                let variable = self.variable(&identifier.name);
+                if let Some((ptr, _)) = variable {
+                    return Ok(ptr);
+                }
+                match self.module_constant(&identifier.name, identifier.location)? {
+                    ModuleConstant::Aggregate(ptr) => ptr,
+                    ModuleConstant::Scalar(_) => {
+                        return Err(CodegenError::AssignmentToConstant {
+                            name: identifier.name(),
+                            location: identifier.location,
+                        });
+                    }
                 }
-                if ctx.is_node_i64(uzumaki_expression.id) {
-                    return self.lower_uzumaki_i64_expression();
+            }
+            Expression::Parenthesized(parenthesized_expression) => {
+                self.lower_address(&parenthesized_expression.expression.borrow(), ctx)?
+            }
+            Expression::MemberAccess(member_access_expression) => {
+                let object = member_access_expression.expression.borrow();
+                let object_type = self.expression_type(&object, ctx);
+                let field_offset = self
+                    .layout
+                    .struct_layout(&object_type)
+                    .ok_or_else(|| CodegenError::UnsupportedType {
+                        type_info: object_type.clone(),
+                        location: object.location(),
+                    })?
+                    .field(&member_access_expression.name.name)
+                    .offset;
+                let object_pointer = self.lower_address(&object, ctx)?;
+                self.field_pointer(object_pointer, field_offset)
+            }
+            Expression::ArrayIndexAccess(array_index_access_expression) => {
+                let array = array_index_access_expression.array.borrow();
+                let array_type = self.expression_type(&array, ctx);
+                self.layout.check_type(&array_type, array.location())?;
+                let array_pointer = self.lower_address(&array, ctx)?;
+                self.element_pointer(
+                    array_pointer,
+                    &array_type,
+                    &array_index_access_expression.index.borrow(),
+                    ctx,
+                )?
+            }
+            Expression::Literal(Literal::Array(array_literal)) => {
+                let array_type = self.expression_type(expression, ctx);
+                self.layout
+                    .check_type(&array_type, array_literal.location)?;
+                let (element_type, _) = DataLayout::array_element(&array_type)
+                    .expect("Array literal must have an array type");
+                let (element_size, _) = self.layout.size_and_align(element_type);
+                let value = self.build_local(&array_type, "array");
+                for (index, element) in (0u32..).zip(array_literal.elements.iter().flatten()) {
+                    let element_pointer = self.field_pointer(value, index * element_size);
+                    self.store_expression(element_pointer, element_type, &element.borrow(), ctx)?;
                 }
-                panic!("Unsupported Uzumaki expression type: {uzumaki_expression:?}");
+                value
+            }
+            Expression::Struct(struct_expression) => {
+                let struct_type = self.expression_type(expression, ctx);
+                let struct_layout = self.layout.struct_layout(&struct_type).ok_or_else(|| {
+                    CodegenError::UnsupportedType {
+                        type_info: struct_type.clone(),
+                        location: struct_expression.location,
+                    }
+                })?;
+                let value = self.build_local(&struct_type, &struct_expression.name());
+                for (field_name, field_value) in struct_expression.fields.iter().flatten() {
+                    let field = struct_layout.field(&field_name.name);
+                    let field_pointer = self.field_pointer(value, field.offset);
+                    self.store_expression(
+                        field_pointer,
+                        &field.type_info,
+                        &field_value.borrow(),
+                        ctx,
+                    )?;
+                }
+                value
+            }
+            Expression::Uzumaki(uzumaki_expression) => {
+                let type_info = self.expression_type(expression, ctx);
+                self.layout
+                    .check_uzumaki_type(&type_info, uzumaki_expression.location)?;
+                let value = self.build_local(&type_info, "uzumaki");
+                self.store_uzumaki(value, &type_info);
+                value
+            }
+            Expression::FunctionCall(function_call_expression) => self
+                .lower_function_call(function_call_expression, ctx)?
+                .expect("A function call used as an aggregate value must return one")
+                .into_pointer_value(),
+            _ => {
+                return Err(CodegenError::NotAPlace {
+                    location: expression.location(),
+                });
+            }
+        };
+        Ok(address)
+    }
+
+    /// Stores the value of `expression` into the memory at `destination`.
+    ///
+    /// Scalars are stored directly; struct and array values are copied from the address
+    /// of `expression`, except for `@`, which is generated in place.
+    fn store_expression(
+        &self,
+        destination: PointerValue<'ctx>,
+        type_info: &TypeInfo,
+        expression: &Expression,
+        ctx: &TypedContext,
+    ) -> Result<(), CodegenError> {
+        if let Expression::Uzumaki(uzumaki_expression) = expression
+            && self.layout.is_aggregate(type_info)
+        {
+            self.layout
+                .check_uzumaki_type(type_info, uzumaki_expression.location)?;
+            self.store_uzumaki(destination, type_info);
+        } else if self.layout.is_aggregate(type_info) {
+            let source = self.lower_address(expression, ctx)?;
+            self.copy_value(destination, source, type_info);
+        } else {
+            let value = self.lower_expression(expression, ctx)?;
+            self.builder.build_store(destination, value).unwrap();
+        }
+        Ok(())
+    }
+
+    /// Copies a value of `type_info` from `source` to `destination`.
+    ///
+    /// Copying scalar by scalar, rather than with `llvm.memcpy`, keeps the output free of
+    /// calls to a `memcpy` the module does not define, and skips the padding bytes.
+    /// Structs are copied field by field and arrays element by element in a loop.
+    /// Copying a value onto itself is harmless.
+    fn copy_value(
+        &self,
+        destination: PointerValue<'ctx>,
+        source: PointerValue<'ctx>,
+        type_info: &TypeInfo,
+    ) {
+        if let Some(struct_layout) = self.layout.struct_layout(type_info) {
+            for field in &struct_layout.fields {
+                let destination_field = self.field_pointer(destination, field.offset);
+                let source_field = self.field_pointer(source, field.offset);
+                self.copy_value(destination_field, source_field, &field.type_info);
+            }
+        } else if let Some((element_type, length)) = DataLayout::array_element(type_info) {
+            self.copy_array(destination, source, element_type, length);
+        } else {
+            let value = self
+                .builder
+                .build_load(self.int_type(type_info), source, "copy")
+                .unwrap();
+            self.builder.build_store(destination, value).unwrap();
+        }
+    }
+
+    /// Copies the `length` elements of an array with a counted loop; see
+    /// [`Self::build_element_loop`].
+    fn copy_array(
+        &self,
+        destination: PointerValue<'ctx>,
+        source: PointerValue<'ctx>,
+        element_type: &TypeInfo,
+        length: u32,
+    ) {
+        self.build_element_loop(element_type, length, |offset| {
+            let destination_element = self.byte_pointer(destination, offset);
+            let source_element = self.byte_pointer(source, offset);
+            self.copy_value(destination_element, source_element, element_type);
+        });
+    }
+
+    /// Stores non-deterministic values into every scalar of the `type_info` value at
+    /// `destination`, field by field and element by element. The type must have passed
+    /// [`DataLayout::check_uzumaki_type`].
+    fn store_uzumaki(&self, destination: PointerValue<'ctx>, type_info: &TypeInfo) {
+        if let Some(struct_layout) = self.layout.struct_layout(type_info) {
+            for field in &struct_layout.fields {
+                let field_pointer = self.field_pointer(destination, field.offset);
+                self.store_uzumaki(field_pointer, &field.type_info);
+            }
+        } else if let Some((element_type, length)) = DataLayout::array_element(type_info) {
+            self.build_element_loop(element_type, length, |offset| {
+                self.store_uzumaki(self.byte_pointer(destination, offset), element_type);
+            });
+        } else {
+            let value = self.lower_uzumaki(type_info);
+            self.builder.build_store(destination, value).unwrap();
+        }
+    }
+
+    /// Builds a counted loop over the `length` elements of an array, calling `body` with
+    /// the byte offset of the current element.
+    ///
+    /// ```text
+    /// loop.body: index = phi [0, entry], [next, latch]
+    ///            body(index * element size)
+    ///            next = index + 1; br (next == length), loop.end, loop.body
+    /// loop.end:  continuation
+    /// ```
+    fn build_element_loop(
+        &self,
+        element_type: &TypeInfo,
+        length: u32,
+        body: impl FnOnce(IntValue<'ctx>),
+    ) {
+        if length == 0 {
+            return;
+        }
+        let (element_size, _) = self.layout.size_and_align(element_type);
+        let i32_type = self.context.i32_type();
+        let function = self.current_function();
+        let entry_block = self
+            .builder
+            .get_insert_block()
+            .expect("Builder must be positioned inside a block");
+        let body_block = self.context.append_basic_block(function, "loop.body");
+        let exit_block = self.context.append_basic_block(function, "loop.end");
+        self.builder.build_unconditional_branch(body_block).unwrap();
+
+        self.builder.position_at_end(body_block);
+        let index = self.builder.build_phi(i32_type, "loop.index").unwrap();
+        index.add_incoming(&[(&i32_type.const_zero(), entry_block)]);
+        let index_value = index.as_basic_value().into_int_value();
+        let offset = self
+            .builder
+            .build_int_mul(
+                index_value,
+                i32_type.const_int(u64::from(element_size), false),
+                "loop.offset",
+            )
+            .unwrap();
+        body(offset);
+        let next = self
+            .builder
+            .build_int_add(index_value, i32_type.const_int(1, false), "loop.next")
+            .unwrap();
+        // A nested loop in `body` leaves the builder in that loop's exit block.
+        let latch_block = self.builder.get_insert_block().unwrap();
+        index.add_incoming(&[(&next, latch_block)]);
+        let done = self
+            .builder
+            .build_int_compare(
+                IntPredicate::EQ,
+                next,
+                i32_type.const_int(u64::from(length), false),
+                "loop.done",
+            )
+            .unwrap();
+        self.builder
+            .build_conditional_branch(done, exit_block, body_block)
+            .unwrap();
+        self.builder.position_at_end(exit_block);
+    }
+
+    /// Returns the address `offset` bytes past `base`.
+    fn field_pointer(&self, base: PointerValue<'ctx>, offset: u32) -> PointerValue<'ctx> {
+        if offset == 0 {
+            return base;
+        }
+        self.byte_pointer(
+            base,
+            self.context.i32_type().const_int(u64::from(offset), false),
+        )
+    }
+
+    /// Returns the address `offset` bytes past `base`, for an offset computed at runtime.
+    fn byte_pointer(&self, base: PointerValue<'ctx>, offset: IntValue<'ctx>) -> PointerValue<'ctx> {
+        // SAFETY: offsets come from the data layout, and array indices are bounds-checked
+        // before they are scaled, so the result stays inside the value `base` points to.
+        unsafe {
+            self.builder
+                .build_in_bounds_gep(self.context.i8_type(), base, &[offset], "field")
+                .unwrap()
+        }
+    }
+
+    /// Returns the address of element `index` of the array at `array`.
+    ///
+    /// The index is widened to 64 bits, sign-extending signed types so that a negative
+    /// index becomes a huge unsigned one, and compared unsigned against the array length.
+    /// An out-of-bounds index branches to a block that executes `unreachable`, which traps
+    /// deterministically.
+    fn element_pointer(
+        &self,
+        array: PointerValue<'ctx>,
+        array_type: &TypeInfo,
+        index: &Expression,
+        ctx: &TypedContext,
+    ) -> Result<PointerValue<'ctx>, CodegenError> {
+        let (element_type, length) = DataLayout::array_element(array_type)
+            .unwrap_or_else(|| panic!("Indexed value of type `{array_type}` is not an array"));
+        let (element_size, _) = self.layout.size_and_align(element_type);
+        let index_is_signed = self.expression_type(index, ctx).is_signed_integer();
+        let index = self.lower_expression(index, ctx)?;
+        let i64_type = self.context.i64_type();
+        let wide_index = if index_is_signed {
+            self.builder
+                .build_int_s_extend_or_bit_cast(index, i64_type, "index.wide")
+                .unwrap()
+        } else {
+            self.builder
+                .build_int_z_extend_or_bit_cast(index, i64_type, "index.wide")
+                .unwrap()
+        };
+        let in_bounds = self
+            .builder
+            .build_int_compare(
+                IntPredicate::ULT,
+                wide_index,
+                i64_type.const_int(u64::from(length), false),
+                "in_bounds",
+            )
+            .unwrap();
+        let function = self.current_function();
+        let ok_block = self.context.append_basic_block(function, "bounds.ok");
+        let trap_block = self.context.append_basic_block(function, "bounds.trap");
+        self.builder
+            .build_conditional_branch(in_bounds, ok_block, trap_block)
+            .unwrap();
+        self.builder.position_at_end(trap_block);
+        self.builder.build_unreachable().unwrap();
+
+        self.builder.position_at_end(ok_block);
+        let i32_type = self.context.i32_type();
+        let index = self
+            .builder
+            .build_int_truncate_or_bit_cast(wide_index, i32_type, "index")
+            .unwrap();
+        let offset = self
+            .builder
+            .build_int_mul(
+                index,
+                i32_type.const_int(u64::from(element_size), false),
+                "offset",
+            )
+            .unwrap();
+        Ok(self.byte_pointer(array, offset))
+    }
+
+    /// Stores `assertion_id` at [`FAILED_ASSERTION_ADDRESS`] and traps.
+    ///
+    /// The store is volatile and the trap goes through `llvm.trap`, so the optimizer
+    /// cannot drop the store as dead code on the way to `unreachable`.
+    fn build_assertion_failure(&self, assertion_id: u32) {
+        let i32_type = self.context.i32_type();
+        let address = i32_type
+            .const_int(u64::from(FAILED_ASSERTION_ADDRESS), false)
+            .const_to_pointer(self.pointer_type());
+        self.builder
+            .build_store(address, i32_type.const_int(u64::from(assertion_id), false))
+            .unwrap()
+            .set_volatile(true)
+            .unwrap();
+        let trap = self.module.get_function(TRAP_INTRINSIC).unwrap_or_else(|| {
+            self.module.add_function(
+                TRAP_INTRINSIC,
+                self.context.void_type().fn_type(&[], false),
+                None,
+            )
+        });
+        self.builder.build_call(trap, &[], "").unwrap();
+        self.builder.build_unreachable().unwrap();
+    }
+
+    /// Binds `name` to the variable stored at `ptr` in the innermost scope.
+    fn define_variable(&self, name: String, ptr: PointerValue<'ctx>, ty: BasicTypeEnum<'ctx>) {
+        self.variables
+            .borrow_mut()
+            .last_mut()
+            .expect("Variables must be defined inside a function")
+            .insert(name, (ptr, ty));
+    }
+
+    /// Returns the variable `name` refers to, looking from the innermost scope outwards.
+    fn variable(&self, name: &str) -> Option<(PointerValue<'ctx>, BasicTypeEnum<'ctx>)> {
+        self.variables
+            .borrow()
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+    }
+
+    /// Allocates an entry-block stack slot for a local of type `type_info`.
+    ///
+    /// Struct and array slots get the alignment of their layout, so every field and
+    /// element is naturally aligned.
+    fn build_local(&self, type_info: &TypeInfo, name: &str) -> PointerValue<'ctx> {
+        let local = self.build_entry_alloca(self.memory_type(type_info), name);
+        if self.layout.is_aggregate(type_info) {
+            let (_, align) = self.layout.size_and_align(type_info);
+            local
+                .as_instruction_value()
+                .unwrap()
+                .set_alignment(align)
+                .unwrap();
+        }
+        local
+    }
+
+    /// Returns the LLVM type used to hold a value of `type_info` in memory.
+    ///
+    /// Structs and arrays are opaque byte arrays of the layout size; fields and elements
+    /// are reached through [`Self::field_pointer`] and [`Self::element_pointer`].
+    fn memory_type(&self, type_info: &TypeInfo) -> BasicTypeEnum<'ctx> {
+        if self.layout.is_aggregate(type_info) {
+            let (size, _) = self.layout.size_and_align(type_info);
+            self.context.i8_type().array_type(size).into()
+        } else {
+            self.int_type(type_info).into()
+        }
+    }
+
+    /// Returns the opaque pointer type used for addresses in linear memory.
+    fn pointer_type(&self) -> PointerType<'ctx> {
+        self.context.ptr_type(AddressSpace::default())
+    }
+
+    /// Returns the type the type checker inferred for a node, with the type parameters of
+    /// the instance being lowered replaced by their type arguments.
+    fn node_type(&self, node_id: u32, ctx: &TypedContext) -> Option<TypeInfo> {
+        ctx.get_node_typeinfo(node_id)
+            .map(|type_info| type_info.substitute(&self.substitutions.borrow()))
+    }
+
+    /// Returns the type the type checker inferred for `expression`, see [`Self::node_type`].
+    fn expression_type(&self, expression: &Expression, ctx: &TypedContext) -> TypeInfo {
+        self.node_type(expression.id(), ctx)
+            .unwrap_or_else(|| panic!("Expression must have a type info: {expression:?}"))
+    }
+
+    /// Lowers a condition expression to an `i1` value suitable for conditional branches.
+    ///
+    /// Boolean values may be represented either as `i1` (e.g. comparison results) or as
+    /// `i32` (boolean literals, per WASM convention). Wider values are compared against
+    /// zero, so any non-zero value is treated as `true`.
+    fn lower_condition(
+        &self,
+        expression: &Expression,
+        ctx: &TypedContext,
+    ) -> Result<IntValue<'ctx>, CodegenError> {
+        let value = self.lower_expression(expression, ctx)?;
+        if value.get_type().get_bit_width() == 1 {
+            return Ok(value);
+        }
+        Ok(self
+            .builder
+            .build_int_compare(
+                IntPredicate::NE,
+                value,
+                value.get_type().const_zero(),
+                "cond",
+            )
+            .unwrap())
+    }
+
+    /// Returns the function that owns the block the builder is currently positioned in.
+    fn current_function(&self) -> FunctionValue<'ctx> {
+        self.builder
+            .get_insert_block()
+            .and_then(BasicBlock::get_parent)
+            .expect("Builder must be positioned inside a function")
+    }
+
+    /// Returns `true` if the block the builder is positioned in already ends with a
+    /// terminator (`ret`, `br`, `unreachable`), meaning no more instructions may follow.
+    fn is_current_block_terminated(&self) -> bool {
+        self.builder
+            .get_insert_block()
+            .and_then(BasicBlock::get_terminator)
+            .is_some()
+    }
+
+    /// Allocates a stack slot in the entry block of the current function.
+    ///
+    /// Keeping all allocas in the entry block prevents the stack from growing on every
+    /// loop iteration and lets LLVM's `mem2reg` promote the slots to SSA registers.
+    fn build_entry_alloca(&self, ty: BasicTypeEnum<'ctx>, name: &str) -> PointerValue<'ctx> {
+        let entry = self
+            .current_function()
+            .get_first_basic_block()
+            .expect("Function must have an entry block");
+        let entry_builder = self.context.create_builder();
+        match entry.get_first_instruction() {
+            Some(first_instruction) => entry_builder.position_before(&first_instruction),
+            None => entry_builder.position_at_end(entry),
+        }
+        entry_builder.build_alloca(ty, name).unwrap()
+    }
+
+    /// Lowers a binary expression.
+    ///
+    /// Both operands share the same type (enforced by the type checker); its signedness
+    /// selects `sdiv`/`udiv`, `srem`/`urem`, `ashr`/`lshr` and signed or unsigned comparison
+    /// predicates. Shift amounts are masked to the operand width, matching WebAssembly
+    /// semantics. `&&` and `||` short-circuit and are lowered by
+    /// [`Self::lower_logical_expression`]; `**` is lowered by [`Self::lower_pow`].
+    fn lower_binary_expression(
+        &self,
+        binary_expression: &BinaryExpression,
+        ctx: &TypedContext,
+    ) -> Result<IntValue<'ctx>, CodegenError> {
+        if matches!(
+            binary_expression.operator,
+            OperatorKind::And | OperatorKind::Or
+        ) {
+            return self.lower_logical_expression(binary_expression, ctx);
+        }
+        let left_expression = binary_expression.left.borrow();
+        let is_signed = self
+            .node_type(left_expression.id(), ctx)
+            .is_some_and(|type_info| type_info.is_signed_integer());
+        let lhs = self.lower_expression(&left_expression, ctx)?;
+        let rhs = self.lower_expression(&binary_expression.right.borrow(), ctx)?;
+        let compare = |signed: IntPredicate, unsigned: IntPredicate, name: &str| {
+            let predicate = if is_signed { signed } else { unsigned };
+            self.builder
+                .build_int_compare(predicate, lhs, rhs, name)
+                .unwrap()
+        };
+        Ok(match binary_expression.operator {
+            OperatorKind::Add => self.builder.build_int_add(lhs, rhs, "add").unwrap(),
+            OperatorKind::Sub => self.builder.build_int_sub(lhs, rhs, "sub").unwrap(),
+            OperatorKind::Mul => self.builder.build_int_mul(lhs, rhs, "mul").unwrap(),
+            OperatorKind::Div if is_signed => {
+                self.builder.build_int_signed_div(lhs, rhs, "div").unwrap()
+            }
+            OperatorKind::Div => self
+                .builder
+                .build_int_unsigned_div(lhs, rhs, "div")
+                .unwrap(),
+            OperatorKind::Mod if is_signed => {
+                self.builder.build_int_signed_rem(lhs, rhs, "rem").unwrap()
             }
+            OperatorKind::Mod => self
+                .builder
+                .build_int_unsigned_rem(lhs, rhs, "rem")
+                .unwrap(),
+            OperatorKind::Pow => self.lower_pow(lhs, rhs),
+            OperatorKind::BitAnd => self.builder.build_and(lhs, rhs, "and").unwrap(),
+            OperatorKind::BitOr => self.builder.build_or(lhs, rhs, "or").unwrap(),
+            OperatorKind::BitXor => self.builder.build_xor(lhs, rhs, "xor").unwrap(),
+            OperatorKind::Shl => {
+                let amount = self.mask_shift_amount(rhs);
+                self.builder.build_left_shift(lhs, amount, "shl").unwrap()
+            }
+            OperatorKind::Shr => {
+                let amount = self.mask_shift_amount(rhs);
+                self.builder
+                    .build_right_shift(lhs, amount, is_signed, "shr")
+                    .unwrap()
+            }
+            OperatorKind::Eq => compare(IntPredicate::EQ, IntPredicate::EQ, "eq"),
+            OperatorKind::Ne => compare(IntPredicate::NE, IntPredicate::NE, "ne"),
+            OperatorKind::Lt => compare(IntPredicate::SLT, IntPredicate::ULT, "lt"),
+            OperatorKind::Le => compare(IntPredicate::SLE, IntPredicate::ULE, "le"),
+            OperatorKind::Gt => compare(IntPredicate::SGT, IntPredicate::UGT, "gt"),
+            OperatorKind::Ge => compare(IntPredicate::SGE, IntPredicate::UGE, "ge"),
+            OperatorKind::BitNot => {
+                unreachable!("`~` is a unary operator and never appears in a binary expression")
+            }
+            OperatorKind::And | OperatorKind::Or => unreachable!("handled above"),
+        })
+    }
+
+    /// Lowers `&&` and `||` with short-circuit evaluation.
+    ///
+    /// The right operand is evaluated in its own basic block that is only entered when the
+    /// left operand does not already determine the result:
+    ///
+    /// ```text
+    /// lhs:     br lhs, and.rhs, and.end      (|| swaps the targets)
+    /// and.rhs: br and.end
+    /// and.end: phi i1 [lhs_value, lhs], [rhs_value, and.rhs]
+    /// ```
+    fn lower_logical_expression(
+        &self,
+        binary_expression: &BinaryExpression,
+        ctx: &TypedContext,
+    ) -> Result<IntValue<'ctx>, CodegenError> {
+        let is_and = binary_expression.operator == OperatorKind::And;
+        let prefix = if is_and { "and" } else { "or" };
+        let function = self.current_function();
+        let lhs = self.lower_condition(&binary_expression.left.borrow(), ctx)?;
+        let lhs_block = self.builder.get_insert_block().unwrap();
+        let rhs_block = self
+            .context
+            .append_basic_block(function, &format!("{prefix}.rhs"));
+        let merge_block = self
+            .context
+            .append_basic_block(function, &format!("{prefix}.end"));
+        if is_and {
+            self.builder
+                .build_conditional_branch(lhs, rhs_block, merge_block)
+                .unwrap();
+        } else {
+            self.builder
+                .build_conditional_branch(lhs, merge_block, rhs_block)
+                .unwrap();
         }
+
+        self.builder.position_at_end(rhs_block);
+        let rhs = self.lower_condition(&binary_expression.right.borrow(), ctx)?;
+        let rhs_end_block = self.builder.get_insert_block().unwrap();
+        self.builder
+            .build_unconditional_branch(merge_block)
+            .unwrap();
+
+        self.builder.position_at_end(merge_block);
+        let phi = self
+            .builder
+            .build_phi(self.context.bool_type(), prefix)
+            .unwrap();
+        phi.add_incoming(&[(&lhs, lhs_block), (&rhs, rhs_end_block)]);
+        Ok(phi.as_basic_value().into_int_value())
+    }
+
+    /// Lowers `base ** exponent` using exponentiation by squaring.
+    ///
+    /// The exponent is treated as an unsigned integer and all multiplications wrap, so the
+    /// result equals the mathematical power modulo `2^width`.
+    ///
+    /// ```text
+    /// pow.cond: result = phi [1, entry], [result', pow.body]
+    ///           base   = phi [base, entry], [base * base, pow.body]
+    ///           exp    = phi [exponent, entry], [exp >> 1, pow.body]
+    ///           br exp == 0, pow.end, pow.body
+    /// pow.body: result' = exp & 1 ? result * base : result
+    ///           br pow.cond
+    /// ```
+    fn lower_pow(&self, base: IntValue<'ctx>, exponent: IntValue<'ctx>) -> IntValue<'ctx> {
+        let ty = base.get_type();
+        let function = self.current_function();
+        let entry_block = self.builder.get_insert_block().unwrap();
+        let cond_block = self.context.append_basic_block(function, "pow.cond");
+        let body_block = self.context.append_basic_block(function, "pow.body");
+        let end_block = self.context.append_basic_block(function, "pow.end");
+        self.builder.build_unconditional_branch(cond_block).unwrap();
+
+        self.builder.position_at_end(cond_block);
+        let result_phi = self.builder.build_phi(ty, "pow.result").unwrap();
+        let base_phi = self.builder.build_phi(ty, "pow.base").unwrap();
+        let exp_phi = self.builder.build_phi(ty, "pow.exp").unwrap();
+        let result = result_phi.as_basic_value().into_int_value();
+        let current_base = base_phi.as_basic_value().into_int_value();
+        let exp = exp_phi.as_basic_value().into_int_value();
+        let is_done = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, exp, ty.const_zero(), "pow.done")
+            .unwrap();
+        self.builder
+            .build_conditional_branch(is_done, end_block, body_block)
+            .unwrap();
+
+        self.builder.position_at_end(body_block);
+        let low_bit = self
+            .builder
+            .build_and(exp, ty.const_int(1, false), "pow.bit")
+            .unwrap();
+        let is_odd = self
+            .builder
+            .build_int_compare(IntPredicate::NE, low_bit, ty.const_zero(), "pow.odd")
+            .unwrap();
+        let multiplied = self
+            .builder
+            .build_int_mul(result, current_base, "pow.mul")
+            .unwrap();
+        let next_result = self
+            .builder
+            .build_select(is_odd, multiplied, result, "pow.next")
+            .unwrap()
+            .into_int_value();
+        let next_base = self
+            .builder
+            .build_int_mul(current_base, current_base, "pow.square")
+            .unwrap();
+        let next_exp = self
+            .builder
+            .build_right_shift(exp, ty.const_int(1, false), false, "pow.shift")
+            .unwrap();
+        self.builder.build_unconditional_branch(cond_block).unwrap();
+
+        result_phi.add_incoming(&[
+            (&ty.const_int(1, false), entry_block),
+            (&next_result, body_block),
+        ]);
+        base_phi.add_incoming(&[(&base, entry_block), (&next_base, body_block)]);
+        exp_phi.add_incoming(&[(&exponent, entry_block), (&next_exp, body_block)]);
+
+        self.builder.position_at_end(end_block);
+        result
+    }
+
+    /// Masks a shift amount to the bit width of the shifted value.
+    ///
+    /// LLVM produces poison for shift amounts greater than or equal to the width, while
+    /// WebAssembly takes the amount modulo the width. Masking makes the narrow integer
+    /// types (`i8`, `i16`) behave the same way as the native ones.
+    fn mask_shift_amount(&self, amount: IntValue<'ctx>) -> IntValue<'ctx> {
+        let ty = amount.get_type();
+        let mask = ty.const_int(u64::from(ty.get_bit_width() - 1), false);
+        self.builder.build_and(amount, mask, "shamt").unwrap()
+    }
+
+    /// Lowers a prefix unary expression.
+    ///
+    /// - `!` - Logical negation of a `bool` (`xor` with `true`)
+    /// - `-` - Two's complement negation of a signed integer
+    /// - `~` - Bitwise complement of an integer
+    fn lower_prefix_unary_expression(
+        &self,
+        prefix_unary_expression: &PrefixUnaryExpression,
+        ctx: &TypedContext,
+    ) -> Result<IntValue<'ctx>, CodegenError> {
+        let value = self.lower_expression(&prefix_unary_expression.expression.borrow(), ctx)?;
+        Ok(match prefix_unary_expression.operator {
+            UnaryOperatorKind::Not | UnaryOperatorKind::BitNot => {
+                self.builder.build_not(value, "not").unwrap()
+            }
+            UnaryOperatorKind::Neg => self.builder.build_int_neg(value, "neg").unwrap(),
+        })
     }
 
     /// Converts an AST literal to an LLVM constant integer value.
@@ -696,36 +2425,116 @@ impl<'ctx> Compiler<'ctx> {
     ///
     /// # Literal Types
     ///
-    /// - **Bool** - Converted to i32 (0 for false, 1 for true) per WASM convention
-    /// - **Number** - Parsed from string and converted to i32 constant
+    /// - **Bool** - Converted to an `i1` constant (0 for false, 1 for true)
+    /// - **Number** - Parsed from string and converted to a constant of the literal's
+    ///   type as recorded in the `TypedContext`. Values are truncated to the type width,
+    ///   so a negative literal produces its two's complement representation
     ///
     /// # Parameters
     ///
     /// - `literal` - AST literal node to convert
+    /// - `ctx` - Typed context used to look up the width of number literals
     ///
     /// # Returns
     ///
     /// LLVM constant integer value
-    fn lower_literal(&self, literal: &Literal) -> inkwell::values::IntValue<'ctx> {
-        match literal {
-            Literal::Array(_array_literal) => todo!(),
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for string literals and unit values, which have no integer
+    /// representation.
+    fn lower_literal(
+        &self,
+        literal: &Literal,
+        ctx: &TypedContext,
+    ) -> Result<IntValue<'ctx>, CodegenError> {
+        let value = match literal {
+            Literal::Array(_array_literal) => {
+                unreachable!("Array values are lowered through `lower_address`")
+            }
             Literal::Bool(bool_literal) => self
                 .context
-                .i32_type()
+                .bool_type()
                 .const_int(u64::from(bool_literal.value), false),
-            Literal::String(_string_literal) => todo!(),
-            Literal::Number(number_literal) => self
-                .context
-                .i32_type()
-                .const_int(number_literal.value.parse::<u64>().unwrap_or(0), false),
-            Literal::Unit(_unit_literal) => todo!(),
+            Literal::String(string_literal) => {
+                return Err(CodegenError::UnsupportedExpression {
+                    expression: "string literal",
+                    location: string_literal.location,
+                });
+            }
+            Literal::Number(number_literal) => {
+                let type_info = self
+                    .node_type(number_literal.id, ctx)
+                    .expect("Number literal must have a type info");
+                self.int_type(&type_info)
+                    .const_int(number_literal_bits(number_literal), false)
+            }
+            Literal::Unit(unit_literal) => {
+                return Err(CodegenError::UnsupportedExpression {
+                    expression: "unit value",
+                    location: unit_literal.location,
+                });
+            }
+        };
+        Ok(value)
+    }
+
+    /// Maps a scalar Inference type to the LLVM integer type that represents it.
+    ///
+    /// See the type mapping table in the module documentation. Enums are represented by
+    /// their discriminant type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not `bool`, an integer type or an enum.
+    fn int_type(&self, type_info: &TypeInfo) -> IntType<'ctx> {
+        match &type_info.kind {
+            TypeInfoKind::Bool => self.context.bool_type(),
+            TypeInfoKind::Number(NumberType::I8 | NumberType::U8) => self.context.i8_type(),
+            TypeInfoKind::Number(NumberType::I16 | NumberType::U16) => self.context.i16_type(),
+            TypeInfoKind::Number(NumberType::I32 | NumberType::U32) => self.context.i32_type(),
+            TypeInfoKind::Number(NumberType::I64 | NumberType::U64) => self.context.i64_type(),
+            _ => match self.layout.enum_layout(type_info) {
+                Some(enum_layout) => self.int_type(&enum_layout.discriminant_type()),
+                None => panic!("Type `{type_info}` has no integer representation"),
+            },
+        }
+    }
+
+    /// Generates a non-deterministic value of the scalar `type_info`.
+    ///
+    /// `i64` and `u64` values come from the i64 intrinsic. Every other type truncates the
+    /// result of the i32 intrinsic to its width, which constrains it to the type's range:
+    /// each value of the type, and nothing else, can be produced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not `bool` or an integer type; such types are rejected by
+    /// [`DataLayout::check_uzumaki_type`] first.
+    fn lower_uzumaki(&self, type_info: &TypeInfo) -> IntValue<'ctx> {
+        match type_info.kind {
+            TypeInfoKind::Number(NumberType::I64 | NumberType::U64) => {
+                self.lower_uzumaki_i64_expression()
+            }
+            TypeInfoKind::Bool | TypeInfoKind::Number(_) => {
+                let value = self.lower_uzumaki_i32_expression();
+                let int_type = self.int_type(type_info);
+                if int_type.get_bit_width() == 32 {
+                    value
+                } else {
+                    self.builder
+                        .build_int_truncate(value, int_type, "uz")
+                        .unwrap()
+                }
+            }
+            _ => panic!("Uzumaki values of type `{type_info}` must be rejected before lowering"),
         }
     }
 
     /// Generates LLVM IR for a 32-bit non-deterministic value (uzumaki expression).
     ///
     /// Emits a call to the `llvm.wasm.uzumaki.i32` intrinsic, which compiles to the
-    /// custom WASM instruction 0xfc 0x3a. This instruction produces a non-deterministic
+    /// custom WASM instruction 0xfc 0x31. This instruction produces a non-deterministic
     /// i32 value at runtime.
     ///
     /// # Returns
@@ -745,7 +2554,7 @@ impl<'ctx> Compiler<'ctx> {
     /// Generates LLVM IR for a 64-bit non-deterministic value (uzumaki expression).
     ///
     /// Emits a call to the `llvm.wasm.uzumaki.i64` intrinsic, which compiles to the
-    /// custom WASM instruction 0xfc 0x3c. This instruction produces a non-deterministic
+    /// custom WASM instruction 0xfc 0x32. This instruction produces a non-deterministic
     /// i64 value at runtime.
     ///
     /// # Returns
@@ -946,6 +2755,41 @@ impl<'ctx> Compiler<'ctx> {
             })
     }
 
+    /// Records the discriminant of every enum variant in the `inference.enums` custom
+    /// section.
+    ///
+    /// The section contents are described in [`DataLayout::enum_discriminants_section`].
+    /// Nothing is emitted for programs without enums.
+    pub(crate) fn record_enum_discriminants(&self) {
+        self.add_custom_section(
+            ENUMS_SECTION_NAME,
+            &self.layout.enum_discriminants_section(),
+        );
+    }
+
+    /// Records the source location of every assertion in the `inference.asserts` custom
+    /// section.
+    ///
+    /// The section contents are described in [`DataLayout::assertions_section`]. Nothing
+    /// is emitted for programs without assertions.
+    pub(crate) fn record_assertion_locations(&self) {
+        self.add_custom_section(ASSERTS_SECTION_NAME, &self.layout.assertions_section());
+    }
+
+    /// Adds a custom section with `contents` to the module, unless `contents` is empty.
+    fn add_custom_section(&self, name: &str, contents: &str) {
+        if contents.is_empty() {
+            return;
+        }
+        let section = self.context.metadata_node(&[
+            self.context.metadata_string(name).into(),
+            self.context.metadata_string(contents).into(),
+        ]);
+        self.module
+            .add_global_metadata("wasm.custom_sections", &section)
+            .unwrap();
+    }
+
     /// Compiles the LLVM module to WebAssembly bytecode.
     ///
     /// This method orchestrates the final compilation stages:
@@ -955,7 +2799,8 @@ impl<'ctx> Compiler<'ctx> {
     /// 4. Read the resulting WASM bytes
     ///
     /// The actual compilation work is delegated to the `utils::compile_to_wasm` function,
-    /// which handles toolchain invocation and temporary file management.
+    /// which handles toolchain invocation and temporary file management. Debug info, if
+    /// enabled, is finalized first.
     ///
     /// # Parameters
     ///
@@ -964,7 +2809,7 @@ impl<'ctx> Compiler<'ctx> {
     ///
     /// # Returns
     ///
-    /// WebAssembly bytecode as a byte vector
+    /// The LLVM IR, the object file and the linked WebAssembly module
     ///
     /// # Errors
     ///
@@ -975,9 +2820,11 @@ impl<'ctx> Compiler<'ctx> {
     pub(crate) fn compile_to_wasm(
         &self,
         output_fname: &str,
-        optimization_level: u32,
-    ) -> anyhow::Result<Vec<u8>> {
-        let has_main = *self.has_main.borrow();
-        utils::compile_to_wasm(&self.module, output_fname, optimization_level, has_main)
+        optimization_level: u8,
+    ) -> anyhow::Result<utils::Artifacts> {
+        if let Some(debug_info) = &self.debug_info {
+            debug_info.builder.finalize();
+        }
+        utils::compile_to_wasm(&self.module, output_fname, optimization_level)
     }
 }
@@ -0,0 +1,161 @@
+//! DWARF line tables for the built-in backend.
+//!
+//! The LLVM backend gets its line tables from LLVM, and `rust-lld` carries them into the
+//! linked module as `.debug_*` custom sections. The built-in backend produces the same
+//! sections itself: the emitter records the location of the statement every instruction
+//! was lowered from, and once the module is assembled, the instructions are matched with
+//! their offsets in the code section.
+//!
+//! Following the WebAssembly DWARF conventions, addresses are byte offsets from the start
+//! of the code section contents. Every function body is a sequence of the line program,
+//! with a row wherever the source location changes. The DWARF version and producer are
+//! shared with the LLVM backend, so both produce the same kind of compile unit.
+
+use gimli::{
+    DW_AT_comp_dir, DW_AT_name, DW_AT_producer, Encoding, Format, LineEncoding, LittleEndian,
+    write::{Address, AttributeValue, DwarfUnit, EndianVec, LineProgram, LineString, Sections},
+};
+use inf_wasmparser::{Operator, Parser, Payload};
+use inference_ast::nodes::Location;
+
+/// DWARF version of the debug info emitted by both backends.
+pub(crate) const DWARF_VERSION: u16 = 4;
+
+/// Producer recorded in the compile unit by both backends.
+pub(crate) const PRODUCER: &str = "inference";
+
+/// Offsets of a function body and its instructions, relative to the code section contents.
+struct FunctionOffsets {
+    start: u64,
+    end: u64,
+    instructions: Vec<u64>,
+}
+
+/// Appends DWARF line tables for `source_name` to an assembled module.
+///
+/// `locations` holds the source location of every instruction of every function body, as
+/// recorded by the emitter, in code section order.
+///
+/// # Errors
+///
+/// Returns an error if the module cannot be parsed or the DWARF sections cannot be
+/// written, which indicates a bug in the emitter.
+pub(crate) fn append_line_tables(
+    wasm: &mut Vec<u8>,
+    source_name: &str,
+    locations: &[Vec<Location>],
+) -> anyhow::Result<()> {
+    let functions = function_offsets(wasm)?;
+    assert_eq!(
+        functions.len(),
+        locations.len(),
+        "Every function body must have recorded locations"
+    );
+    let encoding = Encoding {
+        format: Format::Dwarf32,
+        version: DWARF_VERSION,
+        address_size: 4,
+    };
+    let mut dwarf = DwarfUnit::new(encoding);
+    let mut program = LineProgram::new(
+        encoding,
+        LineEncoding::default(),
+        LineString::String(b".".to_vec()),
+        None,
+        LineString::String(source_name.as_bytes().to_vec()),
+        None,
+    );
+    let directory = program.default_directory();
+    let file = program.add_file(
+        LineString::String(source_name.as_bytes().to_vec()),
+        directory,
+        None,
+    );
+    for (function, locations) in functions.iter().zip(locations) {
+        assert_eq!(
+            function.instructions.len(),
+            locations.len(),
+            "Every instruction must have a recorded location"
+        );
+        program.begin_sequence(Some(Address::Constant(function.start)));
+        let mut previous = None;
+        for (offset, location) in function.instructions.iter().zip(locations) {
+            let position = (location.start_line, location.start_column);
+            if previous == Some(position) {
+                continue;
+            }
+            previous = Some(position);
+            let row = program.row();
+            row.address_offset = offset - function.start;
+            row.file = file;
+            row.line = u64::from(location.start_line);
+            row.column = u64::from(location.start_column);
+            program.generate_row();
+        }
+        program.end_sequence(function.end - function.start);
+    }
+    dwarf.unit.line_program = program;
+    let root = dwarf.unit.root();
+    let entry = dwarf.unit.get_mut(root);
+    entry.set(
+        DW_AT_producer,
+        AttributeValue::String(PRODUCER.as_bytes().to_vec()),
+    );
+    entry.set(
+        DW_AT_name,
+        AttributeValue::String(source_name.as_bytes().to_vec()),
+    );
+    entry.set(DW_AT_comp_dir, AttributeValue::String(b".".to_vec()));
+
+    let mut sections = Sections::new(EndianVec::new(LittleEndian));
+    dwarf.write(&mut sections)?;
+    sections.for_each(|id, section| -> anyhow::Result<()> {
+        if !section.slice().is_empty() {
+            append_custom_section(wasm, id.name(), section.slice());
+        }
+        Ok(())
+    })
+}
+
+/// Returns the offsets of every function body in the code section, without the `end`
+/// that closes each body.
+fn function_offsets(wasm: &[u8]) -> anyhow::Result<Vec<FunctionOffsets>> {
+    let mut code_start = 0;
+    let mut functions = Vec::new();
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload? {
+            Payload::CodeSectionStart { range, .. } => code_start = range.start,
+            Payload::CodeSectionEntry(body) => {
+                let relative = |offset: usize| (offset - code_start) as u64;
+                let mut instructions = Vec::new();
+                for operator in body.get_operators_reader()?.into_iter_with_offsets() {
+                    let (operator, offset) = operator?;
+                    instructions.push((operator, relative(offset)));
+                }
+                let closing = instructions.pop();
+                assert!(
+                    matches!(closing, Some((Operator::End, _))),
+                    "Function body must end with `end`"
+                );
+                functions.push(FunctionOffsets {
+                    start: relative(body.range().start),
+                    end: relative(body.range().end),
+                    instructions: instructions.into_iter().map(|(_, offset)| offset).collect(),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(functions)
+}
+
+/// Appends a custom section to the end of a module.
+fn append_custom_section(wasm: &mut Vec<u8>, name: &str, contents: &[u8]) {
+    let mut payload = Vec::new();
+    leb128::write::unsigned(&mut payload, name.len() as u64).unwrap();
+    payload.extend_from_slice(name.as_bytes());
+    payload.extend_from_slice(contents);
+    wasm.push(0);
+    leb128::write::unsigned(wasm, payload.len() as u64).unwrap();
+    wasm.extend(payload);
+}
@@ -0,0 +1,2061 @@
+//! Built-in WebAssembly backend.
+//!
+//! This module lowers the typed AST straight to WebAssembly text and assembles it with the
+//! in-tree `inf-wast` encoder, which knows the non-deterministic instructions. Unlike the
+//! LLVM backend it needs no external binaries, so it works wherever the compiler builds;
+//! in exchange it performs no optimizations.
+//!
+//! # Correspondence with the LLVM Backend
+//!
+//! Both backends accept the same programs, report the same [`CodegenError`]s and produce
+//! modules with the same observable behavior:
+//!
+//! - Functions use the calling convention described in [`crate::abi`], and struct and
+//!   array values the layout described in [`crate::layout`]
+//! - Functions are exported under their symbol as selected by the [`ExportPolicy`]
+//! - Memory is exported as `memory`; data starts at address 1024 and is followed by a
+//!   64 KiB shadow stack whose top is held in the mutable `__stack_pointer` global
+//! - Struct and array module-level constants live in data segments named `.rodata.NAME`
+//! - Enum discriminants are recorded in the `inference.enums` custom section
+//! - A failing `assert` stores its ID at [`FAILED_ASSERTION_ADDRESS`] and traps; the IDs
+//!   are mapped to source locations in the `inference.asserts` custom section
+//! - Called external functions are imported under their configured [`ImportName`]
+//! - With debug info enabled, DWARF line tables map instructions to source locations; see
+//!   [`crate::debug_info`]
+//! - Instances of generic functions are named by the symbols described in
+//!   [`crate::monomorphize`]
+//!
+//! # Value Representation
+//!
+//! Scalars live in WebAssembly locals. `bool`, enums and every integer type up to 32 bits
+//! are `i32` values, `i64` and `u64` are `i64` values. Narrow integers are kept
+//! normalized, sign-extended for `i8`/`i16` and zero-extended for `u8`/`u16`, so that
+//! comparisons, division and right shifts can use the 32-bit instructions; every operation
+//! that can carry bits past the width (`+`, `-`, `*`, `/`, `**`, `<<`, `-x`, `~x`, and
+//! conversions to a narrow type) normalizes its result. Only MVP instructions are used, matching the `mvp` CPU the LLVM
+//! backend targets.
+//!
+//! Struct and array locals, parameter copies and temporaries live in the function's frame
+//! on the shadow stack. The frame is allocated in the prologue by decrementing
+//! `__stack_pointer` and released before every `return`:
+//!
+//! ```wat
+//! (func $make (param $.result i32) (param $x i32)
+//!   (local $.frame i32)
+//!   global.get $__stack_pointer
+//!   i32.const 16
+//!   i32.sub
+//!   local.tee $.frame
+//!   global.set $__stack_pointer
+//!   ...
+//! )
+//! ```
+//!
+//! # Non-Deterministic Operations
+//!
+//! `forall`, `exists`, `assume` and `unique` blocks become the structured instructions of
+//! the same name, closed by `end`, and `@` becomes `i32.uzumaki` or `i64.uzumaki`. Narrow
+//! integers and `bool` constrain an `i32.uzumaki` value to their range, and `@` of a
+//! struct or array generates every field and element separately.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    rc::Rc,
+};
+
+use inference_ast::nodes::{
+    BinaryExpression, BlockType, ConstantDefinition, Expression, ExternalFunctionDefinition,
+    FunctionCallExpression, FunctionDefinition, Literal, Location, OperatorKind,
+    PrefixUnaryExpression, Statement, UnaryOperatorKind,
+};
+use inference_type_checker::{
+    type_info::{Conversion, NumberType, TypeInfo, TypeInfoKind},
+    typed_context::TypedContext,
+};
+
+use crate::{
+    ExportPolicy, ImportName,
+    abi::FunctionAbi,
+    errors::CodegenError,
+    layout::{
+        ASSERTS_SECTION_NAME, DataLayout, ENUMS_SECTION_NAME, FAILED_ASSERTION_ADDRESS,
+        number_literal_bits,
+    },
+    modules,
+    monomorphize::{Instance, Monomorphization, Substitutions},
+};
+
+/// Address of the first data segment, the default global base of `rust-lld`.
+const DATA_START: u32 = 1024;
+
+/// Size of the shadow stack placed after the data segments.
+const STACK_SIZE: u32 = 64 * 1024;
+
+/// Alignment of the stack pointer, and therefore of every frame.
+const STACK_ALIGN: u32 = 16;
+
+/// Size of a WebAssembly memory page.
+const PAGE_SIZE: u32 = 64 * 1024;
+
+/// Global holding the top of the shadow stack.
+const STACK_POINTER: &str = "__stack_pointer";
+
+/// Local holding the base address of the current frame.
+const FRAME: &str = ".frame";
+
+/// Parameter receiving the address of a struct or array result.
+const RESULT: &str = ".result";
+
+/// Parameter receiving the address of the receiver of an instance method.
+const SELF: &str = "self";
+
+/// A location in linear memory: the address held in a local, or address 0 when `base` is
+/// `None`, plus a static byte offset.
+///
+/// Keeping the offset static lets field accesses fold into the `offset` immediate of
+/// loads and stores.
+#[derive(Debug, Clone)]
+struct Address {
+    base: Option<String>,
+    offset: u32,
+}
+
+impl Address {
+    fn field(&self, offset: u32) -> Self {
+        Self {
+            base: self.base.clone(),
+            offset: self.offset + offset,
+        }
+    }
+}
+
+/// Storage of a local variable.
+#[derive(Debug, Clone)]
+enum Variable {
+    /// A scalar held in the WebAssembly local or parameter with this name.
+    Local(String),
+    /// A struct or array in memory.
+    Memory(Address),
+}
+
+/// The value of a module-level constant as seen from function bodies.
+#[derive(Debug, Clone)]
+enum ModuleConstant {
+    /// A `bool` or integer constant, inlined at every use with this instruction.
+    Scalar(String),
+    /// A struct or array constant, read from the data segment at this address.
+    Aggregate(u32),
+}
+
+/// What a call leaves behind.
+enum CallResult {
+    /// The callee returns `unit`.
+    Nothing,
+    /// A scalar result on the operand stack.
+    Value,
+    /// A struct or array result in the caller's frame.
+    Memory(Address),
+}
+
+/// A line of a function body, with the location of the statement it was lowered from.
+enum Line {
+    /// An instruction at the given nesting depth.
+    Instruction(usize, String, Location),
+    /// Releases the frame, at the given nesting depth. The frame size is only known once
+    /// the whole body is lowered, so the instructions are filled in at the end.
+    Epilogue(usize, Location),
+}
+
+/// Struct and array constant placed in a data segment.
+struct DataSegment {
+    name: String,
+    address: u32,
+    bytes: Vec<u8>,
+}
+
+/// Builds the text of a WebAssembly module from the typed AST.
+///
+/// The module is built in the same order as the LLVM module: module-level constants are
+/// defined first, then every function is declared, and only then are the bodies lowered,
+/// so a call can refer to a function defined later in the file.
+pub(crate) struct Emitter<'a> {
+    ctx: &'a TypedContext,
+
+    /// Sizes, alignments and field offsets of all struct types, used for arrays as well.
+    layout: DataLayout,
+
+    /// Instances of the generic functions, and the instance each generic call targets.
+    monomorphization: Monomorphization,
+
+    /// Calling convention of every declared function, keyed by symbol name.
+    function_abis: HashMap<String, FunctionAbi>,
+
+    /// Which functions the module exports.
+    exports: ExportPolicy,
+
+    /// Module-level constants, keyed by name qualified with their module path.
+    constants: HashMap<String, ModuleConstant>,
+
+    data_segments: Vec<DataSegment>,
+
+    /// First free address after the data segments placed so far.
+    data_end: u32,
+
+    /// Text of every lowered function, in lowering order.
+    functions: Vec<String>,
+
+    /// Source location of every instruction of every lowered function, in the same order
+    /// as [`Self::functions`].
+    instruction_locations: Vec<Vec<Location>>,
+
+    /// Import names of the declared external functions, in declaration order.
+    imports: Vec<(String, ImportName)>,
+
+    /// Symbols of every function called so far. Only called external functions are
+    /// imported, as the linker does for the LLVM backend.
+    called: HashSet<String>,
+
+    /// Errors for the constructs that could not be lowered so far.
+    errors: Vec<CodegenError>,
+}
+
+impl<'a> Emitter<'a> {
+    pub(crate) fn new(
+        ctx: &'a TypedContext,
+        layout: DataLayout,
+        monomorphization: Monomorphization,
+        exports: ExportPolicy,
+    ) -> Self {
+        Self {
+            ctx,
+            layout,
+            monomorphization,
+            function_abis: HashMap::new(),
+            exports,
+            constants: HashMap::new(),
+            data_segments: Vec::new(),
+            data_end: DATA_START,
+            functions: Vec::new(),
+            instruction_locations: Vec::new(),
+            imports: Vec::new(),
+            called: HashSet::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Returns the errors recorded so far, in the order they were found, and clears them.
+    pub(crate) fn take_errors(&mut self) -> Vec<CodegenError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Makes a module-level constant available to the function bodies lowered afterwards.
+    ///
+    /// Scalar constants are inlined at every use. Struct and array constants are encoded
+    /// according to the data layout and placed in a `.rodata.NAME` data segment.
+    pub(crate) fn define_constant(
+        &mut self,
+        constant_definition: &ConstantDefinition,
+        module_path: &str,
+    ) {
+        if let Err(error) = self.try_define_constant(constant_definition, module_path) {
+            self.errors.push(error);
+        }
+    }
+
+    fn try_define_constant(
+        &mut self,
+        constant_definition: &ConstantDefinition,
+        module_path: &str,
+    ) -> Result<(), CodegenError> {
+        let name = modules::qualify(module_path, &constant_definition.name());
+        let type_info = self
+            .ctx
+            .get_node_typeinfo(constant_definition.id)
+            .expect("Constant definition must have a type info");
+        self.layout
+            .check_type(&type_info, constant_definition.location)?;
+        let constant = if self.layout.is_aggregate(&type_info) {
+            let (size, align) = self.layout.size_and_align(&type_info);
+            let mut bytes = vec![0; size as usize];
+            self.layout.encode_constant(
+                &mut bytes,
+                &Expression::Literal(constant_definition.value.clone()),
+                &type_info,
+            )?;
+            let address = self.data_end.next_multiple_of(align);
+            self.data_end = address + size;
+            self.data_segments.push(DataSegment {
+                name: name.clone(),
+                address,
+                bytes,
+            });
+            ModuleConstant::Aggregate(address)
+        } else {
+            ModuleConstant::Scalar(self.literal_instruction(&constant_definition.value)?)
+        };
+        self.constants.insert(name, constant);
+        Ok(())
+    }
+
+    /// Records the calling convention of a function or method without lowering its body.
+    ///
+    /// A function with a parameter or return type that has no WebAssembly representation
+    /// yet is reported, and its body is skipped by [`Self::emit_function`].
+    pub(crate) fn declare_function(
+        &mut self,
+        function_definition: &FunctionDefinition,
+        module_path: &str,
+        owner: Option<&str>,
+    ) {
+        match FunctionAbi::new(function_definition, owner, &self.layout) {
+            Ok(abi) => {
+                let symbol = FunctionAbi::symbol(module_path, function_definition, owner);
+                self.function_abis.insert(symbol, abi);
+            }
+            Err(error) => self.errors.push(error),
+        }
+    }
+
+    /// Returns the instances of generic functions to declare and lower.
+    pub(crate) fn instances(&self) -> Vec<Instance> {
+        self.monomorphization.instances().to_vec()
+    }
+
+    /// Records the calling convention of an instance of a generic function.
+    ///
+    /// An instance with a parameter or return type that has no WebAssembly representation
+    /// yet is reported, and its body is skipped by [`Self::emit_instance`].
+    pub(crate) fn declare_instance(&mut self, instance: &Instance) {
+        match FunctionAbi::instance(instance, &self.layout) {
+            Ok(abi) => {
+                self.function_abis.insert(instance.symbol.clone(), abi);
+            }
+            Err(error) => self.errors.push(error),
+        }
+    }
+
+    /// Records the calling convention and import name of an external function.
+    ///
+    /// An external function with a parameter or return type that has no WebAssembly
+    /// representation yet is reported.
+    pub(crate) fn declare_external_function(
+        &mut self,
+        external_function_definition: &ExternalFunctionDefinition,
+        module_path: &str,
+        import: ImportName,
+    ) {
+        match FunctionAbi::external(external_function_definition, &self.layout) {
+            Ok(abi) => {
+                let symbol = modules::qualify(module_path, &external_function_definition.name());
+                self.function_abis.insert(symbol.clone(), abi);
+                self.imports.push((symbol, import));
+            }
+            Err(error) => self.errors.push(error),
+        }
+    }
+
+    /// Lowers the body of a declared function or method.
+    ///
+    /// Statements that cannot be lowered are reported and skipped.
+    pub(crate) fn emit_function(
+        &mut self,
+        function_definition: &Rc<FunctionDefinition>,
+        module_path: &str,
+        owner: Option<&str>,
+    ) {
+        let symbol = FunctionAbi::symbol(module_path, function_definition, owner);
+        let export = self
+            .exports
+            .is_exported(function_definition, module_path, owner);
+        self.emit_body(
+            &symbol,
+            module_path,
+            function_definition,
+            &Substitutions::default(),
+            export,
+        );
+    }
+
+    /// Lowers the body of a declared instance of a generic function, with every type
+    /// parameter replaced by its type argument. Instances are never exported.
+    pub(crate) fn emit_instance(&mut self, instance: &Instance) {
+        self.emit_body(
+            &instance.symbol,
+            &instance.module_path,
+            &instance.function,
+            &instance.substitutions,
+            false,
+        );
+    }
+
+    fn emit_body(
+        &mut self,
+        symbol: &str,
+        module_path: &str,
+        function_definition: &FunctionDefinition,
+        substitutions: &Substitutions,
+        export: bool,
+    ) {
+        let Some(abi) = self.function_abis.get(symbol) else {
+            return;
+        };
+        let mut function = FunctionEmitter::new(
+            self,
+            symbol,
+            module_path,
+            abi,
+            substitutions,
+            export,
+            function_definition.location,
+        );
+        if let Err(error) =
+            function.lower_statement(&Statement::Block(function_definition.body.clone()))
+        {
+            function.report(error);
+        }
+        if !function.terminated {
+            if function_definition.is_void() {
+                function.push_epilogue();
+            } else {
+                function.push("unreachable");
+            }
+        }
+        let called = std::mem::take(&mut function.called);
+        let (text, locations, errors) = function.finish();
+        self.functions.push(text);
+        self.instruction_locations.push(locations);
+        self.called.extend(called);
+        self.errors.extend(errors);
+    }
+
+    /// Returns the source location of every instruction, per function in the order of
+    /// the code section, for [`crate::debug_info`]. The implicit `end` closing each body
+    /// is not included.
+    pub(crate) fn instruction_locations(&self) -> &[Vec<Location>] {
+        &self.instruction_locations
+    }
+
+    /// Returns the text of the module.
+    pub(crate) fn finish(&self) -> String {
+        let stack_pointer = self.data_end.next_multiple_of(STACK_ALIGN) + STACK_SIZE;
+        let pages = stack_pointer.div_ceil(PAGE_SIZE);
+        let mut module = String::from("(module\n");
+        for (symbol, import) in &self.imports {
+            if !self.called.contains(symbol) {
+                continue;
+            }
+            let _ = writeln!(
+                module,
+                "  (import \"{}\" \"{}\" (func {}{}))",
+                escape(import.module.as_bytes()),
+                escape(import.field.as_bytes()),
+                identifier(symbol),
+                self.signature(&self.function_abis[symbol])
+            );
+        }
+        let _ = writeln!(
+            module,
+            "  (global ${STACK_POINTER} (mut i32) (i32.const {stack_pointer}))"
+        );
+        let _ = writeln!(module, "  (memory (export \"memory\") {pages})");
+        for function in &self.functions {
+            module.push_str(function);
+        }
+        for segment in &self.data_segments {
+            let _ = writeln!(
+                module,
+                "  (data $.rodata.{} (i32.const {}) \"{}\")",
+                segment.name,
+                segment.address,
+                escape(&segment.bytes)
+            );
+        }
+        let enums = self.layout.enum_discriminants_section();
+        if !enums.is_empty() {
+            let _ = writeln!(
+                module,
+                "  (@custom \"{ENUMS_SECTION_NAME}\" \"{}\")",
+                escape(enums.as_bytes())
+            );
+        }
+        let assertions = self.layout.assertions_section();
+        if !assertions.is_empty() {
+            let _ = writeln!(
+                module,
+                "  (@custom \"{ASSERTS_SECTION_NAME}\" \"{}\")",
+                escape(assertions.as_bytes())
+            );
+        }
+        module.push_str(")\n");
+        module
+    }
+
+    /// Returns the unnamed parameter and result types of a function with this ABI.
+    fn signature(&self, abi: &FunctionAbi) -> String {
+        let mut signature = String::new();
+        for _ in 0..abi.first_parameter_index() {
+            signature.push_str(" (param i32)");
+        }
+        for parameter_type in &abi.parameter_types {
+            let _ = write!(signature, " (param {})", value_type(parameter_type));
+        }
+        if !abi.returns_nothing() {
+            let _ = write!(
+                signature,
+                " (result {})",
+                value_type(&self.scalar_type(&abi.return_type))
+            );
+        }
+        signature
+    }
+
+    /// Returns the instruction that pushes the value of a scalar literal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for string literals and unit values, which have no integer
+    /// representation.
+    fn literal_instruction(&self, literal: &Literal) -> Result<String, CodegenError> {
+        match literal {
+            Literal::Array(_array_literal) => {
+                unreachable!("Array values are lowered through `lower_address`")
+            }
+            Literal::Bool(bool_literal) => {
+                Ok(format!("i32.const {}", u8::from(bool_literal.value)))
+            }
+            Literal::Number(number_literal) => {
+                let type_info = self
+                    .ctx
+                    .get_node_typeinfo(number_literal.id)
+                    .expect("Number literal must have a type info");
+                Ok(self.constant_instruction(&type_info, number_literal_bits(number_literal)))
+            }
+            Literal::String(string_literal) => Err(CodegenError::UnsupportedExpression {
+                expression: "string literal",
+                location: string_literal.location,
+            }),
+            Literal::Unit(unit_literal) => Err(CodegenError::UnsupportedExpression {
+                expression: "unit value",
+                location: unit_literal.location,
+            }),
+        }
+    }
+
+    /// Returns the instruction that pushes `bits` truncated to the width of `type_info`
+    /// and normalized as described in the module documentation.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn constant_instruction(&self, type_info: &TypeInfo, bits: u64) -> String {
+        let value = match self.scalar_type(type_info).kind {
+            TypeInfoKind::Number(NumberType::I64 | NumberType::U64) => {
+                return format!("i64.const {}", bits as i64);
+            }
+            TypeInfoKind::Bool => i32::from(bits != 0),
+            TypeInfoKind::Number(NumberType::I8) => i32::from(bits as i8),
+            TypeInfoKind::Number(NumberType::U8) => i32::from(bits as u8),
+            TypeInfoKind::Number(NumberType::I16) => i32::from(bits as i16),
+            TypeInfoKind::Number(NumberType::U16) => i32::from(bits as u16),
+            _ => bits as i32,
+        };
+        format!("i32.const {value}")
+    }
+
+    /// Returns the type that represents values of `type_info` in locals and memory: the
+    /// discriminant type for enums, `type_info` itself otherwise.
+    fn scalar_type(&self, type_info: &TypeInfo) -> TypeInfo {
+        match self.layout.enum_layout(type_info) {
+            Some(enum_layout) => enum_layout.discriminant_type(),
+            None => type_info.clone(),
+        }
+    }
+}
+
+/// Lowers the body of a single function.
+struct FunctionEmitter<'m, 'a> {
+    module: &'m Emitter<'a>,
+
+    /// Symbol of the function, which identifies it as the caller of generic functions.
+    symbol: String,
+
+    /// Path of the module the function is defined in, which determines the constants its
+    /// names refer to.
+    module_path: String,
+
+    /// Type arguments of the instance being lowered; empty for non-generic functions.
+    substitutions: Substitutions,
+
+    /// `(func ...)` header with the name, export, parameters and result.
+    header: String,
+
+    /// Declared locals other than parameters, with their value types.
+    locals: Vec<(String, &'static str)>,
+
+    /// Names of all parameters and locals, used to keep new locals unique.
+    local_names: HashSet<String>,
+
+    /// Storage of every variable in scope, keyed by source name, with one map per
+    /// enclosing block, innermost last.
+    ///
+    /// A definition shadowing an outer one gets a new local or frame slot, and the outer
+    /// variable is visible again once the block ends.
+    variables: Vec<HashMap<String, Variable>>,
+
+    lines: Vec<Line>,
+
+    /// Nesting depth of the next instruction, for indentation.
+    depth: usize,
+
+    /// Bytes of the frame used so far.
+    frame_size: u32,
+
+    /// Whether anything was placed in the frame, even a value of size 0.
+    uses_frame: bool,
+
+    /// Exit labels of the loops enclosing the statement being lowered, innermost on top.
+    loop_exits: Vec<String>,
+
+    /// Counter for unique labels.
+    labels: u32,
+
+    /// Whether the last statement was a `return` or `break`, making the rest of the
+    /// enclosing block unreachable.
+    terminated: bool,
+
+    /// Location of the function definition, to which the prologue and the parameter copies
+    /// belong.
+    function_location: Location,
+
+    /// Location of the statement being lowered, recorded with every instruction.
+    location: Location,
+
+    /// Whether the function writes its struct or array result through [`RESULT`].
+    has_result_pointer: bool,
+
+    /// Symbols of the functions this function calls.
+    called: HashSet<String>,
+
+    errors: Vec<CodegenError>,
+}
+
+impl<'m, 'a> FunctionEmitter<'m, 'a> {
+    /// Starts lowering a function: binds `self` and the named parameters, and copies
+    /// struct and array parameters into the frame, so parameters can be read and
+    /// reassigned like any other local without affecting the caller.
+    fn new(
+        module: &'m Emitter<'a>,
+        symbol: &str,
+        module_path: &str,
+        abi: &FunctionAbi,
+        substitutions: &Substitutions,
+        export: bool,
+        location: Location,
+    ) -> Self {
+        let mut function = Self {
+            module,
+            symbol: symbol.to_string(),
+            module_path: module_path.to_string(),
+            substitutions: substitutions.clone(),
+            header: format!("  (func {}", identifier(symbol)),
+            locals: Vec::new(),
+            local_names: HashSet::new(),
+            variables: vec![HashMap::new()],
+            lines: Vec::new(),
+            depth: 2,
+            frame_size: 0,
+            uses_frame: false,
+            loop_exits: Vec::new(),
+            labels: 0,
+            terminated: false,
+            function_location: location,
+            location,
+            has_result_pointer: abi.has_result_pointer,
+            called: HashSet::new(),
+            errors: Vec::new(),
+        };
+        if export {
+            let _ = write!(function.header, " (export \"{symbol}\")");
+        }
+        if abi.has_result_pointer {
+            let _ = write!(function.header, " (param ${RESULT} i32)");
+            function.local_names.insert(RESULT.to_string());
+        }
+        if abi.has_self {
+            let _ = write!(function.header, " (param ${SELF} i32)");
+            function.local_names.insert(SELF.to_string());
+            function.define_variable(
+                SELF.to_string(),
+                Variable::Memory(Address {
+                    base: Some(SELF.to_string()),
+                    offset: 0,
+                }),
+            );
+        }
+        for (name, parameter_type) in abi.parameter_names.iter().zip(&abi.parameter_types) {
+            let value_type = value_type(parameter_type);
+            let Some(name) = name else {
+                let _ = write!(function.header, " (param {value_type})");
+                continue;
+            };
+            let id = function.unique_name(name);
+            let _ = write!(function.header, " (param ${id} {value_type})");
+            let variable = if module.layout.is_aggregate(parameter_type) {
+                let copy = function.allocate(parameter_type);
+                let argument = Address {
+                    base: Some(id),
+                    offset: 0,
+                };
+                function.copy_value(&copy, &argument, parameter_type);
+                Variable::Memory(copy)
+            } else {
+                Variable::Local(id)
+            };
+            function.define_variable(name.clone(), variable);
+        }
+        if !abi.returns_nothing() {
+            let _ = write!(
+                function.header,
+                " (result {})",
+                value_type(&module.scalar_type(&abi.return_type))
+            );
+        }
+        function
+    }
+
+    /// Returns the text of the function, the source location of each of its instructions
+    /// and the errors found while lowering it.
+    fn finish(self) -> (String, Vec<Location>, Vec<CodegenError>) {
+        let frame_size = self.frame_size.next_multiple_of(STACK_ALIGN);
+        let mut text = self.header;
+        text.push('\n');
+        if self.uses_frame {
+            let _ = writeln!(text, "    (local ${FRAME} i32)");
+        }
+        for (name, value_type) in &self.locals {
+            let _ = writeln!(text, "    (local ${name} {value_type})");
+        }
+        let mut locations = Vec::new();
+        if self.uses_frame {
+            for instruction in [
+                format!("global.get ${STACK_POINTER}"),
+                format!("i32.const {frame_size}"),
+                "i32.sub".to_string(),
+                format!("local.tee ${FRAME}"),
+                format!("global.set ${STACK_POINTER}"),
+            ] {
+                let _ = writeln!(text, "    {instruction}");
+                locations.push(self.function_location);
+            }
+        }
+        for line in &self.lines {
+            match line {
+                Line::Instruction(depth, instruction, location) => {
+                    let _ = writeln!(text, "{:width$}{instruction}", "", width = depth * 2);
+                    locations.push(*location);
+                }
+                Line::Epilogue(depth, location) if self.uses_frame => {
+                    for instruction in [
+                        format!("local.get ${FRAME}"),
+                        format!("i32.const {frame_size}"),
+                        "i32.add".to_string(),
+                        format!("global.set ${STACK_POINTER}"),
+                    ] {
+                        let _ = writeln!(text, "{:width$}{instruction}", "", width = depth * 2);
+                        locations.push(*location);
+                    }
+                }
+                Line::Epilogue(..) => {}
+            }
+        }
+        text.push_str("  )\n");
+        (text, locations, self.errors)
+    }
+
+    /// Records an error for a construct that could not be lowered.
+    fn report(&mut self, error: CodegenError) {
+        self.errors.push(error);
+    }
+
+    /// Appends an instruction, indenting the bodies of structured instructions.
+    fn push(&mut self, instruction: impl Into<String>) {
+        let instruction = instruction.into();
+        let keyword = instruction.split_whitespace().next().unwrap_or_default();
+        let closes = matches!(keyword, "end" | "else");
+        let opens = matches!(
+            keyword,
+            "block" | "loop" | "if" | "else" | "forall" | "exists" | "assume" | "unique"
+        );
+        if closes {
+            self.depth = self.depth.saturating_sub(1);
+        }
+        self.lines
+            .push(Line::Instruction(self.depth, instruction, self.location));
+        if opens {
+            self.depth += 1;
+        }
+    }
+
+    /// Releases the frame; emitted before every `return` and at the end of void functions.
+    fn push_epilogue(&mut self) {
+        self.lines.push(Line::Epilogue(self.depth, self.location));
+    }
+
+    /// Returns `name`, or `name.N` if a parameter or local of that name already exists.
+    fn unique_name(&mut self, name: &str) -> String {
+        let mut id = name.to_string();
+        let mut suffix = 0;
+        while !self.local_names.insert(id.clone()) {
+            suffix += 1;
+            id = format!("{name}.{suffix}");
+        }
+        id
+    }
+
+    /// Declares a new local of the given value type and returns its name.
+    fn add_local(&mut self, name: &str, value_type: &'static str) -> String {
+        let id = self.unique_name(name);
+        self.locals.push((id.clone(), value_type));
+        id
+    }
+
+    /// Binds `name` to `variable` in the innermost scope.
+    fn define_variable(&mut self, name: String, variable: Variable) {
+        self.variables
+            .last_mut()
+            .expect("The function scope is never popped")
+            .insert(name, variable);
+    }
+
+    /// Returns the variable `name` refers to, looking from the innermost scope outwards.
+    fn variable(&self, name: &str) -> Option<&Variable> {
+        self.variables
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+    }
+
+    /// Returns a new unique label starting with `kind`.
+    fn fresh_label(&mut self, kind: &str) -> String {
+        let label = format!("{kind}{}", self.labels);
+        self.labels += 1;
+        label
+    }
+
+    /// Reserves a frame slot for a struct or array value, aligned for its layout.
+    fn allocate(&mut self, type_info: &TypeInfo) -> Address {
+        let (size, align) = self.module.layout.size_and_align(type_info);
+        let offset = self.frame_size.next_multiple_of(align);
+        self.frame_size = offset + size;
+        self.uses_frame = true;
+        Address {
+            base: Some(FRAME.to_string()),
+            offset,
+        }
+    }
+
+    /// Returns the type the type checker inferred for a node, with the type parameters of
+    /// the instance being lowered replaced by their type arguments.
+    fn node_type(&self, node_id: u32) -> Option<TypeInfo> {
+        self.module
+            .ctx
+            .get_node_typeinfo(node_id)
+            .map(|type_info| type_info.substitute(&self.substitutions))
+    }
+
+    /// Returns the type the type checker inferred for `expression`, see [`Self::node_type`].
+    fn expression_type(&self, expression: &Expression) -> TypeInfo {
+        self.node_type(expression.id())
+            .unwrap_or_else(|| panic!("Expression must have a type info: {expression:?}"))
+    }
+
+    /// Lowers a statement.
+    ///
+    /// Statements that follow a `return` or `break` in the same block are unreachable and
+    /// are not lowered.
+    ///
+    /// The instructions of the statement are recorded with its location; the location of
+    /// the enclosing statement is restored afterwards, so that e.g. the `end` of an `if`
+    /// belongs to the `if` and not to the last statement of its arm.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the statement uses a construct that cannot be lowered yet. A
+    /// block reports the errors of its statements and continues with the next statement,
+    /// so lowering a block itself does not fail.
+    fn lower_statement(&mut self, statement: &Statement) -> Result<(), CodegenError> {
+        let enclosing = std::mem::replace(&mut self.location, statement.location());
+        let result = self.lower_statement_kind(statement);
+        self.location = enclosing;
+        result
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn lower_statement_kind(&mut self, statement: &Statement) -> Result<(), CodegenError> {
+        match statement {
+            Statement::Block(block_type) => {
+                let instruction = match block_type {
+                    BlockType::Block(_) => None,
+                    BlockType::Forall(_) => Some("forall"),
+                    BlockType::Exists(_) => Some("exists"),
+                    BlockType::Assume(_) => Some("assume"),
+                    BlockType::Unique(_) => Some("unique"),
+                };
+                if let Some(instruction) = instruction {
+                    self.push(instruction);
+                }
+                self.variables.push(HashMap::new());
+                for statement in block_type.statements() {
+                    if self.terminated {
+                        break;
+                    }
+                    if let Err(error) = self.lower_statement(&statement) {
+                        self.report(error);
+                    }
+                }
+                self.variables.pop();
+                if instruction.is_some() {
+                    // The code after `end` is reachable again as far as validation is
+                    // concerned, even if the block returned.
+                    self.push("end");
+                    self.terminated = false;
+                }
+            }
+            Statement::Expression(expression) => {
+                if let Expression::FunctionCall(function_call_expression) = expression {
+                    if let CallResult::Value = self.lower_function_call(function_call_expression)? {
+                        self.push("drop");
+                    }
+                } else if self
+                    .module
+                    .layout
+                    .is_aggregate(&self.expression_type(expression))
+                {
+                    self.lower_address(expression)?;
+                } else {
+                    self.lower_expression(expression)?;
+                    self.push("drop");
+                }
+            }
+            Statement::Assign(assign_statement) => {
+                let target = assign_statement.left.borrow();
+                let value = assign_statement.right.borrow();
+                if let Some(local) = self.scalar_local(&target) {
+                    self.lower_expression(&value)?;
+                    self.push(format!("local.set ${local}"));
+                } else {
+                    let target_type = self.expression_type(&target);
+                    let address = self.lower_address(&target)?;
+                    self.store_expression(&address, &target_type, &value)?;
+                }
+            }
+            Statement::Return(return_statement) => {
+                let expression = return_statement.expression.borrow();
+                if let Expression::Literal(Literal::Unit(_)) = &*expression {
+                } else if self.has_result_pointer {
+                    let result_type = self.expression_type(&expression);
+                    let result = Address {
+                        base: Some(RESULT.to_string()),
+                        offset: 0,
+                    };
+                    self.store_expression(&result, &result_type, &expression)?;
+                } else {
+                    self.lower_expression(&expression)?;
+                }
+                self.push_epilogue();
+                self.push("return");
+                self.terminated = true;
+            }
+            Statement::Loop(loop_statement) => {
+                let label = self.fresh_label("loop");
+                let exit = format!("{label}.end");
+                self.push(format!("block ${exit}"));
+                self.push(format!("loop ${label}"));
+                if let Some(condition) = &*loop_statement.condition.borrow() {
+                    self.lower_condition(condition)?;
+                    self.push("i32.eqz");
+                    self.push(format!("br_if ${exit}"));
+                }
+                self.loop_exits.push(exit);
+                let body = self.lower_statement(&Statement::Block(loop_statement.body.clone()));
+                self.loop_exits.pop();
+                body?;
+                if !self.terminated {
+                    self.push(format!("br ${label}"));
+                }
+                self.push("end");
+                self.push("end");
+                self.terminated = false;
+            }
+            Statement::Break(break_statement) => {
+                let exit =
+                    self.loop_exits
+                        .last()
+                        .cloned()
+                        .ok_or(CodegenError::BreakOutsideLoop {
+                            location: break_statement.location,
+                        })?;
+                self.push(format!("br ${exit}"));
+                self.terminated = true;
+            }
+            Statement::If(if_statement) => {
+                self.lower_condition(&if_statement.condition.borrow())?;
+                self.push("if");
+                self.lower_statement(&Statement::Block(if_statement.if_arm.clone()))?;
+                if let Some(else_arm) = &if_statement.else_arm {
+                    self.push("else");
+                    self.terminated = false;
+                    self.lower_statement(&Statement::Block(else_arm.clone()))?;
+                }
+                self.push("end");
+                self.terminated = false;
+            }
+            Statement::VariableDefinition(variable_definition_statement) => {
+                let type_info = self
+                    .node_type(variable_definition_statement.id)
+                    .expect("Variable definition must have a type info");
+                self.module
+                    .layout
+                    .check_type(&type_info, variable_definition_statement.location)?;
+                let name = variable_definition_statement.name();
+                let variable = if self.module.layout.is_aggregate(&type_info) {
+                    let address = self.allocate(&type_info);
+                    if let Some(value) = &variable_definition_statement.value {
+                        self.store_expression(&address, &type_info, &value.borrow())?;
+                    }
+                    Variable::Memory(address)
+                } else {
+                    if let Some(value) = &variable_definition_statement.value {
+                        self.lower_expression(&value.borrow())?;
+                    }
+                    let local = self.add_local(&name, value_type(&type_info));
+                    if variable_definition_statement.value.is_some() {
+                        self.push(format!("local.set ${local}"));
+                    }
+                    Variable::Local(local)
+                };
+                self.define_variable(name, variable);
+            }
+            Statement::TypeDefinition(type_definition_statement) => {
+                return Err(CodegenError::UnsupportedStatement {
+                    statement: "type definition",
+                    location: type_definition_statement.location,
+                });
+            }
+            Statement::Assert(assert_statement) => {
+                self.lower_condition(&assert_statement.expression.borrow())?;
+                self.push("i32.eqz");
+                self.push("if");
+                self.push(format!("i32.const {FAILED_ASSERTION_ADDRESS}"));
+                self.push(format!(
+                    "i32.const {}",
+                    self.module.layout.assertion_id(assert_statement.id)
+                ));
+                self.push("i32.store");
+                self.push("unreachable");
+                self.push("end");
+            }
+            Statement::ConstantDefinition(constant_definition) => {
+                let type_info = self
+                    .node_type(constant_definition.id)
+                    .expect("Constant definition must have a type info");
+                if !matches!(type_info.kind, TypeInfoKind::Bool | TypeInfoKind::Number(_)) {
+                    return Err(CodegenError::UnsupportedLocalConstant {
+                        type_info,
+                        location: constant_definition.location,
+                    });
+                }
+                let value = self
+                    .module
+                    .literal_instruction(&constant_definition.value)?;
+                self.push(value);
+                let name = constant_definition.name();
+                let local = self.add_local(&name, value_type(&type_info));
+                self.push(format!("local.set ${local}"));
+                self.define_variable(name, Variable::Local(local));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the local holding the variable `expression` names, if it is a scalar local
+    /// variable or parameter.
+    fn scalar_local(&self, expression: &Expression) -> Option<String> {
+        match expression {
+            Expression::Identifier(identifier) => match self.variable(&identifier.name) {
+                Some(Variable::Local(local)) => Some(local.clone()),
+                _ => None,
+            },
+            Expression::Parenthesized(parenthesized_expression) => {
+                self.scalar_local(&parenthesized_expression.expression.borrow())
+            }
+            _ => None,
+        }
+    }
+
+    /// Lowers a scalar expression, leaving its value on the operand stack.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression, or one of its operands, cannot be lowered yet.
+    fn lower_expression(&mut self, expression: &Expression) -> Result<(), CodegenError> {
+        match expression {
+            Expression::ArrayIndexAccess(_) | Expression::MemberAccess(_) => {
+                let element_type = self.expression_type(expression);
+                let address = self.lower_address(expression)?;
+                self.push_base(&address);
+                self.push(memory_instruction(
+                    load_instruction(&self.module.scalar_type(&element_type)),
+                    address.offset,
+                ));
+            }
+            Expression::Binary(binary_expression) => {
+                self.lower_binary_expression(binary_expression)?;
+            }
+            Expression::TypeMemberAccess(type_member_access_expression) => {
+                let enum_type = self.expression_type(expression);
+                let discriminant = self
+                    .module
+                    .layout
+                    .enum_layout(&enum_type)
+                    .unwrap_or_else(|| panic!("Type member access on non-enum type `{enum_type}`"))
+                    .discriminant(&type_member_access_expression.name.name);
+                self.push(format!("i32.const {discriminant}"));
+            }
+            Expression::FunctionCall(function_call_expression) => {
+                let CallResult::Value = self.lower_function_call(function_call_expression)? else {
+                    panic!("A function call used as a value must return a value");
+                };
+            }
+            Expression::Struct(_struct_expression) => {
+                unreachable!("Struct values are lowered through `lower_address`")
+            }
+            Expression::PrefixUnary(prefix_unary_expression) => {
+                self.lower_prefix_unary_expression(prefix_unary_expression)?;
+            }
+            Expression::Parenthesized(parenthesized_expression) => {
+                self.lower_expression(&parenthesized_expression.expression.borrow())?;
+            }
+            Expression::Literal(literal) => {
+                let instruction = self.module.literal_instruction(literal)?;
+                self.push(instruction);
+            }
+            Expression::Identifier(identifier) => match self.variable(&identifier.name) {
+                Some(Variable::Local(local)) => {
+                    let instruction = format!("local.get ${local}");
+                    self.push(instruction);
+                }
+                Some(Variable::Memory(_)) => {
+                    unreachable!("Aggregate variables are lowered through `lower_address`")
+                }
+                None => match self.module_constant(&identifier.name, identifier.location)? {
+                    ModuleConstant::Scalar(instruction) => self.push(instruction),
+                    ModuleConstant::Aggregate(_) => {
+                        unreachable!("Aggregate constants are lowered through `lower_address`")
+                    }
+                },
+            },
+            Expression::Type(type_expression) => {
+                return Err(CodegenError::UnsupportedExpression {
+                    expression: "type",
+                    location: type_expression.location(),
+                });
+            }
+            Expression::Uzumaki(uzumaki_expression) => {
+                let type_info = self.expression_type(expression);
+                self.module
+                    .layout
+                    .check_uzumaki_type(&type_info, uzumaki_expression.location)?;
+                self.push_uzumaki(&type_info);
+            }
+        }
+        Ok(())
+    }
+
+    /// Lowers a call to a function or method defined in the module.
+    ///
+    /// Arguments are evaluated left to right as written. When named arguments are not in
+    /// parameter order, their values are kept in temporary locals and pushed in parameter
+    /// order afterwards. Struct and array arguments are passed by address and copied by the
+    /// callee; a struct or array result is written to a fresh frame slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the callee is not a function or method name, if the callee has
+    /// no compiled definition, or if an argument cannot be lowered.
+    fn lower_function_call(
+        &mut self,
+        function_call_expression: &FunctionCallExpression,
+    ) -> Result<CallResult, CodegenError> {
+        // The type checker gives the callee of a built-in conversion no type
+        if let Expression::Identifier(callee) = &function_call_expression.function
+            && self.node_type(callee.id).is_none()
+            && let Some(conversion) = Conversion::from_name(&callee.name)
+        {
+            self.lower_conversion(conversion, function_call_expression)?;
+            return Ok(CallResult::Value);
+        }
+        let (name, receiver) = match &function_call_expression.function {
+            callee @ (Expression::Identifier(_)
+            | Expression::MemberAccess(_)
+            | Expression::TypeMemberAccess(_)) => {
+                let TypeInfoKind::Function(name) = self.expression_type(callee).kind else {
+                    panic!("Callee must resolve to a function");
+                };
+                let receiver = match callee {
+                    Expression::MemberAccess(member_access_expression) => {
+                        Some(&member_access_expression.expression)
+                    }
+                    _ => None,
+                };
+                (name, receiver)
+            }
+            callee => {
+                return Err(CodegenError::UnsupportedExpression {
+                    expression: "indirect call",
+                    location: callee.location(),
+                });
+            }
+        };
+        let name = self.module.monomorphization.call_target(
+            &self.symbol,
+            function_call_expression.id,
+            name,
+        );
+        let Some(abi) = self.module.function_abis.get(&name) else {
+            return Err(CodegenError::UnavailableSymbol {
+                name,
+                location: function_call_expression.location,
+            });
+        };
+
+        let result = if abi.has_result_pointer {
+            let result_type = self
+                .node_type(function_call_expression.id)
+                .expect("Function call must have a type info");
+            let result = self.allocate(&result_type);
+            self.push_address(&result);
+            Some(result)
+        } else {
+            None
+        };
+        if let (true, Some(receiver)) = (abi.has_self, receiver) {
+            let receiver = self.lower_address(&receiver.borrow())?;
+            self.push_address(&receiver);
+        }
+        let parameter_indices: Vec<usize> = function_call_expression
+            .argument_parameter_indices(&abi.parameter_names)
+            .into_iter()
+            .map(|index| index.expect("Every argument must bind to a parameter"))
+            .collect();
+        let arguments: Vec<_> = function_call_expression
+            .arguments
+            .iter()
+            .flatten()
+            .collect();
+        if parameter_indices
+            .iter()
+            .copied()
+            .eq(0..parameter_indices.len())
+        {
+            for ((_, argument), parameter_type) in arguments.iter().zip(&abi.parameter_types) {
+                self.lower_argument(&argument.borrow(), parameter_type)?;
+            }
+        } else {
+            let mut temporaries = vec![String::new(); abi.parameter_types.len()];
+            for ((_, argument), parameter_index) in arguments.iter().zip(parameter_indices) {
+                let parameter_type = &abi.parameter_types[parameter_index];
+                self.lower_argument(&argument.borrow(), parameter_type)?;
+                let temporary = self.add_local(".argument", value_type(parameter_type));
+                self.push(format!("local.set ${temporary}"));
+                temporaries[parameter_index] = temporary;
+            }
+            for temporary in temporaries {
+                self.push(format!("local.get ${temporary}"));
+            }
+        }
+        self.push(format!("call {}", identifier(&name)));
+        self.called.insert(name);
+        Ok(match result {
+            Some(result) => CallResult::Memory(result),
+            None if abi.return_type.kind == TypeInfoKind::Unit => CallResult::Nothing,
+            None => CallResult::Value,
+        })
+    }
+
+    /// Lowers a built-in conversion between integer types.
+    ///
+    /// Converting to `i64` or `u64` from a narrower type extends the value by the
+    /// signedness of the source type, converting from them keeps the low 32 bits, and the
+    /// result is normalized to the target type, which wraps it. `checked_cast` first
+    /// compares the value against the bounds of the target type the source type can
+    /// exceed, and executes `unreachable` if it is out of range.
+    fn lower_conversion(
+        &mut self,
+        conversion: Conversion,
+        function_call_expression: &FunctionCallExpression,
+    ) -> Result<(), CodegenError> {
+        let (_, argument) = function_call_expression
+            .arguments
+            .iter()
+            .flatten()
+            .next()
+            .expect("A conversion takes one argument");
+        let argument = argument.borrow();
+        let source_type = self.expression_type(&argument);
+        let target_type = self
+            .node_type(function_call_expression.id)
+            .expect("Conversion must have a type info");
+        let (TypeInfoKind::Number(source), TypeInfoKind::Number(target)) =
+            (&source_type.kind, &target_type.kind)
+        else {
+            panic!("Conversions are between integer types");
+        };
+        self.lower_expression(&argument)?;
+        if conversion == Conversion::CheckedCast && !source.fits_in(*target) {
+            let value_type = value_type(&source_type);
+            let sign = if source.is_signed() { "s" } else { "u" };
+            let value = self.add_local(".cast", value_type);
+            self.push(format!("local.set ${value}"));
+            let mut checks = 0;
+            if target.max_value() < source.max_value() {
+                self.push(format!("local.get ${value}"));
+                self.push(format!("{value_type}.const {}", target.max_value()));
+                self.push(format!("{value_type}.gt_{sign}"));
+                checks += 1;
+            }
+            if source.min_value() < target.min_value() {
+                self.push(format!("local.get ${value}"));
+                self.push(format!("{value_type}.const {}", target.min_value()));
+                self.push(format!("{value_type}.lt_{sign}"));
+                checks += 1;
+            }
+            if checks == 2 {
+                self.push("i32.or");
+            }
+            self.push("if");
+            self.push("unreachable");
+            self.push("end");
+            self.push(format!("local.get ${value}"));
+        }
+        match (value_type(&source_type), value_type(&target_type)) {
+            ("i32", "i64") if source.is_signed() => self.push("i64.extend_i32_s"),
+            ("i32", "i64") => self.push("i64.extend_i32_u"),
+            ("i64", "i32") => self.push("i32.wrap_i64"),
+            _ => {}
+        }
+        self.normalize(&target_type);
+        Ok(())
+    }
+
+    /// Pushes an argument for a parameter of `parameter_type`: the address of a struct or
+    /// array value, the value of anything else.
+    fn lower_argument(
+        &mut self,
+        argument: &Expression,
+        parameter_type: &TypeInfo,
+    ) -> Result<(), CodegenError> {
+        if self.module.layout.is_aggregate(parameter_type) {
+            let address = self.lower_address(argument)?;
+            self.push_address(&address);
+            Ok(())
+        } else {
+            self.lower_expression(argument)
+        }
+    }
+
+    /// Looks up a module-level constant that is not shadowed by a local variable: the one
+    /// of the innermost module around the function that defines `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no constant called `name` exists, because its definition could
+    /// not be lowered.
+    fn module_constant(
+        &self,
+        name: &str,
+        location: Location,
+    ) -> Result<ModuleConstant, CodegenError> {
+        modules::candidates(&self.module_path, name)
+            .iter()
+            .find_map(|candidate| self.module.constants.get(candidate))
+            .cloned()
+            .ok_or_else(|| CodegenError::UnavailableSymbol {
+                name: name.to_string(),
+                location,
+            })
+    }
+
+    /// Lowers an expression that denotes a struct or array value, or a field or element of
+    /// one, and returns its address.
+    ///
+    /// Struct and array literals and calls returning an aggregate are materialized in a
+    /// fresh frame slot. The code emitted here leaves the operand stack unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression has no address, if its type has no memory
+    /// layout, or if a subexpression cannot be lowered.
+    #[allow(clippy::too_many_lines)]
+    fn lower_address(&mut self, expression: &Expression) -> Result<Address, CodegenError> {
+        let address = match expression {
+            Expression::Identifier(identifier) => match self.variable(&identifier.name) {
+                Some(Variable::Memory(address)) => address.clone(),
+                Some(Variable::Local(_)) => {
+                    return Err(CodegenError::NotAPlace {
+                        location: identifier.location,
+                    });
+                }
+                None => match self.module_constant(&identifier.name, identifier.location)? {
+                    ModuleConstant::Aggregate(address) => Address {
+                        base: None,
+                        offset: address,
+                    },
+                    ModuleConstant::Scalar(_) => {
+                        return Err(CodegenError::AssignmentToConstant {
+                            name: identifier.name(),
+                            location: identifier.location,
+                        });
+                    }
+                },
+            },
+            Expression::Parenthesized(parenthesized_expression) => {
+                self.lower_address(&parenthesized_expression.expression.borrow())?
+            }
+            Expression::MemberAccess(member_access_expression) => {
+                let object = member_access_expression.expression.borrow();
+                let object_type = self.expression_type(&object);
+                let field_offset = self
+                    .module
+                    .layout
+                    .struct_layout(&object_type)
+                    .ok_or_else(|| CodegenError::UnsupportedType {
+                        type_info: object_type.clone(),
+                        location: object.location(),
+                    })?
+                    .field(&member_access_expression.name.name)
+                    .offset;
+                self.lower_address(&object)?.field(field_offset)
+            }
+            Expression::ArrayIndexAccess(array_index_access_expression) => {
+                let array = array_index_access_expression.array.borrow();
+                let array_type = self.expression_type(&array);
+                self.module
+                    .layout
+                    .check_type(&array_type, array.location())?;
+                let array_address = self.lower_address(&array)?;
+                self.element_address(
+                    &array_address,
+                    &array_type,
+                    &array_index_access_expression.index.borrow(),
+                )?
+            }
+            Expression::Literal(Literal::Array(array_literal)) => {
+                let array_type = self.expression_type(expression);
+                self.module
+                    .layout
+                    .check_type(&array_type, array_literal.location)?;
+                let (element_type, _) = DataLayout::array_element(&array_type)
+                    .expect("Array literal must have an array type");
+                let (element_size, _) = self.module.layout.size_and_align(element_type);
+                let value = self.allocate(&array_type);
+                for (index, element) in (0u32..).zip(array_literal.elements.iter().flatten()) {
+                    let element_address = value.field(index * element_size);
+                    self.store_expression(&element_address, element_type, &element.borrow())?;
+                }
+                value
+            }
+            Expression::Struct(struct_expression) => {
+                let struct_type = self.expression_type(expression);
+                let struct_layout =
+                    self.module
+                        .layout
+                        .struct_layout(&struct_type)
+                        .ok_or_else(|| CodegenError::UnsupportedType {
+                            type_info: struct_type.clone(),
+                            location: struct_expression.location,
+                        })?;
+                let value = self.allocate(&struct_type);
+                for (field_name, field_value) in struct_expression.fields.iter().flatten() {
+                    let field = struct_layout.field(&field_name.name);
+                    self.store_expression(
+                        &value.field(field.offset),
+                        &field.type_info,
+                        &field_value.borrow(),
+                    )?;
+                }
+                value
+            }
+            Expression::Uzumaki(uzumaki_expression) => {
+                let type_info = self.expression_type(expression);
+                self.module
+                    .layout
+                    .check_uzumaki_type(&type_info, uzumaki_expression.location)?;
+                let value = self.allocate(&type_info);
+                self.store_uzumaki(&value, &type_info);
+                value
+            }
+            Expression::FunctionCall(function_call_expression) => {
+                let CallResult::Memory(address) =
+                    self.lower_function_call(function_call_expression)?
+                else {
+                    panic!("A function call used as an aggregate value must return one");
+                };
+                address
+            }
+            _ => {
+                return Err(CodegenError::NotAPlace {
+                    location: expression.location(),
+                });
+            }
+        };
+        Ok(address)
+    }
+
+    /// Returns the address of element `index` of the array at `array`.
+    ///
+    /// The index is widened to 64 bits, sign-extending signed types so that a negative
+    /// index becomes a huge unsigned one, and compared unsigned against the array length;
+    /// an out-of-bounds index executes `unreachable`. The element address is kept in a new
+    /// local, and the static offset of the array carries over to the element.
+    fn element_address(
+        &mut self,
+        array: &Address,
+        array_type: &TypeInfo,
+        index: &Expression,
+    ) -> Result<Address, CodegenError> {
+        let (element_type, length) = DataLayout::array_element(array_type)
+            .unwrap_or_else(|| panic!("Indexed value of type `{array_type}` is not an array"));
+        let (element_size, _) = self.module.layout.size_and_align(element_type);
+        let index_type = self.expression_type(index);
+        self.lower_expression(index)?;
+        if value_type(&index_type) == "i32" {
+            if index_type.is_signed_integer() {
+                self.push("i64.extend_i32_s");
+            } else {
+                self.push("i64.extend_i32_u");
+            }
+        }
+        let wide_index = self.add_local(".index", "i64");
+        self.push(format!("local.tee ${wide_index}"));
+        self.push(format!("i64.const {length}"));
+        self.push("i64.ge_u");
+        self.push("if");
+        self.push("unreachable");
+        self.push("end");
+        self.push_base(array);
+        self.push(format!("local.get ${wide_index}"));
+        self.push("i32.wrap_i64");
+        self.push(format!("i32.const {element_size}"));
+        self.push("i32.mul");
+        self.push("i32.add");
+        let element = self.add_local(".element", "i32");
+        self.push(format!("local.set ${element}"));
+        Ok(Address {
+            base: Some(element),
+            offset: array.offset,
+        })
+    }
+
+    /// Stores the value of `expression` into the memory at `destination`.
+    ///
+    /// Scalars are stored directly; struct and array values are copied from the address
+    /// of `expression`, except for `@`, which is generated in place.
+    fn store_expression(
+        &mut self,
+        destination: &Address,
+        type_info: &TypeInfo,
+        expression: &Expression,
+    ) -> Result<(), CodegenError> {
+        if let Expression::Uzumaki(uzumaki_expression) = expression
+            && self.module.layout.is_aggregate(type_info)
+        {
+            self.module
+                .layout
+                .check_uzumaki_type(type_info, uzumaki_expression.location)?;
+            self.store_uzumaki(destination, type_info);
+        } else if self.module.layout.is_aggregate(type_info) {
+            let source = self.lower_address(expression)?;
+            self.copy_value(destination, &source, type_info);
+        } else {
+            self.push_base(destination);
+            self.lower_expression(expression)?;
+            self.push_store(type_info, destination.offset);
+        }
+        Ok(())
+    }
+
+    /// Copies a value of `type_info` from `source` to `destination`, scalar by scalar so
+    /// that padding bytes are skipped. Arrays are copied element by element in a loop.
+    fn copy_value(&mut self, destination: &Address, source: &Address, type_info: &TypeInfo) {
+        if let Some(struct_layout) = self.module.layout.struct_layout(type_info) {
+            for field in &struct_layout.fields {
+                self.copy_value(
+                    &destination.field(field.offset),
+                    &source.field(field.offset),
+                    &field.type_info,
+                );
+            }
+        } else if let Some((element_type, length)) = DataLayout::array_element(type_info) {
+            self.copy_array(destination, source, element_type, length);
+        } else {
+            self.push_base(destination);
+            self.push_base(source);
+            self.push(memory_instruction(
+                load_instruction(&self.module.scalar_type(type_info)),
+                source.offset,
+            ));
+            self.push_store(type_info, destination.offset);
+        }
+    }
+
+    /// Copies the `length` elements of an array with a counted loop; see
+    /// [`Self::for_each_element`].
+    fn copy_array(
+        &mut self,
+        destination: &Address,
+        source: &Address,
+        element_type: &TypeInfo,
+        length: u32,
+    ) {
+        self.for_each_element(
+            &[destination, source],
+            element_type,
+            length,
+            |emitter, elements| emitter.copy_value(&elements[0], &elements[1], element_type),
+        );
+    }
+
+    /// Stores non-deterministic values into every scalar of the `type_info` value at
+    /// `destination`, field by field and element by element. The type must have passed
+    /// [`DataLayout::check_uzumaki_type`].
+    fn store_uzumaki(&mut self, destination: &Address, type_info: &TypeInfo) {
+        if let Some(struct_layout) = self.module.layout.struct_layout(type_info) {
+            for field in &struct_layout.fields {
+                self.store_uzumaki(&destination.field(field.offset), &field.type_info);
+            }
+        } else if let Some((element_type, length)) = DataLayout::array_element(type_info) {
+            self.for_each_element(&[destination], element_type, length, |emitter, elements| {
+                emitter.store_uzumaki(&elements[0], element_type);
+            });
+        } else {
+            self.push_base(destination);
+            self.push_uzumaki(type_info);
+            self.push_store(type_info, destination.offset);
+        }
+    }
+
+    /// Pushes a non-deterministic value of the scalar `type_info`.
+    ///
+    /// `i64` and `u64` values come from `i64.uzumaki`. Every other type takes an
+    /// `i32.uzumaki` value constrained to its range: narrow integers are normalized and a
+    /// `bool` keeps only the lowest bit, so each value of the type, and nothing else, can
+    /// be produced.
+    fn push_uzumaki(&mut self, type_info: &TypeInfo) {
+        match type_info.kind {
+            TypeInfoKind::Number(NumberType::I64 | NumberType::U64) => self.push("i64.uzumaki"),
+            TypeInfoKind::Number(_) => {
+                self.push("i32.uzumaki");
+                self.normalize(type_info);
+            }
+            TypeInfoKind::Bool => {
+                self.push("i32.uzumaki");
+                self.push("i32.const 1");
+                self.push("i32.and");
+            }
+            _ => panic!("Uzumaki values of type `{type_info}` must be rejected before lowering"),
+        }
+    }
+
+    /// Runs `body` once for each of the `length` elements of the arrays at `arrays`, with
+    /// the addresses of the current elements, in a counted loop.
+    ///
+    /// ```wat
+    /// i32.const 0
+    /// local.set $.copy
+    /// loop $copy0
+    ///   ;; body with the elements at index $.copy
+    ///   local.get $.copy
+    ///   i32.const 1
+    ///   i32.add
+    ///   local.tee $.copy
+    ///   i32.const LENGTH
+    ///   i32.ne
+    ///   br_if $copy0
+    /// end
+    /// ```
+    fn for_each_element(
+        &mut self,
+        arrays: &[&Address],
+        element_type: &TypeInfo,
+        length: u32,
+        body: impl FnOnce(&mut Self, &[Address]),
+    ) {
+        if length == 0 {
+            return;
+        }
+        let (element_size, _) = self.module.layout.size_and_align(element_type);
+        let index = self.add_local(".copy", "i32");
+        let label = self.fresh_label("copy");
+        self.push("i32.const 0");
+        self.push(format!("local.set ${index}"));
+        self.push(format!("loop ${label}"));
+        let mut elements = Vec::with_capacity(arrays.len());
+        for array in arrays {
+            self.push_base(array);
+            self.push(format!("local.get ${index}"));
+            self.push(format!("i32.const {element_size}"));
+            self.push("i32.mul");
+            self.push("i32.add");
+            let element = self.add_local(".element", "i32");
+            self.push(format!("local.set ${element}"));
+            elements.push(Address {
+                base: Some(element),
+                offset: array.offset,
+            });
+        }
+        body(self, &elements);
+        self.push(format!("local.get ${index}"));
+        self.push("i32.const 1");
+        self.push("i32.add");
+        self.push(format!("local.tee ${index}"));
+        self.push(format!("i32.const {length}"));
+        self.push("i32.ne");
+        self.push(format!("br_if ${label}"));
+        self.push("end");
+    }
+
+    /// Pushes the base of `address`: its local, or 0 for absolute addresses.
+    fn push_base(&mut self, address: &Address) {
+        match &address.base {
+            Some(base) => self.push(format!("local.get ${base}")),
+            None => self.push("i32.const 0"),
+        }
+    }
+
+    /// Pushes the full value of `address`, base plus offset.
+    fn push_address(&mut self, address: &Address) {
+        match &address.base {
+            Some(base) => {
+                self.push(format!("local.get ${base}"));
+                if address.offset != 0 {
+                    self.push(format!("i32.const {}", address.offset));
+                    self.push("i32.add");
+                }
+            }
+            None => self.push(format!("i32.const {}", address.offset)),
+        }
+    }
+
+    /// Pushes the store of a scalar of `type_info` at `offset` past the base and value
+    /// already on the operand stack.
+    fn push_store(&mut self, type_info: &TypeInfo, offset: u32) {
+        let (size, _) = self.module.layout.size_and_align(type_info);
+        let instruction = match size {
+            1 => "i32.store8",
+            2 => "i32.store16",
+            4 => "i32.store",
+            _ => "i64.store",
+        };
+        self.push(memory_instruction(instruction, offset));
+    }
+
+    /// Lowers a condition to an `i32` that is non-zero when the condition holds.
+    fn lower_condition(&mut self, expression: &Expression) -> Result<(), CodegenError> {
+        let value_type = value_type(&self.expression_type(expression));
+        self.lower_expression(expression)?;
+        if value_type == "i64" {
+            self.push("i64.const 0");
+            self.push("i64.ne");
+        }
+        Ok(())
+    }
+
+    /// Lowers a binary expression.
+    ///
+    /// Both operands share the same type (enforced by the type checker); its signedness
+    /// selects the `_s` or `_u` variant of division, remainder, right shift and
+    /// comparisons. `&&` and `||` short-circuit and are lowered by
+    /// [`Self::lower_logical_expression`]; `**` is lowered by [`Self::lower_pow`].
+    fn lower_binary_expression(
+        &mut self,
+        binary_expression: &BinaryExpression,
+    ) -> Result<(), CodegenError> {
+        if matches!(
+            binary_expression.operator,
+            OperatorKind::And | OperatorKind::Or
+        ) {
+            return self.lower_logical_expression(binary_expression);
+        }
+        let left_expression = binary_expression.left.borrow();
+        let operand_type = self
+            .module
+            .scalar_type(&self.expression_type(&left_expression));
+        let value_type = value_type(&operand_type);
+        let sign = if operand_type.is_signed_integer() {
+            "s"
+        } else {
+            "u"
+        };
+        self.lower_expression(&left_expression)?;
+        self.lower_expression(&binary_expression.right.borrow())?;
+        match binary_expression.operator {
+            OperatorKind::Add => self.push_wrapping(&operand_type, &format!("{value_type}.add")),
+            OperatorKind::Sub => self.push_wrapping(&operand_type, &format!("{value_type}.sub")),
+            OperatorKind::Mul => self.push_wrapping(&operand_type, &format!("{value_type}.mul")),
+            OperatorKind::Div => {
+                self.push_wrapping(&operand_type, &format!("{value_type}.div_{sign}"));
+            }
+            OperatorKind::Mod => self.push(format!("{value_type}.rem_{sign}")),
+            OperatorKind::Pow => self.lower_pow(&operand_type),
+            OperatorKind::BitAnd => self.push(format!("{value_type}.and")),
+            OperatorKind::BitOr => self.push(format!("{value_type}.or")),
+            OperatorKind::BitXor => self.push(format!("{value_type}.xor")),
+            OperatorKind::Shl => {
+                self.mask_shift_amount(&operand_type);
+                self.push_wrapping(&operand_type, &format!("{value_type}.shl"));
+            }
+            OperatorKind::Shr => {
+                self.mask_shift_amount(&operand_type);
+                self.push(format!("{value_type}.shr_{sign}"));
+            }
+            OperatorKind::Eq => self.push(format!("{value_type}.eq")),
+            OperatorKind::Ne => self.push(format!("{value_type}.ne")),
+            OperatorKind::Lt => self.push(format!("{value_type}.lt_{sign}")),
+            OperatorKind::Le => self.push(format!("{value_type}.le_{sign}")),
+            OperatorKind::Gt => self.push(format!("{value_type}.gt_{sign}")),
+            OperatorKind::Ge => self.push(format!("{value_type}.ge_{sign}")),
+            OperatorKind::BitNot => {
+                unreachable!("`~` is a unary operator and never appears in a binary expression")
+            }
+            OperatorKind::And | OperatorKind::Or => unreachable!("handled above"),
+        }
+        Ok(())
+    }
+
+    /// Lowers `&&` and `||` with short-circuit evaluation: the right operand is only
+    /// evaluated inside an `if` when the left operand does not determine the result.
+    fn lower_logical_expression(
+        &mut self,
+        binary_expression: &BinaryExpression,
+    ) -> Result<(), CodegenError> {
+        self.lower_condition(&binary_expression.left.borrow())?;
+        self.push("if (result i32)");
+        if binary_expression.operator == OperatorKind::And {
+            self.lower_condition(&binary_expression.right.borrow())?;
+            self.push("else");
+            self.push("i32.const 0");
+        } else {
+            self.push("i32.const 1");
+            self.push("else");
+            self.lower_condition(&binary_expression.right.borrow())?;
+        }
+        self.push("end");
+        Ok(())
+    }
+
+    /// Lowers `base ** exponent`, with both operands on the operand stack, using
+    /// exponentiation by squaring.
+    ///
+    /// The exponent is treated as an unsigned integer of the operand width and all
+    /// multiplications wrap, so the result equals the mathematical power modulo `2^width`.
+    fn lower_pow(&mut self, operand_type: &TypeInfo) {
+        let value_type = value_type(operand_type);
+        let exponent = self.add_local(".exponent", value_type);
+        let base = self.add_local(".base", value_type);
+        let result = self.add_local(".power", value_type);
+        let label = self.fresh_label("pow");
+        let exit = format!("{label}.end");
+        self.push(format!("local.set ${exponent}"));
+        self.push(format!("local.set ${base}"));
+        if let Some(mask) = width_mask(operand_type) {
+            self.push(format!("local.get ${exponent}"));
+            self.push(format!("i32.const {mask}"));
+            self.push("i32.and");
+            self.push(format!("local.set ${exponent}"));
+        }
+        self.push(format!("{value_type}.const 1"));
+        self.push(format!("local.set ${result}"));
+        self.push(format!("block ${exit}"));
+        self.push(format!("loop ${label}"));
+        self.push(format!("local.get ${exponent}"));
+        self.push(format!("{value_type}.eqz"));
+        self.push(format!("br_if ${exit}"));
+        self.push(format!("local.get ${exponent}"));
+        self.push(format!("{value_type}.const 1"));
+        self.push(format!("{value_type}.and"));
+        if value_type == "i64" {
+            self.push("i32.wrap_i64");
+        }
+        self.push("if");
+        self.push(format!("local.get ${result}"));
+        self.push(format!("local.get ${base}"));
+        self.push(format!("{value_type}.mul"));
+        self.push(format!("local.set ${result}"));
+        self.push("end");
+        self.push(format!("local.get ${base}"));
+        self.push(format!("local.get ${base}"));
+        self.push(format!("{value_type}.mul"));
+        self.push(format!("local.set ${base}"));
+        self.push(format!("local.get ${exponent}"));
+        self.push(format!("{value_type}.const 1"));
+        self.push(format!("{value_type}.shr_u"));
+        self.push(format!("local.set ${exponent}"));
+        self.push(format!("br ${label}"));
+        self.push("end");
+        self.push("end");
+        self.push(format!("local.get ${result}"));
+        self.normalize(operand_type);
+    }
+
+    /// Masks the shift amount on top of the operand stack to the width of a narrow
+    /// integer type, matching how WebAssembly treats the native widths.
+    fn mask_shift_amount(&mut self, operand_type: &TypeInfo) {
+        let bits = match operand_type.kind {
+            TypeInfoKind::Number(NumberType::I8 | NumberType::U8) => 8,
+            TypeInfoKind::Number(NumberType::I16 | NumberType::U16) => 16,
+            _ => return,
+        };
+        self.push(format!("i32.const {}", bits - 1));
+        self.push("i32.and");
+    }
+
+    /// Pushes an arithmetic instruction whose result may need normalizing.
+    fn push_wrapping(&mut self, operand_type: &TypeInfo, instruction: &str) {
+        self.push(instruction);
+        self.normalize(operand_type);
+    }
+
+    /// Normalizes the narrow integer on top of the operand stack: sign-extends `i8` and
+    /// `i16` values, zero-extends `u8` and `u16` values.
+    fn normalize(&mut self, type_info: &TypeInfo) {
+        match type_info.kind {
+            TypeInfoKind::Number(NumberType::I8) => self.push_sign_extension(24),
+            TypeInfoKind::Number(NumberType::I16) => self.push_sign_extension(16),
+            _ => {
+                if let Some(mask) = width_mask(type_info) {
+                    self.push(format!("i32.const {mask}"));
+                    self.push("i32.and");
+                }
+            }
+        }
+    }
+
+    fn push_sign_extension(&mut self, shift: u32) {
+        self.push(format!("i32.const {shift}"));
+        self.push("i32.shl");
+        self.push(format!("i32.const {shift}"));
+        self.push("i32.shr_s");
+    }
+
+    /// Lowers a prefix unary expression.
+    ///
+    /// - `!` - Logical negation of a `bool` (`i32.eqz`)
+    /// - `-` - Two's complement negation of a signed integer
+    /// - `~` - Bitwise complement of an integer
+    fn lower_prefix_unary_expression(
+        &mut self,
+        prefix_unary_expression: &PrefixUnaryExpression,
+    ) -> Result<(), CodegenError> {
+        let operand = prefix_unary_expression.expression.borrow();
+        let operand_type = self.module.scalar_type(&self.expression_type(&operand));
+        let value_type = value_type(&operand_type);
+        match prefix_unary_expression.operator {
+            UnaryOperatorKind::Not | UnaryOperatorKind::BitNot
+                if operand_type.kind == TypeInfoKind::Bool =>
+            {
+                self.lower_expression(&operand)?;
+                self.push("i32.eqz");
+            }
+            UnaryOperatorKind::Not | UnaryOperatorKind::BitNot => {
+                self.lower_expression(&operand)?;
+                self.push(format!("{value_type}.const -1"));
+                self.push_wrapping(&operand_type, &format!("{value_type}.xor"));
+            }
+            UnaryOperatorKind::Neg => {
+                self.push(format!("{value_type}.const 0"));
+                self.lower_expression(&operand)?;
+                self.push_wrapping(&operand_type, &format!("{value_type}.sub"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the WebAssembly value type of `type_info`; structs and arrays are passed as
+/// `i32` addresses.
+fn value_type(type_info: &TypeInfo) -> &'static str {
+    match type_info.kind {
+        TypeInfoKind::Number(NumberType::I64 | NumberType::U64) => "i64",
+        _ => "i32",
+    }
+}
+
+/// Returns a load or store instruction with its `offset` immediate.
+fn memory_instruction(instruction: &str, offset: u32) -> String {
+    if offset == 0 {
+        instruction.to_string()
+    } else {
+        format!("{instruction} offset={offset}")
+    }
+}
+
+/// Returns the load instruction for a scalar of `type_info`, extending narrow values as
+/// described in the module documentation.
+fn load_instruction(type_info: &TypeInfo) -> &'static str {
+    match type_info.kind {
+        TypeInfoKind::Number(NumberType::I8) => "i32.load8_s",
+        TypeInfoKind::Bool | TypeInfoKind::Number(NumberType::U8) => "i32.load8_u",
+        TypeInfoKind::Number(NumberType::I16) => "i32.load16_s",
+        TypeInfoKind::Number(NumberType::U16) => "i32.load16_u",
+        TypeInfoKind::Number(NumberType::I64 | NumberType::U64) => "i64.load",
+        _ => "i32.load",
+    }
+}
+
+/// Returns the mask that zero-extends `u8` and `u16` values, and limits the exponent of
+/// `**` for every narrow integer type.
+fn width_mask(type_info: &TypeInfo) -> Option<u32> {
+    match type_info.kind {
+        TypeInfoKind::Number(NumberType::I8 | NumberType::U8) => Some(0xff),
+        TypeInfoKind::Number(NumberType::I16 | NumberType::U16) => Some(0xffff),
+        _ => None,
+    }
+}
+
+/// Returns the WebAssembly text identifier of a symbol: `$symbol`, or `$"symbol"` when
+/// the symbol contains characters that cannot appear in a plain identifier, such as the
+/// spaces and brackets in the symbols of generic instances.
+fn identifier(symbol: &str) -> String {
+    let plain = symbol
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-./:<=>?@\\^_`|~".contains(&byte));
+    if plain {
+        format!("${symbol}")
+    } else {
+        format!("$\"{}\"", escape(symbol.as_bytes()))
+    }
+}
+
+/// Escapes bytes for a WebAssembly text string, keeping printable ASCII readable.
+fn escape(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        if byte.is_ascii_graphic() && byte != b'"' && byte != b'\\' || byte == b' ' {
+            escaped.push(char::from(byte));
+        } else {
+            let _ = write!(escaped, "\\{byte:02x}");
+        }
+    }
+    escaped
+}
+
+/// Encodes the text of a module produced by [`Emitter::finish`].
+///
+/// # Errors
+///
+/// Returns an error if the text is not a valid module, which indicates a bug in the
+/// emitter.
+pub(crate) fn assemble(text: &str) -> anyhow::Result<Vec<u8>> {
+    let buffer = inf_wast::parser::ParseBuffer::new(text)?;
+    let mut module = inf_wast::parser::parse::<inf_wast::Wat>(&buffer)?;
+    Ok(module.encode()?)
+}
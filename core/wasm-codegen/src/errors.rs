@@ -0,0 +1,140 @@
+//! Code Generation Error Types
+//!
+//! This module defines the errors produced when a type-checked program cannot be lowered
+//! to WebAssembly, usually because it uses a construct the code generator does not
+//! support yet.
+//!
+//! ## Error Design
+//!
+//! All code generation errors:
+//! - Include the source location (line and column) of the offending node
+//! - Describe the construct in source terms rather than the compiler's internal state
+//! - Use descriptive error messages via `thiserror`
+//! - Are collected and reported together (error recovery)
+//!
+//! ## Error Categories
+//!
+//! **Unsupported Constructs**:
+//! - [`CodegenError::UnsupportedStatement`] - Statement kind without a lowering
+//! - [`CodegenError::UnsupportedExpression`] - Expression kind without a lowering
+//! - [`CodegenError::UnsupportedType`] - Type without a WebAssembly representation
+//! - [`CodegenError::UnsupportedUzumakiType`] - `@` of a type that is not built from
+//!   `bool` and integers
+//! - [`CodegenError::UnsupportedLocalConstant`] - Local constant of a non-scalar type
+//! - [`CodegenError::UnsupportedConstantValue`] - Module-level constant built from
+//!   something other than literals
+//! - [`CodegenError::DuplicateTypeName`] - Structs or enums with the same name in
+//!   different modules
+//!
+//! **Program Errors**:
+//! - [`CodegenError::AssignmentToConstant`] - Assignment to a module-level constant
+//! - [`CodegenError::NotAPlace`] - Assignment target that is not a place in memory
+//! - [`CodegenError::BreakOutsideLoop`] - `break` without an enclosing loop
+//! - [`CodegenError::RecursiveStruct`] - Struct that contains itself by value
+//! - [`CodegenError::UnavailableSymbol`] - Use of a function or constant that has no
+//!   compiled definition
+//! - [`CodegenError::InstantiationTooDeep`] - Generic function instantiated with ever
+//!   larger types
+//! - [`CodegenError::UninferredTypeArgument`] - Call of a generic function whose type
+//!   arguments cannot be inferred
+//!
+//! ## Error Recovery
+//!
+//! A statement that fails to lower is skipped and lowering continues with the next one;
+//! a function whose signature cannot be lowered is skipped entirely. Errors that can only
+//! be caused by a compiler bug, such as a node the type checker left without a type, are
+//! still panics.
+//!
+//! ## Usage Example
+//!
+//! ```ignore
+//! use inference_wasm_codegen::codegen;
+//!
+//! if let Err(e) = codegen(&typed_context) {
+//!     eprintln!("Code generation failed:");
+//!     for error_msg in e.to_string().split("; ") {
+//!         eprintln!("  - {}", error_msg);
+//!     }
+//! }
+//! ```
+
+use inference_ast::nodes::Location;
+use inference_type_checker::type_info::TypeInfo;
+use thiserror::Error;
+
+/// Represents a code generation error with source location.
+#[derive(Debug, Clone, Error)]
+pub enum CodegenError {
+    #[error("{location}: {statement} statements are not yet supported in codegen")]
+    UnsupportedStatement {
+        statement: &'static str,
+        location: Location,
+    },
+
+    #[error("{location}: {expression} expressions are not yet supported in codegen")]
+    UnsupportedExpression {
+        expression: &'static str,
+        location: Location,
+    },
+
+    #[error("{location}: type `{type_info}` is not yet supported in codegen")]
+    UnsupportedType {
+        type_info: TypeInfo,
+        location: Location,
+    },
+
+    #[error("{location}: uzumaki values of type `{type_info}` are not yet supported in codegen")]
+    UnsupportedUzumakiType {
+        type_info: TypeInfo,
+        location: Location,
+    },
+
+    #[error("{location}: local constants of type `{type_info}` are not yet supported in codegen")]
+    UnsupportedLocalConstant {
+        type_info: TypeInfo,
+        location: Location,
+    },
+
+    #[error(
+        "{location}: constant values other than literals, struct literals and enum variants are not yet supported in codegen"
+    )]
+    UnsupportedConstantValue { location: Location },
+
+    #[error(
+        "{location}: type `{name}` is already defined in another module; types with the same name in different modules are not yet supported in codegen"
+    )]
+    DuplicateTypeName { name: String, location: Location },
+
+    #[error("{location}: cannot assign to constant `{name}`")]
+    AssignmentToConstant { name: String, location: Location },
+
+    #[error("{location}: expression does not denote a place in memory")]
+    NotAPlace { location: Location },
+
+    #[error("{location}: `break` outside of a loop")]
+    BreakOutsideLoop { location: Location },
+
+    #[error("{location}: struct `{name}` contains itself by value")]
+    RecursiveStruct { name: String, location: Location },
+
+    #[error("{location}: `{name}` has no compiled definition")]
+    UnavailableSymbol { name: String, location: Location },
+
+    #[error(
+        "{location}: generic function `{name}` is instantiated with types nested more than {limit} levels deep"
+    )]
+    InstantiationTooDeep {
+        name: String,
+        limit: usize,
+        location: Location,
+    },
+
+    #[error(
+        "{location}: cannot infer type parameter `{type_parameter}` of generic function `{name}` from the arguments of this call"
+    )]
+    UninferredTypeArgument {
+        name: String,
+        type_parameter: String,
+        location: Location,
+    },
+}
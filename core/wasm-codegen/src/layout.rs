@@ -0,0 +1,655 @@
+//! Data layout of aggregate values in linear memory.
+//!
+//! Scalars live in WebAssembly locals, but struct and array values need an address: they
+//! are placed in linear memory (on the shadow stack for locals and temporaries) and every
+//! field or element is reached through a byte offset from the start of the value.
+//!
+//! Enums are scalars: a value is the discriminant of its variant, stored in the smallest
+//! unsigned integer type that can hold every discriminant of the enum.
+//!
+//! # Layout Rules
+//!
+//! The layout follows the C rules for wasm32, so that a host can read aggregate values
+//! written by generated code:
+//!
+//! | Type               | Size | Alignment |
+//! |--------------------|------|-----------|
+//! | `bool`, `i8`, `u8` | 1    | 1         |
+//! | `i16`, `u16`       | 2    | 2         |
+//! | `i32`, `u32`       | 4    | 4         |
+//! | `i64`, `u64`       | 8    | 8         |
+//! | enum               | size of its discriminant type | same as size |
+//! | struct             | sum of fields, padded | largest field alignment |
+//! | `[T; N]`           | `N` × size of `T`     | alignment of `T`        |
+//!
+//! Fields are laid out in declaration order; each field starts at the next offset that
+//! is a multiple of its alignment, and the struct size is rounded up to the struct
+//! alignment so that consecutive values stay aligned. A struct without fields has size
+//! 0 and alignment 1. Array elements are stored back to back: since every size is a
+//! multiple of its alignment, element `i` starts at offset `i × size of T` and needs no
+//! padding. Nested arrays such as `[[bool; 3]; 3]` are therefore laid out row by row.
+//!
+//! ## Example
+//!
+//! ```inference
+//! struct Mixed { flag: bool; value: i64; small: u16; }
+//! ```
+//!
+//! ```text
+//! offset 0   flag   (1 byte, followed by 7 bytes of padding)
+//! offset 8   value  (8 bytes)
+//! offset 16  small  (2 bytes, followed by 6 bytes of padding)
+//! size 24, alignment 8
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    rc::Rc,
+};
+
+use inference_ast::nodes::{
+    Ast, AstNode, Definition, EnumDefinition, Expression, Literal, Location, NumberLiteral,
+    Statement, StructDefinition,
+};
+use inference_type_checker::{
+    type_info::{NumberType, TypeInfo, TypeInfoKind},
+    typed_context::TypedContext,
+};
+
+use crate::{errors::CodegenError, modules::SourceModule};
+
+/// Name of the custom section listing the discriminant of every enum variant.
+pub(crate) const ENUMS_SECTION_NAME: &str = "inference.enums";
+
+/// Name of the custom section mapping every assertion ID to its source location.
+pub(crate) const ASSERTS_SECTION_NAME: &str = "inference.asserts";
+
+/// Address of the `i32` word that receives the ID of a failing assertion just before the
+/// module traps.
+///
+/// A host that catches a trap reads this word from the exported memory and looks the ID
+/// up in the `inference.asserts` custom section; it stays `0` if no assertion failed. The
+/// word lies below the first data segment of both backends, so nothing else uses it.
+pub const FAILED_ASSERTION_ADDRESS: u32 = 8;
+
+/// Placement of a single struct field.
+#[derive(Debug, Clone)]
+pub(crate) struct FieldLayout {
+    pub(crate) name: String,
+    /// Byte offset from the start of the struct value.
+    pub(crate) offset: u32,
+    pub(crate) type_info: TypeInfo,
+}
+
+/// Size, alignment and field placement of a struct type.
+#[derive(Debug, Clone)]
+pub(crate) struct StructLayout {
+    pub(crate) size: u32,
+    pub(crate) align: u32,
+    pub(crate) fields: Vec<FieldLayout>,
+}
+
+impl StructLayout {
+    /// Returns the placement of the field called `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the struct has no such field. The type checker rejects accesses to
+    /// unknown fields, so this indicates a compiler bug.
+    pub(crate) fn field(&self, name: &str) -> &FieldLayout {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .unwrap_or_else(|| panic!("Struct has no field `{name}`"))
+    }
+}
+
+/// Discriminants of an enum type.
+///
+/// Variants are numbered from 0 in declaration order, so the numbering only changes when
+/// the enum definition itself changes.
+#[derive(Debug, Clone)]
+pub(crate) struct EnumLayout {
+    /// Variant names in declaration order; the index of a variant is its discriminant.
+    pub(crate) variants: Vec<String>,
+}
+
+impl EnumLayout {
+    /// Returns the discriminant of the variant called `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the enum has no such variant. The type checker rejects unknown variants,
+    /// so this indicates a compiler bug.
+    pub(crate) fn discriminant(&self, name: &str) -> u32 {
+        self.variants
+            .iter()
+            .position(|variant| variant == name)
+            .and_then(|index| u32::try_from(index).ok())
+            .unwrap_or_else(|| panic!("Enum has no variant `{name}`"))
+    }
+
+    /// Returns the unsigned integer type that represents values of the enum: `u8` for up
+    /// to 256 variants, `u16` for up to 65536, `u32` otherwise.
+    pub(crate) fn discriminant_type(&self) -> TypeInfo {
+        let number_type = match self.variants.len() {
+            0..=0x100 => NumberType::U8,
+            0x101..=0x1_0000 => NumberType::U16,
+            _ => NumberType::U32,
+        };
+        TypeInfo {
+            kind: TypeInfoKind::Number(number_type),
+            type_params: vec![],
+        }
+    }
+}
+
+/// Layouts of every struct type in the compiled program.
+///
+/// Built once before lowering; struct types are looked up by name, which covers both
+/// `TypeInfoKind::Struct` (struct literals) and `TypeInfoKind::Custom` (declared types of
+/// variables, parameters and fields). Array layouts follow from their element type and
+/// are not stored. Enums are looked up by name the same way, from `TypeInfoKind::Enum`
+/// (variant accesses) or `TypeInfoKind::Custom`. Declared types may name an alias; callers
+/// replace aliases with [`Self::resolve_aliases`] before looking a type up.
+pub(crate) struct DataLayout {
+    structs: HashMap<String, StructLayout>,
+    enums: HashMap<String, EnumLayout>,
+    /// Type each alias (`type Num = i32;`) names, as written.
+    aliases: HashMap<String, TypeInfo>,
+    /// Assertion ID of every `assert` statement, by node ID.
+    assertion_ids: HashMap<u32, u32>,
+    /// Path of the module and location of every `assert` statement, ordered by
+    /// assertion ID.
+    assertions: Vec<(String, Location)>,
+}
+
+impl DataLayout {
+    /// Computes the layout of every struct defined in the typed context.
+    ///
+    /// Structs that have no layout, because they contain themselves by value or have a
+    /// field of an unsupported type, are left out, and an error is returned for each of
+    /// them. A struct that only fails because one of its fields is such a struct is left
+    /// out silently, since the field's struct already reports the cause.
+    pub(crate) fn new(ctx: &TypedContext) -> (Self, Vec<CodegenError>) {
+        let modules = SourceModule::all(ctx);
+        let mut errors = Vec::new();
+        let mut type_names = HashSet::new();
+        let mut ordered_definitions: Vec<Rc<StructDefinition>> = Vec::new();
+        let mut enum_definitions: Vec<Rc<EnumDefinition>> = Vec::new();
+        let mut aliases = HashMap::new();
+        for module in &modules {
+            for definition in module.struct_definitions() {
+                if type_names.insert(definition.name()) {
+                    ordered_definitions.push(definition);
+                } else {
+                    errors.push(CodegenError::DuplicateTypeName {
+                        name: definition.name(),
+                        location: definition.location,
+                    });
+                }
+            }
+            for definition in module.enum_definitions() {
+                if type_names.insert(definition.name()) {
+                    enum_definitions.push(definition);
+                } else {
+                    errors.push(CodegenError::DuplicateTypeName {
+                        name: definition.name(),
+                        location: definition.location,
+                    });
+                }
+            }
+            for definition in module.type_definitions() {
+                if type_names.insert(definition.name()) {
+                    aliases.insert(definition.name(), TypeInfo::new(&definition.ty));
+                } else {
+                    errors.push(CodegenError::DuplicateTypeName {
+                        name: definition.name(),
+                        location: definition.location,
+                    });
+                }
+            }
+        }
+        let definitions: HashMap<String, _> = ordered_definitions
+            .iter()
+            .map(|definition| (definition.name(), definition.clone()))
+            .collect();
+        let enums = enum_definitions
+            .into_iter()
+            .map(|definition| {
+                let variants = definition
+                    .variants
+                    .iter()
+                    .map(|variant| variant.name())
+                    .collect();
+                (definition.name(), EnumLayout { variants })
+            })
+            .collect();
+        let mut assertions: Vec<(u32, u32, String, Location)> = ctx
+            .filter_nodes(|node| matches!(node, AstNode::Statement(Statement::Assert(_))))
+            .into_iter()
+            .map(|node| {
+                let (source_file_id, module_path) = Self::enclosing_module(ctx, node.id());
+                (node.id(), source_file_id, module_path, node.location())
+            })
+            .collect();
+        assertions.sort_by_key(|(_, source_file_id, _, location)| {
+            (*source_file_id, location.offset_start)
+        });
+        let mut layout = Self {
+            structs: HashMap::new(),
+            enums,
+            aliases,
+            assertion_ids: assertions
+                .iter()
+                .zip(1..)
+                .map(|((node_id, ..), assertion_id)| (*node_id, assertion_id))
+                .collect(),
+            assertions: assertions
+                .into_iter()
+                .map(|(_, _, module_path, location)| (module_path, location))
+                .collect(),
+        };
+        let mut in_progress = Vec::new();
+        let mut invalid = HashSet::new();
+        for definition in &ordered_definitions {
+            layout.compute_struct(
+                definition,
+                &definitions,
+                &mut in_progress,
+                &mut invalid,
+                &mut errors,
+            );
+        }
+        (layout, errors)
+    }
+
+    /// Returns the ID of the source file `node_id` is in and the path of the module that
+    /// contains it, such as `math::ops`; the path is empty at the top level of a file.
+    fn enclosing_module(ctx: &TypedContext, node_id: u32) -> (u32, String) {
+        let mut module_names = Vec::new();
+        let mut parent = ctx.get_parent_node(node_id);
+        while let Some(node) = parent {
+            match &node {
+                AstNode::Ast(Ast::SourceFile(source_file)) => {
+                    module_names.reverse();
+                    return (source_file.id, module_names.join("::"));
+                }
+                AstNode::Definition(Definition::Module(module)) => {
+                    module_names.push(module.name());
+                }
+                _ => {}
+            }
+            parent = ctx.get_parent_node(node.id());
+        }
+        panic!("Node {node_id} must belong to a source file")
+    }
+
+    fn compute_struct(
+        &mut self,
+        definition: &StructDefinition,
+        definitions: &HashMap<String, Rc<StructDefinition>>,
+        in_progress: &mut Vec<String>,
+        invalid: &mut HashSet<String>,
+        errors: &mut Vec<CodegenError>,
+    ) {
+        let name = definition.name();
+        if self.structs.contains_key(&name) || invalid.contains(&name) {
+            return;
+        }
+        if in_progress.contains(&name) {
+            errors.push(CodegenError::RecursiveStruct {
+                name: name.clone(),
+                location: definition.location,
+            });
+            invalid.insert(name);
+            return;
+        }
+        in_progress.push(name.clone());
+        let mut fields = Vec::with_capacity(definition.fields.len());
+        let mut size = 0u32;
+        let mut align = 1u32;
+        for field in &definition.fields {
+            let type_info = self.resolve_aliases(&TypeInfo::new(&field.type_));
+            let mut stored_type = &type_info;
+            while let Some((element_type, _)) = Self::array_element(stored_type) {
+                stored_type = element_type;
+            }
+            let field_struct = Self::struct_name(stored_type)
+                .and_then(|field_struct| definitions.get(field_struct));
+            if let Some(field_struct) = field_struct {
+                self.compute_struct(field_struct, definitions, in_progress, invalid, errors);
+            }
+            let Some((field_size, field_align)) = self.layout_of(&type_info) else {
+                if field_struct.is_none() {
+                    errors.push(CodegenError::UnsupportedType {
+                        type_info,
+                        location: field.location,
+                    });
+                }
+                in_progress.pop();
+                invalid.insert(name);
+                return;
+            };
+            let offset = size.next_multiple_of(field_align);
+            size = offset + field_size;
+            align = align.max(field_align);
+            fields.push(FieldLayout {
+                name: field.name.name(),
+                offset,
+                type_info,
+            });
+        }
+        in_progress.pop();
+        self.structs.insert(
+            name,
+            StructLayout {
+                size: size.next_multiple_of(align),
+                align,
+                fields,
+            },
+        );
+    }
+
+    /// Replaces every alias in the declared type `type_info` by the type it names,
+    /// following chains of aliases and looking into array element types.
+    ///
+    /// The type checker resolves aliases in the types it records for expressions, but
+    /// parameter, result and field types come from the AST as written. An alias that leads
+    /// back to itself is left as it is.
+    pub(crate) fn resolve_aliases(&self, type_info: &TypeInfo) -> TypeInfo {
+        self.resolve_aliases_with(type_info, &mut Vec::new())
+    }
+
+    fn resolve_aliases_with<'a>(
+        &'a self,
+        type_info: &TypeInfo,
+        visited: &mut Vec<&'a str>,
+    ) -> TypeInfo {
+        match &type_info.kind {
+            TypeInfoKind::Custom(name) if type_info.type_params.is_empty() => {
+                let Some((alias, target)) = self.aliases.get_key_value(name) else {
+                    return type_info.clone();
+                };
+                if visited.contains(&alias.as_str()) {
+                    return type_info.clone();
+                }
+                visited.push(alias);
+                let resolved = self.resolve_aliases_with(target, visited);
+                visited.pop();
+                resolved
+            }
+            TypeInfoKind::Array(element_type, length) => TypeInfo {
+                kind: TypeInfoKind::Array(
+                    Box::new(self.resolve_aliases_with(element_type, visited)),
+                    *length,
+                ),
+                type_params: vec![],
+            },
+            _ => type_info.clone(),
+        }
+    }
+
+    /// Returns the name of the struct `type_info` refers to, if any.
+    ///
+    /// Declared types arrive as `Custom(name)`, which may also name an enum;
+    /// callers check the name against the known layouts.
+    pub(crate) fn struct_name(type_info: &TypeInfo) -> Option<&str> {
+        match &type_info.kind {
+            TypeInfoKind::Struct(name) | TypeInfoKind::Custom(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the element type and length of `type_info` if it is an array type.
+    pub(crate) fn array_element(type_info: &TypeInfo) -> Option<(&TypeInfo, u32)> {
+        match &type_info.kind {
+            TypeInfoKind::Array(element_type, length) => Some((element_type, *length)),
+            _ => None,
+        }
+    }
+
+    /// Returns the layout of `type_info` if it is a struct type.
+    pub(crate) fn struct_layout(&self, type_info: &TypeInfo) -> Option<&StructLayout> {
+        Self::struct_name(type_info).and_then(|name| self.structs.get(name))
+    }
+
+    /// Returns the discriminants of `type_info` if it is an enum type.
+    pub(crate) fn enum_layout(&self, type_info: &TypeInfo) -> Option<&EnumLayout> {
+        match &type_info.kind {
+            TypeInfoKind::Enum(name) | TypeInfoKind::Custom(name) => self.enums.get(name),
+            _ => None,
+        }
+    }
+
+    /// Returns every enum with its discriminants, ordered by enum name.
+    pub(crate) fn enum_layouts(&self) -> Vec<(&str, &EnumLayout)> {
+        let mut enums: Vec<_> = self
+            .enums
+            .iter()
+            .map(|(name, layout)| (name.as_str(), layout))
+            .collect();
+        enums.sort_by_key(|(name, _)| *name);
+        enums
+    }
+
+    /// Returns the contents of the `inference.enums` custom section: one
+    /// `Enum::Variant=N` line per variant, ordered by enum name and then by discriminant,
+    /// so the contents depend only on the enum definitions. Empty for programs without
+    /// enums.
+    pub(crate) fn enum_discriminants_section(&self) -> String {
+        self.enum_layouts()
+            .into_iter()
+            .flat_map(|(enum_name, enum_layout)| {
+                enum_layout
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .map(move |(discriminant, variant)| {
+                        format!("{enum_name}::{variant}={discriminant}\n")
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the ID of the assertion made by the `assert` statement `node_id`.
+    ///
+    /// Assertions are numbered from 1 in source order, file by file, so the IDs depend
+    /// only on the source and are the same for both backends and in every instance of a
+    /// generic function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node_id` is not an `assert` statement of the typed context.
+    pub(crate) fn assertion_id(&self, node_id: u32) -> u32 {
+        self.assertion_ids[&node_id]
+    }
+
+    /// Returns the contents of the `inference.asserts` custom section: one line per
+    /// `assert` statement, ordered by ID. The line is `ID=LINE:COLUMN` for assertions
+    /// outside any module and `ID=MODULE:LINE:COLUMN` for assertions in a module, where
+    /// `MODULE` is the module path such as `math::ops`; line and column are relative to
+    /// the file holding the module. Empty for programs without assertions.
+    pub(crate) fn assertions_section(&self) -> String {
+        let mut section = String::new();
+        for ((module_path, location), assertion_id) in self.assertions.iter().zip(1..) {
+            if module_path.is_empty() {
+                let _ = writeln!(section, "{assertion_id}={location}");
+            } else {
+                let _ = writeln!(section, "{assertion_id}={module_path}:{location}");
+            }
+        }
+        section
+    }
+
+    /// Checks that values of `type_info` can be represented in the generated code, either
+    /// as an integer or in linear memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodegenError::UnsupportedType`] at `location` if the type has no layout.
+    pub(crate) fn check_type(
+        &self,
+        type_info: &TypeInfo,
+        location: Location,
+    ) -> Result<(), CodegenError> {
+        if self.layout_of(type_info).is_some() {
+            Ok(())
+        } else {
+            Err(CodegenError::UnsupportedType {
+                type_info: type_info.clone(),
+                location,
+            })
+        }
+    }
+
+    /// Writes the in-memory representation of a constant expression to `bytes`.
+    ///
+    /// Integers are stored little-endian, as WebAssembly loads them; struct fields and
+    /// array elements are placed at their layout offsets and padding stays zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an element is not a literal, a struct literal or an enum
+    /// variant.
+    pub(crate) fn encode_constant(
+        &self,
+        bytes: &mut [u8],
+        expression: &Expression,
+        type_info: &TypeInfo,
+    ) -> Result<(), CodegenError> {
+        match expression {
+            Expression::Literal(Literal::Array(array_literal)) => {
+                let (element_type, _) = Self::array_element(type_info)
+                    .unwrap_or_else(|| panic!("Array literal for non-array type `{type_info}`"));
+                let (element_size, _) = self.size_and_align(element_type);
+                let element_size = element_size as usize;
+                for (index, element) in array_literal.elements.iter().flatten().enumerate() {
+                    let start = index * element_size;
+                    self.encode_constant(
+                        &mut bytes[start..start + element_size],
+                        &element.borrow(),
+                        element_type,
+                    )?;
+                }
+            }
+            Expression::Struct(struct_expression) => {
+                let struct_layout = self
+                    .struct_layout(type_info)
+                    .unwrap_or_else(|| panic!("Struct literal for non-struct type `{type_info}`"));
+                for (field_name, value) in struct_expression.fields.iter().flatten() {
+                    let field = struct_layout.field(&field_name.name);
+                    let (field_size, _) = self.size_and_align(&field.type_info);
+                    let start = field.offset as usize;
+                    self.encode_constant(
+                        &mut bytes[start..start + field_size as usize],
+                        &value.borrow(),
+                        &field.type_info,
+                    )?;
+                }
+            }
+            Expression::Literal(Literal::Bool(bool_literal)) => {
+                bytes[0] = u8::from(bool_literal.value);
+            }
+            Expression::Literal(Literal::Number(number_literal)) => {
+                let value = number_literal_bits(number_literal).to_le_bytes();
+                bytes.copy_from_slice(&value[..bytes.len()]);
+            }
+            Expression::TypeMemberAccess(type_member_access_expression) => {
+                let discriminant = self
+                    .enum_layout(type_info)
+                    .unwrap_or_else(|| panic!("Enum variant for non-enum type `{type_info}`"))
+                    .discriminant(&type_member_access_expression.name.name);
+                bytes.copy_from_slice(&discriminant.to_le_bytes()[..bytes.len()]);
+            }
+            _ => {
+                return Err(CodegenError::UnsupportedConstantValue {
+                    location: expression.location(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that non-deterministic values of `type_info` can be generated: every scalar
+    /// it is made of must be `bool` or an integer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodegenError::UnsupportedType`] at `location` if the type has no layout,
+    /// and [`CodegenError::UnsupportedUzumakiType`] naming the first offending scalar type
+    /// otherwise.
+    pub(crate) fn check_uzumaki_type(
+        &self,
+        type_info: &TypeInfo,
+        location: Location,
+    ) -> Result<(), CodegenError> {
+        self.check_type(type_info, location)?;
+        if let Some(struct_layout) = self.struct_layout(type_info) {
+            for field in &struct_layout.fields {
+                self.check_uzumaki_type(&field.type_info, location)?;
+            }
+            Ok(())
+        } else if let Some((element_type, _)) = Self::array_element(type_info) {
+            self.check_uzumaki_type(element_type, location)
+        } else if matches!(type_info.kind, TypeInfoKind::Bool | TypeInfoKind::Number(_)) {
+            Ok(())
+        } else {
+            Err(CodegenError::UnsupportedUzumakiType {
+                type_info: type_info.clone(),
+                location,
+            })
+        }
+    }
+
+    /// Returns whether values of `type_info` live in linear memory rather than in locals.
+    pub(crate) fn is_aggregate(&self, type_info: &TypeInfo) -> bool {
+        self.struct_layout(type_info).is_some() || Self::array_element(type_info).is_some()
+    }
+
+    /// Returns the size and alignment in bytes of a value of `type_info`.
+    ///
+    /// # Panics
+    ///
+    /// Panics for types that have no memory representation. Callers check types with
+    /// [`Self::layout_of`] before lowering code that uses them.
+    pub(crate) fn size_and_align(&self, type_info: &TypeInfo) -> (u32, u32) {
+        self.layout_of(type_info)
+            .unwrap_or_else(|| panic!("Type `{type_info}` has no memory layout"))
+    }
+
+    /// Returns the size and alignment in bytes of a value of `type_info`, or `None` for
+    /// types that have no memory representation yet (strings, generics, functions, and
+    /// structs that have no layout).
+    pub(crate) fn layout_of(&self, type_info: &TypeInfo) -> Option<(u32, u32)> {
+        match &type_info.kind {
+            TypeInfoKind::Bool | TypeInfoKind::Number(NumberType::I8 | NumberType::U8) => {
+                Some((1, 1))
+            }
+            TypeInfoKind::Number(NumberType::I16 | NumberType::U16) => Some((2, 2)),
+            TypeInfoKind::Number(NumberType::I32 | NumberType::U32) => Some((4, 4)),
+            TypeInfoKind::Number(NumberType::I64 | NumberType::U64) => Some((8, 8)),
+            TypeInfoKind::Array(element_type, length) => {
+                let (element_size, element_align) = self.layout_of(element_type)?;
+                Some((element_size * length, element_align))
+            }
+            _ => {
+                if let Some(enum_layout) = self.enum_layout(type_info) {
+                    return self.layout_of(&enum_layout.discriminant_type());
+                }
+                self.struct_layout(type_info)
+                    .map(|layout| (layout.size, layout.align))
+            }
+        }
+    }
+}
+
+/// Returns the two's complement bits of a number literal, which may be negative.
+pub(crate) fn number_literal_bits(number_literal: &NumberLiteral) -> u64 {
+    number_literal
+        .value
+        .parse::<u64>()
+        .or_else(|_| number_literal.value.parse::<i64>().map(i64::cast_unsigned))
+        .unwrap_or(0)
+}
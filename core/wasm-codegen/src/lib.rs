@@ -1,12 +1,24 @@
 //! WebAssembly code generation for the Inference compiler.
 //!
-//! This crate provides LLVM-based code generation from Inference's typed AST to WebAssembly
-//! bytecode. It handles standard WebAssembly instructions as well as custom extensions for
-//! non-deterministic operations required for formal verification.
+//! This crate generates WebAssembly bytecode from Inference's typed AST. It handles standard
+//! WebAssembly instructions as well as custom extensions for non-deterministic operations
+//! required for formal verification.
+//!
+//! # Backends
+//!
+//! Two backends are available, selected with [`Backend`]:
+//!
+//! - [`Backend::Llvm`] (default) - Lowers to LLVM IR and compiles it with the external
+//!   toolchain; this is the optimizing backend
+//! - [`Backend::Builtin`] - Emits WebAssembly text directly and assembles it with `inf-wast`;
+//!   needs no external binaries and performs no optimizations
+//!
+//! Both backends share the calling convention and data layout, so they produce modules with
+//! the same exports and the same behavior.
 //!
 //! # Architecture
 //!
-//! The code generation pipeline consists of several layers:
+//! The LLVM pipeline consists of several layers:
 //!
 //! ```text
 //! Typed AST (TypedContext)
@@ -38,9 +50,35 @@
 //! These extensions enable formal verification by preserving non-deterministic semantics
 //! through the compilation pipeline.
 //!
+//! # Assertions
+//!
+//! `assert` statements compile to a conditional trap. Before trapping, a failing assertion
+//! stores its ID, a number from 1 assigned in source order, in the `i32` word at
+//! [`FAILED_ASSERTION_ADDRESS`] of the exported memory. The `inference.asserts` custom
+//! section maps every ID to the `line:column` of its `assert`, prefixed with the module
+//! path for an `assert` in a module, so a host that catches the trap can report which
+//! assertion failed.
+//!
+//! # Debug Info
+//!
+//! When [`CodegenOptions::debug_info`] names the source file, both backends emit DWARF
+//! line tables mapping every instruction to the `line:column` of the statement it was
+//! lowered from. The tables are stored in the standard `.debug_*` custom sections: the
+//! LLVM backend attaches debug locations to the IR and `inf-llc` and `rust-lld` carry
+//! them through, while the built-in backend writes the sections itself, see
+//! [`debug_info`]. Tools reading the module, such as the Rocq translator, use them to
+//! relate instructions back to the source.
+//!
+//! # Modules
+//!
+//! A program may span several source files and nested modules; they are all linked into
+//! one WebAssembly module. Functions in nested modules get symbols qualified with the
+//! module path, such as `math::add`, and calls between modules are direct calls, see
+//! [`modules`].
+//!
 //! # External Dependencies
 //!
-//! This crate requires two external binaries to be available:
+//! The LLVM backend requires two external binaries to be available:
 //!
 //! - **inf-llc** - Modified LLVM compiler with Inference intrinsics support
 //! - **rust-lld** - WebAssembly linker from the Rust toolchain
@@ -56,69 +94,416 @@
 //!
 //! # Module Organization
 //!
+//! - [`abi`] - Calling convention shared by both backends (private)
 //! - [`compiler`] - LLVM IR generation and intrinsic handling (private)
+//! - [`debug_info`] - DWARF line tables for the built-in backend (private)
+//! - [`emitter`] - WebAssembly text generation for the built-in backend (private)
+//! - [`errors`] - Errors for constructs that cannot be lowered
+//! - [`modules`] - Source files, nested modules and qualified symbols (private)
+//! - [`monomorphize`] - Instances of generic functions shared by both backends (private)
 //! - [`utils`] - External toolchain invocation and environment setup (private)
-//! - [`codegen`] - Public API for WebAssembly generation
+//! - [`codegen`], [`codegen_with_backend`], [`codegen_with_options`] - Public API for
+//!   WebAssembly generation; [`CodegenOptions`] selects the backend, optimization level,
+//!   exports and intermediate artifacts
 
 #![warn(clippy::pedantic)]
 
+use std::collections::HashMap;
+
+use inference_ast::nodes::{FunctionDefinition, Visibility};
 use inference_type_checker::typed_context::TypedContext;
 use inkwell::{
     context::Context,
     targets::{InitializationConfig, Target},
 };
 
-use crate::compiler::Compiler;
+use crate::{
+    compiler::Compiler, emitter::Emitter, errors::CodegenError, layout::DataLayout,
+    modules::SourceModule, monomorphize::Monomorphization,
+};
+
+pub use crate::layout::FAILED_ASSERTION_ADDRESS;
 
+mod abi;
 mod compiler;
+mod debug_info;
+mod emitter;
+pub mod errors;
+mod layout;
+mod modules;
+mod monomorphize;
 mod utils;
 
-/// Generates WebAssembly bytecode from a typed AST.
+/// Code generation backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Compiles through LLVM IR with `inf-llc` and links with `rust-lld`.
+    #[default]
+    Llvm,
+    /// Emits WebAssembly directly, without external tools or optimizations.
+    Builtin,
+}
+
+/// Functions a module exports.
 ///
-/// # Errors
+/// Exported functions are named by their symbol: the function name, qualified with the
+/// module path for functions of nested modules, such as `math::add`, or `Struct::method`
+/// for methods. Generic functions are never exported: only their instances are compiled,
+/// and those are internal to the module.
 ///
-/// Returns an error if more than one source file is present in the AST, as multi-file
-/// support is not yet implemented.
+/// The export set depends only on the program and the policy, never on the linker: both
+/// backends export exactly the selected functions, and `memory`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ExportPolicy {
+    /// Public free functions of every module, including `main`. Private functions and
+    /// methods are not exported.
+    #[default]
+    Public,
+    /// Every function and method, regardless of visibility.
+    All,
+    /// Only the functions and methods with these symbols.
+    Only(Vec<String>),
+}
+
+impl ExportPolicy {
+    /// Returns whether the function or method is exported; `module_path` is the path of
+    /// the module it is defined in and `owner` is the name of the struct a method belongs
+    /// to.
+    pub(crate) fn is_exported(
+        &self,
+        function_definition: &FunctionDefinition,
+        module_path: &str,
+        owner: Option<&str>,
+    ) -> bool {
+        match self {
+            Self::Public => owner.is_none() && function_definition.visibility == Visibility::Public,
+            Self::All => true,
+            Self::Only(symbols) => symbols.contains(&abi::FunctionAbi::symbol(
+                module_path,
+                function_definition,
+                owner,
+            )),
+        }
+    }
+}
+
+/// Module and field name under which an external function is imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportName {
+    /// Import module name, `env` unless configured otherwise.
+    pub module: String,
+    /// Import field name, the function name unless configured otherwise.
+    pub field: String,
+}
+
+impl ImportName {
+    /// Module that external functions are imported from by default.
+    pub const DEFAULT_MODULE: &str = "env";
+}
+
+/// Settings for [`codegen_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodegenOptions {
+    /// Backend that produces the module.
+    pub backend: Backend,
+
+    /// Optimization level from 0 (none) to 3 (aggressive); higher values are clamped.
+    /// Ignored by the built-in backend, which does not optimize.
+    pub optimization_level: u8,
+
+    /// Functions the module exports.
+    pub exports: ExportPolicy,
+
+    /// Whether to return the LLVM IR given to `inf-llc`. Requires the LLVM backend.
+    pub emit_llvm_ir: bool,
+
+    /// Whether to return the object file produced by `inf-llc`. Requires the LLVM backend.
+    pub emit_object: bool,
+
+    /// Import names of external functions, keyed by function name. Functions without an
+    /// entry are imported as `env` and their own name.
+    pub imports: HashMap<String, ImportName>,
+
+    /// Name of the source file, such as `main.inf`, to record in DWARF line tables. When
+    /// set, the module maps every instruction to the source location it was lowered from;
+    /// `None` emits no debug info.
+    pub debug_info: Option<String>,
+}
+
+impl CodegenOptions {
+    /// Returns the import name of the external function `name`.
+    #[must_use]
+    pub fn import_name(&self, name: &str) -> ImportName {
+        self.imports
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| ImportName {
+                module: ImportName::DEFAULT_MODULE.to_string(),
+                field: name.to_string(),
+            })
+    }
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            backend: Backend::default(),
+            optimization_level: 3,
+            exports: ExportPolicy::default(),
+            emit_llvm_ir: false,
+            emit_object: false,
+            imports: HashMap::new(),
+            debug_info: None,
+        }
+    }
+}
+
+/// Artifacts produced by [`codegen_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodegenOutput {
+    /// The WebAssembly module.
+    pub wasm: Vec<u8>,
+
+    /// LLVM IR text, when [`CodegenOptions::emit_llvm_ir`] is set.
+    pub llvm_ir: Option<String>,
+
+    /// WebAssembly object file, when [`CodegenOptions::emit_object`] is set.
+    pub object: Option<Vec<u8>>,
+}
+
+/// Generates WebAssembly bytecode from a typed AST with the default [`CodegenOptions`].
 ///
-/// Returns an error if code generation fails.
+/// # Errors
+///
+/// See [`codegen_with_options`].
 pub fn codegen(typed_context: &TypedContext) -> anyhow::Result<Vec<u8>> {
-    Target::initialize_webassembly(&InitializationConfig::default());
-    let context = Context::create();
-    let compiler = Compiler::new(&context, "wasm_module");
+    codegen_with_options(typed_context, &CodegenOptions::default()).map(|output| output.wasm)
+}
 
-    if typed_context.source_files().is_empty() {
-        return compiler.compile_to_wasm("output.wasm", 3);
-    }
-    if typed_context.source_files().len() > 1 {
-        todo!("Multi-file support not yet implemented");
+/// Generates WebAssembly bytecode from a typed AST with the given backend and otherwise
+/// default [`CodegenOptions`].
+///
+/// # Errors
+///
+/// See [`codegen_with_options`].
+pub fn codegen_with_backend(
+    typed_context: &TypedContext,
+    backend: Backend,
+) -> anyhow::Result<Vec<u8>> {
+    let options = CodegenOptions {
+        backend,
+        ..CodegenOptions::default()
+    };
+    codegen_with_options(typed_context, &options).map(|output| output.wasm)
+}
+
+/// Generates WebAssembly bytecode, and the requested intermediate artifacts, from a typed
+/// AST.
+///
+/// # Errors
+///
+/// Returns an error if LLVM IR or an object file is requested from the built-in backend.
+///
+/// Returns an error listing every [`CodegenError`] found if the program uses constructs
+/// that cannot be lowered yet; the messages are separated by `"; "`.
+///
+/// Returns an error if compiling or linking the generated module fails.
+pub fn codegen_with_options(
+    typed_context: &TypedContext,
+    options: &CodegenOptions,
+) -> anyhow::Result<CodegenOutput> {
+    let (layout, mut errors) = DataLayout::new(typed_context);
+    let (monomorphization, monomorphization_errors) = Monomorphization::new(typed_context);
+    errors.extend(monomorphization_errors);
+    match options.backend {
+        Backend::Llvm => {
+            Target::initialize_webassembly(&InitializationConfig::default());
+            let context = Context::create();
+            let compiler = Compiler::new(
+                &context,
+                "wasm_module",
+                layout,
+                monomorphization,
+                options.exports.clone(),
+                options.debug_info.as_deref(),
+            );
+            traverse_t_ast_with_compiler(typed_context, &compiler, options);
+            check_errors(errors.into_iter().chain(compiler.take_errors()))?;
+            let artifacts = compiler.compile_to_wasm("output.wasm", options.optimization_level)?;
+            Ok(CodegenOutput {
+                wasm: artifacts.wasm,
+                llvm_ir: options.emit_llvm_ir.then_some(artifacts.llvm_ir),
+                object: options.emit_object.then_some(artifacts.object),
+            })
+        }
+        Backend::Builtin => {
+            if options.emit_llvm_ir || options.emit_object {
+                anyhow::bail!(
+                    "The built-in backend produces neither LLVM IR nor object files; use the LLVM backend"
+                );
+            }
+            let mut emitter = Emitter::new(
+                typed_context,
+                layout,
+                monomorphization,
+                options.exports.clone(),
+            );
+            traverse_t_ast_with_emitter(typed_context, &mut emitter, options);
+            check_errors(errors.into_iter().chain(emitter.take_errors()))?;
+            let mut wasm = emitter::assemble(&emitter.finish())?;
+            if let Some(source_name) = &options.debug_info {
+                debug_info::append_line_tables(
+                    &mut wasm,
+                    source_name,
+                    emitter.instruction_locations(),
+                )?;
+            }
+            Ok(CodegenOutput {
+                wasm,
+                ..CodegenOutput::default()
+            })
+        }
     }
+}
 
-    traverse_t_ast_with_compiler(typed_context, &compiler);
-    let wasm_bytes = compiler.compile_to_wasm("output.wasm", 3)?;
-    Ok(wasm_bytes)
+/// Fails with all `errors` joined by `"; "`, if there are any.
+fn check_errors(errors: impl IntoIterator<Item = CodegenError>) -> anyhow::Result<()> {
+    let messages: Vec<String> = errors.into_iter().map(|error| error.to_string()).collect();
+    if !messages.is_empty() {
+        anyhow::bail!(messages.join("; "));
+    }
+    Ok(())
 }
 
 /// Traverses the typed AST and compiles all function definitions.
 ///
 /// This function iterates through all source files in the typed context and generates
-/// LLVM IR for each function definition. Currently, only function definitions at the
-/// module level are compiled; other top-level constructs (types, constants, etc.) are
-/// not yet supported.
+/// LLVM IR for each function definition. Module-level constants are defined first, then
+/// external functions are declared as imports and all functions are declared, and their
+/// bodies are lowered afterwards, so calls may refer to functions defined later in the
+/// file. Generic functions are not lowered themselves; each of their instances is
+/// declared and lowered after the other functions. Enum definitions produce no code of
+/// their own; their discriminants are recorded in a custom section once all functions
+/// are lowered.
 ///
 /// # Parameters
 ///
 /// - `typed_context` - Typed AST with type information for all nodes
 /// - `compiler` - LLVM compiler instance for IR generation
+/// - `options` - Code generation settings, used for the import names of external functions
 ///
 /// # Current Limitations
 ///
-/// - Only function definitions are compiled
-/// - Type definitions, constants, and other top-level items are ignored
-/// - Multi-file compilation is not fully tested (see `codegen` function)
-fn traverse_t_ast_with_compiler(typed_context: &TypedContext, compiler: &Compiler) {
-    for source_file in &typed_context.source_files() {
-        for func_def in source_file.function_definitions() {
-            compiler.visit_function_definition(&func_def, typed_context);
+/// - Type definitions and specs are ignored
+fn traverse_t_ast_with_compiler(
+    typed_context: &TypedContext,
+    compiler: &Compiler,
+    options: &CodegenOptions,
+) {
+    let modules = SourceModule::all(typed_context);
+    for module in &modules {
+        for constant_def in module.constant_definitions() {
+            compiler.define_constant(&constant_def, &module.path, typed_context);
         }
+        for external_def in module.external_function_definitions() {
+            compiler.declare_external_function(
+                &external_def,
+                &module.path,
+                &options.import_name(&external_def.name()),
+            );
+        }
+        for func_def in module.function_definitions() {
+            if !monomorphize::is_generic(&func_def) {
+                compiler.declare_function(&func_def, &module.path, None);
+            }
+        }
+        for struct_def in module.struct_definitions() {
+            for method in &struct_def.methods {
+                if !monomorphize::is_generic(method) {
+                    compiler.declare_function(method, &module.path, Some(&struct_def.name()));
+                }
+            }
+        }
+    }
+    for instance in compiler.instances() {
+        compiler.declare_instance(&instance);
+    }
+    for module in &modules {
+        for func_def in module.function_definitions() {
+            if !monomorphize::is_generic(&func_def) {
+                compiler.visit_function_definition(&func_def, &module.path, None, typed_context);
+            }
+        }
+        for struct_def in module.struct_definitions() {
+            for method in &struct_def.methods {
+                if !monomorphize::is_generic(method) {
+                    compiler.visit_function_definition(
+                        method,
+                        &module.path,
+                        Some(&struct_def.name()),
+                        typed_context,
+                    );
+                }
+            }
+        }
+    }
+    for instance in compiler.instances() {
+        compiler.visit_instance(&instance, typed_context);
+    }
+    compiler.record_enum_discriminants();
+    compiler.record_assertion_locations();
+}
+
+/// Traverses the typed AST and lowers it with the built-in backend, in the same order as
+/// [`traverse_t_ast_with_compiler`].
+fn traverse_t_ast_with_emitter(
+    typed_context: &TypedContext,
+    emitter: &mut Emitter,
+    options: &CodegenOptions,
+) {
+    let modules = SourceModule::all(typed_context);
+    for module in &modules {
+        for constant_def in module.constant_definitions() {
+            emitter.define_constant(&constant_def, &module.path);
+        }
+        for external_def in module.external_function_definitions() {
+            emitter.declare_external_function(
+                &external_def,
+                &module.path,
+                options.import_name(&external_def.name()),
+            );
+        }
+        for func_def in module.function_definitions() {
+            if !monomorphize::is_generic(&func_def) {
+                emitter.declare_function(&func_def, &module.path, None);
+            }
+        }
+        for struct_def in module.struct_definitions() {
+            for method in &struct_def.methods {
+                if !monomorphize::is_generic(method) {
+                    emitter.declare_function(method, &module.path, Some(&struct_def.name()));
+                }
+            }
+        }
+    }
+    for instance in emitter.instances() {
+        emitter.declare_instance(&instance);
+    }
+    for module in &modules {
+        for func_def in module.function_definitions() {
+            if !monomorphize::is_generic(&func_def) {
+                emitter.emit_function(&func_def, &module.path, None);
+            }
+        }
+        for struct_def in module.struct_definitions() {
+            for method in &struct_def.methods {
+                if !monomorphize::is_generic(method) {
+                    emitter.emit_function(method, &module.path, Some(&struct_def.name()));
+                }
+            }
+        }
+    }
+    for instance in emitter.instances() {
+        emitter.emit_instance(&instance);
     }
 }
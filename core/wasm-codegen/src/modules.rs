@@ -0,0 +1,168 @@
+//! Source files and nested modules of a program.
+//!
+//! A program is made of one or more source files, and every source file may define
+//! nested modules with `mod`. Both backends link all of them into a single WebAssembly
+//! module: every function of every source file and module is compiled, and calls
+//! between them are direct calls.
+//!
+//! The top level of every source file is the root module, so the definitions there share
+//! one namespace, as they do in the type checker. Definitions in nested modules are
+//! qualified with the module path, so that functions with the same name in different
+//! modules get different symbols:
+//!
+//! | Definition | Symbol |
+//! |------------|--------|
+//! | `fn helper()` at the top level | `helper` |
+//! | `fn helper()` in `mod math` | `math::helper` |
+//! | `fn helper()` in `mod ops` in `mod math` | `math::ops::helper` |
+//! | `fn len(self)` of `struct Point` in `mod math` | `math::Point::len` |
+//!
+//! Module-level constants are qualified the same way. A name used in a function body
+//! refers to the constant of the innermost enclosing module that defines it, as the type
+//! checker resolves it; calls are resolved by the type checker, which records the symbol
+//! of the callee.
+//!
+//! Structs and enums keep their plain names: types are identified by name in the data
+//! layout, so two types with the same name in different modules cannot be compiled yet.
+
+use std::rc::Rc;
+
+use inference_ast::nodes::{
+    ConstantDefinition, Definition, EnumDefinition, ExternalFunctionDefinition, FunctionDefinition,
+    StructDefinition, TypeDefinition,
+};
+use inference_type_checker::typed_context::TypedContext;
+
+/// The definitions of the top level of a source file, or of a nested module.
+pub(crate) struct SourceModule {
+    /// Path of the module, such as `math::ops`; empty for the top level of a source file.
+    pub(crate) path: String,
+
+    /// Definitions directly in the module, in source order.
+    definitions: Vec<Definition>,
+}
+
+impl SourceModule {
+    /// Returns the top level of every source file, each followed by the modules nested in
+    /// it, depth first and in source order.
+    ///
+    /// Source files come in the order they were added to the AST builder, which gives
+    /// their nodes increasing IDs, so that the output does not depend on the arena's
+    /// iteration order.
+    pub(crate) fn all(ctx: &TypedContext) -> Vec<Self> {
+        let mut source_files = ctx.source_files();
+        source_files.sort_by_key(|source_file| source_file.id);
+        let mut modules = Vec::new();
+        for source_file in source_files {
+            Self::collect("", &source_file.definitions, &mut modules);
+        }
+        modules
+    }
+
+    fn collect(path: &str, definitions: &[Definition], modules: &mut Vec<Self>) {
+        let nested: Vec<_> = definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Module(module) => Some(module.clone()),
+                _ => None,
+            })
+            .collect();
+        modules.push(Self {
+            path: path.to_string(),
+            definitions: definitions.to_vec(),
+        });
+        for module in nested {
+            Self::collect(
+                &qualify(path, &module.name()),
+                module.body.as_deref().unwrap_or_default(),
+                modules,
+            );
+        }
+    }
+
+    pub(crate) fn function_definitions(&self) -> Vec<Rc<FunctionDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Function(function_definition) => Some(function_definition.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn struct_definitions(&self) -> Vec<Rc<StructDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Struct(struct_definition) => Some(struct_definition.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn enum_definitions(&self) -> Vec<Rc<EnumDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Enum(enum_definition) => Some(enum_definition.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn type_definitions(&self) -> Vec<Rc<TypeDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Type(type_definition) => Some(type_definition.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn constant_definitions(&self) -> Vec<Rc<ConstantDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Constant(constant_definition) => Some(constant_definition.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn external_function_definitions(&self) -> Vec<Rc<ExternalFunctionDefinition>> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::ExternalFunction(external_function_definition) => {
+                    Some(external_function_definition.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Returns `name` qualified with the module path `path`; names in the root module stay
+/// unqualified.
+pub(crate) fn qualify(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}::{name}")
+    }
+}
+
+/// Returns the qualified names that `name`, used in the module at `path`, may refer to,
+/// from the innermost module to the root module.
+pub(crate) fn candidates(path: &str, name: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut path = path;
+    loop {
+        candidates.push(qualify(path, name));
+        if path.is_empty() {
+            return candidates;
+        }
+        path = path.rsplit_once("::").map_or("", |(parent, _)| parent);
+    }
+}
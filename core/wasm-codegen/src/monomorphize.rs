@@ -0,0 +1,415 @@
+//! Monomorphization of generic functions.
+//!
+//! A generic function such as `fn identity T'(x: T) -> T` has no code of its own. Every
+//! combination of concrete type arguments it is called with becomes an *instance*: a
+//! separate function lowered with each type parameter replaced by its type argument.
+//!
+//! Instances are collected before lowering, starting from the calls in the non-generic
+//! functions and methods and following the calls in every new instance, so an instance
+//! that calls another generic function instantiates that one too. The linker removes the
+//! functions that are not reachable from an export, which leaves exactly the instances
+//! reachable from the exported functions. Generic functions that are never called produce
+//! no code.
+//!
+//! Type arguments are inferred from the argument types at each call site, as the type
+//! checker does: a parameter declared as `T` binds `T` to the type of its argument, a
+//! parameter declared as `[T; N]` binds `T` to the element type of its argument, and a
+//! parameter declared as `Pair T' bool'` binds `T` to the first type argument of its `Pair`
+//! argument. Inside an instance, argument types are taken after substitution, so a call
+//! `identity(x)` in `outer T'(x: T)` instantiates `identity` with whatever `outer` was
+//! instantiated with.
+//!
+//! # Symbols
+//!
+//! An instance is named after the symbol of its generic function, which includes the path
+//! of its module, followed by the type arguments in the order the type parameters are
+//! declared:
+//!
+//! | Function | Type arguments | Symbol |
+//! |----------|----------------|--------|
+//! | `fn identity T'(x: T) -> T` | `T = i32` | `identity<i32>` |
+//! | `fn pair T' U'(a: T, b: U)` | `T = bool`, `U = [i64; 2]` | `pair<bool, [i64; 2]>` |
+//! | `fn map T'(self, x: T)` in `Point` | `T = u8` | `Point::map<u8>` |
+//! | `fn identity T'(x: T) -> T` in `mod math` | `T = i32` | `math::identity<i32>` |
+//!
+//! Symbols depend only on the program, never on the order in which instances are found,
+//! and cannot clash with other functions because `<` cannot appear in an identifier. They
+//! become the function names in the `name` custom section of the module.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Write,
+    rc::Rc,
+};
+
+use inference_ast::nodes::{
+    ArgumentType, AstNode, Definition, Expression, FunctionCallExpression, FunctionDefinition,
+};
+use inference_type_checker::{
+    type_info::{TypeInfo, TypeInfoKind},
+    typed_context::TypedContext,
+};
+use rustc_hash::FxHashMap;
+
+use crate::{abi::FunctionAbi, errors::CodegenError, modules::SourceModule};
+
+/// Deepest nesting of array types allowed in a type argument.
+///
+/// A generic function that calls itself with a larger type, e.g. `f([x, x])` in
+/// `f T'(x: T)`, would otherwise instantiate itself forever.
+pub(crate) const MAX_TYPE_ARGUMENT_DEPTH: usize = 16;
+
+/// Concrete type of every type parameter of an instance, keyed by parameter name.
+pub(crate) type Substitutions = FxHashMap<String, TypeInfo>;
+
+/// A generic function or method specialized to concrete type arguments.
+#[derive(Debug, Clone)]
+pub(crate) struct Instance {
+    /// Symbol of the specialized function, see the module documentation.
+    pub(crate) symbol: String,
+
+    /// The generic function or method.
+    pub(crate) function: Rc<FunctionDefinition>,
+
+    /// Path of the module the generic function is defined in, empty for the root module.
+    pub(crate) module_path: String,
+
+    /// Name of the struct a method belongs to, `None` for free functions.
+    pub(crate) owner: Option<String>,
+
+    /// Concrete type of every type parameter.
+    pub(crate) substitutions: Substitutions,
+}
+
+/// Every instance of the generic functions of a program, and the instance each call of
+/// a generic function targets.
+#[derive(Debug, Default)]
+pub(crate) struct Monomorphization {
+    /// Generic functions and methods keyed by symbol, with the path of their module and the
+    /// struct a method belongs to.
+    generics: HashMap<String, (Rc<FunctionDefinition>, String, Option<String>)>,
+
+    /// Instances in the order they were found.
+    instances: Vec<Instance>,
+
+    /// Symbol of the instance called by each call of a generic function, keyed by the
+    /// symbol of the calling function and the id of the call expression.
+    call_targets: HashMap<(String, u32), String>,
+}
+
+impl Monomorphization {
+    /// Collects the instances of the generic functions called in `ctx`.
+    ///
+    /// Returns the instances found, with an error for every call whose type arguments
+    /// cannot be inferred or would instantiate a function with type arguments nested
+    /// deeper than [`MAX_TYPE_ARGUMENT_DEPTH`]; such calls get no instance.
+    pub(crate) fn new(ctx: &TypedContext) -> (Self, Vec<CodegenError>) {
+        let mut monomorphization = Self::default();
+        let mut pending = VecDeque::new();
+        for module in SourceModule::all(ctx) {
+            let methods = module
+                .struct_definitions()
+                .into_iter()
+                .flat_map(|struct_def| {
+                    let owner = struct_def.name();
+                    struct_def
+                        .methods
+                        .iter()
+                        .map(move |method| (method.clone(), Some(owner.clone())))
+                        .collect::<Vec<_>>()
+                });
+            let functions = module
+                .function_definitions()
+                .into_iter()
+                .map(|function| (function, None));
+            for (function, owner) in functions.chain(methods) {
+                let symbol = FunctionAbi::symbol(&module.path, &function, owner.as_deref());
+                if is_generic(&function) {
+                    monomorphization
+                        .generics
+                        .insert(symbol, (function, module.path.clone(), owner));
+                } else {
+                    pending.push_back((symbol, function, Substitutions::default()));
+                }
+            }
+        }
+
+        let calls = calls_by_function(ctx);
+        let mut errors = Vec::new();
+        let mut found = HashSet::new();
+        while let Some((caller, function, substitutions)) = pending.pop_front() {
+            for call in calls.get(&function.id).into_iter().flatten() {
+                let Some(callee) = callee_symbol(call, ctx) else {
+                    continue;
+                };
+                let Some((generic, module_path, owner)) =
+                    monomorphization.generics.get(&callee).cloned()
+                else {
+                    continue;
+                };
+                let type_arguments = match type_arguments(&generic, call, &substitutions, ctx) {
+                    Ok(type_arguments) => type_arguments,
+                    Err(error) => {
+                        errors.push(error);
+                        continue;
+                    }
+                };
+                if type_arguments
+                    .iter()
+                    .any(|type_argument| array_depth(type_argument) > MAX_TYPE_ARGUMENT_DEPTH)
+                {
+                    errors.push(CodegenError::InstantiationTooDeep {
+                        name: callee,
+                        limit: MAX_TYPE_ARGUMENT_DEPTH,
+                        location: call.location,
+                    });
+                    continue;
+                }
+                let symbol = instance_symbol(&callee, &type_arguments);
+                monomorphization
+                    .call_targets
+                    .insert((caller.clone(), call.id), symbol.clone());
+                if found.insert(symbol.clone()) {
+                    let substitutions: Substitutions = type_parameter_names(&generic)
+                        .into_iter()
+                        .zip(type_arguments)
+                        .collect();
+                    monomorphization.instances.push(Instance {
+                        symbol: symbol.clone(),
+                        function: generic.clone(),
+                        module_path,
+                        owner,
+                        substitutions: substitutions.clone(),
+                    });
+                    pending.push_back((symbol, generic, substitutions));
+                }
+            }
+        }
+        (monomorphization, errors)
+    }
+
+    /// Returns the instances in the order they were found.
+    pub(crate) fn instances(&self) -> &[Instance] {
+        &self.instances
+    }
+
+    /// Returns the symbol a call of the function `symbol` targets: the instance it calls if
+    /// the function is generic, `symbol` itself otherwise.
+    ///
+    /// `caller` is the symbol of the function or instance that contains the call.
+    pub(crate) fn call_target(&self, caller: &str, call_id: u32, symbol: String) -> String {
+        self.call_targets
+            .get(&(caller.to_string(), call_id))
+            .cloned()
+            .unwrap_or(symbol)
+    }
+}
+
+/// Returns whether the function declares type parameters.
+pub(crate) fn is_generic(function_definition: &FunctionDefinition) -> bool {
+    function_definition
+        .type_parameters
+        .as_ref()
+        .is_some_and(|type_parameters| !type_parameters.is_empty())
+}
+
+fn type_parameter_names(function_definition: &FunctionDefinition) -> Vec<String> {
+    function_definition
+        .type_parameters
+        .iter()
+        .flatten()
+        .map(|type_parameter| type_parameter.name.clone())
+        .collect()
+}
+
+/// Returns the symbol of the function or method a call expression calls, as recorded by
+/// the type checker, or `None` for indirect calls, which cannot be lowered anyway.
+pub(crate) fn callee_symbol(call: &FunctionCallExpression, ctx: &TypedContext) -> Option<String> {
+    match &call.function {
+        callee @ (Expression::Identifier(_)
+        | Expression::MemberAccess(_)
+        | Expression::TypeMemberAccess(_)) => match ctx.get_node_typeinfo(callee.id())?.kind {
+            TypeInfoKind::Function(name) => Some(name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Groups the call expressions of the program by the function or method containing them,
+/// keyed by the id of its definition and ordered by position in the source.
+fn calls_by_function(ctx: &TypedContext) -> HashMap<u32, Vec<Rc<FunctionCallExpression>>> {
+    let mut calls: HashMap<u32, Vec<Rc<FunctionCallExpression>>> = HashMap::new();
+    for node in
+        ctx.filter_nodes(|node| matches!(node, AstNode::Expression(Expression::FunctionCall(_))))
+    {
+        let AstNode::Expression(Expression::FunctionCall(call)) = node else {
+            continue;
+        };
+        let mut parent = ctx.get_parent_node(call.id);
+        while let Some(node) = parent {
+            if let AstNode::Definition(Definition::Function(function)) = &node {
+                calls.entry(function.id).or_default().push(call);
+                break;
+            }
+            parent = ctx.get_parent_node(node.id());
+        }
+    }
+    for function_calls in calls.values_mut() {
+        function_calls.sort_by_key(|call| (call.location.offset_start, call.location.offset_end));
+    }
+    calls
+}
+
+/// Infers the type arguments of a call of the generic function `generic`, in the order its
+/// type parameters are declared.
+///
+/// Returns a [`CodegenError::UninferredTypeArgument`] for the first type parameter no
+/// argument type determines.
+fn type_arguments(
+    generic: &FunctionDefinition,
+    call: &FunctionCallExpression,
+    caller_substitutions: &Substitutions,
+    ctx: &TypedContext,
+) -> Result<Vec<TypeInfo>, CodegenError> {
+    let type_parameters = type_parameter_names(generic);
+    let parameters: Vec<&ArgumentType> = generic
+        .arguments
+        .iter()
+        .flatten()
+        .filter(|argument| !matches!(argument, ArgumentType::SelfReference(_)))
+        .collect();
+    let parameter_names: Vec<Option<String>> = parameters
+        .iter()
+        .map(|parameter| parameter.parameter_name())
+        .collect();
+    let mut substitutions = Substitutions::default();
+    for ((_, argument), parameter_index) in call
+        .arguments
+        .iter()
+        .flatten()
+        .zip(call.argument_parameter_indices(&parameter_names))
+    {
+        let Some(parameter_index) = parameter_index else {
+            continue;
+        };
+        let parameter_type = match parameters[parameter_index] {
+            ArgumentType::Argument(argument) => &argument.ty,
+            ArgumentType::IgnoreArgument(ignore_argument) => &ignore_argument.ty,
+            ArgumentType::Type(ty) => ty,
+            ArgumentType::SelfReference(_) => unreachable!("`self` is not an explicit parameter"),
+        };
+        let parameter_type = TypeInfo::new_with_type_params(parameter_type, &type_parameters);
+        if let Some(argument_type) = ctx.get_node_typeinfo(argument.borrow().id()) {
+            bind(
+                &parameter_type,
+                &argument_type.substitute(caller_substitutions),
+                &mut substitutions,
+            );
+        }
+    }
+    type_parameters
+        .iter()
+        .map(|name| {
+            substitutions
+                .remove(name)
+                .ok_or_else(|| CodegenError::UninferredTypeArgument {
+                    name: generic.name(),
+                    type_parameter: name.clone(),
+                    location: call.location,
+                })
+        })
+        .collect()
+}
+
+/// Binds the type parameters in `parameter_type` to the matching parts of
+/// `argument_type`. The first binding of a type parameter wins.
+///
+/// A parameter of an instantiated generic struct type, e.g. `Pair T' bool'`, matches the
+/// type arguments of a `Pair` argument in the order of the struct's type parameters.
+fn bind(parameter_type: &TypeInfo, argument_type: &TypeInfo, substitutions: &mut Substitutions) {
+    match (&parameter_type.kind, &argument_type.kind) {
+        (TypeInfoKind::Generic(name), _) if parameter_type.type_params.is_empty() => {
+            substitutions
+                .entry(name.clone())
+                .or_insert_with(|| argument_type.clone());
+        }
+        (TypeInfoKind::Array(parameter_element, _), TypeInfoKind::Array(argument_element, _)) => {
+            bind(parameter_element, argument_element, substitutions);
+        }
+        (
+            TypeInfoKind::Generic(parameter_name) | TypeInfoKind::Custom(parameter_name),
+            TypeInfoKind::Struct(argument_name) | TypeInfoKind::Custom(argument_name),
+        ) if parameter_name == argument_name
+            && parameter_type.type_params.len() == argument_type.type_params.len() =>
+        {
+            for (parameter_argument, argument_argument) in parameter_type
+                .type_params
+                .iter()
+                .zip(&argument_type.type_params)
+            {
+                let parameter_argument = TypeInfo {
+                    kind: TypeInfoKind::Generic(parameter_argument.clone()),
+                    type_params: vec![],
+                };
+                bind(
+                    &parameter_argument,
+                    &type_argument(argument_argument),
+                    substitutions,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the type a type argument of an instantiated generic struct names, e.g. `i32`
+/// in `Pair i32' bool'`.
+fn type_argument(name: &str) -> TypeInfo {
+    TypeInfo {
+        kind: TypeInfoKind::from_builtin_str(name)
+            .unwrap_or_else(|| TypeInfoKind::Custom(name.to_string())),
+        type_params: vec![],
+    }
+}
+
+fn array_depth(type_info: &TypeInfo) -> usize {
+    match &type_info.kind {
+        TypeInfoKind::Array(element, _) => 1 + array_depth(element),
+        _ => 0,
+    }
+}
+
+/// Returns the symbol of the instance of `generic` with the given type arguments.
+fn instance_symbol(generic: &str, type_arguments: &[TypeInfo]) -> String {
+    let mut symbol = format!("{generic}<");
+    for (index, type_argument) in type_arguments.iter().enumerate() {
+        if index > 0 {
+            symbol.push_str(", ");
+        }
+        write_type_name(&mut symbol, type_argument);
+    }
+    symbol.push('>');
+    symbol
+}
+
+/// Writes a type the way it is spelled in source code.
+///
+/// Struct and enum types are written by name, whichever way the type checker recorded
+/// them, so a struct type reached through a variable and through a struct literal gives
+/// the same symbol.
+fn write_type_name(symbol: &mut String, type_info: &TypeInfo) {
+    match &type_info.kind {
+        TypeInfoKind::Array(element, length) => {
+            symbol.push('[');
+            write_type_name(symbol, element);
+            let _ = write!(symbol, "; {length}]");
+        }
+        kind => match kind.as_builtin_str() {
+            Some(name) => symbol.push_str(name),
+            None => {
+                let _ = write!(symbol, "{kind}");
+            }
+        },
+    }
+}
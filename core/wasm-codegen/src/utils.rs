@@ -41,14 +41,14 @@
 //! The rust-lld linker combines the object file into a final WebAssembly module:
 //! - `-flavor wasm` - Use WebAssembly linker mode
 //! - `--no-entry` - Reactor model (no implicit `_start` function)
-//! - `--export=main` - Explicitly export `main` function if present
 //!
 //! Output: `.wasm` WebAssembly module
 //!
 //! ## Stage 4: Cleanup
 //!
-//! Read the final WASM bytes and remove temporary files. The WASM module is returned
-//! as a byte vector.
+//! Read the IR text, the object file and the final WASM bytes, then remove the temporary
+//! directory. All three artifacts are returned, so callers can keep the intermediate ones
+//! for debugging.
 //!
 //! # WebAssembly Execution Model
 //!
@@ -81,8 +81,9 @@
 //! │  WASM Module (wasm32-unknown-unknown)   │
 //! │                                         │
 //! │  Exports:                               │
-//! │    ├─ main  (pub fn main)               │
-//! │    └─ foo   (pub fn foo)                │
+//! │    ├─ main       (pub fn main)          │
+//! │    ├─ foo        (pub fn foo)           │
+//! │    └─ math::add  (pub fn add in math)   │
 //! │                                         │
 //! │  Internal (not exported):               │
 //! │    └─ bar   (fn bar)                    │
@@ -105,7 +106,8 @@
 //! ## Linker Flags
 //!
 //! - `--no-entry`: Tells LLD there's no `_start` function (reactor mode)
-//! - `--export=main`: Explicitly exports `main` if present (LLD creates argc/argv wrapper)
+//! - `--no-merge-data-segments`: Keeps one data segment per constant, so each segment
+//!   keeps its `.rodata.NAME` name in the name section
 //!
 //! ## Future Consideration
 //!
@@ -119,6 +121,16 @@ use std::{path::PathBuf, process::Command};
 use inkwell::{module::Module, targets::TargetTriple};
 use tempfile::tempdir;
 
+/// Output of every stage of the external toolchain.
+pub(crate) struct Artifacts {
+    /// LLVM IR text given to inf-llc.
+    pub(crate) llvm_ir: String,
+    /// WebAssembly object file produced by inf-llc.
+    pub(crate) object: Vec<u8>,
+    /// Linked WebAssembly module produced by rust-lld.
+    pub(crate) wasm: Vec<u8>,
+}
+
 /// Compiles an LLVM module to WebAssembly bytecode via external toolchain.
 ///
 /// This function orchestrates the complete compilation pipeline from LLVM IR to WASM,
@@ -129,18 +141,20 @@ use tempfile::tempdir;
 /// 1. **IR emission** - Write LLVM module to temporary `.ll` file
 /// 2. **Object compilation** - Invoke inf-llc with target wasm32-unknown-unknown
 /// 3. **Linking** - Invoke rust-lld with wasm flavor to produce final module
-/// 4. **Cleanup** - Read WASM bytes and remove temporary object file
+/// 4. **Cleanup** - Read all artifacts and remove the temporary directory
 ///
 /// # Parameters
 ///
 /// - `module` - LLVM module containing the IR to compile
 /// - `output_fname` - Base filename for intermediate files (extensions added automatically)
 /// - `optimization_level` - LLVM optimization level (0-3, clamped to max 3)
-/// - `has_main` - Whether to export a `main` function (only if the module contains one)
+///
+/// Functions are exported through their `wasm-export-name` attribute, so the linker
+/// needs no export flags.
 ///
 /// # Returns
 ///
-/// WebAssembly bytecode as a byte vector
+/// The IR text, the object file and the WebAssembly module
 ///
 /// # Errors
 ///
@@ -179,17 +193,16 @@ use tempfile::tempdir;
 pub(crate) fn compile_to_wasm(
     module: &Module,
     output_fname: &str,
-    optimization_level: u32,
-    has_main: bool,
-) -> anyhow::Result<Vec<u8>> {
+    optimization_level: u8,
+) -> anyhow::Result<Artifacts> {
     let llc_path = get_inf_llc_path()?;
     let temp_dir = tempdir()?;
     let obj_path = temp_dir.path().join(output_fname).with_extension("o");
     let ir_path = temp_dir.path().join(output_fname).with_extension("ll");
     let triple = TargetTriple::create("wasm32-unknown-unknown");
     module.set_triple(&triple);
-    let ir_str = module.print_to_string().to_string();
-    std::fs::write(&ir_path, ir_str)?;
+    let llvm_ir = module.print_to_string().to_string();
+    std::fs::write(&ir_path, &llvm_ir)?;
     let opt_flag = format!("-O{}", optimization_level.min(3));
     let mut llc_cmd = Command::new(&llc_path);
     configure_llvm_env(&mut llc_cmd)?;
@@ -219,10 +232,8 @@ pub(crate) fn compile_to_wasm(
         .arg("-flavor")
         .arg("wasm")
         .arg(&obj_path)
-        .arg("--no-entry");
-    if has_main {
-        lld_cmd.arg("--export=main");
-    }
+        .arg("--no-entry")
+        .arg("--no-merge-data-segments");
     let wasm_lld_output = lld_cmd.arg("-o").arg(&wasm_path).output()?;
 
     if !wasm_lld_output.status.success() {
@@ -233,9 +244,13 @@ pub(crate) fn compile_to_wasm(
         ));
     }
 
-    let wasm_bytes = std::fs::read(&wasm_path)?;
-    std::fs::remove_file(obj_path)?;
-    Ok(wasm_bytes)
+    let wasm = std::fs::read(&wasm_path)?;
+    let object = std::fs::read(&obj_path)?;
+    Ok(Artifacts {
+        llvm_ir,
+        object,
+        wasm,
+    })
 }
 
 /// Locates the inf-llc binary required for compilation.
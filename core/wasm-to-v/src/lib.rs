@@ -66,7 +66,8 @@
 //! - **Data Count Section**: Number of data segments (bulk memory proposal)
 //! - **Data Section**: Memory initialization segments
 //! - **Code Section**: Function bodies with local variables and instructions
-//! - **Custom Section**: Debug information (module, function, and local names)
+//! - **Custom Section**: Debug information (module, function, and local names, and DWARF
+//!   line tables, which become source location comments)
 //!
 //! Component model sections are recognized but generate empty stubs.
 //!
@@ -113,14 +114,21 @@
 //! computation and formal verification. These extensions enable explicit representation
 //! of non-deterministic choices in the binary format:
 //!
-//! | Instruction | Encoding | Purpose |
-//! |-------------|----------|---------|
-//! | `forall.start` | `0xfc 0x3a` | Begin universal quantification block |
-//! | `exists.start` | `0xfc 0x3b` | Begin existential quantification block |
-//! | `uzumaki.i32` | `0xfc 0x3c` | Generate non-deterministic i32 value |
-//! | `uzumaki.i64` | `0xfc 0x3d` | Generate non-deterministic i64 value |
-//! | `assume` | `0xfc 0x3e` | Filter execution paths by constraint |
-//! | `unique` | `0xfc 0x3f` | Assert exactly one execution path exists |
+//! | Instruction | Encoding | Rocq | Purpose |
+//! |-------------|----------|------|---------|
+//! | `i32.uzumaki` | `0xfc 0x31` | `BI_uzumaki_num T_i32` | Generate non-deterministic i32 value |
+//! | `i64.uzumaki` | `0xfc 0x32` | `BI_uzumaki_num T_i64` | Generate non-deterministic i64 value |
+//! | `forall` | `0xfc 0x3a` | `BI_forall` | Universal quantification block |
+//! | `exists` | `0xfc 0x3b` | `BI_exists` | Existential quantification block |
+//! | `assume` | `0xfc 0x3c` | `BI_assume` | Filter execution paths by constraint |
+//! | `unique` | `0xfc 0x3d` | `BI_unique` | Assert exactly one execution path exists |
+//!
+//! Non-deterministic values of every other primitive type are built from these two: the
+//! compiler narrows an `i32.uzumaki` value to the range of `bool`, `i8`, `u8`, `i16` or
+//! `u16` with ordinary masking and shift instructions, and fills structs and arrays one
+//! scalar at a time. The translation therefore needs no further constructs; the range
+//! constraint appears in the Rocq code as ordinary instructions applied to the
+//! `BI_uzumaki_num T_i32` value.
 //!
 //! These instructions are parsed by the forked [`inf-wasmparser`] dependency and
 //! translated to corresponding Rocq constructs that enable formal reasoning about
@@ -160,6 +168,7 @@
 //! - [Rocq Documentation](https://rocq-prover.org/) - Rocq proof assistant
 //! - [WebAssembly Specification](https://webassembly.github.io/spec/) - WASM standard
 
+mod source_map;
 pub mod translator;
 pub mod wasm_parser;
 
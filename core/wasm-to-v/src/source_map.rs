@@ -0,0 +1,84 @@
+//! Source Locations from DWARF Line Tables
+//!
+//! The Inference compiler can record, in DWARF line tables, the source location every
+//! instruction was lowered from. The tables live in the `.debug_*` custom sections of the
+//! module; addresses are byte offsets from the start of the code section contents, as
+//! described by the WebAssembly DWARF conventions.
+//!
+//! [`SourceMap`] reads the tables so that the translator can annotate the generated Rocq
+//! definitions with `(* file.inf:LINE:COLUMN *)` comments.
+
+use std::collections::HashMap;
+
+use gimli::{Dwarf, EndianSlice, LittleEndian};
+
+/// Prefix of the names of the custom sections holding DWARF debug info.
+pub(crate) const DEBUG_SECTION_PREFIX: &str = ".debug_";
+
+/// Source locations of the instructions of a module, from its DWARF line tables.
+pub(crate) struct SourceMap {
+    /// Rows of the line tables sorted by address: the `file:line:column` of the
+    /// instructions starting at the address, or `None` past the end of a sequence and for
+    /// instructions without a location (line 0).
+    rows: Vec<(u64, Option<String>)>,
+}
+
+impl SourceMap {
+    /// Reads the line tables from the `.debug_*` custom sections, keyed by section name.
+    ///
+    /// Returns `None` if the module has no line tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the DWARF data is malformed.
+    pub(crate) fn new(sections: &HashMap<&str, &[u8]>) -> anyhow::Result<Option<Self>> {
+        if !sections.contains_key(".debug_line") {
+            return Ok(None);
+        }
+        let dwarf = Dwarf::load(|id| -> gimli::Result<_> {
+            let data = sections.get(id.name()).copied().unwrap_or_default();
+            Ok(EndianSlice::new(data, LittleEndian))
+        })?;
+        let mut rows = Vec::new();
+        let mut units = dwarf.units();
+        while let Some(header) = units.next()? {
+            let unit = dwarf.unit(header)?;
+            let Some(program) = unit.line_program.clone() else {
+                continue;
+            };
+            let mut program_rows = program.rows();
+            while let Some((header, row)) = program_rows.next_row()? {
+                if row.end_sequence() {
+                    rows.push((row.address(), None));
+                    continue;
+                }
+                let file = match row.file(header) {
+                    Some(file) => dwarf
+                        .attr_string(&unit, file.path_name())?
+                        .to_string_lossy()
+                        .into_owned(),
+                    None => String::new(),
+                };
+                let location = row.line().map(|line| {
+                    let column = match row.column() {
+                        gimli::ColumnType::LeftEdge => 0,
+                        gimli::ColumnType::Column(column) => column.get(),
+                    };
+                    format!("{file}:{line}:{column}")
+                });
+                rows.push((row.address(), location));
+            }
+        }
+        // Stable, so that a sequence starting where the previous one ends takes precedence
+        // over that end.
+        rows.sort_by_key(|(address, _)| *address);
+        Ok(Some(Self { rows }))
+    }
+
+    /// Returns the source location of the instruction at `address`, an offset from the
+    /// start of the code section contents.
+    pub(crate) fn location(&self, address: u64) -> Option<&str> {
+        let index = self.rows.partition_point(|(start, _)| *start <= address);
+        self.rows[..index].last()?.1.as_deref()
+    }
+}
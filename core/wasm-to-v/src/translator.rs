@@ -116,7 +116,10 @@
 //!
 //! Generated Rocq identifiers follow these rules:
 //!
-//! - **Named functions**: Use names from custom name section if available
+//! - **Named functions**: Use names from custom name section if available. Characters
+//!   that cannot appear in a Rocq identifier are replaced, so `Point::len` becomes
+//!   `Point_len` and the generic instance `pair<bool, i32>` becomes `pair_bool_i32`; a
+//!   numeric suffix keeps the result unique
 //! - **Anonymous functions**: Generate unique names using UUID (`func_<uuid>`)
 //! - **Module name**: Use name from custom section, or parameter to `translate_bytes`
 //!
@@ -163,11 +166,13 @@ use std::{collections::HashMap, fmt::Display};
 
 use inf_wasmparser::{
     BlockType, CompositeInnerType, Data, DataKind, Element, ElementItems, ElementKind, Export,
-    FunctionBody, Global, Import, MemoryType, Operator, OperatorsIterator, OperatorsReader,
-    RecGroup, RefType, Table, TableType, TypeRef, ValType as wpValType,
+    FunctionBody, Global, Import, MemoryType, Operator, OperatorsIteratorWithOffsets,
+    OperatorsReader, RecGroup, RefType, Table, TableType, TypeRef, ValType as wpValType,
 };
 use uuid::Uuid;
 
+use crate::source_map::SourceMap;
+
 const LCB: &str = "{|\n";
 const RCB_DOT: &str = "|}.\n";
 
@@ -192,7 +197,14 @@ const LIST_SEAL: &str = "nil";
 /// - `mod_name`: Rocq module identifier (from parameter or custom name section)
 /// - `func_names_map`: Maps function index → name (from custom name section)
 /// - `func_locals_name_map`: Maps function index → (local index → name) (from custom name section)
+/// - `data_names_map`: Maps data segment index → name (from custom name section)
 /// - `start_function`: Optional module entry point function index
+/// - `enum_variants`: Enum variant name → discriminant (from the `inference.enums` custom section)
+/// - `assertions`: Assertion ID → `line:column` of the `assert`, prefixed with its module path
+///   in a module (from the `inference.asserts` custom section)
+/// - `debug_sections`: Contents of the `.debug_*` custom sections, keyed by name; their
+///   line tables map instructions to source locations
+/// - `code_section_start`: Offset of the code section contents, the base of DWARF addresses
 ///
 /// ## WASM Sections
 /// - `imports`: External dependencies (functions, tables, memories, globals)
@@ -213,8 +225,13 @@ pub(crate) struct WasmParseData<'a> {
     pub(crate) mod_name: String,
     pub(crate) func_names_map: Option<HashMap<u32, String>>,
     pub(crate) func_locals_name_map: Option<HashMap<u32, HashMap<u32, String>>>,
+    pub(crate) data_names_map: Option<HashMap<u32, String>>,
 
     pub(crate) start_function: Option<u32>,
+    pub(crate) enum_variants: Vec<(String, u32)>,
+    pub(crate) assertions: Vec<(u32, String)>,
+    pub(crate) debug_sections: HashMap<&'a str, &'a [u8]>,
+    pub(crate) code_section_start: usize,
 
     pub(crate) imports: Vec<Import<'a>>,
     pub(crate) exports: Vec<Export<'a>>,
@@ -245,7 +262,12 @@ impl WasmParseData<'_> {
             mod_name,
             func_names_map: None,
             func_locals_name_map: None,
+            data_names_map: None,
             start_function: None,
+            enum_variants: Vec::new(),
+            assertions: Vec::new(),
+            debug_sections: HashMap::new(),
+            code_section_start: 0,
             imports: Vec::new(),
             exports: Vec::new(),
             tables: Vec::new(),
@@ -331,6 +353,17 @@ impl WasmParseData<'_> {
         res.push('\n');
         res.push_str("Definition Ma of al := {|memarg_offset := of; memarg_align := al|}.\n");
         res.push('\n');
+        if !self.enum_variants.is_empty() {
+            for (variant, discriminant) in &self.enum_variants {
+                let rocq_name = variant.replace("::", "_");
+                res.push_str(&format!("Definition {rocq_name} : Z := {discriminant}.\n"));
+            }
+            res.push('\n');
+        }
+        if !self.assertions.is_empty() {
+            res.push_str(&translate_assertions(&self.assertions));
+            res.push('\n');
+        }
 
         let mut errors = Vec::new();
 
@@ -415,11 +448,21 @@ impl WasmParseData<'_> {
         created_globals.push_str(LIST_SEAL);
 
         let mut created_data_segments = String::new();
-        for data in &self.data {
+        for (index, data) in self.data.iter().enumerate() {
             match translate_data(data) {
                 Ok(translated_data) => {
                     created_data_segments.push_str("    ");
-                    created_data_segments.push_str(translated_data.as_str());
+                    if let Some(constant_name) = self.data_constant_name(index as u32) {
+                        res.push_str(
+                            format!(
+                                "Definition {constant_name} : module_data := {translated_data}.\n\n"
+                            )
+                            .as_str(),
+                        );
+                        created_data_segments.push_str(constant_name);
+                    } else {
+                        created_data_segments.push_str(translated_data.as_str());
+                    }
                     created_data_segments.push_str(LIST_EXT);
                 }
                 Err(e) => errors.push(e),
@@ -503,15 +546,45 @@ impl WasmParseData<'_> {
         Ok(res)
     }
 
+    /// Returns the name of the constant stored in a data segment.
+    ///
+    /// The Inference compiler places every struct and array constant in its own
+    /// `.rodata.NAME` segment; other segments have no constant name.
+    fn data_constant_name(&self, index: u32) -> Option<&str> {
+        self.data_names_map
+            .as_ref()?
+            .get(&index)?
+            .strip_prefix(".rodata.")
+    }
+
+    /// Turns a function name into a Rocq identifier that no translated function uses yet.
+    fn unique_rocq_identifier(&self, name: &str) -> String {
+        let identifier = rocq_identifier(name);
+        let mut unique = identifier.clone();
+        let mut suffix = 0;
+        while self.translated_function_names.contains(&unique) {
+            suffix += 1;
+            unique = format!("{identifier}_{suffix}");
+        }
+        unique
+    }
+
     //Record module_func
     fn translate_functions(&mut self) -> anyhow::Result<()> {
+        let source_map = SourceMap::new(&self.debug_sections)?;
+        // The name section uses function-space indices, where imports come first.
+        let imported_functions = self
+            .imports
+            .iter()
+            .filter(|import| matches!(import.ty, TypeRef::Func(_)))
+            .count();
         for (index, function_body) in self.function_bodies.iter().enumerate() {
             let modfunc_type = *self.function_type_indexes.get(index).unwrap_or(&0);
             let func_name = if let Some(func_names_map) = &self.func_names_map {
                 func_names_map
-                    .get(&(index as u32))
-                    .unwrap_or(&format!("func_{}", get_id()))
-                    .to_owned()
+                    .get(&((imported_functions + index) as u32))
+                    .map(|name| self.unique_rocq_identifier(name))
+                    .unwrap_or_else(|| format!("func_{}", get_id()))
             } else {
                 format!("func_{}", get_id())
             };
@@ -529,14 +602,31 @@ impl WasmParseData<'_> {
             }
             modfunc_locals.push_str("nil");
 
+            let mut location_comments = source_map.as_ref().map(|source_map| LocationComments {
+                source_map,
+                code_section_start: self.code_section_start,
+                last: None,
+            });
             let modfunc_body = match &self.func_locals_name_map {
-                Some(func_locals_name_map) => translate_expr(
+                Some(func_locals_name_map) => translate_annotated_expr(
                     &mut function_body.get_operators_reader()?,
                     func_locals_name_map.get(&modfunc_type).cloned(),
+                    &mut location_comments,
+                )?,
+                None => translate_annotated_expr(
+                    &mut function_body.get_operators_reader()?,
+                    None,
+                    &mut location_comments,
                 )?,
-                None => translate_expr(&mut function_body.get_operators_reader()?, None)?,
             };
 
+            let body_start = function_body.get_operators_reader()?.original_position();
+            if let Some(location) = source_map.as_ref().and_then(|source_map| {
+                source_map.location((body_start - self.code_section_start) as u64)
+            }) {
+                self.translated_functions_string
+                    .push_str(format!("(* {location} *)\n").as_str());
+            }
             self.translated_functions_string
                 .push_str(format!("Definition {func_name} : module_func := ").as_str());
             self.translated_functions_string.push_str(LCB);
@@ -553,6 +643,52 @@ impl WasmParseData<'_> {
     }
 }
 
+/// Translates the assertions recorded in the `inference.asserts` custom section into proof
+/// obligations.
+///
+/// A failing assertion stores its ID in linear memory and traps, so the obligations are
+/// stated over a predicate `failed_assertion` that holds for the ID observed after a trap:
+/// `assertion_N_holds` requires that assertion `N` never fails, and `assertions_hold`
+/// combines all of them.
+fn translate_assertions(assertions: &[(u32, String)]) -> String {
+    let mut res = String::new();
+    for (id, location) in assertions {
+        res.push_str(&format!("(* assert at {location} *)\n"));
+        res.push_str(&format!("Definition assertion_{id} : N := {id}%N.\n"));
+        res.push_str(&format!(
+            "Definition assertion_{id}_holds (failed_assertion : N -> Prop) : Prop := ~ failed_assertion assertion_{id}.\n"
+        ));
+    }
+    let obligations = assertions
+        .iter()
+        .map(|(id, _)| format!("assertion_{id}_holds failed_assertion"))
+        .collect::<Vec<_>>()
+        .join(" /\\ ");
+    res.push_str(&format!(
+        "Definition assertions_hold (failed_assertion : N -> Prop) : Prop :=\n  {obligations}.\n"
+    ));
+    res
+}
+
+/// Replaces every run of characters that cannot appear in a Rocq identifier with `_`.
+fn rocq_identifier(name: &str) -> String {
+    let mut identifier = String::with_capacity(name.len());
+    for character in name.chars() {
+        if character.is_ascii_alphanumeric() || character == '_' || character == '\'' {
+            identifier.push(character);
+        } else if !identifier.ends_with('_') {
+            identifier.push('_');
+        }
+    }
+    let identifier = identifier.trim_end_matches('_');
+    if identifier.starts_with(|character: char| character.is_ascii_alphabetic() || character == '_')
+    {
+        identifier.to_string()
+    } else {
+        format!("f_{identifier}")
+    }
+}
+
 //Inductive reference_type
 fn translate_ref_type(ref_type: &RefType) -> anyhow::Result<String> {
     if *ref_type == RefType::FUNCREF {
@@ -716,18 +852,63 @@ struct ConditionExpr<'a> {
 #[derive(Default)]
 struct Expression<'a> {
     parts: Vec<ExpressionPart<'a>>,
+    /// Offset of every part in the module, parallel to `parts`.
+    offsets: Vec<usize>,
     local_name_map: Option<HashMap<u32, String>>,
 }
 
-impl Expression<'_> {
+/// Source location comments for the instructions of a function body.
+struct LocationComments<'m> {
+    source_map: &'m SourceMap,
+    code_section_start: usize,
+    /// Location of the last commented instruction.
+    last: Option<&'m str>,
+}
+
+impl<'m> LocationComments<'m> {
+    /// Returns the `(* file.inf:LINE:COLUMN *)` comment for the instruction at `offset`,
+    /// unless it has the same location as the previous one.
+    fn comment(&mut self, offset: usize) -> Option<String> {
+        let location = self
+            .source_map
+            .location((offset - self.code_section_start) as u64)?;
+        if self.last == Some(location) {
+            return None;
+        }
+        self.last = Some(location);
+        Some(format!("(* {location} *)"))
+    }
+}
+
+impl<'a> Expression<'a> {
     fn last_part(&'_ self) -> Option<&'_ ExpressionPart<'_>> {
         self.parts.last()
     }
 
-    fn print_with_offset(&self, tabs_count: usize) -> anyhow::Result<String> {
+    fn push(&mut self, offset: usize, part: ExpressionPart<'a>) {
+        self.offsets.push(offset);
+        self.parts.push(part);
+    }
+
+    fn print_with_offset(
+        &self,
+        tabs_count: usize,
+        location_comments: &mut Option<LocationComments>,
+    ) -> anyhow::Result<String> {
         let mut res = String::new();
         let offset = "  ".repeat(tabs_count);
-        for part in &self.parts {
+        for (part, part_offset) in self.parts.iter().zip(&self.offsets) {
+            if !matches!(
+                part,
+                ExpressionPart::Operator(Operator::Else | Operator::End)
+            ) && let Some(comment) = location_comments
+                .as_mut()
+                .and_then(|comments| comments.comment(*part_offset))
+            {
+                res.push_str(offset.as_str());
+                res.push_str(comment.as_str());
+                res.push('\n');
+            }
             match part {
                 ExpressionPart::Operator(op) => match op {
                     Operator::Else | Operator::End => {}
@@ -743,7 +924,12 @@ impl Expression<'_> {
                         translate_basic_operator(&block.label, &self.local_name_map)?.as_str(),
                     );
                     res.push_str(" (\n");
-                    res.push_str(block.parts.print_with_offset(tabs_count + 1)?.as_str());
+                    res.push_str(
+                        block
+                            .parts
+                            .print_with_offset(tabs_count + 1, location_comments)?
+                            .as_str(),
+                    );
                     res.push_str(") ");
                     res.push_str("::\n");
                 }
@@ -753,9 +939,17 @@ impl Expression<'_> {
                         translate_basic_operator(&cond.label, &self.local_name_map)?.as_str(),
                     );
                     res.push_str(" (\n");
-                    res.push_str(cond.then_arm.print_with_offset(tabs_count + 1)?.as_str());
+                    res.push_str(
+                        cond.then_arm
+                            .print_with_offset(tabs_count + 1, location_comments)?
+                            .as_str(),
+                    );
                     res.push_str(") (\n");
-                    res.push_str(cond.else_arm.print_with_offset(tabs_count + 1)?.as_str());
+                    res.push_str(
+                        cond.else_arm
+                            .print_with_offset(tabs_count + 1, location_comments)?
+                            .as_str(),
+                    );
                     res.push_str(") ");
                     res.push_str("::\n");
                 }
@@ -771,18 +965,18 @@ impl Display for Expression<'_> {
         write!(
             f,
             "{}",
-            self.print_with_offset(2)
+            self.print_with_offset(2, &mut None)
                 .unwrap_or(String::from("Error rendering expression"))
         )
     }
 }
 
 fn translate_expression<'a>(
-    operators_reader: &mut OperatorsIterator<'a>,
+    operators_reader: &mut OperatorsIteratorWithOffsets<'a>,
 ) -> anyhow::Result<Expression<'a>> {
     let mut result = Expression::default();
     while let Some(next_operator) = operators_reader.next() {
-        let next_operator = next_operator.as_ref().unwrap();
+        let (next_operator, offset) = next_operator.as_ref().unwrap();
         match next_operator {
             inf_wasmparser::Operator::Block { .. }
             | inf_wasmparser::Operator::Loop { .. }
@@ -796,7 +990,7 @@ fn translate_expression<'a>(
                     label: next_operator.to_owned(),
                     parts: block_operations,
                 };
-                result.parts.push(ExpressionPart::Block(block));
+                result.push(*offset, ExpressionPart::Block(block));
             }
             inf_wasmparser::Operator::If { .. } => {
                 // operators_reader.next();
@@ -815,17 +1009,13 @@ fn translate_expression<'a>(
                     then_arm,
                     else_arm,
                 };
-                result.parts.push(ExpressionPart::Condition(condition));
+                result.push(*offset, ExpressionPart::Condition(condition));
             }
             inf_wasmparser::Operator::Else | inf_wasmparser::Operator::End => {
-                result
-                    .parts
-                    .push(ExpressionPart::Operator(next_operator.to_owned()));
+                result.push(*offset, ExpressionPart::Operator(next_operator.to_owned()));
                 break;
             }
-            _ => result
-                .parts
-                .push(ExpressionPart::Operator(next_operator.to_owned())),
+            _ => result.push(*offset, ExpressionPart::Operator(next_operator.to_owned())),
         }
     }
     Ok(result)
@@ -835,10 +1025,20 @@ fn translate_expr(
     operators_reader: &mut OperatorsReader,
     local_name_map: Option<HashMap<u32, String>>,
 ) -> anyhow::Result<String> {
-    let mut peekable_operators_reader = operators_reader.clone().into_iter();
+    translate_annotated_expr(operators_reader, local_name_map, &mut None)
+}
+
+/// Translates an expression like [`translate_expr`], preceding instructions with comments
+/// on their source location if `location_comments` is set.
+fn translate_annotated_expr(
+    operators_reader: &mut OperatorsReader,
+    local_name_map: Option<HashMap<u32, String>>,
+    location_comments: &mut Option<LocationComments>,
+) -> anyhow::Result<String> {
+    let mut peekable_operators_reader = operators_reader.clone().into_iter_with_offsets();
     let mut expression = translate_expression(&mut peekable_operators_reader)?;
     expression.local_name_map = local_name_map;
-    Ok(expression.to_string())
+    expression.print_with_offset(2, location_comments)
 }
 
 fn translate_block_type(block_type: &BlockType) -> anyhow::Result<String> {
@@ -48,10 +48,34 @@
 //! - **Module name**: Overrides the default module name parameter
 //! - **Function names**: Maps function indices to human-readable identifiers
 //! - **Local names**: Maps (function index, local index) to variable names
+//! - **Data segment names**: Segments named `.rodata.NAME` hold Inference constants and
+//!   become `Definition NAME : module_data` entries instead of anonymous ones
 //!
 //! This information dramatically improves readability of generated Rocq code by
 //! preserving original source-level names.
 //!
+//! ## Inference Enums Section
+//!
+//! The Inference compiler records enum discriminants in the custom "inference.enums"
+//! section as `Enum::Variant=N` lines. Each entry becomes a Rocq definition such as
+//! `Definition Color_Red : Z := 0.`, so proofs can refer to variants by name.
+//!
+//! ## Inference Asserts Section
+//!
+//! A failing `assert` stores its ID in linear memory and traps. The custom
+//! "inference.asserts" section maps every ID to the source location of its `assert` as
+//! `ID=LINE:COLUMN` lines, or `ID=MODULE:LINE:COLUMN` for an `assert` in a module such as
+//! `math::ops`. Each entry becomes a proof obligation `assertion_ID_holds`
+//! instead of being left as an opaque trap.
+//!
+//! ## DWARF Sections
+//!
+//! When compiled with debug info, the module carries DWARF line tables in `.debug_*`
+//! custom sections. They are collected as they are, together with the start of the code
+//! section that DWARF addresses are relative to, and function bodies are annotated with
+//! `(* file.inf:LINE:COLUMN *)` comments wherever the source location changes; see
+//! [`crate::source_map`].
+//!
 //! ## Component Model Sections
 //!
 //! WebAssembly component model sections are recognized but generate empty stubs:
@@ -94,7 +118,13 @@ use inf_wasmparser::{
 };
 use std::{collections::HashMap, io::Read};
 
-use crate::translator::WasmParseData;
+use crate::{source_map::DEBUG_SECTION_PREFIX, translator::WasmParseData};
+
+/// Name of the custom section in which the Inference compiler records enum discriminants.
+const ENUMS_SECTION_NAME: &str = "inference.enums";
+
+/// Name of the custom section in which the Inference compiler records assertion locations.
+const ASSERTS_SECTION_NAME: &str = "inference.asserts";
 
 /// Translates WebAssembly bytecode into Rocq (Coq) formal verification code.
 ///
@@ -184,7 +214,8 @@ pub fn translate_bytes(mod_name: &str, bytes: &[u8]) -> anyhow::Result<String> {
 /// - **Element Section**: Table element initialization
 /// - **Data Section**: Memory initialization data
 /// - **Code Section**: Function bodies with local variables and instructions
-/// - **Custom Section**: Name mappings for functions and local variables (debug info)
+/// - **Custom Section**: Name mappings for functions and local variables, and DWARF line
+///   tables (debug info)
 ///
 /// Unsupported sections (component model, tags, unknown sections) are silently ignored.
 ///
@@ -268,7 +299,9 @@ fn parse(mod_name: String, data: &'_ [u8]) -> anyhow::Result<WasmParseData<'_>>
             // `CodeSectionEntry`, so we can prepare for that, and
             // afterwards we can parse and handle each function
             // individually.
-            CodeSectionStart { .. } => {}
+            CodeSectionStart { range, .. } => {
+                wasm_parse_data.code_section_start = range.start;
+            }
             CodeSectionEntry(body) => {
                 wasm_parse_data.function_bodies.push(body);
             }
@@ -287,7 +320,17 @@ fn parse(mod_name: String, data: &'_ [u8]) -> anyhow::Result<WasmParseData<'_>>
             ComponentExportSection(_) => { /* ... */ }
 
             CustomSection(custom_section) => {
-                if let inf_wasmparser::KnownCustom::Name(name_section) = custom_section.as_known() {
+                if custom_section.name() == ENUMS_SECTION_NAME {
+                    wasm_parse_data.enum_variants = parse_enum_variants(custom_section.data())?;
+                } else if custom_section.name() == ASSERTS_SECTION_NAME {
+                    wasm_parse_data.assertions = parse_assertions(custom_section.data())?;
+                } else if custom_section.name().starts_with(DEBUG_SECTION_PREFIX) {
+                    wasm_parse_data
+                        .debug_sections
+                        .insert(custom_section.name(), custom_section.data());
+                } else if let inf_wasmparser::KnownCustom::Name(name_section) =
+                    custom_section.as_known()
+                {
                     for name in name_section {
                         let name = name?;
                         match name {
@@ -305,6 +348,17 @@ fn parse(mod_name: String, data: &'_ [u8]) -> anyhow::Result<WasmParseData<'_>>
                                     wasm_parse_data.func_names_map = Some(func_names_map);
                                 }
                             }
+                            inf_wasmparser::Name::Data(data_names) => {
+                                let mut data_names_map = HashMap::new();
+                                for data_name in data_names {
+                                    let data_name = data_name?;
+                                    data_names_map
+                                        .insert(data_name.index, data_name.name.to_string());
+                                }
+                                if !data_names_map.is_empty() {
+                                    wasm_parse_data.data_names_map = Some(data_names_map);
+                                }
+                            }
                             inf_wasmparser::Name::Local(locals) => {
                                 let mut func_locals_name_map: HashMap<u32, HashMap<u32, String>> =
                                     HashMap::new();
@@ -343,3 +397,34 @@ fn parse(mod_name: String, data: &'_ [u8]) -> anyhow::Result<WasmParseData<'_>>
     }
     Ok(wasm_parse_data)
 }
+
+/// Parses the `inference.enums` custom section emitted by the Inference compiler.
+///
+/// The section is UTF-8 text with one `Enum::Variant=N` line per enum variant.
+fn parse_enum_variants(data: &[u8]) -> anyhow::Result<Vec<(String, u32)>> {
+    let text = std::str::from_utf8(data)?;
+    text.lines()
+        .map(|line| {
+            let (variant, discriminant) = line
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Malformed enum variant entry `{line}`"))?;
+            Ok((variant.to_string(), discriminant.parse()?))
+        })
+        .collect()
+}
+
+/// Parses the `inference.asserts` custom section emitted by the Inference compiler.
+///
+/// The section is UTF-8 text with one `ID=LINE:COLUMN` or `ID=MODULE:LINE:COLUMN` line
+/// per `assert` statement; the location is kept as it is.
+fn parse_assertions(data: &[u8]) -> anyhow::Result<Vec<(u32, String)>> {
+    let text = std::str::from_utf8(data)?;
+    text.lines()
+        .map(|line| {
+            let (id, location) = line
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Malformed assertion entry `{line}`"))?;
+            Ok((id.parse()?, location.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,200 @@
+//! Tests for the control flow checks of the analyze phase
+//!
+//! This module contains tests verifying:
+//! - Functions returning a value must return it on every path
+//! - `break` is only allowed inside a loop
+//! - Statements after `return`, `break` or a never-ending `loop` are reported
+//! - Methods and functions of nested modules are analyzed too
+
+use crate::utils::{build_ast, build_ast_with_modules};
+use inference_ast::arena::Arena;
+use inference_type_checker::TypeCheckerBuilder;
+
+fn try_analyze_arena(arena: Arena) -> anyhow::Result<()> {
+    let typed_context = TypeCheckerBuilder::build_typed_context(arena)?.typed_context();
+    inference::analyze(&typed_context)
+}
+
+fn try_analyze(source: &str) -> anyhow::Result<()> {
+    try_analyze_arena(build_ast(source.to_string()))
+}
+
+fn analysis_error(source: &str) -> String {
+    match try_analyze(source) {
+        Ok(()) => panic!("Expected an analysis error for:\n{source}"),
+        Err(error) => error.to_string(),
+    }
+}
+
+fn assert_analyzes(source: &str) {
+    let result = try_analyze(source);
+    assert!(
+        result.is_ok(),
+        "Analysis should succeed for:\n{source}\ngot: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn function_returning_on_every_path_is_accepted() {
+    assert_analyzes(r#"fn test(a: bool) -> i32 { if a { return 1; } else { return 2; } }"#);
+}
+
+#[test]
+fn unit_function_without_return_is_accepted() {
+    assert_analyzes(r#"fn test(a: i32) { let b: i32 = a; }"#);
+}
+
+#[test]
+fn spec_constructor_without_return_is_accepted() {
+    assert_analyzes(r#"spec Empty { } fn constructor() -> Empty { }"#);
+}
+
+#[test]
+fn missing_return_at_end_of_body_errors() {
+    let error_msg = analysis_error(r#"fn test(a: i32) -> i32 { let b: i32 = a; }"#);
+    assert!(
+        error_msg.contains("1:1: not all paths return a value in function `test`"),
+        "Error should name the function, got: {error_msg}"
+    );
+}
+
+#[test]
+fn missing_return_in_if_without_else_errors() {
+    let error_msg = analysis_error(r#"fn test(a: bool) -> i32 { if a { return 1; } }"#);
+    assert!(
+        error_msg.contains("not all paths return a value in function `test`"),
+        "An `if` without `else` may complete, got: {error_msg}"
+    );
+}
+
+#[test]
+fn missing_return_in_one_arm_errors() {
+    let error_msg = analysis_error(
+        r#"fn test(a: bool) -> i32 { if a { return 1; } else { let b: i32 = 2; } }"#,
+    );
+    assert!(
+        error_msg.contains("not all paths return a value in function `test`"),
+        "Error should report the arm falling through, got: {error_msg}"
+    );
+}
+
+#[test]
+fn loop_without_condition_and_break_never_completes() {
+    assert_analyzes(r#"fn test() -> i32 { loop { return 1; } }"#);
+}
+
+#[test]
+fn loop_left_with_break_requires_return_after_it() {
+    let error_msg = analysis_error(r#"fn test() -> i32 { loop { break; } }"#);
+    assert!(
+        error_msg.contains("not all paths return a value in function `test`"),
+        "A `loop` with a reachable `break` completes, got: {error_msg}"
+    );
+    assert_analyzes(r#"fn test() -> i32 { loop { break; } return 1; }"#);
+}
+
+#[test]
+fn loop_with_condition_may_complete() {
+    let error_msg = analysis_error(r#"fn test(a: bool) -> i32 { loop a { return 1; } }"#);
+    assert!(
+        error_msg.contains("not all paths return a value in function `test`"),
+        "A `loop` with a condition may not run, got: {error_msg}"
+    );
+}
+
+#[test]
+fn break_outside_loop_errors() {
+    let error_msg = analysis_error(r#"fn test() { break; }"#);
+    assert!(
+        error_msg.contains("1:13: `break` outside of a loop"),
+        "Error should point to the `break`, got: {error_msg}"
+    );
+}
+
+#[test]
+fn break_in_if_inside_loop_is_accepted() {
+    assert_analyzes(r#"fn test(a: bool) { loop { if a { break; } } }"#);
+}
+
+#[test]
+fn break_in_nested_loop_leaves_inner_loop_only() {
+    let error_msg = analysis_error(r#"fn test() -> i32 { loop { loop { break; } } return 1; }"#);
+    assert!(
+        error_msg.contains("unreachable statement"),
+        "The outer `loop` never completes, got: {error_msg}"
+    );
+}
+
+#[test]
+fn statement_after_return_is_unreachable() {
+    let error_msg = analysis_error(r#"fn test() -> i32 { return 1; let a: i32 = 2; return a; }"#);
+    assert!(
+        error_msg.contains("1:30: unreachable statement, control never gets past 1:20"),
+        "Error should point to the statement and the `return`, got: {error_msg}"
+    );
+    assert_eq!(
+        error_msg.matches("unreachable statement").count(),
+        1,
+        "Only the first unreachable statement should be reported, got: {error_msg}"
+    );
+}
+
+#[test]
+fn statement_after_break_is_unreachable() {
+    let error_msg = analysis_error(r#"fn test() { loop { break; let a: i32 = 1; } }"#);
+    assert!(
+        error_msg.contains("unreachable statement"),
+        "Statement after `break` should be unreachable, got: {error_msg}"
+    );
+}
+
+#[test]
+fn statement_after_if_returning_in_both_arms_is_unreachable() {
+    let error_msg = analysis_error(
+        r#"fn test(a: bool) -> i32 { if a { return 1; } else { return 2; } return 3; }"#,
+    );
+    assert!(
+        error_msg.contains("unreachable statement"),
+        "Statement after a diverging `if` should be unreachable, got: {error_msg}"
+    );
+}
+
+#[test]
+fn errors_from_several_functions_are_reported_together() {
+    let error_msg = analysis_error(r#"fn first() -> i32 { } fn second() { break; }"#);
+    assert!(
+        error_msg.contains("`first`") && error_msg.contains("`break` outside of a loop"),
+        "Errors of every function should be reported, got: {error_msg}"
+    );
+}
+
+#[test]
+fn methods_are_analyzed() {
+    let error_msg = analysis_error(
+        r#"struct Counter { value: i32; fn get(self) -> i32 { let v: i32 = self.value; } }"#,
+    );
+    assert!(
+        error_msg.contains("not all paths return a value in function `get`"),
+        "Methods should be analyzed, got: {error_msg}"
+    );
+}
+
+#[test]
+fn functions_of_nested_modules_are_analyzed() {
+    let arena = build_ast_with_modules(
+        "pub fn main() -> i32 { return math::one(); }",
+        &[(
+            "math",
+            "pub fn one() -> i32 { break; return 1; }".to_string(),
+        )],
+    );
+    let error_msg = match try_analyze_arena(arena) {
+        Ok(()) => panic!("Expected an analysis error in module `math`"),
+        Err(error) => error.to_string(),
+    };
+    assert!(
+        error_msg.contains("`break` outside of a loop"),
+        "Module functions should be analyzed, got: {error_msg}"
+    );
+}
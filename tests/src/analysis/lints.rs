@@ -0,0 +1,225 @@
+//! Tests for the lints reported by `analyze_with_lints`
+//!
+//! This module contains tests verifying:
+//! - Each lint warns about the code it targets and stays quiet otherwise
+//! - Lint levels allow, warn about or deny lints
+//! - Analysis errors are reported as error diagnostics alongside warnings
+
+use crate::utils::{build_ast, build_ast_with_modules};
+use inference::{Diagnostic, Lint, LintConfig, LintLevel, Severity};
+use inference_type_checker::TypeCheckerBuilder;
+
+fn diagnostics_with(source: &str, lints: &LintConfig) -> Vec<Diagnostic> {
+    let arena = build_ast(source.to_string());
+    let typed_context = TypeCheckerBuilder::build_typed_context(arena)
+        .unwrap_or_else(|error| panic!("Type checking should succeed for:\n{source}\ngot: {error}"))
+        .typed_context();
+    inference::analyze_with_lints(&typed_context, lints)
+}
+
+fn diagnostics(source: &str) -> Vec<String> {
+    diagnostics_with(source, &LintConfig::default())
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn lint_messages(source: &str, lint: Lint) -> Vec<String> {
+    diagnostics_with(source, &LintConfig::default())
+        .iter()
+        .filter(|diagnostic| diagnostic.lint == Some(lint))
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn unused_variable_warns() {
+    let messages = lint_messages(
+        r#"pub fn test() -> i32 {
+    let unused: i32 = 1;
+    let mut overwritten: i32 = 0;
+    overwritten = 2;
+    let read: i32 = 3;
+    return read;
+}"#,
+        Lint::UnusedVariable,
+    );
+    assert_eq!(
+        messages,
+        [
+            "2:9: warning: unused variable `unused` [unused_variable]",
+            "3:13: warning: unused variable `overwritten` [unused_variable]",
+        ]
+    );
+}
+
+#[test]
+fn variable_read_in_nested_block_or_field_assignment_is_used() {
+    let messages = lint_messages(
+        r#"struct Point { x: i32; y: i32; }
+pub fn test(a: bool) -> i32 {
+    let mut count: i32 = 0;
+    let mut p: Point = Point { x: 1, y: 2 };
+    let _ignored: i32 = 5;
+    p.x = 3;
+    if a { count = count + 1; }
+    return count;
+}"#,
+        Lint::UnusedVariable,
+    );
+    assert!(
+        messages.is_empty(),
+        "No variable is unused, got: {messages:?}"
+    );
+}
+
+#[test]
+fn unused_import_warns_for_each_name() {
+    let arena = build_ast_with_modules(
+        "use shapes::{Point, Line}; pub fn test() -> i32 { return 0; }",
+        &[(
+            "shapes",
+            "pub struct Point { x: i32; } pub struct Line { x: i32; }".to_string(),
+        )],
+    );
+    let typed_context = TypeCheckerBuilder::build_typed_context(arena)
+        .unwrap()
+        .typed_context();
+    let messages: Vec<String> = typed_context
+        .diagnostics()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "1:14: warning: unused import `Point` [unused_import]",
+            "1:21: warning: unused import `Line` [unused_import]",
+        ]
+    );
+}
+
+#[test]
+fn unused_private_function_warns() {
+    let messages = lint_messages(
+        r#"fn recursive(n: i32) -> i32 { return recursive(n); }
+fn called() -> i32 { return 1; }
+fn main() -> i32 { return called(); }
+pub fn exported() -> i32 { return 0; }"#,
+        Lint::UnusedPrivateFunction,
+    );
+    assert_eq!(
+        messages,
+        ["1:4: warning: function `recursive` is never used [unused_private_function]"]
+    );
+}
+
+#[test]
+fn spec_functions_are_not_unused() {
+    let messages = lint_messages(
+        r#"spec Counter { external fn next() -> i32; }
+fn counter() -> Counter { }
+fn next() -> i32 { return 1; }"#,
+        Lint::UnusedPrivateFunction,
+    );
+    assert!(
+        messages.is_empty(),
+        "Spec functions and constructors are required by the spec, got: {messages:?}"
+    );
+}
+
+#[test]
+fn shadowed_binding_warns_with_note() {
+    let messages = lint_messages(
+        r#"const LIMIT: i32 = 10;
+pub fn test(a: i32) -> i32 {
+    let LIMIT: i32 = a;
+    if a > LIMIT {
+        let a: i32 = 2;
+        return a;
+    }
+    return LIMIT;
+}"#,
+        Lint::ShadowedBinding,
+    );
+    assert_eq!(
+        messages,
+        [
+            "3:9: warning: `LIMIT` shadows a binding of an enclosing scope [shadowed_binding]\n  1:1: note: `LIMIT` was first bound here",
+            "5:13: warning: `a` shadows a binding of an enclosing scope [shadowed_binding]\n  2:13: note: `a` was first bound here",
+        ]
+    );
+}
+
+#[test]
+fn constant_condition_warns() {
+    let messages = lint_messages(
+        r#"pub fn test(a: i32) -> i32 {
+    if true { return 1; }
+    if (1 + 2) * 3 > 10 || !false { return 2; }
+    if a > 1 { return 3; }
+    return 0;
+}"#,
+        Lint::ConstantCondition,
+    );
+    assert_eq!(
+        messages,
+        [
+            "2:8: warning: condition is always `true` [constant_condition]",
+            "3:8: warning: condition is always `true` [constant_condition]",
+        ]
+    );
+}
+
+#[test]
+fn implicit_type_parameter_warns_for_lowercase_names() {
+    let messages = lint_messages(
+        r#"struct Pair { fst: A; snd: [Item; 2]; }
+struct Typo { x: i3; y: i3; }
+pub fn test(t: Typo u8', p: Pair i32' bool') -> u8 { return t.x; }"#,
+        Lint::ImplicitTypeParameter,
+    );
+    assert_eq!(
+        messages,
+        [
+            "2:18: warning: `i3` is not a type, so struct `Typo` is generic over it [implicit_type_parameter]"
+        ]
+    );
+}
+
+#[test]
+fn lint_levels_allow_and_deny_lints() {
+    let source = r#"pub fn test() -> i32 {
+    let unused: i32 = 1;
+    if false { return 1; }
+    return 0;
+}"#;
+    let mut lints = LintConfig::default();
+    lints.set(Lint::UnusedVariable, LintLevel::Allow);
+    lints.set(Lint::ConstantCondition, LintLevel::Deny);
+    let diagnostics = diagnostics_with(source, &lints);
+    assert_eq!(diagnostics.len(), 1, "Got: {diagnostics:?}");
+    assert_eq!(diagnostics[0].lint, Some(Lint::ConstantCondition));
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "3:8: error: condition is always `false` [constant_condition]"
+    );
+}
+
+#[test]
+fn analysis_errors_are_reported_among_warnings() {
+    let messages = diagnostics(
+        r#"pub fn test() {
+    let unused: i32 = 1;
+    break;
+}"#,
+    );
+    assert_eq!(
+        messages,
+        [
+            "2:9: warning: unused variable `unused` [unused_variable]",
+            "3:5: error: `break` outside of a loop",
+        ]
+    );
+}
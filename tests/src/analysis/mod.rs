@@ -0,0 +1,3 @@
+mod control_flow;
+mod nondeterminism;
+mod lints;
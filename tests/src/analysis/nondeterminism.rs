@@ -0,0 +1,279 @@
+//! Tests for the non-determinism checks of the analyze phase
+//!
+//! This module contains tests verifying:
+//! - `@` is only accepted as the whole value of a definition, an assignment or a `return`
+//! - `assume` blocks cannot assign to variables declared outside of them, nor call their
+//!   `mut self` methods
+//! - `return` and `break` cannot leave `forall`, `exists` or `unique` blocks
+//! - Exported functions cannot return values derived from `@` outside of a quantifier
+
+use crate::utils::{build_ast, build_ast_with_modules};
+use inference_type_checker::TypeCheckerBuilder;
+
+fn try_analyze(source: &str) -> anyhow::Result<()> {
+    let arena = build_ast(source.to_string());
+    let typed_context = TypeCheckerBuilder::build_typed_context(arena)?.typed_context();
+    inference::analyze(&typed_context)
+}
+
+fn analysis_error(source: &str) -> String {
+    match try_analyze(source) {
+        Ok(()) => panic!("Expected an analysis error for:\n{source}"),
+        Err(error) => error.to_string(),
+    }
+}
+
+fn assert_analyzes(source: &str) {
+    let result = try_analyze(source);
+    assert!(
+        result.is_ok(),
+        "Analysis should succeed for:\n{source}\ngot: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn uzumaki_bound_to_a_variable_is_accepted() {
+    assert_analyzes(
+        r#"struct Point { x: i32; y: i32; }
+pub fn test() {
+    forall {
+        let mut a: i32 = @;
+        let p: Point = @;
+        a = @;
+        assert a == a;
+    }
+}
+fn any() -> u8 { return @; }"#,
+    );
+}
+
+#[test]
+fn uzumaki_as_operand_errors() {
+    let error_msg = analysis_error(r#"fn test() { forall { let a: i32 = @ + 1; } }"#);
+    assert!(
+        error_msg.contains(
+            "1:35: `@` can only be the value of a variable definition, an assignment or a `return`"
+        ),
+        "Error should point at `@`, got: {error_msg}"
+    );
+}
+
+#[test]
+fn uzumaki_as_condition_or_argument_errors() {
+    let error_msg = analysis_error(
+        r#"fn check(a: i32) { }
+fn test() { forall { if @ { check(@); } } }"#,
+    );
+    assert!(
+        error_msg.contains("2:25: `@` can only be") && error_msg.contains("2:35: `@` can only be"),
+        "Both uses should be reported, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assume_may_call_functions_and_bind_its_own_variables() {
+    assert_analyzes(
+        r#"fn positive(a: i32) { assert a > 0; }
+fn test() {
+    forall {
+        let a: i32 = @;
+        assume {
+            let mut b: i32 = a;
+            b = b + 1;
+            positive(b);
+        }
+    }
+}"#,
+    );
+}
+
+#[test]
+fn assignment_to_outer_variable_in_assume_errors() {
+    let error_msg = analysis_error(
+        r#"fn test() {
+    let mut count: i32 = 0;
+    assume {
+        count = 1;
+    }
+}"#,
+    );
+    assert!(
+        error_msg
+            .contains("4:9: `assume` block assigns to `count`, which is declared outside of it"),
+        "Error should name the variable, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_to_parameter_field_in_assume_errors() {
+    let error_msg = analysis_error(
+        r#"struct Point { x: i32; y: i32; }
+fn test(mut p: Point) { assume { p.x = 1; } }"#,
+    );
+    assert!(
+        error_msg.contains("`assume` block assigns to `p`, which is declared outside of it"),
+        "Error should name the parameter, got: {error_msg}"
+    );
+}
+
+#[test]
+fn mut_self_method_call_on_outer_variable_in_assume_errors() {
+    let error_msg = analysis_error(
+        r#"struct Counter { value: i32; fn inc(mut self) { self.value = self.value + 1; } }
+fn test() {
+    let mut c: Counter = Counter { value: 0 };
+    assume {
+        c.inc();
+    }
+}"#,
+    );
+    assert!(
+        error_msg.contains("5:9: `assume` block assigns to `c`, which is declared outside of it"),
+        "Error should point at the call, got: {error_msg}"
+    );
+}
+
+#[test]
+fn mut_self_method_call_through_self_in_assume_errors() {
+    let error_msg = analysis_error(
+        r#"struct Counter {
+    value: i32;
+    fn inc(mut self) { self.value = self.value + 1; }
+    fn check(mut self) { assume { self.inc(); } }
+}"#,
+    );
+    assert!(
+        error_msg.contains("4:35: `assume` block assigns to `self`, which is declared outside"),
+        "Error should name `self`, got: {error_msg}"
+    );
+}
+
+#[test]
+fn mut_self_method_of_a_module_in_assume_errors() {
+    let arena = build_ast_with_modules(
+        "pub fn main() {}",
+        &[(
+            "counters",
+            r#"struct Counter {
+    value: i32;
+    fn inc(mut self) { self.value = self.value + 1; }
+}
+pub fn test() {
+    let mut c: Counter = Counter { value: 0 };
+    assume {
+        c.inc();
+    }
+}"#
+            .to_string(),
+        )],
+    );
+    let typed_context = TypeCheckerBuilder::build_typed_context(arena)
+        .unwrap()
+        .typed_context();
+    let error_msg = inference::analyze(&typed_context)
+        .expect_err("Calling a `mut self` method of a module in `assume` should fail")
+        .to_string();
+    assert!(
+        error_msg.contains("8:9: `assume` block assigns to `c`, which is declared outside"),
+        "Error should point at the call, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assume_may_call_methods_that_do_not_change_outer_variables() {
+    assert_analyzes(
+        r#"struct Counter {
+    value: i32;
+    fn inc(mut self) { self.value = self.value + 1; }
+    fn get(self) -> i32 { return self.value; }
+}
+fn test() {
+    let c: Counter = Counter { value: 0 };
+    assume {
+        let mut d: Counter = Counter { value: c.get() };
+        d.inc();
+        assert d.get() > 0;
+    }
+}"#,
+    );
+}
+
+#[test]
+fn return_inside_quantifier_errors() {
+    let error_msg = analysis_error(
+        r#"fn test(a: bool) -> i32 {
+    exists {
+        if a { return 1; }
+    }
+    return 0;
+}"#,
+    );
+    assert!(
+        error_msg.contains("3:16: `return` cannot leave an `exists` block"),
+        "Error should name the block, got: {error_msg}"
+    );
+}
+
+#[test]
+fn break_out_of_quantifier_errors() {
+    let error_msg = analysis_error(r#"fn test() { loop { unique { break; } } }"#);
+    assert!(
+        error_msg.contains("1:29: `break` cannot leave a `unique` block"),
+        "Error should name the block, got: {error_msg}"
+    );
+}
+
+#[test]
+fn break_of_loop_inside_quantifier_is_accepted() {
+    assert_analyzes(r#"fn test() { forall { let a: bool = @; loop { if a { break; } } } }"#);
+}
+
+#[test]
+fn exported_function_returning_uzumaki_errors() {
+    let error_msg = analysis_error(r#"pub fn test() -> i32 { return @; }"#);
+    assert!(
+        error_msg.contains(
+            "1:24: exported function `test` returns a non-deterministic value outside of a quantifier"
+        ),
+        "Error should name the function, got: {error_msg}"
+    );
+}
+
+#[test]
+fn nondeterminism_flows_through_variables_and_loops() {
+    let error_msg = analysis_error(
+        r#"pub fn test(n: i32) -> i32 {
+    let mut result: i32 = 0;
+    let mut next: i32 = 0;
+    loop n > result {
+        result = next + 1;
+        next = @;
+    }
+    return result * 2;
+}"#,
+    );
+    assert!(
+        error_msg.contains("8:5: exported function `test` returns a non-deterministic value"),
+        "Error should point at the return, got: {error_msg}"
+    );
+}
+
+#[test]
+fn values_bound_inside_quantifier_are_not_nondeterministic() {
+    assert_analyzes(
+        r#"pub fn test() -> i32 {
+    let mut result: i32 = 0;
+    forall {
+        let a: i32 = @;
+        result = a;
+    }
+    return result;
+}"#,
+    );
+}
+
+#[test]
+fn private_function_may_return_nondeterministic_value() {
+    assert_analyzes(r#"fn test() -> i32 { let a: i32 = @; return a; }"#);
+}
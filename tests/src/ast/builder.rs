@@ -477,6 +477,25 @@ fn test_parse_variable_declaration_no_init() {
     assert_variable_def(&arena, "x");
 }
 
+#[test]
+fn test_parse_mut_variable_declaration() {
+    let source = r#"fn test() { let mut x: i32 = 5; let y: i32 = 6; }"#;
+    let arena = build_ast(source.to_string());
+    let mut mutability: Vec<(String, bool)> = arena
+        .filter_nodes(|node| matches!(node, AstNode::Statement(Statement::VariableDefinition(_))))
+        .iter()
+        .filter_map(|node| match node {
+            AstNode::Statement(Statement::VariableDefinition(v)) => Some((v.name(), v.is_mut)),
+            _ => None,
+        })
+        .collect();
+    mutability.sort();
+    assert_eq!(
+        mutability,
+        [("x".to_string(), true), ("y".to_string(), false)]
+    );
+}
+
 #[test]
 fn test_parse_assignment() {
     let source = r#"fn test() { x = 10; }"#;
@@ -768,38 +787,118 @@ fn test_parse_chained_member_access() {
 
     let member_accesses =
         arena.filter_nodes(|node| matches!(node, AstNode::Expression(Expression::MemberAccess(_))));
-    assert!(
-        !member_accesses.is_empty(),
-        "Should find at least 1 member access"
+    assert_eq!(member_accesses.len(), 2, "Should find 2 member accesses");
+
+    // `filter_nodes` does not keep source order, so find the outermost access by its shape
+    let outermost = member_accesses
+        .iter()
+        .find_map(|node| match node {
+            AstNode::Expression(Expression::MemberAccess(ma))
+                if matches!(&*ma.expression.borrow(), Expression::MemberAccess(_)) =>
+            {
+                Some(ma.clone())
+            }
+            _ => None,
+        })
+        .expect("One member access should access a member of the other");
+    assert_eq!(
+        outermost.name.name, "subfield",
+        "Outermost member access should be 'subfield'"
     );
+    let Expression::MemberAccess(inner) = &*outermost.expression.borrow() else {
+        panic!("Object of 'subfield' should be a member access");
+    };
+    assert_eq!(inner.name.name, "field");
+    assert!(matches!(&*inner.expression.borrow(), Expression::Identifier(id) if id.name == "obj"));
+}
 
-    if let AstNode::Expression(Expression::MemberAccess(ma)) = &member_accesses[0] {
+#[test]
+fn test_parse_struct_expression() {
+    let source = r#"fn test() -> Point { return Point { x: 1, y: 2 }; }"#;
+    let arena = build_ast(source.to_string());
+    assert_eq!(arena.source_files().len(), 1, "Should have 1 source file");
+
+    let struct_exprs =
+        arena.filter_nodes(|node| matches!(node, AstNode::Expression(Expression::Struct(_))));
+    assert_eq!(struct_exprs.len(), 1, "Should find 1 struct expression");
+
+    if let AstNode::Expression(Expression::Struct(se)) = &struct_exprs[0] {
+        assert_eq!(se.name.name, "Point", "Struct expression should be 'Point'");
+        let field_names: Vec<&str> = se
+            .fields
+            .iter()
+            .flatten()
+            .map(|(name, _)| name.name.as_str())
+            .collect();
         assert_eq!(
-            ma.name.name, "subfield",
-            "Outermost member access should be 'subfield'"
+            field_names,
+            ["x", "y"],
+            "Struct expression should initialize x and y"
         );
     } else {
-        panic!("Expected member access expression");
+        panic!("Expected struct expression");
     }
 }
 
 #[test]
-fn test_parse_struct_expression() {
-    let source = r#"fn test() -> Point { return Point { x: 1, y: 2 }; }"#;
+fn test_parse_generic_struct_expression() {
+    let source =
+        r#"fn test() -> Pair i32' bool' { return Pair i32' bool' { fst: 1, snd: true }; }"#;
     let arena = build_ast(source.to_string());
-    assert_eq!(arena.source_files().len(), 1, "Should have 1 source file");
 
     let struct_exprs =
         arena.filter_nodes(|node| matches!(node, AstNode::Expression(Expression::Struct(_))));
     assert_eq!(struct_exprs.len(), 1, "Should find 1 struct expression");
 
     if let AstNode::Expression(Expression::Struct(se)) = &struct_exprs[0] {
-        assert_eq!(se.name.name, "Point", "Struct expression should be 'Point'");
+        assert_eq!(
+            se.name.name, "Pair",
+            "Struct expression should name the base type"
+        );
+        let type_parameters: Vec<&str> = se
+            .type_parameters
+            .iter()
+            .flatten()
+            .map(|parameter| parameter.name.as_str())
+            .collect();
+        assert_eq!(
+            type_parameters,
+            ["i32", "bool"],
+            "Struct expression should keep its type arguments"
+        );
     } else {
         panic!("Expected struct expression");
     }
 }
 
+#[test]
+fn test_parse_function_call_with_type_arguments() {
+    let source = r#"fn test(x: i32) -> i64 { return cast i64'(x); }"#;
+    let arena = build_ast(source.to_string());
+
+    let calls =
+        arena.filter_nodes(|node| matches!(node, AstNode::Expression(Expression::FunctionCall(_))));
+    assert_eq!(calls.len(), 1, "Should find 1 function call");
+
+    if let AstNode::Expression(Expression::FunctionCall(call)) = &calls[0] {
+        assert_eq!(call.name(), "cast", "Callee should be the base name");
+        let type_parameters: Vec<&str> = call
+            .type_parameters
+            .iter()
+            .flatten()
+            .map(|parameter| parameter.name.as_str())
+            .collect();
+        assert_eq!(
+            type_parameters,
+            ["i64"],
+            "Call should keep its type arguments"
+        );
+        assert_eq!(call.arguments.as_ref().map(Vec::len), Some(1));
+    } else {
+        panic!("Expected function call expression");
+    }
+}
+
 #[test]
 fn test_parse_external_function() {
     let source = r#"external fn sorting_function(Address, Address) -> Address;"#;
@@ -723,6 +723,21 @@ fn test_parse_external_function_with_return() {
     }
 }
 
+#[test]
+fn test_parse_external_function_with_arguments() {
+    let source = r#"external fn sum(a: i32, b: i32) -> i32;"#;
+    let arena = build_ast(source.to_string());
+
+    let ext_funcs = arena
+        .filter_nodes(|node| matches!(node, AstNode::Definition(Definition::ExternalFunction(_))));
+    assert_eq!(ext_funcs.len(), 1);
+
+    if let AstNode::Definition(Definition::ExternalFunction(ext_func)) = &ext_funcs[0] {
+        let args = ext_func.arguments.as_ref().expect("Should have arguments");
+        assert_eq!(args.len(), 2, "Should find 2 arguments");
+    }
+}
+
 #[test]
 fn test_parse_external_function_basic() {
     let source = r#"external fn do_something();"#;
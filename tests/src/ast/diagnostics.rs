@@ -0,0 +1,55 @@
+use inference_ast::diagnostics::{Diagnostic, Lint, LintConfig, LintLevel, Severity};
+use inference_ast::nodes::Location;
+
+fn location(line: u32, column: u32) -> Location {
+    Location::new(0, 0, line, column, line, column)
+}
+
+#[test]
+fn test_lint_names_round_trip() {
+    for lint in Lint::ALL {
+        assert_eq!(Lint::from_name(lint.name()), Some(lint));
+    }
+    assert_eq!(Lint::from_name("unused"), None);
+    assert_eq!(LintLevel::from_name("deny"), Some(LintLevel::Deny));
+    assert_eq!(LintLevel::from_name("forbid"), None);
+}
+
+#[test]
+fn test_diagnostic_display_shows_severity_lint_and_notes() {
+    let diagnostic = Diagnostic::lint(
+        Lint::ShadowedBinding,
+        location(3, 5),
+        "`x` shadows an earlier binding",
+    )
+    .with_note(location(1, 9), "`x` is first bound here");
+    assert_eq!(
+        diagnostic.to_string(),
+        "3:5: warning: `x` shadows an earlier binding [shadowed_binding]\n  1:9: note: `x` is first bound here"
+    );
+}
+
+#[test]
+fn test_diagnostic_from_error_drops_the_location_prefix() {
+    let diagnostic = Diagnostic::from_error(location(2, 1), &"2:1: something went wrong");
+    assert_eq!(diagnostic.message, "something went wrong");
+    assert!(diagnostic.is_error());
+}
+
+#[test]
+fn test_lint_config_allows_and_denies_lints() {
+    let mut config = LintConfig::default();
+    config.set(Lint::UnusedVariable, LintLevel::Allow);
+    config.set(Lint::UnusedImport, LintLevel::Deny);
+    let diagnostics = config.apply(vec![
+        Diagnostic::lint(Lint::UnusedVariable, location(1, 1), "unused"),
+        Diagnostic::lint(Lint::UnusedImport, location(2, 1), "unused import"),
+        Diagnostic::lint(Lint::ConstantCondition, location(3, 1), "constant"),
+        Diagnostic::error(location(4, 1), "broken"),
+    ]);
+    let severities: Vec<Severity> = diagnostics.iter().map(|d| d.severity).collect();
+    assert_eq!(
+        severities,
+        [Severity::Error, Severity::Warning, Severity::Error]
+    );
+}
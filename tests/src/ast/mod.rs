@@ -1,5 +1,6 @@
 mod arena;
 mod builder;
 mod builder_features;
+mod diagnostics;
 mod nodes;
 mod primitive_type;
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod arrays_codegen_tests {
+    use crate::utils::{call_wasm_export, get_test_file_path, instantiate_wasm, wasm_codegen};
+
+    fn compile(test_name: &str) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        wasm_codegen(&source_code)
+    }
+
+    fn assert_calls_return(wasm_bytes: &[u8], expected: &[(&str, &[i64], i64)]) {
+        let (mut store, instance) = instantiate_wasm(wasm_bytes);
+        for (name, args, value) in expected {
+            let result = call_wasm_export(&mut store, &instance, name, args)
+                .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"));
+            assert_eq!(
+                result,
+                Some(*value),
+                "Unexpected result of '{name}{args:?}'"
+            );
+        }
+    }
+
+    #[test]
+    fn literals_test() {
+        let wasm_bytes = compile("literals");
+        assert_calls_return(
+            &wasm_bytes,
+            &[
+                ("sum_literal", &[], 10),
+                ("element_assignment", &[], 5_000_000_001),
+                ("narrow_elements", &[], 51),
+                ("nested_arrays", &[], 35),
+                ("copy_semantics", &[], 104),
+                ("array_of_structs", &[], 1234),
+                ("array_field", &[], 24),
+            ],
+        );
+    }
+
+    #[test]
+    fn functions_test() {
+        let wasm_bytes = compile("functions");
+        assert_calls_return(
+            &wasm_bytes,
+            &[
+                ("array_parameter", &[], 10),
+                ("array_return", &[], 4321),
+                ("parameter_is_copied", &[], 101),
+                ("index_call_result", &[], 12),
+                ("nested_return", &[], 1001),
+            ],
+        );
+    }
+
+    #[test]
+    fn in_bounds_access_test() {
+        let wasm_bytes = compile("bounds");
+        assert_calls_return(
+            &wasm_bytes,
+            &[
+                ("read_at", &[0], 10),
+                ("read_at", &[2], 30),
+                ("write_at", &[0], 100),
+                ("write_at", &[2], 1),
+                ("nested_at", &[1, 0], 3),
+                ("nested_at", &[2, 1], 6),
+                ("narrow_index", &[1], 8),
+            ],
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_access_traps_test() {
+        let wasm_bytes = compile("bounds");
+        let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+        let cases: &[(&str, &[i64])] = &[
+            ("read_at", &[3]),
+            ("read_at", &[-1]),
+            ("read_at", &[i64::from(i32::MAX)]),
+            ("write_at", &[3]),
+            ("write_at", &[-1]),
+            ("nested_at", &[3, 0]),
+            ("nested_at", &[0, 2]),
+            ("narrow_index", &[-1]),
+        ];
+        for (name, args) in cases {
+            let error = call_wasm_export(&mut store, &instance, name, args)
+                .expect_err(&format!("'{name}{args:?}' must trap"));
+            assert_eq!(
+                error.downcast_ref::<wasmtime::Trap>(),
+                Some(&wasmtime::Trap::UnreachableCodeReached),
+                "'{name}{args:?}' must trap on the bounds check"
+            );
+        }
+    }
+
+    #[test]
+    fn array_code_translates_to_rocq_test() {
+        let wasm_bytes = compile("functions");
+        let rocq = inference::wasm_to_v("arrays", &wasm_bytes)
+            .unwrap_or_else(|e| panic!("Failed to translate array code to Rocq: {e}"));
+        assert!(rocq.contains("BI_load"), "Element reads must translate");
+        assert!(rocq.contains("BI_store"), "Element writes must translate");
+        assert!(
+            rocq.contains("BI_unreachable"),
+            "Bounds checks must translate"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod asserts_codegen_tests {
+    use inference_wasm_codegen::{Backend, FAILED_ASSERTION_ADDRESS};
+
+    use crate::utils::{
+        build_ast_with_modules, call_wasm_export, get_test_file_path, instantiate_wasm,
+        try_wasm_codegen_with, wasm_codegen,
+    };
+
+    fn read_test_file(test_name: &str) -> String {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"))
+    }
+
+    fn compile(test_name: &str, backend: Backend) -> Vec<u8> {
+        try_wasm_codegen_with(&read_test_file(test_name), backend)
+            .unwrap_or_else(|e| panic!("{backend:?} backend failed: {e}"))
+    }
+
+    /// Compiles `project_main.inf` with `project_math.inf` as the module `math` and
+    /// `project_square.inf` as the module `shapes::square`.
+    fn compile_project(backend: Backend) -> Vec<u8> {
+        let arena = build_ast_with_modules(
+            &read_test_file("project_main"),
+            &[
+                ("math", read_test_file("project_math")),
+                ("shapes::square", read_test_file("project_square")),
+            ],
+        );
+        let typed_context = inference_type_checker::TypeCheckerBuilder::build_typed_context(arena)
+            .unwrap()
+            .typed_context();
+        inference_wasm_codegen::codegen_with_backend(&typed_context, backend)
+            .unwrap_or_else(|e| panic!("{backend:?} backend failed: {e}"))
+    }
+
+    /// Reads the ID of the failed assertion from the exported memory.
+    fn failed_assertion(store: &mut wasmtime::Store<()>, instance: &wasmtime::Instance) -> u32 {
+        let memory = instance
+            .get_memory(&mut *store, "memory")
+            .expect("Module must export its memory");
+        let mut word = [0; 4];
+        memory
+            .read(&*store, FAILED_ASSERTION_ADDRESS as usize, &mut word)
+            .unwrap();
+        u32::from_le_bytes(word)
+    }
+
+    fn asserts_section(wasm_bytes: &[u8]) -> Option<String> {
+        inf_wasmparser::Parser::new(0)
+            .parse_all(wasm_bytes)
+            .find_map(
+                |payload| match payload.expect("Generated wasm must parse") {
+                    inf_wasmparser::Payload::CustomSection(reader)
+                        if reader.name() == "inference.asserts" =>
+                    {
+                        Some(String::from_utf8(reader.data().to_vec()).unwrap())
+                    }
+                    _ => None,
+                },
+            )
+    }
+
+    #[test]
+    fn passing_assertions_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = compile("checks", backend);
+            let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+            let expected: &[(&str, &[i64], i64)] = &[
+                ("positive", &[5], 5),
+                ("in_range", &[1], 2),
+                ("in_range", &[3], 6),
+                ("calls_helper", &[6], 7),
+                ("loop_checked", &[4], 6),
+            ];
+            for (name, args, value) in expected {
+                let result = call_wasm_export(&mut store, &instance, name, args)
+                    .unwrap_or_else(|e| panic!("{backend:?}: '{name}{args:?}' trapped: {e}"));
+                assert_eq!(result, Some(*value), "{backend:?}: '{name}{args:?}'");
+            }
+            assert_eq!(failed_assertion(&mut store, &instance), 0);
+        }
+    }
+
+    #[test]
+    fn failing_assertion_records_its_id_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = compile("checks", backend);
+            let expected: &[(&str, &[i64], u32)] = &[
+                ("positive", &[0], 1),
+                ("in_range", &[0], 2),
+                ("in_range", &[4], 3),
+                ("calls_helper", &[7], 4),
+                ("loop_checked", &[6], 5),
+            ];
+            for (name, args, assertion_id) in expected {
+                let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+                assert!(
+                    call_wasm_export(&mut store, &instance, name, args).is_err(),
+                    "{backend:?}: '{name}{args:?}' must trap"
+                );
+                assert_eq!(
+                    failed_assertion(&mut store, &instance),
+                    *assertion_id,
+                    "{backend:?}: '{name}{args:?}'"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn asserts_section_maps_ids_to_locations_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let section = asserts_section(&compile("checks", backend))
+                .expect("Assertion locations must be recorded in a custom section");
+            assert_eq!(section, "1=2:5\n2=7:5\n3=8:5\n4=13:5\n5=25:9\n");
+        }
+    }
+
+    #[test]
+    fn asserts_section_names_the_module_of_each_assertion_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = compile_project(backend);
+            let section = asserts_section(&wasm_bytes)
+                .expect("Assertion locations must be recorded in a custom section");
+            assert_eq!(section, "1=2:5\n2=math:2:5\n3=shapes::square:2:5\n");
+            let expected: &[(&str, &[i64], u32)] = &[
+                ("checked_half", &[-2], 1),
+                ("checked_half", &[3], 2),
+                ("checked_area", &[100], 3),
+            ];
+            for (name, args, assertion_id) in expected {
+                let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+                assert!(
+                    call_wasm_export(&mut store, &instance, name, args).is_err(),
+                    "{backend:?}: '{name}{args:?}' must trap"
+                );
+                assert_eq!(
+                    failed_assertion(&mut store, &instance),
+                    *assertion_id,
+                    "{backend:?}: '{name}{args:?}'"
+                );
+            }
+        }
+        let rocq = inference::wasm_to_v("project", &compile_project(Backend::Llvm))
+            .unwrap_or_else(|e| panic!("Failed to translate assertions to Rocq: {e}"));
+        assert!(
+            rocq.contains("(* assert at shapes::square:2:5 *)"),
+            "{rocq}"
+        );
+    }
+
+    #[test]
+    fn no_section_without_assertions_test() {
+        let wasm_bytes = wasm_codegen("pub fn answer() -> i32 { return 42; }");
+        assert!(asserts_section(&wasm_bytes).is_none());
+    }
+
+    #[test]
+    fn assertions_translate_to_rocq_obligations_test() {
+        let wasm_bytes = compile("checks", Backend::Llvm);
+        let rocq = inference::wasm_to_v("asserts", &wasm_bytes)
+            .unwrap_or_else(|e| panic!("Failed to translate assertions to Rocq: {e}"));
+        for definition in [
+            "(* assert at 2:5 *)",
+            "Definition assertion_1 : N := 1%N.",
+            "Definition assertion_5_holds (failed_assertion : N -> Prop) : Prop := ~ failed_assertion assertion_5.",
+            "Definition assertions_hold (failed_assertion : N -> Prop) : Prop :=\n  \
+             assertion_1_holds failed_assertion /\\ assertion_2_holds failed_assertion",
+        ] {
+            assert!(rocq.contains(definition), "Missing `{definition}`");
+        }
+    }
+}
@@ -75,6 +75,8 @@ mod base_codegen_tests {
         assert_eq!(result, 42, "Expected 'hello_world' function to return 42");
     }
 
+    /// `nondet.inf` is a codegen-only input: `hello_uzumaki` returns `@` from an exported
+    /// function, which the analyze phase rejects, so it is compiled without analysis.
     #[test]
     fn nondet_test() {
         let test_name = "nondet";
@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod builtin_codegen_tests {
+    use inference_wasm_codegen::{Backend, FAILED_ASSERTION_ADDRESS};
+
+    use crate::utils::{
+        call_wasm_export, get_test_data_path, instantiate_wasm, try_wasm_codegen_with,
+    };
+
+    /// Programs under `test_data/codegen/wasm` that both backends compile and run.
+    const PROGRAMS: &[&str] = &[
+        "arrays/bounds",
+        "arrays/functions",
+        "arrays/literals",
+        "asserts/checks",
+        "base/const",
+        "base/trivial",
+        "constants/module",
+        "control_flow/block_scopes",
+        "control_flow/if_else",
+        "control_flow/loop_break",
+        "enums/variants",
+        "function_calls/calls",
+        "function_calls/recursion",
+        "generics/instances",
+        "operators/arithmetic",
+        "operators/bitwise",
+        "operators/comparison",
+        "operators/conversions",
+        "structs/fields",
+        "structs/functions",
+        "structs/methods",
+    ];
+
+    /// Arguments every export is called with, for each parameter.
+    const ARGUMENTS: &[i64] = &[0, 1, 3, 7];
+
+    fn read_program(name: &str) -> String {
+        let path = get_test_data_path()
+            .join("codegen")
+            .join("wasm")
+            .join(format!("{name}.inf"));
+        std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {path:?}"))
+    }
+
+    fn builtin_codegen(source_code: &str) -> Vec<u8> {
+        try_wasm_codegen_with(source_code, Backend::Builtin)
+            .unwrap_or_else(|e| panic!("Built-in backend failed: {e}"))
+    }
+
+    /// Names and parameter counts of the exported functions, in export order.
+    fn exported_functions(wasm_bytes: &[u8]) -> Vec<(String, usize)> {
+        let (mut store, instance) = instantiate_wasm(wasm_bytes);
+        let names: Vec<String> = instance
+            .exports(&mut store)
+            .map(|export| export.name().to_string())
+            .collect();
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let func = instance.get_func(&mut store, &name)?;
+                Some((name, func.ty(&store).params().len()))
+            })
+            .collect()
+    }
+
+    /// Every combination of [`ARGUMENTS`] for `arity` parameters.
+    fn argument_lists(arity: usize) -> Vec<Vec<i64>> {
+        (0..arity).fold(vec![vec![]], |lists, _| {
+            lists
+                .iter()
+                .flat_map(|list| {
+                    ARGUMENTS.iter().map(move |argument| {
+                        let mut list = list.clone();
+                        list.push(*argument);
+                        list
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Calls every export with every argument list, recording results and traps along with
+    /// the ID of the assertion that failed, if any.
+    fn observe(wasm_bytes: &[u8], exports: &[(String, usize)]) -> Vec<String> {
+        let mut observations = Vec::new();
+        for (name, arity) in exports {
+            for arguments in argument_lists(*arity) {
+                // A fresh instance per call, so that no call sees the memory of another.
+                let (mut store, instance) = instantiate_wasm(wasm_bytes);
+                let outcome = match call_wasm_export(&mut store, &instance, name, &arguments) {
+                    Ok(result) => format!("{result:?}"),
+                    Err(_) => {
+                        let memory = instance.get_memory(&mut store, "memory").unwrap();
+                        let mut word = [0; 4];
+                        memory
+                            .read(&store, FAILED_ASSERTION_ADDRESS as usize, &mut word)
+                            .unwrap();
+                        format!("trap (failed assertion {})", u32::from_le_bytes(word))
+                    }
+                };
+                observations.push(format!("{name}{arguments:?} = {outcome}"));
+            }
+        }
+        observations
+    }
+
+    #[test]
+    fn backends_agree_on_test_programs_test() {
+        for program in PROGRAMS {
+            let source_code = read_program(program);
+            let llvm = try_wasm_codegen_with(&source_code, Backend::Llvm)
+                .unwrap_or_else(|e| panic!("LLVM backend failed on {program}: {e}"));
+            let builtin = builtin_codegen(&source_code);
+            let exports = exported_functions(&llvm);
+            assert_eq!(
+                exported_functions(&builtin),
+                exports,
+                "Exports of {program} differ"
+            );
+            assert_eq!(
+                observe(&builtin, &exports),
+                observe(&llvm, &exports),
+                "Behavior of {program} differs"
+            );
+        }
+    }
+
+    #[test]
+    fn nondet_program_validates_and_translates_test() {
+        let wasm_bytes = builtin_codegen(&read_program("base/nondet"));
+        inf_wasmparser::validate(&wasm_bytes)
+            .unwrap_or_else(|e| panic!("Generated Wasm module is invalid: {e}"));
+        let rocq = inference::wasm_to_v("nondet", &wasm_bytes)
+            .unwrap_or_else(|e| panic!("Failed to translate to Rocq: {e}"));
+        assert!(!rocq.is_empty());
+    }
+
+    #[test]
+    fn conversions_translate_to_rocq_conversion_operators_test() {
+        let wasm_bytes = builtin_codegen(&read_program("operators/conversions"));
+        let rocq = inference::wasm_to_v("conversions", &wasm_bytes)
+            .unwrap_or_else(|e| panic!("Failed to translate to Rocq: {e}"));
+        for expected in [
+            "BI_cvtop T_i64 (CVO_extend T_i32 (Some SX_S))",
+            "BI_cvtop T_i64 (CVO_extend T_i32 (Some SX_U))",
+            "BI_cvtop T_i32 (CVO_wrap T_i64 None)",
+        ] {
+            assert!(rocq.contains(expected), "Missing `{expected}` in:\n{rocq}");
+        }
+    }
+
+    #[test]
+    fn uzumaki_lowers_to_nondet_instructions_test() {
+        let wasm_bytes = builtin_codegen(
+            r#"pub fn pick() -> i64 {
+    let a: i32 = @;
+    let b: i64 = @;
+    forall {
+        let c: i32 = @;
+    }
+    return b;
+}"#,
+        );
+        let mut operators = Vec::new();
+        for payload in inf_wasmparser::Parser::new(0).parse_all(&wasm_bytes) {
+            if let inf_wasmparser::Payload::CodeSectionEntry(body) = payload.unwrap() {
+                let mut reader = body.get_operators_reader().unwrap();
+                while !reader.eof() {
+                    operators.push(format!("{:?}", reader.read().unwrap()));
+                }
+            }
+        }
+        for expected in ["I32Uzumaki", "I64Uzumaki", "Forall"] {
+            assert!(
+                operators
+                    .iter()
+                    .any(|operator| operator.starts_with(expected)),
+                "Missing `{expected}` in {operators:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn module_layout_matches_llvm_backend_test() {
+        let wasm_bytes = builtin_codegen(&read_program("constants/module"));
+        let mut data_names = Vec::new();
+        let mut exports = Vec::new();
+        for payload in inf_wasmparser::Parser::new(0).parse_all(&wasm_bytes) {
+            match payload.unwrap() {
+                inf_wasmparser::Payload::ExportSection(reader) => {
+                    for export in reader {
+                        exports.push(export.unwrap().name.to_string());
+                    }
+                }
+                inf_wasmparser::Payload::CustomSection(reader) if reader.name() == "name" => {
+                    data_names.push(String::from_utf8_lossy(reader.data()).to_string());
+                }
+                _ => {}
+            }
+        }
+        assert!(exports.contains(&"memory".to_string()));
+        assert!(
+            data_names.iter().any(|names| names.contains(".rodata.")),
+            "Aggregate constants must live in named read-only data segments"
+        );
+    }
+
+    #[test]
+    fn codegen_errors_match_llvm_backend_test() {
+        let source_code = r#"pub fn check(x: i32) -> i32 {
+    type Alias = i32;
+    return x;
+}"#;
+        let llvm = try_wasm_codegen_with(source_code, Backend::Llvm).unwrap_err();
+        let builtin = try_wasm_codegen_with(source_code, Backend::Builtin).unwrap_err();
+        assert_eq!(builtin.to_string(), llvm.to_string());
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod constants_codegen_tests {
+    use crate::utils::{call_wasm_export, get_test_file_path, instantiate_wasm, wasm_codegen};
+
+    fn compile(test_name: &str) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        wasm_codegen(&source_code)
+    }
+
+    #[test]
+    fn module_constants_test() {
+        let wasm_bytes = compile("module");
+        let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+        let expected: &[(&str, &[i64], i64)] = &[
+            ("max_mem", &[], 1000),
+            ("limit", &[], 200),
+            ("negative", &[], -14),
+            ("enabled", &[], 1),
+            ("board_at", &[0, 0], 0),
+            ("board_at", &[1, 1], 1),
+            ("board_at", &[2, 0], 1),
+            ("board_at", &[2, 1], 0),
+            ("prime_at", &[0], 2),
+            ("prime_at", &[3], 7),
+            ("wide_at", &[0], 5_000_000_000),
+            ("wide_at", &[1], -1),
+            ("point_at", &[0], 12),
+            ("point_at", &[1], 34),
+            ("is_red", &[0], 0),
+            ("is_red", &[1], 1),
+            ("copy_is_independent", &[2], 105),
+            ("shadowed", &[], 5),
+        ];
+        for (name, args, value) in expected {
+            let result = call_wasm_export(&mut store, &instance, name, args)
+                .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"));
+            assert_eq!(
+                result,
+                Some(*value),
+                "Unexpected result of '{name}{args:?}'"
+            );
+        }
+    }
+
+    #[test]
+    fn aggregate_constants_are_read_only_data_test() {
+        let wasm_bytes = compile("module");
+        let segment_names: Vec<String> = inf_wasmparser::Parser::new(0)
+            .parse_all(&wasm_bytes)
+            .filter_map(
+                |payload| match payload.expect("Generated wasm must parse") {
+                    inf_wasmparser::Payload::CustomSection(reader) => match reader.as_known() {
+                        inf_wasmparser::KnownCustom::Name(names) => Some(names),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+            )
+            .flatten()
+            .filter_map(|name| match name.expect("Name section must parse") {
+                inf_wasmparser::Name::Data(data_names) => Some(data_names),
+                _ => None,
+            })
+            .flatten()
+            .map(|naming| naming.expect("Data name must parse").name.to_string())
+            .collect();
+        for constant in ["BOARD", "PRIMES", "WIDE", "POINTS", "ORDER"] {
+            assert!(
+                segment_names.contains(&format!(".rodata.{constant}")),
+                "`{constant}` must have its own data segment, found {segment_names:?}"
+            );
+        }
+        for constant in ["MAX_MEM", "LIMIT", "NEGATIVE", "ENABLED"] {
+            assert!(
+                !segment_names.iter().any(|name| name.ends_with(constant)),
+                "Scalar constant `{constant}` must be inlined"
+            );
+        }
+    }
+
+    #[test]
+    fn constants_translate_to_named_rocq_definitions_test() {
+        let wasm_bytes = compile("module");
+        let rocq = inference::wasm_to_v("constants", &wasm_bytes)
+            .unwrap_or_else(|e| panic!("Failed to translate constants to Rocq: {e}"));
+        assert!(
+            rocq.contains(
+                "Definition PRIMES : module_data := {|\n    moddata_init := \
+                 #02 :: #00 :: #00 :: #00 :: #03 :: #00 :: #00 :: #00 :: \
+                 #05 :: #00 :: #00 :: #00 :: #07 :: #00 :: #00 :: #00 :: nil;"
+            ),
+            "Array constants must become named data definitions"
+        );
+        assert!(
+            rocq.contains("Definition BOARD : module_data := {|"),
+            "Nested array constants must become named data definitions"
+        );
+        let mod_datas = rocq
+            .split("mod_datas :=")
+            .nth(1)
+            .and_then(|rest| rest.split(";\n").next())
+            .expect("Module must list its data segments");
+        assert!(
+            mod_datas.contains("PRIMES") && !mod_datas.contains("moddata_init"),
+            "Module must refer to constants by name, found {mod_datas}"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod control_flow_codegen_tests {
+    use inference_wasm_codegen::Backend;
+
+    use crate::utils::{
+        call_wasm_export, get_test_file_path, instantiate_wasm, try_wasm_codegen_with, wasm_codegen,
+    };
+
+    fn compile(test_name: &str) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        wasm_codegen(&source_code)
+    }
+
+    fn assert_exports_return(wasm_bytes: &[u8], expected: &[(&str, i64)]) {
+        let (mut store, instance) = instantiate_wasm(wasm_bytes);
+        for (name, value) in expected {
+            let result = call_wasm_export(&mut store, &instance, name, &[])
+                .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"));
+            assert_eq!(result, Some(*value), "Unexpected result of '{name}'");
+        }
+    }
+
+    #[test]
+    fn if_else_test() {
+        let wasm_bytes = compile("if_else");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("if_true", 1),
+                ("if_false", 2),
+                ("if_without_else", 3),
+                ("nested_if", 4),
+            ],
+        );
+    }
+
+    #[test]
+    fn loop_break_test() {
+        let wasm_bytes = compile("loop_break");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("loop_break", 1),
+                ("conditional_loop_skipped", 2),
+                ("conditional_loop_entered", 3),
+                ("break_in_if", 5),
+                ("nested_break", 6),
+            ],
+        );
+    }
+
+    #[test]
+    fn block_scopes_test() {
+        let test_file_path = get_test_file_path(module_path!(), "block_scopes");
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = try_wasm_codegen_with(&source_code, backend)
+                .unwrap_or_else(|e| panic!("{backend:?} backend failed: {e}"));
+            let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+            for (name, args, expected) in [
+                ("shadow_in_if", 0, 1),
+                ("shadow_in_if", 1, 1),
+                ("shadow_in_else", 0, 1),
+                ("shadow_in_else", 1, 2),
+                ("shadow_in_loop", 0, 11),
+                ("shadow_in_nested_block", 3, 21),
+            ] {
+                let result = call_wasm_export(&mut store, &instance, name, &[args])
+                    .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"));
+                assert_eq!(
+                    result,
+                    Some(expected),
+                    "Unexpected result of '{name}({args})' with the {backend:?} backend"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn void_loop_test() {
+        let wasm_bytes = compile("loop_break");
+        let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+        let result = call_wasm_export(&mut store, &instance, "void_loop", &[])
+            .unwrap_or_else(|e| panic!("Failed to execute 'void_loop' function: {e}"));
+        assert_eq!(result, None);
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod debug_info_codegen_tests {
+    use inference_wasm_codegen::{Backend, CodegenOptions, codegen_with_options};
+
+    use crate::utils::{build_ast, get_test_file_path};
+
+    fn compile(backend: Backend, debug_info: Option<&str>) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), "lines");
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        let typed_context =
+            inference_type_checker::TypeCheckerBuilder::build_typed_context(build_ast(source_code))
+                .unwrap()
+                .typed_context();
+        let options = CodegenOptions {
+            backend,
+            optimization_level: 0,
+            debug_info: debug_info.map(str::to_string),
+            ..CodegenOptions::default()
+        };
+        let wasm_bytes = codegen_with_options(&typed_context, &options)
+            .unwrap_or_else(|e| panic!("{backend:?} backend failed: {e}"))
+            .wasm;
+        inf_wasmparser::validate(&wasm_bytes)
+            .unwrap_or_else(|e| panic!("{backend:?} generated an invalid module: {e}"));
+        wasm_bytes
+    }
+
+    fn custom_sections(wasm_bytes: &[u8]) -> Vec<String> {
+        inf_wasmparser::Parser::new(0)
+            .parse_all(wasm_bytes)
+            .filter_map(|payload| match payload.unwrap() {
+                inf_wasmparser::Payload::CustomSection(reader) => Some(reader.name().to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Source location comments in the Rocq translation, in order.
+    fn location_comments(wasm_bytes: &[u8]) -> Vec<String> {
+        let rocq = inference::wasm_to_v("lines", &wasm_bytes.to_vec())
+            .unwrap_or_else(|e| panic!("Failed to translate to Rocq: {e}"));
+        rocq.lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("(* lines.inf:"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn line_tables_are_emitted_on_request_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let sections = custom_sections(&compile(backend, Some("lines.inf")));
+            for section in [".debug_info", ".debug_line"] {
+                assert!(
+                    sections.iter().any(|name| name == section),
+                    "{backend:?}: missing `{section}` in {sections:?}"
+                );
+            }
+            let sections = custom_sections(&compile(backend, None));
+            assert!(
+                !sections.iter().any(|name| name.starts_with(".debug_")),
+                "{backend:?}: unexpected debug info in {sections:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rocq_translation_is_annotated_with_statements_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let comments = location_comments(&compile(backend, Some("lines.inf")));
+            for expected in [
+                "(* lines.inf:2:5 *)",
+                "(* lines.inf:6:5 *)",
+                "(* lines.inf:8:9 *)",
+                "(* lines.inf:10:9 *)",
+                "(* lines.inf:12:5 *)",
+            ] {
+                assert!(
+                    comments.iter().any(|comment| comment == expected),
+                    "{backend:?}: missing `{expected}` in {comments:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn builtin_backend_annotates_every_statement_in_order_test() {
+        let comments = location_comments(&compile(Backend::Builtin, Some("lines.inf")));
+        assert_eq!(
+            comments,
+            [
+                // `double`: the definition, then its only statement.
+                "(* lines.inf:2:5 *)",
+                "(* lines.inf:2:5 *)",
+                // `compute`: the definition, then the body; the `if` is not repeated for
+                // `else` and `end`, which have no instruction of their own in Rocq.
+                "(* lines.inf:6:5 *)",
+                "(* lines.inf:6:5 *)",
+                "(* lines.inf:7:5 *)",
+                "(* lines.inf:8:9 *)",
+                "(* lines.inf:10:9 *)",
+                "(* lines.inf:12:5 *)",
+            ]
+        );
+    }
+
+    #[test]
+    fn translation_without_debug_info_has_no_location_comments_test() {
+        assert!(location_comments(&compile(Backend::Builtin, None)).is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod enums_codegen_tests {
+    use crate::utils::{call_wasm_export, get_test_file_path, instantiate_wasm, wasm_codegen};
+
+    fn compile(test_name: &str) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        wasm_codegen(&source_code)
+    }
+
+    #[test]
+    fn variants_test() {
+        let wasm_bytes = compile("variants");
+        let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+        let expected: &[(&str, &[i64], i64)] = &[
+            ("blue", &[], 2),
+            ("west", &[], 3),
+            ("parameter", &[0], 10),
+            ("parameter", &[1], 20),
+            ("parameter", &[2], 30),
+            ("local_reassignment", &[], 30),
+            ("wraps_around", &[], 20),
+            ("inequality", &[], 1),
+            ("struct_field", &[], 30),
+            ("array_element", &[], 60),
+        ];
+        for (name, args, value) in expected {
+            let result = call_wasm_export(&mut store, &instance, name, args)
+                .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"));
+            assert_eq!(
+                result,
+                Some(*value),
+                "Unexpected result of '{name}{args:?}'"
+            );
+        }
+    }
+
+    #[test]
+    fn discriminants_section_test() {
+        let wasm_bytes = compile("variants");
+        let section = inf_wasmparser::Parser::new(0)
+            .parse_all(&wasm_bytes)
+            .find_map(
+                |payload| match payload.expect("Generated wasm must parse") {
+                    inf_wasmparser::Payload::CustomSection(reader)
+                        if reader.name() == "inference.enums" =>
+                    {
+                        Some(String::from_utf8(reader.data().to_vec()).unwrap())
+                    }
+                    _ => None,
+                },
+            )
+            .expect("Enum discriminants must be recorded in a custom section");
+        assert_eq!(
+            section,
+            "Color::Red=0\nColor::Green=1\nColor::Blue=2\n\
+             Direction::North=0\nDirection::East=1\nDirection::South=2\nDirection::West=3\n"
+        );
+    }
+
+    #[test]
+    fn no_section_without_enums_test() {
+        let wasm_bytes = wasm_codegen("pub fn answer() -> i32 { return 42; }");
+        let has_section = inf_wasmparser::Parser::new(0)
+            .parse_all(&wasm_bytes)
+            .any(|payload| {
+                matches!(
+                    payload,
+                    Ok(inf_wasmparser::Payload::CustomSection(reader))
+                        if reader.name() == "inference.enums"
+                )
+            });
+        assert!(
+            !has_section,
+            "Programs without enums must not carry the section"
+        );
+    }
+
+    #[test]
+    fn variants_translate_to_rocq_definitions_test() {
+        let wasm_bytes = compile("variants");
+        let rocq = inference::wasm_to_v("enums", &wasm_bytes)
+            .unwrap_or_else(|e| panic!("Failed to translate enum code to Rocq: {e}"));
+        for definition in [
+            "Definition Color_Red : Z := 0.",
+            "Definition Color_Blue : Z := 2.",
+            "Definition Direction_West : Z := 3.",
+        ] {
+            assert!(rocq.contains(definition), "Missing `{definition}`");
+        }
+    }
+}
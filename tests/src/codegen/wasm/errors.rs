@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod codegen_errors_tests {
+    use crate::utils::try_wasm_codegen;
+
+    fn codegen_error(source_code: &str) -> String {
+        match try_wasm_codegen(source_code) {
+            Ok(_) => panic!("Expected codegen to fail for:\n{source_code}"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn type_definition_statement_is_reported_with_location_test() {
+        let error = codegen_error(
+            r#"pub fn check(x: i32) -> i32 {
+    type Alias = i32;
+    return x;
+}"#,
+        );
+        assert_eq!(
+            error,
+            "2:5: type definition statements are not yet supported in codegen"
+        );
+    }
+
+    #[test]
+    fn unsupported_variable_type_is_reported_test() {
+        let error = codegen_error(
+            r#"pub fn greet() {
+    let s: String = "hi";
+}"#,
+        );
+        assert!(
+            error.starts_with("2:5: type `String` is not yet supported in codegen"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn unsupported_parameter_type_skips_function_test() {
+        let error = codegen_error(
+            r#"pub fn length(s: String) -> i32 {
+    return 0;
+}
+
+pub fn answer() -> i32 {
+    return 42;
+}"#,
+        );
+        assert_eq!(error, "1:18: type `String` is not yet supported in codegen");
+    }
+
+    #[test]
+    fn errors_are_collected_across_functions_test() {
+        let error = codegen_error(
+            r#"pub fn first(x: i32) -> i32 {
+    type Small = i8;
+    type Large = i64;
+    return x;
+}
+
+pub fn second() -> i32 {
+    type Alias = i32;
+    return 1;
+}"#,
+        );
+        let messages: Vec<&str> = error.split("; ").collect();
+        assert_eq!(
+            messages,
+            [
+                "2:5: type definition statements are not yet supported in codegen",
+                "3:5: type definition statements are not yet supported in codegen",
+                "8:5: type definition statements are not yet supported in codegen",
+            ]
+        );
+    }
+
+    #[test]
+    fn recursive_struct_is_reported_test() {
+        let error = codegen_error(
+            r#"struct Node {
+    value: i32;
+    next: Node;
+}
+
+pub fn answer() -> i32 {
+    return 42;
+}"#,
+        );
+        assert_eq!(error, "1:1: struct `Node` contains itself by value");
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod function_calls_codegen_tests {
+    use crate::utils::{call_wasm_export, get_test_file_path, instantiate_wasm, wasm_codegen};
+
+    fn compile(test_name: &str) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        wasm_codegen(&source_code)
+    }
+
+    fn assert_calls_return(wasm_bytes: &[u8], expected: &[(&str, &[i64], i64)]) {
+        let (mut store, instance) = instantiate_wasm(wasm_bytes);
+        for (name, args, value) in expected {
+            let result = call_wasm_export(&mut store, &instance, name, args)
+                .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"));
+            assert_eq!(result, Some(*value), "Unexpected result of '{name}{args:?}'");
+        }
+    }
+
+    #[test]
+    fn calls_test() {
+        let wasm_bytes = compile("calls");
+        assert_calls_return(
+            &wasm_bytes,
+            &[
+                ("call_defined_later", &[], 42),
+                ("named_arguments", &[], 7),
+                ("mixed_arguments", &[], 9),
+                ("narrow_arguments", &[], 144),
+                ("nested_calls", &[], 10),
+                ("reassigned_parameter", &[], 87),
+                ("void_call", &[], 9),
+                ("max", &[3, 8], 8),
+                ("max", &[-1, -5], -1),
+            ],
+        );
+    }
+
+    #[test]
+    fn recursion_test() {
+        let wasm_bytes = compile("recursion");
+        assert_calls_return(
+            &wasm_bytes,
+            &[
+                ("factorial_of_five", &[], 120),
+                ("factorial", &[0], 1),
+                ("factorial", &[10], 3_628_800),
+                ("is_even", &[10], 1),
+                ("is_even", &[7], 0),
+            ],
+        );
+    }
+
+    #[test]
+    fn private_functions_are_not_exported_test() {
+        let wasm_bytes = compile("calls");
+        let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+        for name in ["add", "sub", "double", "increment", "noop"] {
+            assert!(
+                instance.get_func(&mut store, name).is_none(),
+                "Private function '{name}' must not be exported"
+            );
+        }
+    }
+}
@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod generics_codegen_tests {
+    use inference_wasm_codegen::Backend;
+
+    use crate::utils::{
+        call_wasm_export, get_test_file_path, instantiate_wasm, try_wasm_codegen,
+        try_wasm_codegen_with,
+    };
+
+    fn read_test_file(test_name: &str) -> String {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"))
+    }
+
+    /// Function names recorded in the `name` custom section, in function index order.
+    fn function_names(wasm_bytes: &[u8]) -> Vec<String> {
+        let mut names = Vec::new();
+        for payload in inf_wasmparser::Parser::new(0).parse_all(wasm_bytes) {
+            if let inf_wasmparser::Payload::CustomSection(reader) = payload.unwrap()
+                && let inf_wasmparser::KnownCustom::Name(name_reader) = reader.as_known()
+            {
+                for name in name_reader {
+                    if let inf_wasmparser::Name::Function(function_names) = name.unwrap() {
+                        for naming in function_names {
+                            names.push(naming.unwrap().name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    #[test]
+    fn instances_test() {
+        let source_code = read_test_file("instances");
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = try_wasm_codegen_with(&source_code, backend)
+                .unwrap_or_else(|e| panic!("{backend:?} backend failed: {e}"));
+            let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+            let mut call = |name: &str, arguments: &[i64]| {
+                call_wasm_export(&mut store, &instance, name, arguments)
+                    .unwrap_or_else(|e| panic!("Calling {name} failed: {e}"))
+            };
+            assert_eq!(call("pick", &[1, 3, 4]), Some(3));
+            assert_eq!(call("pick", &[0, 3, 4]), Some(4));
+            assert_eq!(
+                call("pick_wide", &[0, 3, 5_000_000_000]),
+                Some(5_000_000_000)
+            );
+            assert_eq!(call("point_x", &[17]), Some(17));
+            assert_eq!(call("first_of", &[9]), Some(9));
+            assert_eq!(call("twice_sum", &[21]), Some(42));
+
+            let mut instances: Vec<String> = function_names(&wasm_bytes)
+                .into_iter()
+                .filter(|name| name.contains('<'))
+                .collect();
+            instances.sort();
+            assert_eq!(
+                instances,
+                [
+                    "choose<i32>",
+                    "choose<i64>",
+                    "first<i32>",
+                    "identity<Point>",
+                    "identity<i32>",
+                    "twice<i32>",
+                ],
+                "Unexpected instances from the {backend:?} backend"
+            );
+        }
+    }
+
+    #[test]
+    fn instances_are_not_exported_test() {
+        let wasm_bytes = try_wasm_codegen(&read_test_file("instances")).unwrap();
+        let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+        let mut exports: Vec<String> = instance
+            .exports(&mut store)
+            .map(|export| export.name().to_string())
+            .collect();
+        exports.sort();
+        assert_eq!(
+            exports,
+            [
+                "first_of",
+                "memory",
+                "pick",
+                "pick_wide",
+                "point_x",
+                "twice_sum"
+            ]
+        );
+    }
+
+    #[test]
+    fn instance_symbols_are_deterministic_test() {
+        let source_code = read_test_file("instances");
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let first = try_wasm_codegen_with(&source_code, backend).unwrap();
+            let second = try_wasm_codegen_with(&source_code, backend).unwrap();
+            assert_eq!(first, second, "{backend:?} output differs between runs");
+        }
+    }
+
+    #[test]
+    fn instances_translate_to_rocq_test() {
+        let wasm_bytes = try_wasm_codegen(&read_test_file("instances")).unwrap();
+        let rocq = inference::wasm_to_v("instances", &wasm_bytes)
+            .unwrap_or_else(|e| panic!("Failed to translate to Rocq: {e}"));
+        for definition in ["identity_Point", "choose_i64", "twice_i32"] {
+            assert!(
+                rocq.contains(&format!("Definition {definition} : module_func")),
+                "Missing `{definition}` in:\n{rocq}"
+            );
+        }
+    }
+
+    #[test]
+    fn nested_generic_calls_instantiate_transitively_test() {
+        let source_code = r#"fn identity T'(x: T) -> T {
+    return x;
+}
+
+fn outer T'(x: T) -> T {
+    return identity(x);
+}
+
+pub fn run(a: u8) -> u8 {
+    return outer(a);
+}"#;
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = try_wasm_codegen_with(source_code, backend).unwrap();
+            let names = function_names(&wasm_bytes);
+            assert!(names.contains(&"outer<u8>".to_string()), "{names:?}");
+            assert!(names.contains(&"identity<u8>".to_string()), "{names:?}");
+            let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+            assert_eq!(
+                call_wasm_export(&mut store, &instance, "run", &[200]).unwrap(),
+                Some(200)
+            );
+        }
+    }
+
+    #[test]
+    fn unbounded_instantiation_is_reported_test() {
+        let source_code = r#"fn grow T'(x: T) -> i32 {
+    return grow([x, x]);
+}
+
+pub fn run(a: i32) -> i32 {
+    return grow(a);
+}"#;
+        let llvm = try_wasm_codegen_with(source_code, Backend::Llvm).unwrap_err();
+        let builtin = try_wasm_codegen_with(source_code, Backend::Builtin).unwrap_err();
+        assert!(
+            llvm.to_string().contains(
+                "generic function `grow` is instantiated with types nested more than 16 levels deep"
+            ),
+            "{llvm}"
+        );
+        assert_eq!(builtin.to_string(), llvm.to_string());
+    }
+
+    #[test]
+    fn type_argument_inferred_through_generic_struct_test() {
+        let source_code = r#"struct Pair { fst: A; snd: B; }
+
+fn first T'(p: Pair T' bool') -> T {
+    return p.fst;
+}
+
+pub fn run(x: i32) -> i32 {
+    let p: Pair i32' bool' = Pair { fst: x, snd: true };
+    return first(p);
+}"#;
+        // Values of generic struct types have no layout yet, but the instance `first<i32>`
+        // is inferred and the failure is reported instead of panicking
+        let llvm = try_wasm_codegen_with(source_code, Backend::Llvm).unwrap_err();
+        let builtin = try_wasm_codegen_with(source_code, Backend::Builtin).unwrap_err();
+        assert!(
+            llvm.to_string()
+                .contains("8:5: type `Pair i32' bool'` is not yet supported in codegen")
+                && llvm.to_string().contains("`first<i32>`"),
+            "{llvm}"
+        );
+        assert_eq!(builtin.to_string(), llvm.to_string());
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod imports_codegen_tests {
+    use std::sync::{Arc, Mutex};
+
+    use inference_wasm_codegen::{Backend, CodegenOptions, ImportName, codegen_with_options};
+    use wasmtime::{Engine, Linker, Module, Store};
+
+    use crate::utils::{build_ast, call_wasm_export, get_test_file_path};
+
+    fn compile(test_name: &str, options: &CodegenOptions) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        let arena = build_ast(source_code);
+        let typed_context = inference_type_checker::TypeCheckerBuilder::build_typed_context(arena)
+            .unwrap()
+            .typed_context();
+        codegen_with_options(&typed_context, options).unwrap().wasm
+    }
+
+    /// Function imports of a module as `(module, field)` pairs, in import order.
+    fn function_imports(wasm_bytes: &[u8]) -> Vec<(String, String)> {
+        let mut imports = Vec::new();
+        for payload in inf_wasmparser::Parser::new(0).parse_all(wasm_bytes) {
+            if let inf_wasmparser::Payload::ImportSection(reader) = payload.unwrap() {
+                for import in reader {
+                    let import = import.unwrap();
+                    if let inf_wasmparser::TypeRef::Func(_) = import.ty {
+                        imports.push((import.module.to_string(), import.name.to_string()));
+                    }
+                }
+            }
+        }
+        imports
+    }
+
+    /// Instantiates the `host` program with host functions under `module`, recording the
+    /// values passed to `host_log`.
+    fn instantiate_with_host(
+        wasm_bytes: &[u8],
+        module: &str,
+    ) -> (Store<()>, wasmtime::Instance, Arc<Mutex<Vec<i32>>>) {
+        inf_wasmparser::validate(wasm_bytes)
+            .unwrap_or_else(|e| panic!("Generated Wasm module is invalid: {e}"));
+        let engine = Engine::default();
+        let wasm_module = Module::new(&engine, wasm_bytes).unwrap();
+        let mut store = Store::new(&engine, ());
+        let mut linker = Linker::new(&engine);
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        linker
+            .func_wrap(module, "host_add", |a: i32, b: i32| a * 100 + b)
+            .unwrap();
+        linker
+            .func_wrap(module, "host_wide", |value: i64| value + 1)
+            .unwrap();
+        let log = Arc::clone(&logged);
+        linker
+            .func_wrap(module, "host_log", move |value: i32| {
+                log.lock().unwrap().push(value);
+            })
+            .unwrap();
+        let instance = linker.instantiate(&mut store, &wasm_module).unwrap();
+        (store, instance, logged)
+    }
+
+    #[test]
+    fn host_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let options = CodegenOptions {
+                backend,
+                ..CodegenOptions::default()
+            };
+            let wasm_bytes = compile("host", &options);
+            let mut imports = function_imports(&wasm_bytes);
+            imports.sort();
+            assert_eq!(
+                imports,
+                [
+                    ("env".to_string(), "host_add".to_string()),
+                    ("env".to_string(), "host_log".to_string()),
+                    ("env".to_string(), "host_wide".to_string()),
+                ],
+                "Only called external functions are imported ({backend:?})"
+            );
+
+            let (mut store, instance, logged) = instantiate_with_host(&wasm_bytes, "env");
+            let mut call = |name: &str, args: &[i64]| {
+                call_wasm_export(&mut store, &instance, name, args)
+                    .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"))
+            };
+            assert_eq!(call("add_through_host", &[4, 2]), Some(403));
+            assert_eq!(
+                call("wide_through_host", &[5_000_000_000]),
+                Some(10_000_000_002)
+            );
+            assert_eq!(call("log_twice", &[7]), Some(7));
+            assert_eq!(call("named_arguments", &[]), Some(310));
+            assert_eq!(*logged.lock().unwrap(), [7, 8], "{backend:?}");
+        }
+    }
+
+    #[test]
+    fn configured_import_names_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let mut options = CodegenOptions {
+                backend,
+                ..CodegenOptions::default()
+            };
+            options.imports.insert(
+                "host_log".to_string(),
+                ImportName {
+                    module: "host".to_string(),
+                    field: "log_i32".to_string(),
+                },
+            );
+            let wasm_bytes = compile("host", &options);
+            let imports = function_imports(&wasm_bytes);
+            assert!(
+                imports.contains(&("host".to_string(), "log_i32".to_string())),
+                "{imports:?} ({backend:?})"
+            );
+            assert!(
+                imports.contains(&("env".to_string(), "host_add".to_string())),
+                "{imports:?} ({backend:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn imports_translate_to_rocq_test() {
+        let wasm_bytes = compile("host", &CodegenOptions::default());
+        let rocq = inference::wasm_to_v("host", &wasm_bytes)
+            .unwrap_or_else(|e| panic!("Failed to translate to Rocq: {e}"));
+        assert!(rocq.contains(r#"Mi "env" "host_add" (MID_func"#), "{rocq}");
+        for name in [
+            "add_through_host",
+            "wide_through_host",
+            "log_twice",
+            "named_arguments",
+        ] {
+            assert!(
+                rocq.contains(&format!("Definition {name} : module_func")),
+                "missing `{name}` in:\n{rocq}"
+            );
+        }
+        for name in ["host_add", "host_wide", "host_log", "never_called"] {
+            assert!(
+                !rocq.contains(&format!("Definition {name} : module_func")),
+                "import `{name}` names a defined function in:\n{rocq}"
+            );
+        }
+    }
+}
@@ -1 +1,18 @@
+mod arrays;
+mod asserts;
 mod base;
+mod builtin;
+mod constants;
+mod control_flow;
+mod debug_info;
+mod enums;
+mod errors;
+mod function_calls;
+mod generics;
+mod imports;
+mod modules;
+mod operators;
+mod options;
+mod structs;
+mod type_aliases;
+mod uzumaki;
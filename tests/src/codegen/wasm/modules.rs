@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod modules_codegen_tests {
+    use inference_wasm_codegen::{Backend, CodegenOptions, ExportPolicy};
+
+    use crate::utils::{
+        build_ast_with_modules, call_wasm_export, get_test_file_path, instantiate_wasm,
+    };
+
+    fn read_test_file(test_name: &str) -> String {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"))
+    }
+
+    /// Compiles `main.inf` with `math.inf` as the module `math` and `square.inf` as the
+    /// module `shapes::square`.
+    fn project_codegen(backend: Backend) -> anyhow::Result<Vec<u8>> {
+        let options = CodegenOptions {
+            backend,
+            ..CodegenOptions::default()
+        };
+        project_codegen_with(&options)
+    }
+
+    fn project_codegen_with(options: &CodegenOptions) -> anyhow::Result<Vec<u8>> {
+        let arena = build_ast_with_modules(
+            &read_test_file("main"),
+            &[
+                ("math", read_test_file("math")),
+                ("shapes::square", read_test_file("square")),
+            ],
+        );
+        let typed_context = inference_type_checker::TypeCheckerBuilder::build_typed_context(arena)
+            .unwrap()
+            .typed_context();
+        inference_wasm_codegen::codegen_with_options(&typed_context, options)
+            .map(|output| output.wasm)
+    }
+
+    /// Names of the exported functions, sorted.
+    fn exported_functions(wasm_bytes: &[u8]) -> Vec<String> {
+        let mut exports = Vec::new();
+        for payload in inf_wasmparser::Parser::new(0).parse_all(wasm_bytes) {
+            if let inf_wasmparser::Payload::ExportSection(reader) = payload.unwrap() {
+                for export in reader {
+                    let export = export.unwrap();
+                    if export.kind == inf_wasmparser::ExternalKind::Func {
+                        exports.push(export.name.to_string());
+                    }
+                }
+            }
+        }
+        exports.sort();
+        exports
+    }
+
+    /// Function names recorded in the `name` custom section, in function index order.
+    fn function_names(wasm_bytes: &[u8]) -> Vec<String> {
+        let mut names = Vec::new();
+        for payload in inf_wasmparser::Parser::new(0).parse_all(wasm_bytes) {
+            if let inf_wasmparser::Payload::CustomSection(reader) = payload.unwrap()
+                && let inf_wasmparser::KnownCustom::Name(name_reader) = reader.as_known()
+            {
+                for name in name_reader {
+                    if let inf_wasmparser::Name::Function(function_names) = name.unwrap() {
+                        for naming in function_names {
+                            names.push(naming.unwrap().name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    #[test]
+    fn cross_module_calls_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = project_codegen(backend)
+                .unwrap_or_else(|e| panic!("{backend:?} backend failed: {e}"));
+            let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+            let mut call = |name: &str, arguments: &[i64]| {
+                call_wasm_export(&mut store, &instance, name, arguments)
+                    .unwrap_or_else(|e| panic!("Calling {name} failed: {e}"))
+            };
+            // `helper` and `SCALE` resolve to the definitions of the calling module
+            assert_eq!(call("compute", &[4]), Some(115));
+            assert_eq!(call("compute", &[-8]), Some(117));
+            assert_eq!(call("area", &[3]), Some(19));
+            assert_eq!(call("origin_distance", &[-3, 4]), Some(7));
+        }
+    }
+
+    #[test]
+    fn functions_get_module_qualified_symbols_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = project_codegen(backend).unwrap();
+            let mut names: Vec<String> = function_names(&wasm_bytes)
+                .into_iter()
+                .filter(|name| name.contains("helper") || name.contains("area"))
+                .collect();
+            names.sort();
+            assert_eq!(
+                names,
+                ["area", "helper", "math::helper", "shapes::square::area"],
+                "Unexpected symbols from the {backend:?} backend"
+            );
+        }
+    }
+
+    #[test]
+    fn public_functions_are_exported_under_qualified_names_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = project_codegen(backend).unwrap();
+            assert_eq!(
+                exported_functions(&wasm_bytes),
+                [
+                    "area",
+                    "compute",
+                    "math::add",
+                    "math::distance",
+                    "origin_distance",
+                    "shapes::square::area",
+                ],
+                "Unexpected exports from the {backend:?} backend"
+            );
+        }
+    }
+
+    #[test]
+    fn only_policy_selects_qualified_symbols_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let options = CodegenOptions {
+                backend,
+                exports: ExportPolicy::Only(vec![
+                    "math::helper".to_string(),
+                    "math::Point::manhattan".to_string(),
+                ]),
+                ..CodegenOptions::default()
+            };
+            let wasm_bytes = project_codegen_with(&options).unwrap();
+            assert_eq!(
+                exported_functions(&wasm_bytes),
+                ["math::Point::manhattan", "math::helper"],
+                "Unexpected exports from the {backend:?} backend"
+            );
+            let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+            assert_eq!(
+                call_wasm_export(&mut store, &instance, "math::helper", &[-5]).unwrap(),
+                Some(5)
+            );
+        }
+    }
+
+    #[test]
+    fn private_module_functions_are_not_callable_test() {
+        let arena = build_ast_with_modules(
+            "pub fn run(x: i32) -> i32 { return math::helper(x); }",
+            &[("math", read_test_file("math"))],
+        );
+        let error = inference_type_checker::TypeCheckerBuilder::build_typed_context(arena)
+            .err()
+            .expect("Calling a private function of another module should not type check");
+        assert!(
+            error.to_string().contains("math::helper"),
+            "Unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn modules_are_linked_deterministically_test() {
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let first = project_codegen(backend).unwrap();
+            let second = project_codegen(backend).unwrap();
+            assert_eq!(first, second, "{backend:?} output differs between runs");
+        }
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod operators_codegen_tests {
+    use crate::utils::{call_wasm_export, get_test_file_path, instantiate_wasm, wasm_codegen};
+
+    fn compile(test_name: &str) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        wasm_codegen(&source_code)
+    }
+
+    fn assert_exports_return(wasm_bytes: &[u8], expected: &[(&str, i64)]) {
+        let (mut store, instance) = instantiate_wasm(wasm_bytes);
+        for (name, value) in expected {
+            let result = call_wasm_export(&mut store, &instance, name, &[])
+                .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"));
+            assert_eq!(result, Some(*value), "Unexpected result of '{name}'");
+        }
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let wasm_bytes = compile("arithmetic");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("add_i32", 42),
+                ("sub_i64", -42),
+                ("mul_u8_wraps", 144),
+                ("div_signed", -3),
+                ("div_unsigned", 2_147_483_644),
+                ("rem_signed", -1),
+                ("rem_unsigned", 9),
+                ("pow_i32", 81),
+                ("pow_u64", 1 << 40),
+                ("pow_zero", 1),
+                ("neg_i64", -5),
+                ("precedence", 18),
+                ("loop_sum", 55),
+            ],
+        );
+    }
+
+    #[test]
+    fn bitwise_test() {
+        let wasm_bytes = compile("bitwise");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("and_or_xor", 14),
+                ("bitnot_u8", 240),
+                ("bitnot_i16", -1),
+                ("shl_i8", -128),
+                ("shl_masks_amount", 2),
+                ("shr_signed", -4),
+                ("shr_unsigned", 1),
+                ("shr_u64", 15),
+            ],
+        );
+    }
+
+    #[test]
+    fn comparison_test() {
+        let wasm_bytes = compile("comparison");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("lt_signed", 1),
+                ("lt_unsigned", 0),
+                ("ge_u8", 1),
+                ("le_i8", 1),
+                ("gt_u64", 1),
+                ("eq_i64", 1),
+                ("ne_bool", 1),
+                ("not_bool", 1),
+                ("logical", 1),
+                ("and_short_circuit", 2),
+                ("or_short_circuit", 3),
+            ],
+        );
+    }
+
+    #[test]
+    fn conversions_test() {
+        let wasm_bytes = compile("conversions");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("cast_i32_to_i64", -5),
+                ("cast_u32_to_u64", 4_294_967_295),
+                ("cast_u8_to_i16", 200),
+                ("wrapping_i64_to_i32", 1),
+                ("wrapping_i32_to_u8", 44),
+                ("wrapping_u8_to_i8", -56),
+                ("wrapping_i8_to_u64", -1),
+                ("wrapping_i64_to_i16", -1),
+                ("checked_in_range", 255),
+                ("pages", 16_384),
+            ],
+        );
+        let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+        for (name, argument, expected) in [
+            ("checked_i64_to_i8", 2, Some(100)),
+            ("checked_i64_to_i8", 3, None),
+            ("checked_i64_to_i8", -3, None),
+            ("checked_i32_to_u32", 3, Some(0)),
+            ("checked_i32_to_u32", 2, None),
+            ("checked_u64_to_i32", 1, Some(1_073_741_824)),
+            ("checked_u64_to_i32", 2, None),
+        ] {
+            let result = call_wasm_export(&mut store, &instance, name, &[argument]);
+            match expected {
+                Some(value) => assert_eq!(
+                    result.unwrap_or_else(|e| panic!("Failed to execute '{name}': {e}")),
+                    Some(value),
+                    "Unexpected result of '{name}({argument})'"
+                ),
+                None => assert!(result.is_err(), "'{name}({argument})' must trap"),
+            }
+        }
+    }
+}
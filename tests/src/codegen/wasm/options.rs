@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod options_codegen_tests {
+    use inference_wasm_codegen::{
+        Backend, CodegenOptions, CodegenOutput, ExportPolicy, codegen_with_options,
+    };
+
+    use crate::utils::{build_ast, call_wasm_export, instantiate_wasm};
+
+    const SOURCE: &str = r#"struct Counter {
+    value: i32;
+
+    fn get(self) -> i32 {
+        return self.value;
+    }
+}
+
+fn helper() -> i32 {
+    return 40;
+}
+
+pub fn main() -> i32 {
+    return helper() + 2;
+}
+
+pub fn answer() -> i32 {
+    return 42;
+}"#;
+
+    fn compile(options: &CodegenOptions) -> anyhow::Result<CodegenOutput> {
+        let arena = build_ast(SOURCE.to_string());
+        let typed_context = inference_type_checker::TypeCheckerBuilder::build_typed_context(arena)
+            .unwrap()
+            .typed_context();
+        codegen_with_options(&typed_context, options)
+    }
+
+    fn exported_functions(wasm_bytes: &[u8]) -> Vec<String> {
+        let mut exports = Vec::new();
+        for payload in inf_wasmparser::Parser::new(0).parse_all(wasm_bytes) {
+            if let inf_wasmparser::Payload::ExportSection(reader) = payload.unwrap() {
+                for export in reader {
+                    let export = export.unwrap();
+                    if export.kind == inf_wasmparser::ExternalKind::Func {
+                        exports.push(export.name.to_string());
+                    }
+                }
+            }
+        }
+        exports.sort();
+        exports
+    }
+
+    fn backends() -> [Backend; 2] {
+        [Backend::Llvm, Backend::Builtin]
+    }
+
+    #[test]
+    fn public_policy_exports_main_like_other_functions_test() {
+        for backend in backends() {
+            let options = CodegenOptions {
+                backend,
+                ..CodegenOptions::default()
+            };
+            let wasm = compile(&options).unwrap().wasm;
+            assert_eq!(exported_functions(&wasm), ["answer", "main"], "{backend:?}");
+            let (mut store, instance) = instantiate_wasm(&wasm);
+            assert_eq!(
+                call_wasm_export(&mut store, &instance, "main", &[]).unwrap(),
+                Some(42)
+            );
+        }
+    }
+
+    #[test]
+    fn all_policy_exports_private_functions_and_methods_test() {
+        for backend in backends() {
+            let options = CodegenOptions {
+                backend,
+                exports: ExportPolicy::All,
+                ..CodegenOptions::default()
+            };
+            let wasm = compile(&options).unwrap().wasm;
+            assert_eq!(
+                exported_functions(&wasm),
+                ["Counter::get", "answer", "helper", "main"],
+                "{backend:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn only_policy_exports_listed_symbols_test() {
+        for backend in backends() {
+            let options = CodegenOptions {
+                backend,
+                exports: ExportPolicy::Only(vec!["helper".to_string()]),
+                ..CodegenOptions::default()
+            };
+            let wasm = compile(&options).unwrap().wasm;
+            assert_eq!(exported_functions(&wasm), ["helper"], "{backend:?}");
+        }
+    }
+
+    #[test]
+    fn optimization_levels_preserve_behavior_test() {
+        for optimization_level in 0..=3 {
+            let options = CodegenOptions {
+                optimization_level,
+                ..CodegenOptions::default()
+            };
+            let wasm = compile(&options).unwrap().wasm;
+            let (mut store, instance) = instantiate_wasm(&wasm);
+            assert_eq!(
+                call_wasm_export(&mut store, &instance, "main", &[]).unwrap(),
+                Some(42),
+                "-O{optimization_level}"
+            );
+        }
+    }
+
+    #[test]
+    fn intermediate_artifacts_are_returned_on_request_test() {
+        let output = compile(&CodegenOptions::default()).unwrap();
+        assert!(output.llvm_ir.is_none());
+        assert!(output.object.is_none());
+
+        let options = CodegenOptions {
+            emit_llvm_ir: true,
+            emit_object: true,
+            ..CodegenOptions::default()
+        };
+        let output = compile(&options).unwrap();
+        let llvm_ir = output.llvm_ir.unwrap();
+        assert!(llvm_ir.contains("define i32 @main()"), "{llvm_ir}");
+        assert!(llvm_ir.contains("wasm32-unknown-unknown"), "{llvm_ir}");
+        assert!(output.object.unwrap().starts_with(b"\0asm"));
+    }
+
+    #[test]
+    fn builtin_backend_rejects_llvm_artifacts_test() {
+        let options = CodegenOptions {
+            backend: Backend::Builtin,
+            emit_llvm_ir: true,
+            ..CodegenOptions::default()
+        };
+        let error = compile(&options).unwrap_err();
+        assert!(error.to_string().contains("LLVM backend"), "{error}");
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod structs_codegen_tests {
+    use crate::utils::{call_wasm_export, get_test_file_path, instantiate_wasm, wasm_codegen};
+
+    fn compile(test_name: &str) -> Vec<u8> {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        wasm_codegen(&source_code)
+    }
+
+    fn assert_exports_return(wasm_bytes: &[u8], expected: &[(&str, i64)]) {
+        let (mut store, instance) = instantiate_wasm(wasm_bytes);
+        for (name, value) in expected {
+            let result = call_wasm_export(&mut store, &instance, name, &[])
+                .unwrap_or_else(|e| panic!("Failed to execute '{name}' function: {e}"));
+            assert_eq!(result, Some(*value), "Unexpected result of '{name}'");
+        }
+    }
+
+    #[test]
+    fn fields_test() {
+        let wasm_bytes = compile("fields");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("read_fields", 34),
+                ("field_assignment", 21),
+                ("mixed_layout", 5_000_000_000),
+                ("nested_fields", 608),
+                ("copy_semantics", 102),
+                ("nested_copy", 3412),
+            ],
+        );
+    }
+
+    #[test]
+    fn functions_test() {
+        let wasm_bytes = compile("functions");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("struct_return", 56),
+                ("struct_parameter", 42),
+                ("parameter_is_copied", 12),
+                ("chained_struct_calls", 9),
+                ("call_result_field", 9),
+            ],
+        );
+    }
+
+    #[test]
+    fn methods_test() {
+        let wasm_bytes = compile("methods");
+        assert_exports_return(
+            &wasm_bytes,
+            &[
+                ("associated_function", 3),
+                ("instance_methods", 10),
+                ("method_calling_methods", 16),
+            ],
+        );
+    }
+
+    #[test]
+    fn methods_are_not_exported_test() {
+        let wasm_bytes = compile("methods");
+        let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+        for name in ["new", "get", "Counter::new", "Counter::get"] {
+            assert!(
+                instance.get_func(&mut store, name).is_none(),
+                "Method '{name}' must not be exported"
+            );
+        }
+    }
+}
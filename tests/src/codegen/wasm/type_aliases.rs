@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod type_aliases_codegen_tests {
+    use inference_wasm_codegen::Backend;
+
+    use crate::utils::{
+        call_wasm_export, get_test_file_path, instantiate_wasm, try_wasm_codegen_with,
+    };
+
+    #[test]
+    fn aliases_test() {
+        let test_file_path = get_test_file_path(module_path!(), "aliases");
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = try_wasm_codegen_with(&source_code, backend)
+                .unwrap_or_else(|e| panic!("{backend:?} backend failed: {e}"));
+            let (mut store, instance) = instantiate_wasm(&wasm_bytes);
+            let mut call = |name: &str, arguments: &[i64]| {
+                call_wasm_export(&mut store, &instance, name, arguments)
+                    .unwrap_or_else(|e| panic!("Calling {name} failed: {e}"))
+            };
+            assert_eq!(call("identity", &[42]), Some(42));
+            assert_eq!(call("increment", &[41]), Some(42));
+            assert_eq!(call("widen", &[3_000_000_000]), Some(6_000_000_000));
+            assert_eq!(call("middle_of_row", &[]), Some(5));
+            assert_eq!(call("origin_y", &[5_000_000_000]), Some(5_000_000_007));
+            assert_eq!(call("cell_sum", &[10]), Some(13));
+        }
+    }
+}
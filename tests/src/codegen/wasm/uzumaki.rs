@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod uzumaki_codegen_tests {
+    use inference_wasm_codegen::Backend;
+
+    use crate::utils::{get_test_file_path, try_wasm_codegen_with};
+
+    /// Compiles without running the analyze phase: most sources here return `@` from
+    /// exported functions, which analysis rejects outside of a quantifier.
+    fn compile(source_code: &str, backend: Backend) -> Vec<u8> {
+        let wasm_bytes = try_wasm_codegen_with(source_code, backend)
+            .unwrap_or_else(|e| panic!("{backend:?} backend failed: {e}"));
+        inf_wasmparser::validate(&wasm_bytes)
+            .unwrap_or_else(|e| panic!("{backend:?} generated an invalid module: {e}"));
+        wasm_bytes
+    }
+
+    fn read_test_file(test_name: &str) -> String {
+        let test_file_path = get_test_file_path(module_path!(), test_name);
+        std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"))
+    }
+
+    /// Operators of every function body, in order.
+    fn operators(wasm_bytes: &[u8]) -> Vec<String> {
+        let mut operators = Vec::new();
+        for payload in inf_wasmparser::Parser::new(0).parse_all(wasm_bytes) {
+            if let inf_wasmparser::Payload::CodeSectionEntry(body) = payload.unwrap() {
+                let mut reader = body.get_operators_reader().unwrap();
+                while !reader.eof() {
+                    operators.push(format!("{:?}", reader.read().unwrap()));
+                }
+            }
+        }
+        operators
+    }
+
+    fn count(operators: &[String], prefix: &str) -> usize {
+        operators
+            .iter()
+            .filter(|operator| operator.starts_with(prefix))
+            .count()
+    }
+
+    #[test]
+    fn primitives_compile_on_both_backends_test() {
+        let source_code = read_test_file("primitives");
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let wasm_bytes = compile(&source_code, backend);
+            let rocq = inference::wasm_to_v("primitives", &wasm_bytes)
+                .unwrap_or_else(|e| panic!("{backend:?}: failed to translate to Rocq: {e}"));
+            for expected in ["BI_uzumaki_num T_i32", "BI_uzumaki_num T_i64"] {
+                assert!(rocq.contains(expected), "{backend:?}: missing `{expected}`");
+            }
+        }
+    }
+
+    #[test]
+    fn narrow_types_are_range_constrained_test() {
+        let expected: &[(&str, &[&str])] = &[
+            ("bool", &["I32Uzumaki", "I32Const { value: 1 }", "I32And"]),
+            ("u8", &["I32Uzumaki", "I32Const { value: 255 }", "I32And"]),
+            (
+                "u16",
+                &["I32Uzumaki", "I32Const { value: 65535 }", "I32And"],
+            ),
+            (
+                "i8",
+                &[
+                    "I32Uzumaki",
+                    "I32Const { value: 24 }",
+                    "I32Shl",
+                    "I32Const { value: 24 }",
+                    "I32ShrS",
+                ],
+            ),
+            (
+                "i16",
+                &[
+                    "I32Uzumaki",
+                    "I32Const { value: 16 }",
+                    "I32Shl",
+                    "I32Const { value: 16 }",
+                    "I32ShrS",
+                ],
+            ),
+            ("u32", &["I32Uzumaki", "Return"]),
+            ("u64", &["I64Uzumaki", "Return"]),
+        ];
+        for (type_name, sequence) in expected {
+            let source_code = format!("pub fn pick() -> {type_name} {{\n    return @;\n}}");
+            let lowered = operators(&compile(&source_code, Backend::Builtin));
+            assert!(
+                lowered.windows(sequence.len()).any(|window| window
+                    .iter()
+                    .zip(*sequence)
+                    .all(|(operator, expected)| operator.starts_with(expected))),
+                "`@` of `{type_name}` must lower to {sequence:?}, got {lowered:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn aggregates_are_generated_field_wise_test() {
+        let source_code = r#"struct Mixed {
+    flag: bool;
+    small: u16;
+    value: i64;
+}
+
+pub fn any_mixed() -> Mixed {
+    return @;
+}"#;
+        let lowered = operators(&compile(source_code, Backend::Builtin));
+        assert_eq!(count(&lowered, "I32Uzumaki"), 2, "{lowered:?}");
+        assert_eq!(count(&lowered, "I64Uzumaki"), 1, "{lowered:?}");
+
+        let source_code = r#"pub fn any_cell() -> u8 {
+    let cells: [u8; 16] = @;
+    return cells[3];
+}"#;
+        for backend in [Backend::Llvm, Backend::Builtin] {
+            let lowered = operators(&compile(source_code, backend));
+            assert!(
+                count(&lowered, "I32Uzumaki") >= 1,
+                "{backend:?}: {lowered:?}"
+            );
+        }
+        let lowered = operators(&compile(source_code, Backend::Builtin));
+        assert_eq!(
+            count(&lowered, "I32Uzumaki"),
+            1,
+            "Array elements must be generated in a loop: {lowered:?}"
+        );
+        assert_eq!(count(&lowered, "Loop"), 1, "{lowered:?}");
+    }
+
+    #[test]
+    fn unsupported_uzumaki_type_is_reported_test() {
+        let source_code = r#"enum Color {
+    Red,
+    Green,
+}
+
+struct Pixel {
+    color: Color;
+}
+
+pub fn any_pixel() -> Pixel {
+    return @;
+}"#;
+        let llvm = try_wasm_codegen_with(source_code, Backend::Llvm).unwrap_err();
+        let builtin = try_wasm_codegen_with(source_code, Backend::Builtin).unwrap_err();
+        assert_eq!(
+            llvm.to_string(),
+            "11:12: uzumaki values of type `Color` are not yet supported in codegen"
+        );
+        assert_eq!(builtin.to_string(), llvm.to_string());
+    }
+}
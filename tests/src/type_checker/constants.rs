@@ -0,0 +1,188 @@
+//! Tests for integer literal range checking and constant expression overflow
+//!
+//! This module contains tests verifying:
+//! - Integer literals must fit the type they are given, at the bounds of every type
+//! - Expressions built only from integer literals are evaluated and must fit their type
+//! - Division by zero in such expressions is rejected
+//! - Errors point to the literal or the overflowing part of the expression
+
+use crate::utils::{assert_type_checks, type_check_error};
+
+#[test]
+fn literals_at_the_bounds_of_every_type_are_accepted() {
+    for (ty, min, max) in [
+        ("i8", "-128", "127"),
+        ("i16", "-32768", "32767"),
+        ("i32", "-2147483648", "2147483647"),
+        ("i64", "-9223372036854775808", "9223372036854775807"),
+        ("u8", "0", "255"),
+        ("u16", "0", "65535"),
+        ("u32", "0", "4294967295"),
+        ("u64", "0", "18446744073709551615"),
+    ] {
+        assert_type_checks(&format!(
+            "fn test() -> {ty} {{ let a: {ty} = {min}; let b: {ty} = {max}; return b; }}"
+        ));
+    }
+}
+
+#[test]
+fn literals_past_the_bounds_of_every_type_error() {
+    for (ty, below, above) in [
+        ("i8", "-129", "128"),
+        ("i16", "-32769", "32768"),
+        ("i32", "-2147483649", "2147483648"),
+        ("i64", "-9223372036854775809", "9223372036854775808"),
+        ("u8", "-1", "256"),
+        ("u16", "-1", "65536"),
+        ("u32", "-1", "4294967296"),
+        ("u64", "-1", "18446744073709551616"),
+    ] {
+        let error_msg = type_check_error(&format!(
+            "fn test() -> {ty} {{ let a: {ty} = {below}; let b: {ty} = {above}; return b; }}"
+        ));
+        for literal in [below, above] {
+            assert!(
+                error_msg.contains(&format!(
+                    "integer literal `{literal}` is out of range for `{ty}`"
+                )),
+                "`{literal}` should not fit `{ty}`, got: {error_msg}"
+            );
+        }
+    }
+}
+
+#[test]
+fn out_of_range_literal_error_points_to_literal() {
+    let error_msg = type_check_error(r#"fn test() { let x: u8 = 300; }"#);
+    assert!(
+        error_msg
+            .contains("1:25: integer literal `300` is out of range for `u8`, which holds 0 to 255"),
+        "Error should point to the literal, got: {error_msg}"
+    );
+}
+
+#[test]
+fn negative_constant_of_unsigned_type_errors() {
+    let error_msg = type_check_error(r#"const X: u32 = -1;"#);
+    assert!(
+        error_msg.contains("integer literal `-1` is out of range for `u32`"),
+        "A negative `u32` constant should be rejected, got: {error_msg}"
+    );
+}
+
+#[test]
+fn literal_operand_takes_type_of_wider_variable() {
+    assert_type_checks(r#"fn test(x: i64) -> i64 { return x + 5000000000; }"#);
+}
+
+#[test]
+fn literal_operand_takes_type_of_variable() {
+    let error_msg = type_check_error(r#"fn test(x: u8) -> u8 { return x + 256; }"#);
+    assert!(
+        error_msg.contains("1:35: integer literal `256` is out of range for `u8`"),
+        "The literal should be checked against `u8`, got: {error_msg}"
+    );
+}
+
+#[test]
+fn array_elements_are_checked() {
+    let error_msg = type_check_error(r#"fn test() { let a: [u8; 2] = [1, 256]; }"#);
+    assert!(
+        error_msg.contains("integer literal `256` is out of range for `u8`"),
+        "Array elements should be checked against the element type, got: {error_msg}"
+    );
+}
+
+#[test]
+fn constant_addition_overflow_errors() {
+    let error_msg = type_check_error(r#"fn test() { let x: i8 = 127 + 1; }"#);
+    assert!(
+        error_msg.contains("1:25: constant expression `127 + 1` overflows `i8`"),
+        "Error should point to the addition, got: {error_msg}"
+    );
+}
+
+#[test]
+fn constant_subtraction_below_zero_of_unsigned_errors() {
+    let error_msg = type_check_error(r#"fn test() { let x: u8 = 0 - 1; }"#);
+    assert!(
+        error_msg.contains("constant expression `0 - 1` overflows `u8`"),
+        "Subtraction below zero should overflow `u8`, got: {error_msg}"
+    );
+}
+
+#[test]
+fn constant_multiplication_overflow_errors() {
+    let error_msg = type_check_error(r#"fn test() -> u64 { return 4294967296 * 4294967296; }"#);
+    assert!(
+        error_msg.contains("constant expression `4294967296 * 4294967296` overflows `u64`"),
+        "Multiplication should overflow `u64`, got: {error_msg}"
+    );
+}
+
+#[test]
+fn constant_negation_overflow_errors() {
+    let error_msg = type_check_error(r#"fn test() { let x: i8 = -(-128); }"#);
+    assert!(
+        error_msg.contains("constant expression `-(-128)` overflows `i8`"),
+        "Negating the minimum should overflow, got: {error_msg}"
+    );
+}
+
+#[test]
+fn constant_shift_past_width_errors() {
+    let error_msg = type_check_error(r#"fn test() { let x: i32 = 1 << 32; }"#);
+    assert!(
+        error_msg.contains("constant expression `1 << 32` overflows `i32`"),
+        "Shifting by the width of the type should overflow, got: {error_msg}"
+    );
+}
+
+#[test]
+fn constant_expressions_that_fit_are_accepted() {
+    assert_type_checks(
+        r#"fn test() {
+            let a: i8 = 100 + 27;
+            let b: u8 = (16 * 15) + 15;
+            let c: u8 = ~0;
+            let d: i32 = 1 << 31;
+            let e: i64 = -9223372036854775807 - 1;
+            let f: u8 = 255 / 2 + 128;
+            let g: i32 = 2 ** 30;
+        }"#,
+    );
+}
+
+#[test]
+fn only_innermost_overflow_is_reported() {
+    let error_msg = type_check_error(r#"fn test() { let x: i8 = (100 + 100) - 100; }"#);
+    assert!(
+        error_msg.contains("constant expression `100 + 100` overflows `i8`"),
+        "The inner addition should be reported, got: {error_msg}"
+    );
+    assert_eq!(
+        error_msg.matches("overflows").count(),
+        1,
+        "The enclosing subtraction should not be reported, got: {error_msg}"
+    );
+}
+
+#[test]
+fn constant_division_by_zero_errors() {
+    let error_msg = type_check_error(r#"fn test() -> i32 { return 42 / (2 - 2); }"#);
+    assert!(
+        error_msg.contains("1:27: division by zero"),
+        "Error should point to the division, got: {error_msg}"
+    );
+    let error_msg = type_check_error(r#"fn test() -> i32 { return 42 % 0; }"#);
+    assert!(
+        error_msg.contains("division by zero"),
+        "Remainder by zero should be rejected too, got: {error_msg}"
+    );
+}
+
+#[test]
+fn division_by_zero_variable_is_left_to_run_time() {
+    assert_type_checks(r#"fn test(x: i32) -> i32 { return 42 / x; }"#);
+}
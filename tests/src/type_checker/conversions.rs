@@ -0,0 +1,149 @@
+//! Tests for conversions between integer types
+//!
+//! This module contains tests verifying:
+//! - `cast T'(x)`, `wrapping_cast T'(x)` and `checked_cast T'(x)` have the type `T`
+//! - `cast` accepts only conversions that keep every value of the argument type
+//! - Conversions take exactly one integer argument and one integer target type
+//! - A function named like a conversion takes precedence over the built-in
+//! - Generic functions accept explicit type arguments in the same syntax
+
+use crate::utils::{assert_type_checks, type_check_error};
+
+#[test]
+fn widening_cast_is_accepted() {
+    assert_type_checks(
+        r#"fn widen(a: i32, b: u8, c: u32) -> i64 {
+    let d: i16 = cast i16'(b);
+    return cast i64'(a) + cast i64'(d) + cast i64'(c);
+}"#,
+    );
+}
+
+#[test]
+fn cast_mixes_i64_constants_with_i32_counters() {
+    assert_type_checks(
+        r#"const MAX_MEM: i64 = 65536;
+fn pages() -> i32 {
+    let mut i: i32 = 0;
+    loop cast i64'(i) * 4096 < MAX_MEM {
+        i = i + 1;
+    }
+    return i;
+}"#,
+    );
+}
+
+#[test]
+fn narrowing_cast_is_rejected() {
+    let error = type_check_error("fn narrow(x: i64) -> i32 { return cast i32'(x); }");
+    assert!(
+        error.contains(
+            "1:35: `cast` from `i64` to `i32` may change the value; use `wrapping_cast` or `checked_cast`"
+        ),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn sign_changing_cast_is_rejected() {
+    let error = type_check_error("fn unsigned(x: i8) -> u64 { return cast u64'(x); }");
+    assert!(
+        error.contains("`cast` from `i8` to `u64` may change the value"),
+        "got: {error}"
+    );
+    let error = type_check_error("fn signed(x: u32) -> i32 { return cast i32'(x); }");
+    assert!(
+        error.contains("`cast` from `u32` to `i32` may change the value"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn wrapping_and_checked_casts_convert_between_any_integer_types() {
+    assert_type_checks(
+        r#"fn low_byte(x: i64) -> u8 { return wrapping_cast u8'(x); }
+fn to_signed(x: u64) -> i32 { return checked_cast i32'(x); }
+fn same(x: u16) -> u16 { return checked_cast u16'(x); }"#,
+    );
+}
+
+#[test]
+fn conversion_has_the_target_type() {
+    let error = type_check_error("fn widen(x: i32) -> i32 { return cast i64'(x); }");
+    assert!(
+        error.contains("type mismatch in return statement: expected `i32`, found `i64`"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn conversion_of_a_non_integer_is_rejected() {
+    let error = type_check_error("fn flag(b: bool) -> i32 { return wrapping_cast i32'(b); }");
+    assert!(
+        error.contains(
+            "`wrapping_cast` converts between integer types, cannot convert `Bool` to `i32`"
+        ),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn conversion_to_a_non_integer_is_rejected() {
+    let error = type_check_error("fn flag(x: i32) -> bool { return checked_cast bool'(x); }");
+    assert!(
+        error.contains(
+            "`checked_cast` converts between integer types, cannot convert `i32` to `Bool`"
+        ),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn conversion_to_a_type_alias_converts_to_the_aliased_type() {
+    assert_type_checks(
+        r#"type Address = u64;
+fn address(offset: u32) -> u64 { return cast Address'(offset); }"#,
+    );
+}
+
+#[test]
+fn conversion_without_a_target_type_is_rejected() {
+    let error = type_check_error("fn widen(x: i32) -> i64 { return cast(x); }");
+    assert!(
+        error.contains("type parameter count mismatch for `cast`: expected 1, found 0"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn conversion_takes_one_argument() {
+    let error = type_check_error("fn widen(x: i32) -> i64 { return cast i64'(x, x); }");
+    assert!(
+        error.contains("function `cast` expects 1 arguments, but 2 provided"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn function_named_like_a_conversion_takes_precedence() {
+    assert_type_checks(
+        r#"fn cast(flag: bool) -> bool { return !flag; }
+fn negate(flag: bool) -> bool { return cast(flag); }"#,
+    );
+}
+
+#[test]
+fn generic_function_accepts_explicit_type_arguments() {
+    assert_type_checks(
+        r#"fn identity T'(x: T) -> T { return x; }
+fn use_identity(x: u16) -> u16 { return identity u16'(x); }"#,
+    );
+    let error = type_check_error(
+        r#"fn identity T'(x: T) -> T { return x; }
+fn use_identity(x: u16) -> u16 { return identity u32'(x); }"#,
+    );
+    assert!(
+        error.contains("type mismatch in return statement: expected `u16`, found `u32`"),
+        "got: {error}"
+    );
+}
@@ -221,7 +221,7 @@ mod statement_coverage {
 
     #[test]
     fn test_assign_statement() {
-        let source = r#"fn test() -> i32 { let x: i32 = 0; x = 42; return x; }"#;
+        let source = r#"fn test() -> i32 { let mut x: i32 = 0; x = 42; return x; }"#;
         let result = try_type_check(source);
         assert!(
             result.is_ok(),
@@ -232,7 +232,7 @@ mod statement_coverage {
 
     #[test]
     fn test_assign_statement_type_mismatch() {
-        let source = r#"fn test() -> i32 { let x: i32 = 0; x = true; return x; }"#;
+        let source = r#"fn test() -> i32 { let mut x: i32 = 0; x = true; return x; }"#;
         let result = try_type_check(source);
         assert!(result.is_err(), "Assignment with type mismatch should fail");
         if let Err(error) = result {
@@ -247,7 +247,7 @@ mod statement_coverage {
 
     #[test]
     fn test_assign_uzumaki_to_variable() {
-        let source = r#"fn test() -> i32 { let x: i32; x = @; return x; }"#;
+        let source = r#"fn test() -> i32 { let mut x: i32; x = @; return x; }"#;
         let result = try_type_check(source);
         assert!(
             result.is_ok(),
@@ -266,6 +266,31 @@ mod statement_coverage {
             result.err()
         );
     }
+
+    #[test]
+    fn test_uzumaki_takes_expected_type() {
+        let source = r#"fn check(a: u8) { }
+fn test(x: i64) { if @ { check(@); } assert x < @; let y: i64 = @ + 1; }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_ok(),
+            "Uzumaki should take the type its context expects, got: {:?}",
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_uzumaki_without_expected_type() {
+        let source = r#"fn test() -> bool { return @ == @; }"#;
+        let error_msg = match try_type_check(source) {
+            Ok(_) => panic!("Comparing two uzumaki expressions should fail"),
+            Err(error) => error.to_string(),
+        };
+        assert!(
+            error_msg.contains("1:28: cannot infer type for uzumaki expression"),
+            "Error should point at the first uzumaki, got: {error_msg}"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -898,8 +923,7 @@ mod expression_coverage {
         }
     }
 
-    // FIXME: Test disabled due to parser or type checker limitation
-    // #[test]
+    #[test]
     fn test_type_member_access_on_identifier() {
         let source =
             r#"enum Status { Active, Inactive } fn test() -> Status { return Status::Active; }"#;
@@ -911,8 +935,7 @@ mod expression_coverage {
         );
     }
 
-    // FIXME: Test disabled due to parser or type checker limitation
-    // #[test]
+    #[test]
     fn test_type_member_access_on_simple_type() {
         let source = r#"enum Color { Red, Green, Blue } fn test() -> Color { return Color::Red; }"#;
         let result = try_type_check(source);
@@ -1071,6 +1094,29 @@ mod function_registration_coverage {
             result.err()
         );
     }
+
+    #[test]
+    fn test_array_constant_at_module_level() {
+        let source = r#"const BOARD: [[bool; 2]; 2] = [[true, false], [false, true]]; fn test() -> bool { return BOARD[1][1]; }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_ok(),
+            "Module-level array constant should work, got: {:?}",
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_constant_value_type_mismatch() {
+        let source = r#"const FLAGS: [bool; 2] = [1, 2]; fn test() -> bool { return FLAGS[0]; }"#;
+        let result = try_type_check(source);
+        assert!(result.is_err(), "Constant value of the wrong type should fail");
+        let error = result.err().unwrap().to_string();
+        assert!(
+            error.contains("in constant definition"),
+            "Error should mention the constant definition, got: {error}"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -1164,8 +1210,7 @@ mod symbol_table_coverage {
         );
     }
 
-    // FIXME: Test disabled due to parser or type checker limitation
-    // #[test]
+    #[test]
     fn test_enum_variant_lookup() {
         let source = r#"enum Color { Red, Green, Blue } fn test() -> Color { return Color::Red; }"#;
         let result = try_type_check(source);
@@ -1129,6 +1129,25 @@ mod generics_tests {
         );
     }
 
+    #[test]
+    fn test_infer_type_param_from_array_element() {
+        let source = r#"
+            fn first T'(values: [T; 3]) -> T {
+                return values[0];
+            }
+            fn test() -> i64 {
+                let values: [i64; 3] = [1, 2, 3];
+                return first(values);
+            }
+        "#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_ok(),
+            "Type inference from array element type should work, got: {:?}",
+            result.err()
+        );
+    }
+
     // ============================================
     // Additional Edge Cases
     // ============================================
@@ -0,0 +1,204 @@
+//! Tests for generic structs
+//!
+//! This module contains tests verifying:
+//! - Names a struct's field types use that are not types are its type parameters
+//! - Instantiated types (`Pair i32' bool'`) give fields and methods concrete types
+//! - Struct expressions take explicit type arguments or infer them from field values
+//! - Field values must match the instantiated field types
+//! - Generic functions infer their type parameters from instantiated struct arguments
+
+use crate::utils::{assert_type_checks, type_check_error};
+
+const PAIR: &str = "struct Pair { fst: A; snd: B; }\n";
+
+#[test]
+fn fields_of_an_instantiated_struct_take_its_type_arguments() {
+    assert_type_checks(&format!(
+        r#"{PAIR}
+fn first(p: Pair i32' bool') -> i32 {{ return p.fst; }}
+fn second(p: Pair i32' bool') -> bool {{ return p.snd; }}"#
+    ));
+}
+
+#[test]
+fn field_of_an_instantiated_struct_is_checked_against_its_use() {
+    let error = type_check_error(&format!(
+        r#"{PAIR}
+fn second(p: Pair i32' bool') -> i32 {{ return p.snd; }}"#
+    ));
+    assert!(
+        error.contains("type mismatch in return statement: expected `i32`, found `Bool`"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn struct_expression_with_type_arguments_is_accepted() {
+    assert_type_checks(&format!(
+        r#"{PAIR}
+fn make() -> Pair i32' bool' {{
+    let p: Pair i32' bool' = Pair i32' bool' {{ fst: 1, snd: true }};
+    return p;
+}}"#
+    ));
+}
+
+#[test]
+fn struct_expression_infers_type_arguments_from_field_values() {
+    assert_type_checks(&format!(
+        r#"{PAIR}
+fn make(flag: bool) -> i64 {{
+    let x: i64 = 7;
+    let p: Pair i64' bool' = Pair {{ fst: x, snd: flag }};
+    return p.fst;
+}}"#
+    ));
+}
+
+#[test]
+fn inferred_instantiation_must_match_the_declared_type() {
+    let error = type_check_error(&format!(
+        r#"{PAIR}
+fn make() {{
+    let p: Pair bool' i32' = Pair {{ fst: 1, snd: true }};
+}}"#
+    ));
+    assert!(
+        error.contains("type mismatch in variable definition"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn field_value_must_match_the_type_argument() {
+    let error = type_check_error(&format!(
+        r#"{PAIR}
+fn make() {{
+    let p: Pair i32' bool' = Pair i32' bool' {{ fst: true, snd: true }};
+}}"#
+    ));
+    assert!(
+        error.contains(
+            "type mismatch in field `fst` of struct `Pair`: expected `i32`, found `Bool`"
+        ),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn field_literal_takes_the_type_argument() {
+    assert_type_checks(&format!(
+        r#"{PAIR}
+fn make() -> u8 {{
+    let p: Pair u8' bool' = Pair u8' bool' {{ fst: 255, snd: false }};
+    return p.fst;
+}}"#
+    ));
+    let error = type_check_error(&format!(
+        r#"{PAIR}
+fn make() {{
+    let p: Pair u8' bool' = Pair u8' bool' {{ fst: 256, snd: false }};
+}}"#
+    ));
+    assert!(
+        error.contains("integer literal `256` is out of range for `u8`"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn struct_expression_with_wrong_number_of_type_arguments_is_rejected() {
+    let error = type_check_error(&format!(
+        r#"{PAIR}
+fn make() {{
+    let p: Pair i32' i32' = Pair i32' {{ fst: 1, snd: 2 }};
+}}"#
+    ));
+    assert!(
+        error.contains("type parameter count mismatch for `Pair`: expected 2, found 1"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn parameter_type_with_wrong_number_of_type_arguments_is_rejected() {
+    let error = type_check_error(&format!(
+        r#"{PAIR}
+fn first(p: Pair i32') {{ }}"#
+    ));
+    assert!(
+        error.contains("type parameter count mismatch for `Pair`: expected 2, found 1"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn parameter_type_without_type_arguments_is_rejected() {
+    let error = type_check_error(&format!(
+        r#"{PAIR}
+fn first(p: Pair) {{ }}"#
+    ));
+    assert!(
+        error.contains("3:13: type parameter count mismatch for `Pair`: expected 2, found 0"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn conflicting_field_values_are_reported() {
+    let error = type_check_error(
+        r#"struct Twins { left: T; right: T; }
+fn make() {
+    let t: Twins i32' = Twins { left: 1, right: true };
+}"#,
+    );
+    assert!(
+        error.contains("conflicting types for type parameter `T`: inferred `i32` and `Bool`"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn methods_of_an_instantiated_struct_take_its_type_arguments() {
+    let source = r#"struct Boxed {
+    value: T;
+    fn get(self) -> T { return self.value; }
+    fn or(self, fallback: T) -> T { return fallback; }
+}
+fn unwrap(b: Boxed u64') -> u64 { return b.or(b.get()); }"#;
+    assert_type_checks(source);
+    let error = type_check_error(
+        r#"struct Boxed {
+    value: T;
+    fn get(self) -> T { return self.value; }
+}
+fn unwrap(b: Boxed u64') -> bool { return b.get(); }"#,
+    );
+    assert!(
+        error.contains("type mismatch in return statement: expected `Bool`, found `u64`"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn generic_function_infers_type_parameters_from_struct_arguments() {
+    assert_type_checks(&format!(
+        r#"{PAIR}
+fn first T'(p: Pair T' bool') -> T {{ return p.fst; }}
+fn use_first(p: Pair i64' bool') -> i64 {{ return first(p); }}"#
+    ));
+}
+
+#[test]
+fn struct_without_type_parameters_rejects_type_arguments() {
+    let error = type_check_error(
+        r#"struct Point { x: i32; }
+fn make() {
+    let p: Point = Point i32' { x: 1 };
+}"#,
+    );
+    assert!(
+        error.contains("type parameter count mismatch for `Point`: expected 0, found 1"),
+        "got: {error}"
+    );
+}
@@ -3,7 +3,13 @@ mod type_checker;
 
 mod array_tests;
 mod associated_functions;
+mod constants;
+mod conversions;
 mod coverage;
 mod error_recovery;
 mod features;
+mod generic_structs;
+mod mutability;
+mod specs;
+mod type_aliases;
 mod type_info_tests;
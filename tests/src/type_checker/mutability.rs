@@ -0,0 +1,289 @@
+//! Tests for mutability checking of assignment targets
+//!
+//! This module contains tests verifying:
+//! - Variables and arguments can only be assigned when declared `mut`
+//! - Constants, module-level and local, can never be assigned
+//! - `self` and its fields can only be assigned in methods taking `mut self`
+//! - `mut self` methods can only be called on places that could be assigned
+//! - Errors point to both the assignment and the declaration
+
+use crate::utils::{try_type_check, type_check_error};
+
+#[test]
+fn assignment_to_mut_variable_is_allowed() {
+    let source = r#"fn test() -> i32 { let mut x: i32 = 1; x = x + 1; return x; }"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "Assigning a `let mut` variable should succeed, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn assignment_to_immutable_variable_errors() {
+    let source = r#"fn test() -> i32 { let x: i32 = 1; x = 5; return x; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("1:36: cannot assign to immutable variable `x`, declared at 1:20"),
+        "Error should point to the assignment and the variable, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_to_element_of_immutable_variable_errors() {
+    let source =
+        r#"fn test() -> i32 { let values: [i32; 2] = [1, 2]; values[0] = 3; return values[0]; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("cannot assign to immutable variable `values`"),
+        "Error should name the variable, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_to_field_of_immutable_variable_errors() {
+    let source = r#"struct Point { x: i32; }
+fn test() -> i32 { let p: Point = Point { x: 1 }; p.x = 2; return p.x; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("2:51: cannot assign to immutable variable `p`, declared at 2:20"),
+        "Error should point to the assignment and the variable, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_to_element_and_field_of_mut_variables_is_allowed() {
+    let source = r#"struct Point { x: i32; }
+fn test() -> i32 {
+    let mut values: [i32; 2] = [1, 2];
+    let mut p: Point = Point { x: 1 };
+    values[1] = 3;
+    p.x = values[1];
+    return p.x;
+}"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "Assigning an element or a field of a `let mut` variable should succeed, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn assignment_to_mut_argument_is_allowed() {
+    let source = r#"fn test(mut a: bool) -> bool { a = !a; return a; }"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "Assigning a `mut` argument should succeed, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn assignment_to_immutable_argument_errors() {
+    let source = r#"fn test(a: bool) -> bool { a = !a; return a; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("1:28: cannot assign to immutable argument `a`, declared at 1:9"),
+        "Error should point to the assignment and the argument, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_to_field_of_immutable_argument_errors() {
+    let source = r#"struct Point { x: i32; } fn test(p: Point) -> i32 { p.x = 1; return p.x; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("cannot assign to immutable argument `p`"),
+        "Error should name the argument, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_to_element_of_immutable_argument_errors() {
+    let source = r#"fn test(values: [i32; 2]) -> i32 { values[0] = 1; return values[0]; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("cannot assign to immutable argument `values`"),
+        "Error should name the argument, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_to_module_constant_errors() {
+    let source = r#"const LIMIT: i32 = 10;
+fn test() -> i32 {
+    LIMIT = 20;
+    return LIMIT;
+}"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("3:5: cannot assign to immutable constant `LIMIT`, declared at 1:1"),
+        "Error should point to the assignment and the constant, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_to_local_constant_errors() {
+    let source = r#"fn test() -> i32 { const LIMIT: i32 = 10; LIMIT = 20; return LIMIT; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("cannot assign to immutable constant `LIMIT`"),
+        "Error should name the constant, got: {error_msg}"
+    );
+}
+
+#[test]
+fn assignment_through_mut_self_is_allowed() {
+    let source = r#"struct Counter { value: i32; fn reset(mut self) { self.value = 0; } }"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "Assigning a field of `mut self` should succeed, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn assignment_through_immutable_self_errors() {
+    let source = r#"struct Counter {
+    value: i32;
+
+    fn reset(self) {
+        self.value = 0;
+    }
+}"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg
+            .contains("5:9: cannot assign through `self`, which is not declared `mut` at 4:14"),
+        "Error should point to the assignment and `self`, got: {error_msg}"
+    );
+}
+
+#[test]
+fn shadowing_mut_variable_is_mutable() {
+    let source =
+        r#"fn test(a: i32) -> i32 { if a > 0 { let mut a: i32 = 1; a = 2; return a; } return a; }"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "A `let mut` variable shadowing an argument should be assignable, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn shadowing_immutable_variable_is_immutable() {
+    let source =
+        r#"fn test(mut a: i32) -> i32 { if a > 0 { let a: i32 = 1; a = 2; return a; } return a; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("cannot assign to immutable variable `a`"),
+        "A `let` variable shadowing a `mut` argument should not be assignable, got: {error_msg}"
+    );
+}
+
+#[test]
+fn mut_self_method_call_on_mut_variable_is_allowed() {
+    let source = r#"struct Counter { value: i32; fn inc(mut self) { self.value = self.value + 1; } }
+fn test() -> i32 { let mut c: Counter = Counter { value: 1 }; c.inc(); return c.value; }"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "Calling a `mut self` method on a `let mut` variable should succeed, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn mut_self_method_call_on_immutable_variable_errors() {
+    let source = r#"struct Counter { value: i32; fn inc(mut self) { self.value = self.value + 1; } }
+fn test() -> i32 { let c: Counter = Counter { value: 1 }; c.inc(); return c.value; }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("cannot assign to immutable variable `c`, declared at 2:20"),
+        "Error should name the variable, got: {error_msg}"
+    );
+}
+
+#[test]
+fn mut_self_method_call_on_immutable_argument_errors() {
+    let source = r#"struct Counter { value: i32; fn inc(mut self) { self.value = self.value + 1; } }
+fn test(c: Counter) { c.inc(); }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("2:23: cannot assign to immutable argument `c`, declared at 2:9"),
+        "Error should point to the call and the argument, got: {error_msg}"
+    );
+}
+
+#[test]
+fn mut_self_method_call_on_field_of_immutable_argument_errors() {
+    let source = r#"struct Counter { value: i32; fn inc(mut self) { self.value = self.value + 1; } }
+struct Pair { first: Counter; }
+fn test(p: Pair) { p.first.inc(); }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains("cannot assign to immutable argument `p`"),
+        "Error should name the argument, got: {error_msg}"
+    );
+}
+
+#[test]
+fn mut_self_method_call_through_immutable_self_errors() {
+    let source = r#"struct Counter {
+    value: i32;
+
+    fn inc(mut self) {
+        self.value = self.value + 1;
+    }
+
+    fn sneaky(self) -> i32 {
+        self.inc();
+        return self.value;
+    }
+}"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg
+            .contains("9:9: cannot assign through `self`, which is not declared `mut` at 8:15"),
+        "Error should point to the call and `self`, got: {error_msg}"
+    );
+}
+
+#[test]
+fn mut_self_method_call_through_mut_self_is_allowed() {
+    let source = r#"struct Counter {
+    value: i32;
+
+    fn inc(mut self) {
+        self.value = self.value + 1;
+    }
+
+    fn inc_twice(mut self) {
+        self.inc();
+        self.inc();
+    }
+}"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "Calling a `mut self` method through `mut self` should succeed, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn self_method_call_on_immutable_argument_is_allowed() {
+    let source = r#"struct Counter { value: i32; fn get(self) -> i32 { return self.value; } }
+fn test(c: Counter) -> i32 { return c.get(); }"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "Calling a `self` method on an immutable argument should succeed, got: {:?}",
+        result.err()
+    );
+}
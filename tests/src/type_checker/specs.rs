@@ -0,0 +1,214 @@
+//! Tests for spec conformance checking
+//!
+//! This module contains tests verifying:
+//! - A function returning a spec claims the spec for its module
+//! - The module must define every `external fn` of the spec with a matching signature
+//! - The module must define every `type` of the spec, as an alias, struct or enum
+//! - A type alias must name the same type as the spec's declaration
+//! - Signatures and aliased types are compared with type aliases resolved
+//! - Errors name the spec and the missing or mismatched item
+
+use crate::utils::{build_ast_with_modules, try_type_check, type_check_error};
+use inference_type_checker::TypeCheckerBuilder;
+
+const ARITHMETIC_SPEC: &str = r#"
+spec Arithmetic {
+    external fn sum(a: i32, b: i32) -> i32;
+    external fn negate(i32) -> i32;
+    type Number = i32;
+}
+"#;
+
+#[test]
+fn conforming_module_is_accepted() {
+    let source = format!(
+        r#"{ARITHMETIC_SPEC}
+type Number = i32;
+fn sum(x: i32, y: i32) -> i32 {{ return x + y; }}
+fn negate(x: i32) -> i32 {{ return -x; }}
+fn constructor() -> Arithmetic {{ }}"#
+    );
+    let result = try_type_check(&source);
+    assert!(
+        result.is_ok(),
+        "A module defining every item of the spec should conform, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn unclaimed_spec_requires_nothing() {
+    let result = try_type_check(ARITHMETIC_SPEC);
+    assert!(
+        result.is_ok(),
+        "A spec no function returns should not be checked, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn missing_function_errors() {
+    let source = format!(
+        r#"{ARITHMETIC_SPEC}
+type Number = i32;
+fn sum(x: i32, y: i32) -> i32 {{ return x + y; }}
+fn constructor() -> Arithmetic {{ }}"#
+    );
+    let error_msg = type_check_error(&source);
+    assert!(
+        error_msg.contains("10:1: missing function `negate` required by spec `Arithmetic`"),
+        "Error should point to the constructor and name the function, got: {error_msg}"
+    );
+}
+
+#[test]
+fn missing_type_errors() {
+    let source = format!(
+        r#"{ARITHMETIC_SPEC}
+fn sum(x: i32, y: i32) -> i32 {{ return x + y; }}
+fn negate(x: i32) -> i32 {{ return -x; }}
+fn constructor() -> Arithmetic {{ }}"#
+    );
+    let error_msg = type_check_error(&source);
+    assert!(
+        error_msg.contains("10:1: missing type `Number` required by spec `Arithmetic`"),
+        "Error should point to the constructor and name the type, got: {error_msg}"
+    );
+}
+
+#[test]
+fn struct_provides_declared_type() {
+    let source = r#"
+spec Shape {
+    type Point = i32;
+}
+struct Point { x: i32; y: i32; }
+fn constructor() -> Shape { }"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "A struct should provide a type of the spec, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn alias_to_a_different_type_errors() {
+    let source = r#"
+spec Ledger {
+    type Address = u32;
+}
+type Address = i64;
+fn constructor() -> Ledger { }"#;
+    let error_msg = type_check_error(source);
+    assert!(
+        error_msg.contains(
+            "5:1: type `Address` does not match its declaration in spec `Ledger`: expected `u32`, found `i64`"
+        ),
+        "Error should point to the alias and show both types, got: {error_msg}"
+    );
+}
+
+#[test]
+fn alias_resolving_to_the_declared_type_conforms() {
+    let source = r#"
+spec Ledger {
+    type Address = u32;
+}
+type Word = u32;
+type Address = Word;
+fn constructor() -> Ledger { }"#;
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "An alias naming the declared type through another alias should conform, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn parameter_type_mismatch_errors() {
+    let source = format!(
+        r#"{ARITHMETIC_SPEC}
+type Number = i32;
+fn sum(x: u32, y: u32) -> i32 {{ return 0; }}
+fn negate(x: i32) -> i32 {{ return -x; }}
+fn constructor() -> Arithmetic {{ }}"#
+    );
+    let error_msg = type_check_error(&source);
+    assert!(
+        error_msg.contains(
+            "9:1: function `sum` does not match its declaration in spec `Arithmetic`: expected `fn(i32, i32) -> i32`, found `fn(u32, u32) -> i32`"
+        ),
+        "Error should point to the function and show both signatures, got: {error_msg}"
+    );
+}
+
+#[test]
+fn return_type_mismatch_errors() {
+    let source = format!(
+        r#"{ARITHMETIC_SPEC}
+type Number = i32;
+fn sum(x: i32, y: i32) -> i32 {{ return x + y; }}
+fn negate(x: i32) -> bool {{ return true; }}
+fn constructor() -> Arithmetic {{ }}"#
+    );
+    let error_msg = type_check_error(&source);
+    assert!(
+        error_msg.contains("expected `fn(i32) -> i32`, found `fn(i32) -> Bool`"),
+        "Return types should be compared, got: {error_msg}"
+    );
+}
+
+#[test]
+fn parameter_count_mismatch_errors() {
+    let source = format!(
+        r#"{ARITHMETIC_SPEC}
+type Number = i32;
+fn sum(x: i32) -> i32 {{ return x; }}
+fn negate(x: i32) -> i32 {{ return -x; }}
+fn constructor() -> Arithmetic {{ }}"#
+    );
+    let error_msg = type_check_error(&source);
+    assert!(
+        error_msg.contains("expected `fn(i32, i32) -> i32`, found `fn(i32) -> i32`"),
+        "Parameter counts should be compared, got: {error_msg}"
+    );
+}
+
+#[test]
+fn signatures_are_compared_through_aliases() {
+    let source = format!(
+        r#"{ARITHMETIC_SPEC}
+type Number = i32;
+fn sum(x: Number, y: Number) -> Number {{ return x + y; }}
+fn negate(x: i32) -> Number {{ return -x; }}
+fn constructor() -> Arithmetic {{ }}"#
+    );
+    let result = try_type_check(&source);
+    assert!(
+        result.is_ok(),
+        "An alias of `i32` should match `i32` in a spec signature, got: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn functions_of_other_modules_do_not_conform() {
+    let arena = build_ast_with_modules(
+        &format!("{ARITHMETIC_SPEC}\ntype Number = i32;\nfn sum(x: i32, y: i32) -> i32 {{ return x + y; }}\nfn negate(x: i32) -> i32 {{ return -x; }}"),
+        &[(
+            "math",
+            "type Number = i32;\nfn sum(x: i32, y: i32) -> i32 { return x + y; }\nfn constructor() -> Arithmetic { }"
+                .to_string(),
+        )],
+    );
+    let error_msg = match TypeCheckerBuilder::build_typed_context(arena) {
+        Ok(_) => panic!("Expected module `math` not to conform to `Arithmetic`"),
+        Err(error) => error.to_string(),
+    };
+    assert!(
+        error_msg.contains("missing function `negate` required by spec `Arithmetic`"),
+        "Only functions of the claiming module should count, got: {error_msg}"
+    );
+}
@@ -0,0 +1,85 @@
+//! Tests for type aliases
+//!
+//! This module contains tests verifying:
+//! - An alias denotes the type it names wherever types are compared
+//! - Chains of aliases resolve, in any order of definition
+//! - Aliases of structs and of instantiated generic structs give access to their fields
+//! - An alias that leads back to itself does not stop type checking
+
+use crate::utils::{assert_type_checks, try_type_check};
+
+#[test]
+fn alias_of_a_number_type_is_that_type() {
+    assert_type_checks(
+        r#"type Index = i32;
+fn next(i: Index) -> i32 {
+    let j: Index = i + 1;
+    return j;
+}"#,
+    );
+}
+
+#[test]
+fn alias_chain_resolves_regardless_of_order() {
+    assert_type_checks(
+        r#"type Amount = Balance;
+type Balance = u64;
+fn total(a: Amount, b: Balance) -> u64 { return a + b; }"#,
+    );
+}
+
+#[test]
+fn alias_of_a_different_type_is_a_mismatch() {
+    let error = match try_type_check(
+        r#"type Flag = bool;
+fn count(f: Flag) -> i32 { return f; }"#,
+    ) {
+        Ok(_) => panic!("Returning a `Flag` as `i32` should fail"),
+        Err(error) => error.to_string(),
+    };
+    assert!(
+        error.contains("type mismatch in return statement: expected `i32`, found `Bool`"),
+        "got: {error}"
+    );
+}
+
+#[test]
+fn alias_of_a_struct_gives_access_to_its_fields() {
+    assert_type_checks(
+        r#"struct Point { x: i32; y: i32; }
+type Position = Point;
+fn sum(p: Position) -> i32 { return p.x + p.y; }
+fn origin() -> Position { return Point { x: 0, y: 0 }; }"#,
+    );
+}
+
+#[test]
+fn alias_of_an_instantiated_generic_struct() {
+    assert_type_checks(
+        r#"struct Pair { fst: A; snd: B; }
+type Range = Pair u32' u32';
+fn length(r: Range) -> u32 { return r.snd - r.fst; }
+fn make() -> Range { return Pair u32' u32' { fst: 1, snd: 2 }; }"#,
+    );
+}
+
+#[test]
+fn array_of_aliases_is_an_array_of_the_named_type() {
+    assert_type_checks(
+        r#"type Byte = u8;
+fn head(bytes: [Byte; 2]) -> u8 { return bytes[0]; }
+fn make() -> [u8; 2] {
+    let bytes: [Byte; 2] = [1, 2];
+    return bytes;
+}"#,
+    );
+}
+
+#[test]
+fn cyclic_aliases_do_not_stop_type_checking() {
+    assert_type_checks(
+        r#"type Left = Right;
+type Right = Left;
+fn same(x: Left) -> Left { return x; }"#,
+    );
+}
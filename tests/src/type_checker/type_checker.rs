@@ -826,7 +826,7 @@ mod type_inference_tests {
         fn test_assignment_type_check() {
             let source = r#"
             fn test() {
-                let x: i32 = 10;
+                let mut x: i32 = 10;
                 x = 20;
             }"#;
             let typed_context = try_type_check(source).expect("Type checking should succeed");
@@ -970,7 +970,7 @@ mod type_inference_tests {
                 let c: i32 = @;
                 let d: i64 = @;
 
-                let e: u8;
+                let mut e: u8;
                 e = @;
                 let f: u16 = @;
                 let g: u32 = @;
@@ -2443,3 +2443,252 @@ mod division_operator_tests {
         );
     }
 }
+
+/// Tests that integer literals take the integer type expected by their context.
+#[cfg(test)]
+mod literal_typing_tests {
+    use crate::utils::build_ast;
+    use inference_ast::nodes::{AstNode, Expression, Literal};
+    use inference_type_checker::TypeCheckerBuilder;
+    use inference_type_checker::type_info::{NumberType, TypeInfoKind};
+
+    fn try_type_check(
+        source: &str,
+    ) -> anyhow::Result<inference_type_checker::typed_context::TypedContext> {
+        let arena = build_ast(source.to_string());
+        Ok(TypeCheckerBuilder::build_typed_context(arena)?.typed_context())
+    }
+
+    fn number_literal_types(
+        typed_context: &inference_type_checker::typed_context::TypedContext,
+    ) -> Vec<TypeInfoKind> {
+        let mut literals = typed_context
+            .filter_nodes(|node| {
+                matches!(
+                    node,
+                    AstNode::Expression(Expression::Literal(Literal::Number(_)))
+                )
+            })
+            .into_iter()
+            .filter_map(|node| match node {
+                AstNode::Expression(Expression::Literal(Literal::Number(lit))) => Some(lit),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        literals.sort_by_key(|lit| lit.location.offset_start);
+        literals
+            .iter()
+            .map(|lit| typed_context.get_node_typeinfo(lit.id).unwrap().kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_variable_definition_literal_takes_declared_type() {
+        let source = r#"fn test() -> u8 { let x: u8 = 200; return x; }"#;
+        let typed_context = try_type_check(source).expect("Type checking should succeed");
+        assert_eq!(
+            number_literal_types(&typed_context),
+            vec![TypeInfoKind::Number(NumberType::U8)]
+        );
+    }
+
+    #[test]
+    fn test_return_literal_takes_return_type() {
+        let source = r#"fn test() -> i64 { return -(1 + 2); }"#;
+        let typed_context = try_type_check(source).expect("Type checking should succeed");
+        assert_eq!(
+            number_literal_types(&typed_context),
+            vec![
+                TypeInfoKind::Number(NumberType::I64),
+                TypeInfoKind::Number(NumberType::I64)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_binary_operand_literal_takes_other_operand_type() {
+        let source = r#"fn test(x: u16) -> bool { return 1 + x < 10; }"#;
+        let typed_context = try_type_check(source).expect("Type checking should succeed");
+        assert_eq!(
+            number_literal_types(&typed_context),
+            vec![
+                TypeInfoKind::Number(NumberType::U16),
+                TypeInfoKind::Number(NumberType::U16)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assignment_literal_takes_target_type() {
+        let source = r#"fn test() { let mut x: i8 = 0; x = 5; }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_ok(),
+            "Assigning a literal to an i8 should succeed, got: {:?}",
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_untyped_literal_defaults_to_i32() {
+        let source = r#"fn test() -> bool { return 1 < 2; }"#;
+        let typed_context = try_type_check(source).expect("Type checking should succeed");
+        assert_eq!(
+            number_literal_types(&typed_context),
+            vec![
+                TypeInfoKind::Number(NumberType::I32),
+                TypeInfoKind::Number(NumberType::I32)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_literal_does_not_coerce_to_bool() {
+        let source = r#"fn test() -> bool { return 1; }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_err(),
+            "Returning a number from a bool function should fail"
+        );
+    }
+
+    #[test]
+    fn test_argument_literal_takes_parameter_type() {
+        let source = r#"fn f(a: u8, b: i64) {} fn test() { f(1, 2); }"#;
+        let typed_context = try_type_check(source).expect("Type checking should succeed");
+        assert_eq!(
+            number_literal_types(&typed_context),
+            vec![
+                TypeInfoKind::Number(NumberType::U8),
+                TypeInfoKind::Number(NumberType::I64)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_named_argument_literal_takes_named_parameter_type() {
+        let source = r#"fn f(a: u8, b: i64) {} fn test() { f(b: 1, a: 2); }"#;
+        let typed_context = try_type_check(source).expect("Type checking should succeed");
+        assert_eq!(
+            number_literal_types(&typed_context),
+            vec![
+                TypeInfoKind::Number(NumberType::I64),
+                TypeInfoKind::Number(NumberType::U8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_positional_argument_fills_remaining_parameter() {
+        let source = r#"fn f(a: u8, b: i64) {} fn test() { f(1, a: 2); }"#;
+        let typed_context = try_type_check(source).expect("Type checking should succeed");
+        assert_eq!(
+            number_literal_types(&typed_context),
+            vec![
+                TypeInfoKind::Number(NumberType::I64),
+                TypeInfoKind::Number(NumberType::U8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_named_argument_is_reported() {
+        let source = r#"fn f(a: i32) {} fn test() { f(c: 1); }"#;
+        let result = try_type_check(source);
+        assert!(result.is_err(), "Unknown named argument should fail");
+        let error_msg = result.err().unwrap().to_string();
+        assert!(
+            error_msg.contains("function `f` has no parameter `c` left to bind"),
+            "Error message should name the argument, got: {error_msg}"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_named_argument_is_reported() {
+        let source = r#"fn f(a: i32, b: i32) {} fn test() { f(a: 1, a: 2); }"#;
+        let result = try_type_check(source);
+        assert!(result.is_err(), "Binding a parameter twice should fail");
+    }
+
+    #[test]
+    fn test_positional_argument_type_mismatch_is_reported() {
+        let source = r#"fn f(a: i64, b: i32) {} fn test() { let x: i32 = 1; f(x, 1); }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_err(),
+            "Passing an i32 to an i64 parameter should fail"
+        );
+        let error_msg = result.err().unwrap().to_string();
+        assert!(
+            error_msg.contains(
+                "1:55: type mismatch in argument 0 `a` of function `f`: expected `i64`, found `i32`"
+            ),
+            "Error message should name the argument, got: {error_msg}"
+        );
+    }
+
+    #[test]
+    fn test_named_argument_type_mismatch_is_reported() {
+        let source = r#"fn f(a: i64, b: bool) {} fn test() { let x: i32 = 1; f(b: true, a: x); }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_err(),
+            "Passing an i32 to an i64 parameter should fail"
+        );
+        let error_msg = result.err().unwrap().to_string();
+        assert!(
+            error_msg.contains("type mismatch in argument 0 `a` of function `f`"),
+            "Error message should name the parameter the argument binds to, got: {error_msg}"
+        );
+    }
+
+    #[test]
+    fn test_method_argument_type_mismatch_is_reported() {
+        let source = r#"struct P { x: i64; fn set(mut self, x: i64) { self.x = x; } }
+            fn test() { let p: P = P { x: 1 }; let y: bool = true; p.set(y); }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_err(),
+            "Passing a bool to an i64 parameter should fail"
+        );
+        let error_msg = result.err().unwrap().to_string();
+        assert!(
+            error_msg.contains("type mismatch in argument 0 `x` of method `P::set`"),
+            "Error message should name the method, got: {error_msg}"
+        );
+    }
+
+    #[test]
+    fn test_generic_function_argument_type_mismatch_is_reported() {
+        let source = r#"fn wrap T'(a: T, n: i64) -> T { return a; }
+            fn test() -> bool { let y: i32 = 1; return wrap(true, y); }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_err(),
+            "Passing an i32 to an i64 parameter should fail"
+        );
+        let error_msg = result.err().unwrap().to_string();
+        assert!(
+            error_msg.contains("type mismatch in argument 1 `n` of function `wrap`"),
+            "Error message should name the argument, got: {error_msg}"
+        );
+    }
+
+    #[test]
+    fn test_defined_type_named_like_a_builtin_is_not_the_builtin() {
+        let source =
+            r#"enum Unit { Value } fn f(u: Unit) {} fn test() { let x: bool = true; f(x); }"#;
+        let result = try_type_check(source);
+        assert!(
+            result.is_err(),
+            "Passing a bool to a `Unit` enum parameter should fail"
+        );
+        let error_msg = result.err().unwrap().to_string();
+        assert!(
+            error_msg.contains(
+                "type mismatch in argument 0 `u` of function `f`: expected `Unit`, found `Bool`"
+            ),
+            "The parameter should have the enum type, got: {error_msg}"
+        );
+    }
+}
@@ -643,6 +643,25 @@ mod number_type_methods {
         assert!("i128".parse::<NumberType>().is_err());
         assert!("".parse::<NumberType>().is_err());
     }
+
+    #[test]
+    fn test_fits_in_widening_types() {
+        assert!(NumberType::I32.fits_in(NumberType::I64));
+        assert!(NumberType::U8.fits_in(NumberType::I16));
+        assert!(NumberType::U32.fits_in(NumberType::U64));
+        assert!(NumberType::U32.fits_in(NumberType::I64));
+        for nt in NumberType::ALL {
+            assert!(nt.fits_in(*nt));
+        }
+    }
+
+    #[test]
+    fn test_fits_in_rejects_narrowing_and_sign_change() {
+        assert!(!NumberType::I64.fits_in(NumberType::I32));
+        assert!(!NumberType::I8.fits_in(NumberType::U64));
+        assert!(!NumberType::U32.fits_in(NumberType::I32));
+        assert!(!NumberType::U64.fits_in(NumberType::I64));
+    }
 }
 
 mod type_info_kind_builtin_methods {
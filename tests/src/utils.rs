@@ -30,12 +30,75 @@ pub(crate) fn try_build_ast(source_code: String) -> anyhow::Result<Arena> {
     builder.build_ast()
 }
 
+/// Builds the AST of `source` and type checks it.
+pub(crate) fn try_type_check(
+    source: &str,
+) -> anyhow::Result<inference_type_checker::typed_context::TypedContext> {
+    let arena = build_ast(source.to_string());
+    Ok(inference_type_checker::TypeCheckerBuilder::build_typed_context(arena)?.typed_context())
+}
+
+/// Type checks `source`, which must fail, and returns the error message.
+pub(crate) fn type_check_error(source: &str) -> String {
+    match try_type_check(source) {
+        Ok(_) => panic!("Expected a type error for:\n{source}"),
+        Err(error) => error.to_string(),
+    }
+}
+
+/// Asserts that `source` type checks.
+pub(crate) fn assert_type_checks(source: &str) {
+    let result = try_type_check(source);
+    assert!(
+        result.is_ok(),
+        "Type checking should succeed for:\n{source}\ngot: {:?}",
+        result.err()
+    );
+}
+
 pub(crate) fn wasm_codegen(source_code: &str) -> Vec<u8> {
+    try_wasm_codegen(source_code).unwrap()
+}
+
+/// Type checks `source_code` and generates WebAssembly, returning the codegen error
+/// instead of panicking. The source must type check.
+pub(crate) fn try_wasm_codegen(source_code: &str) -> anyhow::Result<Vec<u8>> {
+    try_wasm_codegen_with(source_code, inference_wasm_codegen::Backend::default())
+}
+
+/// Like [`try_wasm_codegen`], with the given code generation backend.
+pub(crate) fn try_wasm_codegen_with(
+    source_code: &str,
+    backend: inference_wasm_codegen::Backend,
+) -> anyhow::Result<Vec<u8>> {
     let arena = build_ast(source_code.to_string());
     let typed_context = inference_type_checker::TypeCheckerBuilder::build_typed_context(arena)
         .unwrap()
         .typed_context();
-    inference_wasm_codegen::codegen(&typed_context).unwrap()
+    inference_wasm_codegen::codegen_with_backend(&typed_context, backend)
+}
+
+/// Builds the AST of a project split into a main file and one file per module; each
+/// module is given as its path, such as `math` or `shapes::square`, and its source.
+pub(crate) fn build_ast_with_modules(main_source: &str, modules: &[(&str, String)]) -> Arena {
+    let inference_language = tree_sitter_inference::language();
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&inference_language)
+        .expect("Error loading Inference grammar");
+    let main_tree = parser.parse(main_source, None).unwrap();
+    let module_trees: Vec<_> = modules
+        .iter()
+        .map(|(_, source)| parser.parse(source, None).unwrap())
+        .collect();
+    let mut builder = Builder::new();
+    builder.add_source_code(main_tree.root_node(), main_source.as_bytes());
+    for ((name, source), tree) in modules.iter().zip(&module_trees) {
+        builder.add_module_source_code(name, tree.root_node(), source.as_bytes());
+    }
+    builder
+        .build_ast()
+        .expect("Failed to build AST - check for syntax errors in the test sources")
 }
 
 /// Automatically resolves a test data file path based on the test's module path and name.
@@ -104,6 +167,75 @@ pub(crate) fn assert_wasms_modules_equivalence(expected: &[u8], actual: &[u8]) {
     }
 }
 
+/// Instantiates a generated WASM module in wasmtime for execution tests.
+///
+/// The module is validated first, and the `env.__linear_memory` import expected by
+/// modules linked with `rust-lld` is satisfied with a single-page memory.
+///
+/// # Panics
+/// Panics if the module is invalid or cannot be instantiated.
+pub(crate) fn instantiate_wasm(wasm_bytes: &[u8]) -> (wasmtime::Store<()>, wasmtime::Instance) {
+    use wasmtime::{Engine, Linker, Memory, MemoryType, Module, Store};
+
+    inf_wasmparser::validate(wasm_bytes)
+        .unwrap_or_else(|e| panic!("Generated Wasm module is invalid: {e}"));
+
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm_bytes)
+        .unwrap_or_else(|e| panic!("Failed to create Wasm module: {e}"));
+    let mut store = Store::new(&engine, ());
+
+    let mut linker = Linker::new(&engine);
+    let memory = Memory::new(&mut store, MemoryType::new(1, None))
+        .unwrap_or_else(|e| panic!("Failed to create memory: {e}"));
+    linker
+        .define(&mut store, "env", "__linear_memory", memory)
+        .unwrap_or_else(|e| panic!("Failed to define memory import: {e}"));
+
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap_or_else(|e| panic!("Failed to instantiate Wasm module: {e}"));
+    (store, instance)
+}
+
+/// Calls an exported function with integer arguments and returns its integer result.
+///
+/// Arguments are converted to the export's parameter types (`i32` or `i64`) and the
+/// result is widened to `i64`. Returns `Ok(None)` for functions without a result and
+/// `Err` if execution traps.
+///
+/// # Panics
+/// Panics if the export does not exist or has a non-integer signature.
+pub(crate) fn call_wasm_export(
+    store: &mut wasmtime::Store<()>,
+    instance: &wasmtime::Instance,
+    name: &str,
+    args: &[i64],
+) -> wasmtime::Result<Option<i64>> {
+    use wasmtime::{Val, ValType};
+
+    let func = instance
+        .get_func(&mut *store, name)
+        .unwrap_or_else(|| panic!("Failed to get '{name}' function"));
+    let func_type = func.ty(&*store);
+    let params = func_type
+        .params()
+        .zip(args)
+        .map(|(ty, arg)| match ty {
+            ValType::I32 => Val::I32(i32::try_from(*arg).unwrap_or(*arg as i32)),
+            ValType::I64 => Val::I64(*arg),
+            other => panic!("Unsupported parameter type {other} of '{name}'"),
+        })
+        .collect::<Vec<_>>();
+    let mut results = func_type.results().map(|_| Val::I32(0)).collect::<Vec<_>>();
+    func.call(&mut *store, &params, &mut results)?;
+    Ok(results.first().map(|result| match result {
+        Val::I32(value) => i64::from(*value),
+        Val::I64(value) => *value,
+        other => panic!("Unsupported result {other:?} of '{name}'"),
+    }))
+}
+
 pub(crate) fn parse_simple_type(type_name: &str) -> Option<inference_ast::nodes::SimpleTypeKind> {
     use inference_ast::nodes::SimpleTypeKind;
 
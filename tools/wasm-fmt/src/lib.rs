@@ -831,8 +831,9 @@ fn format_operator(op: &Operator) -> String {
         Operator::Exists(x) => format!("exists {x}"),
         Operator::Assume(x) => format!("assume {x}"),
         Operator::Unique(x) => format!("unique {x}"),
-        Operator::I32Uzumaki(x) => format!("i32.uzumaki {x}"),
-        Operator::I64Uzumaki(x) => format!("i64.uzumaki {x}"),
+        // The uzumaki instructions have no immediates in the text or binary format.
+        Operator::I32Uzumaki(_) => "i32.uzumaki".into(),
+        Operator::I64Uzumaki(_) => "i64.uzumaki".into(),
         Operator::I64Add128 => "i64.add128".into(),
         Operator::I64Sub128 => "i64.sub128".into(),
         Operator::I64MulWideS => "i64.mul_wide_s".into(),